@@ -8,6 +8,6 @@ use noted::bins;
 
 fn main() -> color_eyre::eyre::Result<()> {
     color_eyre::install()?;
-    bins::icli::execute()?;
+    bins::icli::execute(std::env::args())?;
     Ok(())
 }
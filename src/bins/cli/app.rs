@@ -12,6 +12,10 @@ use tinyid::TinyId;
 
 fn validate_add_note_json(s: &str) -> Result<(), String> {
     crate::flame_guard!("bins", "cli", "app", "validate_add_note_json");
+    // The JSON is read from stdin later, so it can only be validated then.
+    if s == "-" {
+        return Ok(());
+    }
     let converted: NoteShape = serde_json::from_str(s).map_err(|e| e.to_string())?;
     if converted.title.is_empty() && converted.content.is_empty() {
         return Err(
@@ -35,6 +39,45 @@ fn create_count_arg() -> clap::Arg<'static> {
         .required(false)
 }
 
+/// Arg-Name: `format`
+fn create_output_format_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_output_format_arg");
+    clap::Arg::new("format")
+        .help("How to print the results: an aligned table, JSON, or plain lines.")
+        .long("format")
+        .short('f')
+        .takes_value(true)
+        .required(false)
+        .ignore_case(true)
+        .possible_values(&["table", "json", "plain"])
+        .default_value("table")
+}
+
+/// Arg-Name: `filter`
+fn create_text_filter_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_text_filter_arg");
+    clap::Arg::new("filter")
+        .help("Only include notes whose title contains the given text.")
+        .long("filter")
+        .takes_value(true)
+        .required(false)
+        .value_name("TEXT")
+}
+
+/// Arg-Name: `tags`
+fn create_tags_filter_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_tags_filter_arg");
+    clap::Arg::new("tags")
+        .help("Only include notes tagged with each of the given tags. Separate multiple tags with commas.")
+        .long("tags")
+        .short('T')
+        .takes_value(true)
+        .required(false)
+        .use_value_delimiter(true)
+        .multiple_values(true)
+        .require_value_delimiter(true)
+}
+
 /// Arg-Name: `order`
 fn create_order_arg() -> clap::Arg<'static> {
     crate::flame_guard!("bins", "cli", "app", "create_order_arg");
@@ -74,11 +117,11 @@ fn add_note_cmd() -> clap::Command<'static> {
                 .alias("heading")
                 .alias("header")
                 .takes_value(true)
-                .required_unless_present_any(&["content", "json"]),
+                .required_unless_present_any(&["content", "stdin", "json"]),
         )
         .arg(
             clap::Arg::new("content")
-                .long_help("The main content, or body, of the note. Markdown is supported for note content.")
+                .long_help("The main content, or body, of the note. Markdown is supported for note content. Use '-' to read the content from stdin.")
                 .help("The content of the note to be created.")
                 .long("content")
                 .short('c')
@@ -87,6 +130,14 @@ fn add_note_cmd() -> clap::Command<'static> {
                 .takes_value(true)
                 .required(false),
         )
+        .arg(
+            clap::Arg::new("stdin")
+                .help("Read the content of the note from stdin, the same as '--content -'.")
+                .long("stdin")
+                .takes_value(false)
+                .required(false)
+                .conflicts_with("content"),
+        )
         .arg(
             clap::Arg::new("tags")
                 .long_help("A comma-separated list of strings which will be applied to the new note as tags. Tags can be used to better organize notes through cataloging and grouping, and allows for easier searching.")
@@ -103,7 +154,7 @@ fn add_note_cmd() -> clap::Command<'static> {
         .arg(
             clap::Arg::new("json")
                 .help("Submit the note as a Json object (in string form).")
-                .long_help("Submit the note as a JSON object. Expected format is { \"title\": \"...\", \"content\": \"...\", \"tags\": [\"...\", \"...\"] }. All fields are technically optional, but at least one of title or content is required.")
+                .long_help("Submit the note as a JSON object. Expected format is { \"title\": \"...\", \"content\": \"...\", \"tags\": [\"...\", \"...\"] }. All fields are technically optional, but at least one of title or content is required. Use '-' to read the JSON from stdin.")
                 .short('j')
                 .long("json")
                 .takes_value(true)
@@ -146,6 +197,7 @@ fn search_notes_cmd() -> clap::Command<'static> {
         .arg(create_count_arg())
         .arg(create_order_arg())
         .arg(create_reverse_arg())
+        .arg(create_output_format_arg())
 }
 
 fn list_all_cmd() -> clap::Command<'static> {
@@ -156,6 +208,16 @@ fn list_all_cmd() -> clap::Command<'static> {
         .arg(create_count_arg())
         .arg(create_order_arg())
         .arg(create_reverse_arg())
+        .arg(create_output_format_arg())
+        .arg(create_text_filter_arg())
+        .arg(create_tags_filter_arg())
+        .arg(
+            clap::Arg::new("no-archived")
+                .help("Hide archived notes.")
+                .long("no-archived")
+                .takes_value(false)
+                .required(false),
+        )
 }
 
 fn delete_note_cmd() -> clap::Command<'static> {
@@ -175,6 +237,178 @@ fn delete_note_cmd() -> clap::Command<'static> {
         )
 }
 
+/// Creates one of the `pin`, `unpin`, `archive`, or `unarchive` commands, which all take a single note id.
+fn flag_note_cmd(name: &'static str, about: &'static str) -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "flag_note_cmd");
+    clap::Command::new(name).about(about).arg(
+        clap::Arg::new("id")
+            .help("The id of the note.")
+            .forbid_empty_values(true)
+            .required(true)
+            .value_name("NOTE_ID")
+            .validator(|input| TinyId::from_str(input).map_err(|err| err.to_string())),
+    )
+}
+
+fn stats_cmd() -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "stats_cmd");
+    clap::Command::new("stats")
+        .about("Show a summary of the notes database")
+        .arg(
+            clap::Arg::new("json")
+                .help("Print the summary as JSON instead of human readable text.")
+                .long("json")
+                .takes_value(false)
+                .required(false),
+        )
+}
+
+fn merge_cmd() -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "merge_cmd");
+    clap::Command::new("merge")
+        .about("Merge the notes of two database files")
+        .long_about("Merges the notes of the incoming database into the base database and writes the result to the output file. Neither input file is modified.")
+        .arg(
+            clap::Arg::new("base")
+                .help("The database file to merge into.")
+                .required(true)
+                .forbid_empty_values(true)
+                .value_name("BASE_DB"),
+        )
+        .arg(
+            clap::Arg::new("incoming")
+                .help("The database file whose notes are merged into the base.")
+                .required(true)
+                .forbid_empty_values(true)
+                .value_name("INCOMING_DB"),
+        )
+        .arg(
+            clap::Arg::new("out")
+                .help("Where to write the merged database.")
+                .long("out")
+                .short('o')
+                .alias("output")
+                .takes_value(true)
+                .forbid_empty_values(true)
+                .value_name("OUT_DB")
+                .required_unless_present("dry-run"),
+        )
+        .arg(
+            clap::Arg::new("policy")
+                .help("How to resolve notes that exist in both databases.")
+                .long_help("How to resolve notes that exist in both databases. 'skip' keeps the base note, 'overwrite' keeps the incoming note, 'reassign' keeps both by giving the incoming note a new id, and 'newer' keeps whichever note was updated most recently.")
+                .long("policy")
+                .short('p')
+                .takes_value(true)
+                .required(false)
+                .ignore_case(true)
+                .possible_values(&["skip", "overwrite", "reassign", "newer"])
+                .default_value("newer"),
+        )
+        .arg(
+            clap::Arg::new("dry-run")
+                .help("Print the merge report without writing the output file.")
+                .long("dry-run")
+                .short('n')
+                .takes_value(false)
+                .required(false),
+        )
+}
+
+fn export_cmd() -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "export_cmd");
+    clap::Command::new("export")
+        .about("Export notes as JSON, Markdown, or CSV")
+        .long_about("Exports every note, or only the notes matching '--filter' and '--tags', sorted by '--order' and limited to '--count'. Without '--order' or '--reverse' notes are exported in canonical order (oldest created first, then by id) so that exporting the same notes always gives the same output.")
+        .arg(create_count_arg())
+        // `-o` is taken by `--out`.
+        .arg(create_order_arg().short('O'))
+        .arg(create_reverse_arg())
+        .arg(create_text_filter_arg())
+        .arg(create_tags_filter_arg())
+        .arg(
+            clap::Arg::new("format")
+                .help("The format to export the notes in.")
+                .long_help("The format to export the notes in. 'json' writes every note in full, 'markdown' writes each note as a heading followed by its content and tags, and 'csv' writes the id, title, tags, created and updated timestamps of each note.")
+                .long("format")
+                .short('f')
+                .takes_value(true)
+                .required(false)
+                .ignore_case(true)
+                .possible_values(&["json", "markdown", "md", "csv"])
+                .default_value("json"),
+        )
+        .arg(
+            clap::Arg::new("wrap")
+                .help("Hard-wrap note content at the given column when exporting markdown.")
+                .long("wrap")
+                .short('w')
+                .takes_value(true)
+                .required(false)
+                .value_name("COLUMNS")
+                .validator(|input| input.parse::<usize>().map(|_| ()).map_err(|err| err.to_string())),
+        )
+        .arg(
+            clap::Arg::new("out")
+                .help("Where to write the export, defaults to stdout.")
+                .long("out")
+                .short('o')
+                .alias("output")
+                .takes_value(true)
+                .required(false)
+                .forbid_empty_values(true)
+                .value_name("PATH"),
+        )
+}
+
+fn import_cmd() -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "import_cmd");
+    clap::Command::new("import")
+        .about("Import notes from a JSON or Markdown file")
+        .long_about("Imports every note in the given file as a new note. Entries that cannot be read are skipped with a warning rather than aborting the import.")
+        .arg(
+            clap::Arg::new("file")
+                .help("The file to import notes from.")
+                .required(true)
+                .forbid_empty_values(true)
+                .value_name("FILE"),
+        )
+        .arg(
+            clap::Arg::new("format")
+                .help("The format of the file, defaults to markdown for '.md' files and json otherwise.")
+                .long_help("The format of the file. 'json' expects an array of { \"title\": \"...\", \"content\": \"...\", \"tags\": [\"...\"] } objects, 'markdown' starts a new note at every '# ' heading. Defaults to markdown for '.md' and '.markdown' files and json otherwise.")
+                .long("format")
+                .short('f')
+                .takes_value(true)
+                .required(false)
+                .ignore_case(true)
+                .possible_values(&["json", "markdown", "md"]),
+        )
+}
+
+fn completions_cmd() -> clap::Command<'static> {
+    crate::flame_guard!("bins", "cli", "app", "completions_cmd");
+    clap::Command::new("completions")
+        .about("Print a shell completion script to stdout")
+        .hide(true)
+        .arg(
+            clap::Arg::new("shell")
+                .help("The shell to generate completions for.")
+                .required(true)
+                .ignore_case(true)
+                .possible_values(&["bash", "zsh", "fish", "powershell"])
+                .value_name("SHELL"),
+        )
+}
+
+/// Writes a completion script for `shell` to `out`, describing the given `app` (which should come from
+/// [`create_app`]).
+pub fn write_completions(shell: clap_complete::Shell, app: &mut clap::Command<'static>, out: &mut dyn std::io::Write) {
+    crate::flame_guard!("bins", "cli", "app", "write_completions");
+    let bin_name = app.get_bin_name().unwrap_or_else(|| app.get_name()).to_string();
+    clap_complete::generate(shell, app, bin_name, out);
+}
+
 /// Arg-Name: `interactive`
 fn create_interactive_arg() -> clap::Arg<'static> {
     crate::flame_guard!("bins", "cli", "app", "create_interactive_arg");
@@ -203,6 +437,49 @@ fn create_verbosity_arg() -> clap::Arg<'static> {
         .global(true)
 }
 
+/// Arg-Name: `db`
+fn create_db_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_db_arg");
+    clap::Arg::new("db")
+        .help("The database file to use.")
+        .long_help("The database file to use. Defaults to 'notes.fdb' in the platform data directory.")
+        .long("db")
+        .alias("database")
+        .takes_value(true)
+        .required(false)
+        .forbid_empty_values(true)
+        .value_name("PATH")
+        .global(true)
+}
+
+/// Arg-Name: `log-level`
+fn create_log_level_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_log_level_arg");
+    clap::Arg::new("log-level")
+        .help("Enable logging at the given level.")
+        .long_help("Enables logging of events at or above the given level. Logs are written to stderr unless '--log-file' is given.")
+        .long("log-level")
+        .takes_value(true)
+        .required(false)
+        .ignore_case(true)
+        .possible_values(&["trace", "debug", "info", "warn", "error"])
+        .global(true)
+}
+
+/// Arg-Name: `log-file`
+fn create_log_file_arg() -> clap::Arg<'static> {
+    crate::flame_guard!("bins", "cli", "app", "create_log_file_arg");
+    clap::Arg::new("log-file")
+        .help("Write logs to the given file instead of stderr.")
+        .long_help("Appends logs to the given file instead of writing them to stderr. Implies '--log-level info' if no log level is given.")
+        .long("log-file")
+        .takes_value(true)
+        .required(false)
+        .forbid_empty_values(true)
+        .value_name("PATH")
+        .global(true)
+}
+
 pub fn create_app() -> clap::Command<'static> {
     crate::flame_guard!("bins", "cli", "app", "create_app");
     clap::Command::new(clap::crate_name!())
@@ -220,10 +497,22 @@ pub fn create_app() -> clap::Command<'static> {
         .infer_long_args(true)
         .arg(create_verbosity_arg())
         .arg(create_interactive_arg())
+        .arg(create_db_arg())
+        .arg(create_log_level_arg())
+        .arg(create_log_file_arg())
         .subcommand(add_note_cmd().display_order(1))
         .subcommand(list_all_cmd().display_order(2))
         .subcommand(search_notes_cmd().display_order(3))
         .subcommand(delete_note_cmd().display_order(4))
+        .subcommand(merge_cmd().display_order(5))
+        .subcommand(flag_note_cmd("pin", "Pin the indicated note").display_order(6))
+        .subcommand(flag_note_cmd("unpin", "Unpin the indicated note").display_order(7))
+        .subcommand(flag_note_cmd("archive", "Archive the indicated note").display_order(8))
+        .subcommand(flag_note_cmd("unarchive", "Unarchive the indicated note").display_order(9))
+        .subcommand(stats_cmd().display_order(10))
+        .subcommand(export_cmd().display_order(11))
+        .subcommand(import_cmd().display_order(12))
+        .subcommand(completions_cmd())
 }
 
 #[cfg(test)]
@@ -238,6 +527,62 @@ mod tests {
         list_all_cmd().debug_assert();
         search_notes_cmd().debug_assert();
         delete_note_cmd().debug_assert();
+        merge_cmd().debug_assert();
+        flag_note_cmd("pin", "Pin").debug_assert();
+        stats_cmd().debug_assert();
+        export_cmd().debug_assert();
+        import_cmd().debug_assert();
+        completions_cmd().debug_assert();
         create_app().debug_assert();
     }
+
+    #[test]
+    #[no_coverage]
+    fn bash_completions() {
+        let mut output = Vec::new();
+        write_completions(clap_complete::Shell::Bash, &mut create_app(), &mut output);
+        let script = String::from_utf8(output).expect("completions should be utf-8");
+        let lines = script.lines().map(str::trim).collect::<Vec<_>>();
+
+        assert_eq!(lines.first(), Some(&"_noted() {"));
+        assert_eq!(lines.last(), Some(&"complete -F _noted -o bashdefault -o default noted"));
+
+        // The top level offers every subcommand, in the order they were added to the app.
+        let subcommands = [
+            "add",
+            "list",
+            "find",
+            "delete",
+            "merge",
+            "pin",
+            "unpin",
+            "archive",
+            "unarchive",
+            "stats",
+            "export",
+            "import",
+            "completions",
+            "help",
+        ];
+        let top_level = lines
+            .iter()
+            .skip_while(|line| **line != "noted)")
+            .nth(1)
+            .and_then(|line| line.strip_prefix("opts=\""))
+            .and_then(|line| line.strip_suffix('"'))
+            .expect("completions are missing the top level options");
+        let (flags, words): (Vec<_>, Vec<_>) = top_level.split_whitespace().partition(|word| word.starts_with('-'));
+        assert_eq!(words, subcommands);
+        for flag in ["--verbose", "--interactive", "--db", "--log-level", "--log-file"] {
+            assert!(flags.contains(&flag), "completions are missing `{}`", flag);
+        }
+
+        // Each subcommand is recognised while walking the words, and has its own set of options.
+        for subcommand in subcommands {
+            let recognised = format!("cmd+=\"__{}\"", subcommand);
+            let case = format!("noted__{})", subcommand);
+            assert!(lines.contains(&recognised.as_str()), "completions are missing `{}`", recognised);
+            assert!(lines.contains(&case.as_str()), "completions are missing `{}`", case);
+        }
+    }
 }
@@ -0,0 +1,205 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Persistent configuration for the CLI, read from a TOML file in the platform config directory.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{CliVerbosity, OutputFormat};
+
+/// Settings read from the CLI config file, see [`CliConfig::load_default`]. Every field is optional, values
+/// given on the command line always win over the file, and anything not set in either falls back to the
+/// built-in default.
+///
+/// ```toml
+/// database_path = "/home/me/notes.fdb"
+/// default_format = "plain"
+/// default_verbosity = "verbose"
+/// date_format = "iso-24h"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct CliConfig {
+    /// The database used when `--db` is not given.
+    pub database_path: Option<PathBuf>,
+    /// The output format of `list` and `find` when `--format` is not given.
+    pub default_format: Option<OutputFormat>,
+    /// The verbosity used when no `--verbose` flags are given.
+    pub default_verbosity: Option<CliVerbosity>,
+    /// How timestamps are printed, either a preset name or a format description, see
+    /// [`crate::util::dtf::format`].
+    pub date_format: Option<String>,
+}
+
+impl CliConfig {
+    const DEFAULT_FILENAME: &'static str = "cli.toml";
+
+    /// The path of the config file in the platform config directory, or `None` if there is no platform
+    /// config directory.
+    pub fn default_config_file() -> Option<PathBuf> {
+        crate::flame_guard!("bins", "cli", "config", "default_config_file");
+        directories::ProjectDirs::from("rs", "imtony", "Noted")
+            .map(|dirs| dirs.config_dir().join(Self::DEFAULT_FILENAME))
+    }
+
+    /// Loads the config from [`CliConfig::default_config_file`], see [`CliConfig::load`].
+    ///
+    /// ## Errors
+    /// - See [`CliConfig::load`].
+    pub fn load_default() -> crate::Result<Self> {
+        crate::flame_guard!("bins", "cli", "config", "load_default");
+        Self::default_config_file().map_or_else(|| Ok(Self::default()), Self::load)
+    }
+
+    /// Loads the config file at `path`. A missing file is not an error, it just means nothing has been
+    /// configured, so the default (empty) config is returned.
+    ///
+    /// ## Errors
+    /// - If the file exists but cannot be read.
+    /// - If the file is not valid TOML, or contains unknown keys or values.
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        crate::flame_guard!("bins", "cli", "config", "load");
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|err| {
+                crate::Error::serde(format!("invalid config file '{}': {}", path.display(), err))
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Parses a config from the TOML in `text`.
+    ///
+    /// ## Errors
+    /// - If `text` is not valid TOML, or contains unknown keys or values.
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        crate::flame_guard!("bins", "cli", "config", "parse");
+        toml::from_str(text).map_err(|err| crate::Error::serde(err.to_string()))
+    }
+
+    /// The database to use: the global `--db` arg, then `database_path`, then `notes.fdb` in the platform data
+    /// directory (or the working directory if there is no platform data directory).
+    pub fn database_path(&self, args: &clap::ArgMatches) -> PathBuf {
+        crate::flame_guard!("bins", "cli", "config", "database_path");
+        if let Some(path) = args.value_of("db") {
+            return PathBuf::from(path);
+        }
+        self.database_path.clone().unwrap_or_else(|| {
+            directories::ProjectDirs::from("rs", "imtony", "Noted").map_or_else(
+                || PathBuf::from("notes.fdb"),
+                |dirs| dirs.data_dir().join("notes.fdb"),
+            )
+        })
+    }
+
+    /// The verbosity to use: the number of `--verbose` flags if there are any, then `default_verbosity`, then
+    /// [`CliVerbosity::Quiet`].
+    pub fn verbosity(&self, args: &clap::ArgMatches) -> CliVerbosity {
+        crate::flame_guard!("bins", "cli", "config", "verbosity");
+        match args.occurrences_of("verbose") {
+            0 => self.default_verbosity.unwrap_or(CliVerbosity::Quiet),
+            count => count.into(),
+        }
+    }
+
+    /// The date format to pass to [`crate::util::dtf::format`]: `date_format`, then
+    /// [`crate::util::dtf::DEFAULT_DATE_FORMAT`].
+    pub fn date_format(&self) -> &str {
+        self.date_format
+            .as_deref()
+            .unwrap_or(crate::util::dtf::DEFAULT_DATE_FORMAT)
+    }
+
+    /// The output format of `list` or `find`: the `--format` arg if it was given explicitly, then
+    /// `default_format`, then the default value of the `--format` arg.
+    pub fn output_format(&self, args: &clap::ArgMatches) -> OutputFormat {
+        crate::flame_guard!("bins", "cli", "config", "output_format");
+        match self.default_format {
+            Some(format) if args.occurrences_of("format") == 0 => format,
+            _ => super::parse_output_format(args),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn matches(args: &[&str]) -> clap::ArgMatches {
+        super::super::app::create_app()
+            .try_get_matches_from(std::iter::once(&"noted").chain(args))
+            .expect("Unable to parse args")
+    }
+
+    #[test]
+    #[no_coverage]
+    fn precedence() {
+        let config = CliConfig::parse(
+            r#"
+            database_path = "from-config.fdb"
+            default_format = "json"
+            default_verbosity = "verbose"
+            date_format = "iso-24h"
+            "#,
+        )
+        .expect("Unable to parse config");
+
+        // Flags beat the config file.
+        let input = matches(&["--db", "from-flag.fdb", "-v", "list", "--format", "plain"]);
+        let (_, list_args) = input.subcommand().unwrap();
+        assert_eq!(config.database_path(&input), PathBuf::from("from-flag.fdb"));
+        assert_eq!(config.verbosity(&input), CliVerbosity::Normal);
+        assert_eq!(config.output_format(list_args), OutputFormat::Plain);
+
+        // The config file beats the built-in defaults.
+        let input = matches(&["list"]);
+        let (_, list_args) = input.subcommand().unwrap();
+        assert_eq!(
+            config.database_path(&input),
+            PathBuf::from("from-config.fdb")
+        );
+        assert_eq!(config.verbosity(&input), CliVerbosity::Verbose);
+        assert_eq!(config.output_format(list_args), OutputFormat::Json);
+        assert_eq!(config.date_format(), "iso-24h");
+
+        // Without either the built-in defaults are used.
+        let empty = CliConfig::default();
+        assert_eq!(empty.verbosity(&input), CliVerbosity::Quiet);
+        assert_eq!(empty.output_format(list_args), OutputFormat::Table);
+        assert!(empty.database_path(&input).ends_with("notes.fdb"));
+        assert_eq!(empty.date_format(), crate::util::dtf::DEFAULT_DATE_FORMAT);
+
+        assert!(CliConfig::parse("default_format = \"yaml\"").is_err());
+        assert!(CliConfig::parse("unknown_key = 1").is_err());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn missing_file_uses_defaults() {
+        let path = std::env::temp_dir().join(format!(
+            "cli-config-tests-missing-{:010}.toml",
+            fastrand::u32(..)
+        ));
+        assert_eq!(
+            CliConfig::load(&path).expect("A missing config should not be an error"),
+            CliConfig::default()
+        );
+
+        std::fs::write(&path, "default_verbosity = \"very-verbose\"\n")
+            .expect("Unable to write config");
+        let config = CliConfig::load(&path).expect("Unable to load config");
+        assert_eq!(config.default_verbosity, Some(CliVerbosity::VeryVerbose));
+        assert_eq!(config.database_path, None);
+
+        std::fs::write(&path, "not toml at all").expect("Unable to write config");
+        assert!(CliConfig::load(&path).is_err());
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+}
@@ -4,19 +4,26 @@
 //! Typical process will be:
 //! - Use `clap` to parse args
 //! - Build a DTO from the args
-//! - Open a connection to the database
-//!   - This is not exactly correct, there should definitely be something between the database and the cli. Repository pattern? Service pattern? Something.
+//! - Open the database through a [`NoteService`], which validates changes and saves them
 //! - Process the command and return the results
 //! - Format and pretty-print the results
 
+use std::path::Path;
+
 use tinyid::TinyId;
 
+use crate::db::{Database, DatabaseStats, MergeReport, MergeStrategy};
+use crate::services::NoteService;
 use crate::types::{
     api::{Count, Filter, NoteFilter, Ordering},
-    CreateNote, DeleteNote, NoteDto,
+    CreateNote, DeleteNote, FlagNote, Note, NoteDto, NoteFlag,
 };
+use crate::util::validation::NoteValidator;
 
 mod app;
+mod config;
+
+use config::CliConfig;
 
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -26,7 +33,8 @@ pub struct NoteShape {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
 enum CliVerbosity {
     Quiet,
     Normal,
@@ -67,70 +75,54 @@ pub fn run_cli(args: std::env::Args) -> crate::Result {
     use crate::types::api::StringSearch;
     crate::flame_guard!("bins", "cli", "run_cli");
 
-    println!("Noted CLI. Args: {}", args.collect::<Vec<_>>().join(" "));
-    // let mut app = app::create_app();
+    // The same `App` is kept around so the `completions` command can describe it.
     let mut app = app::create_app();
-    let input = app.get_matches();
-    println!("ArgMatches: {:#?}", input);
+    let input = app.get_matches_mut();
 
+    init_logging_from_args(&input)?;
+
+    // Values given on the command line always take precedence over the config file.
+    let config = CliConfig::load_default()?;
+    let db_path = config.database_path(&input);
     let interactive = input.is_present("interactive");
-    let verbosity: CliVerbosity = input.occurrences_of("verbose").into();
+    let verbosity = config.verbosity(&input);
+    if verbosity >= CliVerbosity::VeryVerbose {
+        println!("Noted CLI. Args: {}", args.collect::<Vec<_>>().join(" "));
+        println!("ArgMatches: {:#?}", input);
+    }
     match input.subcommand() {
         Some(("add", add_args)) => {
-            let dto: CreateNote = if add_args.is_present("json") {
-                let json_string = add_args
-                    .value_of("json")
-                    .expect("json arg has already been checked to exist");
-                let NoteShape {
-                    title,
-                    content,
-                    tags,
-                } = serde_json::from_str(json_string)
-                    .expect("json arg has already been validated to be valid");
-
-                (title, content, tags).into()
-            } else {
-                let title = add_args
-                    .value_of("title")
-                    .map(ToString::to_string)
-                    .unwrap_or_default();
-                let content = add_args
-                    .value_of("content")
-                    .map(ToString::to_string)
-                    .unwrap_or_default();
-                let tags = add_args
-                    .values_of("tags")
-                    .map(|vals| vals.map(ToString::to_string).collect::<Vec<_>>())
-                    .unwrap_or_default();
-
-                (title, content, tags).into()
-            };
+            let dto = create_dto_from_args(add_args, &mut std::io::stdin().lock())?;
 
             println!(
                 "Running `add` command ({}interactively) with verbosity level of `{}`",
                 if interactive { "" } else { "not " },
                 verbosity
             );
-            println!("CreateNote DTO: {:#?}", dto);
-            // TODO: apply CreateNote dto to database
+            if verbosity >= CliVerbosity::VeryVerbose {
+                println!("CreateNote DTO: {:#?}", dto);
+            }
+            let note = add_note(&db_path, dto)?;
+            match verbosity {
+                CliVerbosity::Quiet => println!("{}", note.id()),
+                CliVerbosity::Normal => println!("Created note {}", note.id()),
+                CliVerbosity::Verbose | CliVerbosity::VeryVerbose => println!("Created note:\n{}", note),
+            }
         }
         Some(("list", list_args)) => {
-            println!(
-                "Running `list` command ({}interactively) with verbosity level of `{}`",
-                if interactive { "" } else { "not " },
-                verbosity
-            );
             let (order, count) = parse_order_count(list_args);
-            let filter = Filter::empty();
+            let filter = parse_filter(list_args);
+            let include_archived = !list_args.is_present("no-archived");
 
-            // TODO: Get notes from database and apply filter, order, and count
+            let db = NoteService::open(&db_path)?.into_database();
+            let notes = list_notes(&db, &filter, order, count, include_archived);
+            let format = config.output_format(list_args);
+            print!(
+                "{}",
+                query_output("list", &notes, format, interactive, verbosity, config.date_format())?
+            );
         }
         Some(("find", find_args)) => {
-            println!(
-                "Running `find` command ({}interactively) with verbosity level of `{}`",
-                if interactive { "" } else { "not " },
-                verbosity
-            );
             let needle = find_args
                 .value_of("text")
                 .expect("text is required but could not be obtained");
@@ -165,7 +157,26 @@ pub fn run_cli(args: std::env::Args) -> crate::Result {
                 f
             };
 
-            // TODO: Query database using filter, order, and count
+            let mut service = NoteService::open(&db_path)?;
+            let results = find_notes(service.database(), needle, &filter, is_fts, order, count)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if interactive {
+                println!("Running `find` command (interactively) with verbosity level of `{}`", verbosity);
+                crate::bins::icli::pick_and_act(&mut service, &results)?;
+            } else {
+                let results = results.iter().collect::<Vec<_>>();
+                let format = config.output_format(find_args);
+                if results.is_empty() && verbosity > CliVerbosity::Quiet && format != OutputFormat::Json {
+                    println!("No notes found matching '{}'.", needle);
+                }
+                print!(
+                    "{}",
+                    query_output("find", &results, format, interactive, verbosity, config.date_format())?
+                );
+            }
         }
         Some(("delete", delete_args)) => {
             println!(
@@ -175,8 +186,157 @@ pub fn run_cli(args: std::env::Args) -> crate::Result {
             );
             let id = delete_args.value_of_t_or_exit::<TinyId>("id");
             let dto: DeleteNote = id.into();
-            println!("DeleteNote DTO: {0:?}\nID: {1} ({1:?}", dto, id);
-            // TODO: apply DeleteNote dto to database
+            if verbosity >= CliVerbosity::VeryVerbose {
+                println!("DeleteNote DTO: {0:?}\nID: {1} ({1:?})", dto, id);
+            }
+
+            match delete_note(&db_path, dto) {
+                Ok(note) => match verbosity {
+                    CliVerbosity::Quiet => {}
+                    CliVerbosity::Normal => println!("Deleted note {}", note.id()),
+                    CliVerbosity::Verbose | CliVerbosity::VeryVerbose => println!("Deleted note:\n{}", note),
+                },
+                Err(error) => {
+                    if verbosity > CliVerbosity::Quiet {
+                        eprintln!("Unable to delete note {}: {}", id, error);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Some(("merge", merge_args)) => {
+            println!(
+                "Running `merge` command ({}interactively) with verbosity level of `{}`",
+                if interactive { "" } else { "not " },
+                verbosity
+            );
+            let base = merge_args
+                .value_of("base")
+                .expect("base is required but could not be obtained");
+            let incoming = merge_args
+                .value_of("incoming")
+                .expect("incoming is required but could not be obtained");
+            let out = if merge_args.is_present("dry-run") {
+                None
+            } else {
+                merge_args.value_of("out").map(Path::new)
+            };
+            let strategy = parse_merge_strategy(merge_args);
+
+            let report = merge_files(base, incoming, out, strategy)?;
+            match out {
+                Some(out) => println!("Merged into '{}': {}", out.display(), report),
+                None => println!("Dry run, nothing written: {}", report),
+            }
+        }
+        Some((command @ ("pin" | "unpin" | "archive" | "unarchive"), flag_args)) => {
+            println!(
+                "Running `{}` command ({}interactively) with verbosity level of `{}`",
+                command,
+                if interactive { "" } else { "not " },
+                verbosity
+            );
+            let id = flag_args.value_of_t_or_exit::<TinyId>("id");
+            let (flag, value) = match command {
+                "pin" => (NoteFlag::Pinned, true),
+                "unpin" => (NoteFlag::Pinned, false),
+                "archive" => (NoteFlag::Archived, true),
+                _ => (NoteFlag::Archived, false),
+            };
+
+            let note = set_note_flag(&db_path, FlagNote::new(id, flag, value))?;
+            println!(
+                "Note {} is now {} and {}.",
+                note.id(),
+                if note.is_pinned() { "pinned" } else { "not pinned" },
+                if note.is_archived() { "archived" } else { "not archived" }
+            );
+        }
+        Some(("stats", stats_args)) => {
+            let json = stats_args.is_present("json");
+            if !json {
+                println!(
+                    "Running `stats` command ({}interactively) with verbosity level of `{}`",
+                    if interactive { "" } else { "not " },
+                    verbosity
+                );
+            }
+            let db = NoteService::open(&db_path)?.into_database();
+            println!("{}", format_stats(&db.stats(), json)?);
+        }
+        Some(("export", export_args)) => {
+            let out = export_args.value_of("out").map(Path::new);
+            if out.is_some() {
+                println!(
+                    "Running `export` command ({}interactively) with verbosity level of `{}`",
+                    if interactive { "" } else { "not " },
+                    verbosity
+                );
+            }
+            let format = parse_export_format(export_args);
+            let wrap = export_args.value_of_t::<usize>("wrap").ok();
+            let (order, count) = parse_order_count(export_args);
+            let explicit_order = export_args.occurrences_of("order") > 0 || export_args.is_present("reverse");
+            let filter = parse_filter(export_args);
+            let db = NoteService::open(&db_path)?.into_database();
+            let notes = if explicit_order {
+                db.query_owned(&filter, order, count)
+            } else {
+                let mut notes = db.query_owned(&filter, order, Count::All);
+                Database::sort_canonical(&mut notes);
+                count.apply(notes.into_iter()).collect()
+            };
+            let output = export_notes(&notes, format, wrap)?;
+            match out {
+                Some(out) => {
+                    std::fs::write(out, output)?;
+                    if verbosity > CliVerbosity::Quiet {
+                        println!("Exported {} notes to '{}'.", notes.len(), out.display());
+                    }
+                }
+                None => print!("{}", output),
+            }
+        }
+        Some(("import", import_args)) => {
+            println!(
+                "Running `import` command ({}interactively) with verbosity level of `{}`",
+                if interactive { "" } else { "not " },
+                verbosity
+            );
+            let file = Path::new(
+                import_args
+                    .value_of("file")
+                    .expect("file is required but could not be obtained"),
+            );
+            let format = parse_import_format(import_args, file);
+            let report = import_file(&db_path, file, format)?;
+            for warning in &report.skipped {
+                eprintln!("Warning: {}", warning);
+            }
+            if verbosity > CliVerbosity::Quiet {
+                println!(
+                    "Imported {} notes from '{}' ({} skipped).",
+                    report.imported.len(),
+                    file.display(),
+                    report.skipped.len()
+                );
+            }
+            if verbosity >= CliVerbosity::Verbose {
+                print!("{}", format_notes(&report.imported.iter().collect::<Vec<_>>(), CliVerbosity::Normal));
+            }
+        }
+        Some(("completions", completions_args)) => {
+            let shell = match completions_args
+                .value_of("shell")
+                .map(str::to_lowercase)
+                .as_deref()
+            {
+                Some("zsh") => clap_complete::Shell::Zsh,
+                Some("fish") => clap_complete::Shell::Fish,
+                Some("powershell") => clap_complete::Shell::PowerShell,
+                _ => clap_complete::Shell::Bash,
+            };
+            app::write_completions(shell, &mut app, &mut std::io::stdout());
         }
         _ => unreachable!(),
     }
@@ -192,6 +352,526 @@ pub fn run_cli(args: std::env::Args) -> crate::Result {
     Ok(())
 }
 
+/// How many columns a single note is given when printing a list of notes.
+const SUMMARY_WIDTH: usize = 80;
+
+/// Builds the [`CreateNote`] dto for the `add` command. When `--json -`, `--content -`, or `--stdin` is given
+/// the JSON or content is read from `stdin` (which is only a parameter so it can be tested). Empty input
+/// gives a note with empty content.
+///
+/// ## Errors
+/// - If `stdin` cannot be read, or is not valid utf-8.
+/// - If the JSON read from `stdin` is not a valid [`NoteShape`].
+fn create_dto_from_args(args: &clap::ArgMatches, stdin: &mut dyn std::io::Read) -> crate::Result<CreateNote> {
+    crate::flame_guard!("bins", "cli", "create_dto_from_args");
+    let mut read_stdin = || -> crate::Result<String> {
+        let mut input = String::new();
+        stdin.read_to_string(&mut input)?;
+        Ok(input)
+    };
+
+    if let Some(json_string) = args.value_of("json") {
+        let NoteShape {
+            title,
+            content,
+            tags,
+        } = if json_string == "-" {
+            serde_json::from_str(&read_stdin()?)?
+        } else {
+            serde_json::from_str(json_string).expect("json arg has already been validated to be valid")
+        };
+
+        return Ok((title, content, tags).into());
+    }
+
+    let title = args
+        .value_of("title")
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let content = match args.value_of("content") {
+        _ if args.is_present("stdin") => read_stdin()?,
+        Some("-") => read_stdin()?,
+        content => content.map(ToString::to_string).unwrap_or_default(),
+    };
+    let tags = args
+        .values_of("tags")
+        .map(|vals| vals.map(ToString::to_string).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    Ok((title, content, tags).into())
+}
+
+/// Validates and applies the [`CreateNote`] dto to the database at `path` (creating the database if it does
+/// not exist yet), saves it, and returns the newly created note.
+///
+/// ## Errors
+/// - If the database fails to load or save.
+/// - If the note is invalid or cannot be created, see [`NoteService::create`].
+fn add_note(path: impl AsRef<Path>, dto: CreateNote) -> crate::Result<Note> {
+    crate::flame_guard!("bins", "cli", "add_note");
+    NoteService::open(path)?.create(dto)
+}
+
+/// Applies the [`DeleteNote`] dto to the database at `path`, saves it, and returns the removed note.
+///
+/// ## Errors
+/// - If the database fails to load or save.
+/// - [`DatabaseError::IdNotFound`](crate::DatabaseError::IdNotFound) if the note does not exist.
+fn delete_note(path: impl AsRef<Path>, dto: DeleteNote) -> crate::Result<Note> {
+    crate::flame_guard!("bins", "cli", "delete_note");
+    NoteService::open(path)?.delete(dto.id)
+}
+
+/// Formats `notes` for printing, one line per note. Quiet output is just the ids so it can be piped into
+/// other commands, normal output adds a summary line, and verbose output prints each note in full.
+fn format_notes(notes: &[&Note], verbosity: CliVerbosity) -> String {
+    use std::fmt::Write;
+    crate::flame_guard!("bins", "cli", "format_notes");
+
+    let mut output = String::new();
+    for note in notes {
+        // Writing to a `String` cannot fail.
+        let _ = match verbosity {
+            CliVerbosity::Quiet => writeln!(output, "{}", note.id()),
+            CliVerbosity::Normal => writeln!(output, "{}  {}", note.id(), note.summary_line(SUMMARY_WIDTH)),
+            CliVerbosity::Verbose | CliVerbosity::VeryVerbose => writeln!(output, "{}", note),
+        };
+    }
+    output
+}
+
+/// The formats supported by the `list` and `find` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum OutputFormat {
+    Table,
+    Json,
+    Plain,
+}
+
+fn parse_output_format(args: &clap::ArgMatches) -> OutputFormat {
+    crate::flame_guard!("bins", "cli", "parse_output_format");
+    match args
+        .value_of("format")
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("json") => OutputFormat::Json,
+        Some("plain") => OutputFormat::Plain,
+        _ => OutputFormat::Table,
+    }
+}
+
+/// The widest a title can be in the table printed by [`format_table`].
+const TABLE_TITLE_WIDTH: usize = 40;
+
+/// Renders `notes` in the given [`OutputFormat`], printing timestamps with `date_format` (see
+/// [`crate::util::dtf::format`]).
+/// - Table is an aligned table (see [`format_table`]) at normal verbosity, and otherwise the same as plain.
+/// - Json is the full list of notes, pretty printed, regardless of verbosity.
+/// - Plain is one line per note, see [`format_notes`].
+///
+/// ## Errors
+/// - If the notes cannot be serialized to JSON.
+fn render_notes(
+    notes: &[&Note],
+    format: OutputFormat,
+    verbosity: CliVerbosity,
+    date_format: &str,
+) -> crate::Result<String> {
+    crate::flame_guard!("bins", "cli", "render_notes");
+    match (format, verbosity) {
+        (OutputFormat::Json, _) => Ok(serde_json::to_string_pretty(notes)? + "\n"),
+        (OutputFormat::Table, CliVerbosity::Normal) => Ok(format_table(notes, date_format)),
+        _ => Ok(format_notes(notes, verbosity)),
+    }
+}
+
+/// Builds everything the `list` and `find` commands print for `notes`: the `Running ...` banner followed by
+/// the notes rendered by [`render_notes`]. The banner is left out of JSON output so that it can be piped
+/// straight into other tools, the same as `stats --json`.
+///
+/// ## Errors
+/// - If the notes cannot be serialized to JSON.
+fn query_output(
+    command: &str,
+    notes: &[&Note],
+    format: OutputFormat,
+    interactive: bool,
+    verbosity: CliVerbosity,
+    date_format: &str,
+) -> crate::Result<String> {
+    crate::flame_guard!("bins", "cli", "query_output");
+    let rendered = render_notes(notes, format, verbosity, date_format)?;
+    if format == OutputFormat::Json {
+        return Ok(rendered);
+    }
+
+    Ok(format!(
+        "Running `{}` command ({}interactively) with verbosity level of `{}`\n{}",
+        command,
+        if interactive { "" } else { "not " },
+        verbosity,
+        rendered
+    ))
+}
+
+/// Formats `notes` as a table with id, title, tag count, and last updated columns. Column widths are taken
+/// from the widest value in each column (as measured by [`unicode_width`]), and titles wider than
+/// [`TABLE_TITLE_WIDTH`] are truncated with an ellipsis. Nothing is printed if there are no notes.
+fn format_table(notes: &[&Note], date_format: &str) -> String {
+    use std::fmt::Write;
+    use unicode_width::UnicodeWidthStr;
+    crate::flame_guard!("bins", "cli", "format_table");
+
+    if notes.is_empty() {
+        return String::new();
+    }
+
+    let header = ["ID", "TITLE", "TAGS", "UPDATED"].map(ToString::to_string);
+    let rows = notes
+        .iter()
+        .map(|note| {
+            [
+                note.id().to_string(),
+                crate::util::text::truncate(note.title(), TABLE_TITLE_WIDTH).into_owned(),
+                note.tags().len().to_string(),
+                crate::util::dtf::format(note.updated(), date_format),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut widths = [0; 4];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let mut line = String::new();
+        for (cell, width) in row.iter().zip(widths) {
+            // Writing to a `String` cannot fail.
+            let _ = write!(line, "{}{}  ", cell, " ".repeat(width - cell.width()));
+        }
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Gets the notes in `db` that match `filter`, sorted by `order` and limited to `count`. Archived notes
+/// are skipped unless `include_archived` is set.
+fn list_notes<'db>(
+    db: &'db Database,
+    filter: &Filter,
+    order: Ordering,
+    count: Count,
+    include_archived: bool,
+) -> Vec<&'db Note> {
+    crate::flame_guard!("bins", "cli", "list_notes");
+    let predicate = filter.predicate();
+    let comparison = order.comparison();
+    let mut notes = db.find(|note| (include_archived || !note.is_archived()) && predicate(note));
+    notes.sort_by(|a, b| comparison(a, b));
+    count.apply(notes.into_iter()).collect()
+}
+
+/// Gets the notes in `db` found by `find`, sorted by `order` and limited to `count`. When `full_text` is set
+/// `needle` is searched for everywhere, otherwise only the fields in `filter` are checked.
+fn find_notes<'db>(
+    db: &'db Database,
+    needle: &str,
+    filter: &Filter,
+    full_text: bool,
+    order: Ordering,
+    count: Count,
+) -> Vec<&'db Note> {
+    crate::flame_guard!("bins", "cli", "find_notes");
+    if !full_text {
+        return list_notes(db, filter, order, count, true);
+    }
+
+    let comparison = order.comparison();
+    let mut notes = db.text_search(needle);
+    notes.sort_by(|a, b| comparison(a, b));
+    count.apply(notes.into_iter()).collect()
+}
+
+/// Formats `stats` for the `stats` command, either as pretty printed JSON or as human readable text.
+///
+/// ## Errors
+/// - If `stats` fails to serialize.
+fn format_stats(stats: &DatabaseStats, json: bool) -> crate::Result<String> {
+    crate::flame_guard!("bins", "cli", "format_stats");
+    if json {
+        Ok(serde_json::to_string_pretty(stats)?)
+    } else {
+        Ok(stats.to_string())
+    }
+}
+
+/// The formats supported by the `export` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum ExportFormat {
+    Json,
+    Markdown,
+    Csv,
+}
+
+/// Renders `notes` in the given [`ExportFormat`].
+/// - Json is the full list of notes, pretty printed.
+/// - Markdown is a `# title` heading, the content, and a `Tags:` footer (if there are any tags) for each note.
+///   The content is hard-wrapped at `wrap` columns if it is given, see [`Note::wrap_content`].
+/// - Csv is a header row followed by the `id,title,tags,created,updated` of each note. Tags are separated by
+///   `;` and timestamps are RFC 3339.
+///
+/// ## Errors
+/// - If the notes fail to serialize, or a timestamp cannot be formatted.
+fn export_notes(notes: &[Note], format: ExportFormat, wrap: Option<usize>) -> crate::Result<String> {
+    use time::format_description::well_known::Rfc3339;
+    crate::flame_guard!("bins", "cli", "export_notes");
+
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(notes)?),
+        ExportFormat::Markdown => Ok(notes
+            .iter()
+            .map(|note| {
+                let content = wrap.map_or_else(|| note.content().to_string(), |width| note.wrap_content(width).join("\n"));
+                let mut section = format!("# {}\n\n{}\n", note.title(), content);
+                if !note.tags().is_empty() {
+                    section.push_str(&format!("\nTags: {}\n", note.tags().join(", ")));
+                }
+                section
+            })
+            .collect::<Vec<_>>()
+            .join("\n")),
+        ExportFormat::Csv => {
+            let mut output = String::from("id,title,tags,created,updated\n");
+            for note in notes {
+                let fields = [
+                    note.id().to_string(),
+                    note.title().to_string(),
+                    note.tags().join(";"),
+                    note.created().format(&Rfc3339).map_err(time::Error::from)?,
+                    note.updated().format(&Rfc3339).map_err(time::Error::from)?,
+                ];
+                let row = fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>();
+                output.push_str(&row.join(","));
+                output.push('\n');
+            }
+            Ok(output)
+        }
+    }
+}
+
+/// Quotes `field` for a CSV file if it contains a comma, quote, or line break, doubling any quotes inside it.
+fn csv_field(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+fn parse_export_format(args: &clap::ArgMatches) -> ExportFormat {
+    crate::flame_guard!("bins", "cli", "parse_export_format");
+    match args
+        .value_of("format")
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("markdown" | "md") => ExportFormat::Markdown,
+        Some("csv") => ExportFormat::Csv,
+        _ => ExportFormat::Json,
+    }
+}
+
+/// The formats supported by the `import` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum ImportFormat {
+    Json,
+    Markdown,
+}
+
+/// The outcome of the `import` command.
+#[derive(Debug, Clone, Default)]
+struct ImportReport {
+    /// The notes that were added to the database.
+    imported: Vec<Note>,
+    /// A warning for every entry that was skipped.
+    skipped: Vec<String>,
+}
+
+/// Parses `text` into the notes it contains. Entries that cannot be read, or that fail validation, are
+/// skipped and a warning is returned for each of them instead.
+/// - Json must be an array of objects matching [`NoteShape`].
+/// - Markdown starts a new note at every `# ` heading, with the lines up to the next heading as its content.
+///   A trailing `Tags: a, b` line (as written by the `export` command) is read as the note's tags.
+///
+/// ## Errors
+/// - If the text is not a JSON array, when importing JSON.
+fn parse_import(text: &str, format: ImportFormat) -> crate::Result<(Vec<CreateNote>, Vec<String>)> {
+    crate::flame_guard!("bins", "cli", "parse_import");
+    let mut warnings = Vec::new();
+    let shapes = match format {
+        ImportFormat::Json => {
+            let entries: Vec<serde_json::Value> = serde_json::from_str(text)?;
+            entries
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, entry)| match serde_json::from_value::<NoteShape>(entry) {
+                    Ok(shape) => Some((format!("entry {}", i + 1), shape)),
+                    Err(error) => {
+                        warnings.push(format!("Skipping entry {}, {}", i + 1, error));
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
+        ImportFormat::Markdown => {
+            let mut shapes = Vec::new();
+            let mut current: Option<(usize, String, Vec<&str>)> = None;
+            let finish = |current: Option<(usize, String, Vec<&str>)>, shapes: &mut Vec<_>| {
+                let Some((line, title, mut lines)) = current else { return };
+                while lines.last().map_or(false, |l| l.trim().is_empty()) {
+                    lines.pop();
+                }
+                let tags = match lines.last().and_then(|l| l.strip_prefix("Tags: ")) {
+                    Some(tags) => {
+                        let tags = tags.split(',').map(|t| t.trim().to_string()).collect();
+                        lines.pop();
+                        tags
+                    }
+                    None => Vec::new(),
+                };
+                let content = lines.join("\n").trim().to_string();
+                shapes.push((format!("heading on line {}", line), NoteShape { title, content, tags }));
+            };
+            for (i, line) in text.lines().enumerate() {
+                if let Some(title) = line.strip_prefix("# ") {
+                    finish(current.take(), &mut shapes);
+                    current = Some((i + 1, title.trim().to_string(), Vec::new()));
+                } else if let Some((_, _, lines)) = &mut current {
+                    lines.push(line);
+                } else if !line.trim().is_empty() {
+                    warnings.push(format!("Skipping line {}, it is not under a `# ` heading", i + 1));
+                }
+            }
+            finish(current, &mut shapes);
+            shapes
+        }
+    };
+
+    let validator = NoteValidator::default();
+    let mut notes = Vec::with_capacity(shapes.len());
+    for (location, NoteShape { title, content, tags }) in shapes {
+        let dto: CreateNote = (title, content, tags).into();
+        match validator.validate_create(&dto) {
+            Ok(()) => notes.push(dto),
+            Err(error) => warnings.push(format!("Skipping {}, {}", location, error)),
+        }
+    }
+
+    Ok((notes, warnings))
+}
+
+/// Reads the notes in `file` and adds each of them to the database at `path` (creating the database if it
+/// does not exist yet), then saves it. Malformed entries are skipped, see [`parse_import`].
+///
+/// ## Errors
+/// - If `file` cannot be read, or is not a JSON array when importing JSON.
+/// - If the database fails to load or save.
+/// - If the notes cannot be created, see [`NoteService::import`].
+fn import_file(path: impl AsRef<Path>, file: impl AsRef<Path>, format: ImportFormat) -> crate::Result<ImportReport> {
+    crate::flame_guard!("bins", "cli", "import_file");
+    let (dtos, skipped) = parse_import(&std::fs::read_to_string(file)?, format)?;
+    let imported = NoteService::open(path)?.import(dtos)?;
+
+    Ok(ImportReport { imported, skipped })
+}
+
+fn parse_import_format(args: &clap::ArgMatches, file: &Path) -> ImportFormat {
+    crate::flame_guard!("bins", "cli", "parse_import_format");
+    let format = args
+        .value_of("format")
+        .map(str::to_lowercase)
+        .or_else(|| file.extension().map(|ext| ext.to_string_lossy().to_lowercase()));
+    match format.as_deref() {
+        Some("markdown" | "md") => ImportFormat::Markdown,
+        _ => ImportFormat::Json,
+    }
+}
+
+/// Applies the [`FlagNote`] dto to the database at `path`, saving it if the flag changed, and returns
+/// the resulting note.
+///
+/// ## Errors
+/// - If the database fails to load or save.
+/// - [`DatabaseError::IdNotFound`](crate::DatabaseError::IdNotFound) if the note does not exist.
+fn set_note_flag(path: impl AsRef<Path>, dto: FlagNote) -> crate::Result<Note> {
+    crate::flame_guard!("bins", "cli", "set_note_flag");
+    NoteService::open(path)?.flag(dto)
+}
+
+/// Sets up logging from the global `--log-level` and `--log-file` args. Logging stays disabled unless
+/// at least one of them is given.
+///
+/// ## Errors
+/// - See [`init_logging`](crate::util::logging::init_logging).
+fn init_logging_from_args(args: &clap::ArgMatches) -> crate::Result {
+    use crate::util::logging::{init_logging, LogTarget};
+    crate::flame_guard!("bins", "cli", "init_logging_from_args");
+
+    let level = args.value_of_t::<tracing::Level>("log-level").ok();
+    let file = args.value_of("log-file");
+    if level.is_none() && file.is_none() {
+        return Ok(());
+    }
+
+    let target = file.map_or(LogTarget::Stderr, |path| LogTarget::File(path.into()));
+    init_logging(level.unwrap_or(tracing::Level::INFO), target)
+}
+
+/// Loads the databases at `base` and `incoming`, merges `incoming` into `base` using `strategy`, and saves
+/// the result to `out`. When `out` is `None` nothing is written, which is used for `--dry-run`. The merged
+/// database is saved with the same [`Method`](crate::Method) that `base` was stored with.
+///
+/// ## Errors
+/// - If either database fails to load.
+/// - If the merge fails, see [`Database::merge`].
+/// - If the merged database fails to save.
+fn merge_files(
+    base: impl AsRef<Path>,
+    incoming: impl AsRef<Path>,
+    out: Option<&Path>,
+    strategy: MergeStrategy,
+) -> crate::Result<MergeReport> {
+    crate::flame_guard!("bins", "cli", "merge_files");
+    let mut db = Database::load(base)?;
+    let other = Database::load(incoming)?;
+    let report = db.merge(other, strategy)?;
+    if let Some(out) = out {
+        db.save(out)?;
+    }
+
+    Ok(report)
+}
+
+fn parse_merge_strategy(args: &clap::ArgMatches) -> MergeStrategy {
+    crate::flame_guard!("bins", "cli", "parse_merge_strategy");
+    match args
+        .value_of("policy")
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("skip") => MergeStrategy::Skip,
+        Some("overwrite") => MergeStrategy::Overwrite,
+        Some("reassign") => MergeStrategy::Reassign,
+        _ => MergeStrategy::Newer,
+    }
+}
+
 fn parse_order_count(args: &clap::ArgMatches) -> (Ordering, Count) {
     use crate::types::api::OrderBy;
     crate::flame_guard!("bins", "cli", "parse_order_count");
@@ -207,7 +887,645 @@ fn parse_order_count(args: &clap::ArgMatches) -> (Ordering, Count) {
         ordering.reverse();
     }
 
-    let count_num: usize = args.value_of_t("count").unwrap_or_default();
+    // A missing `--count` means all results, an explicit `--count 0` means zero results.
+    let count = args
+        .value_of_t::<usize>("count")
+        .map_or(Count::All, Count::from);
+
+    (ordering, count)
+}
+
+/// Builds the [`Filter`] for the `--filter` and `--tags` args shared by `list` and `export`. `--filter` matches
+/// notes whose title contains the text, and each of the `--tags` must be found in the note's tags the same way
+/// `find --search tags` searches them.
+fn parse_filter(args: &clap::ArgMatches) -> Filter {
+    use crate::types::api::StringSearch;
+    crate::flame_guard!("bins", "cli", "parse_filter");
+    let mut filter = Filter::empty();
+    if let Some(text) = args.value_of("filter") {
+        filter.add_filter(NoteFilter::title(StringSearch::contains(text.to_string(), false)));
+    }
+    for tag in args.values_of("tags").into_iter().flatten() {
+        filter.add_filter(NoteFilter::tag(StringSearch::contains(tag.to_string(), false)));
+    }
+    filter.normalize();
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::types::Note;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    #[no_coverage]
+    fn merge_files() {
+        let tempfile = |name: &str| {
+            std::env::temp_dir().join(format!(
+                "cli-tests-merge_files-{}-{:010}.tmp",
+                name,
+                fastrand::u32(..)
+            ))
+        };
+        let (base_path, incoming_path, out_path) =
+            (tempfile("base"), tempfile("incoming"), tempfile("out"));
+
+        let shared = Note::create(("Shared", "Original content"));
+        let mut changed = shared.clone();
+        changed.set_content("Changed content");
+        let base = Database::from_notes_vec(vec![
+            shared,
+            Note::create(("Base Only", "")),
+        ])
+        .expect("Unable to create base database");
+        let incoming = Database::from_notes_vec(vec![
+            changed,
+            Note::create(("Incoming One", "")),
+            Note::create(("Incoming Two", "")),
+        ])
+        .expect("Unable to create incoming database");
+        base.save(&base_path).expect("Unable to save base database");
+        incoming
+            .save(&incoming_path)
+            .expect("Unable to save incoming database");
+
+        let report = super::merge_files(&base_path, &incoming_path, None, MergeStrategy::Newer)
+            .expect("Dry run failed");
+        assert_eq!(report, MergeReport { added: 2, skipped: 0, overwritten: 1, reassigned: 0 });
+        assert!(!out_path.exists(), "dry run should not write the output");
+
+        let report =
+            super::merge_files(&base_path, &incoming_path, Some(&out_path), MergeStrategy::Newer)
+                .expect("Merge failed");
+        assert_eq!(report, MergeReport { added: 2, skipped: 0, overwritten: 1, reassigned: 0 });
+        let merged = Database::load(&out_path).expect("Unable to load merged database");
+        assert_eq!(merged.len(), 4);
+        assert!(merged
+            .get_all()
+            .iter()
+            .any(|n| n.content() == "Changed content"));
+
+        let report =
+            super::merge_files(&base_path, &incoming_path, Some(&out_path), MergeStrategy::Skip)
+                .expect("Merge failed");
+        assert_eq!(report, MergeReport { added: 2, skipped: 1, overwritten: 0, reassigned: 0 });
+        let merged = Database::load(&out_path).expect("Unable to load merged database");
+        assert_eq!(merged.len(), 4);
+        assert!(merged
+            .get_all()
+            .iter()
+            .any(|n| n.content() == "Original content"));
+        assert_eq!(Database::load(&base_path).unwrap().len(), 2);
+
+        for path in [base_path, incoming_path, out_path] {
+            std::fs::remove_file(path).expect("Unable to delete tempfile");
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn add_note() {
+        let dir = std::env::temp_dir().join(format!("cli-tests-add_note-{:010}", fastrand::u32(..)));
+        let path = dir.join("notes.fdb");
+        assert!(!path.exists());
+
+        let first = super::add_note(&path, ("First", "Some content", vec!["a"]).into())
+            .expect("Unable to add note to new database");
+        assert!(path.exists(), "adding a note should create the database");
+        let second = super::add_note(&path, ("Second", "", vec!["b"]).into())
+            .expect("Unable to add note to existing database");
+        assert_ne!(first.id(), second.id());
+
+        let db = Database::load(&path).expect("Unable to reload database");
+        assert_eq!(db.len(), 2);
+        let reloaded = db.get(first.id()).expect("First note missing after reload");
+        assert_eq!(reloaded.title(), "First");
+        assert_eq!(reloaded.content(), "Some content");
+        assert_eq!(reloaded.tags(), &["a"]);
+        assert_eq!(db.get(second.id()).expect("Second note missing after reload").title(), "Second");
+
+        std::fs::remove_dir_all(dir).expect("Unable to delete temp dir");
+    }
+
+    #[no_coverage]
+    fn seeded_database(name: &str) -> (PathBuf, Vec<TinyId>) {
+        let path = std::env::temp_dir().join(format!("cli-tests-{}-{:010}.tmp", name, fastrand::u32(..)));
+        let notes = vec![
+            Note::create(("Charlie", "apples and pears", vec!["fruit"])),
+            Note::create(("Alpha", "Carrots", vec!["veg"])),
+            Note::create(("Bravo", "More apples", vec!["fruit", "veg"])),
+        ];
+        let ids = notes.iter().map(Note::id).collect();
+        let db = Database::from_notes_vec(notes).expect("Unable to create database");
+        db.save(&path).expect("Unable to save database");
+        (path, ids)
+    }
+
+    #[test]
+    #[no_coverage]
+    fn list_command() {
+        use crate::types::api::OrderBy;
+
+        let (path, ids) = seeded_database("list_command");
+        let db = Database::load(&path).expect("Unable to load database");
+
+        let order = Ordering::ascending(OrderBy::Title);
+        let titles = |notes: Vec<&Note>| notes.iter().map(|n| n.title().to_string()).collect::<Vec<_>>();
+        assert_eq!(titles(list_notes(&db, &Filter::empty(), order, Count::All, true)), ["Alpha", "Bravo", "Charlie"]);
+        assert_eq!(titles(list_notes(&db, &Filter::empty(), order, Count::Some(2), true)), ["Alpha", "Bravo"]);
+        let mut reversed = order;
+        reversed.reverse();
+        assert_eq!(titles(list_notes(&db, &Filter::empty(), reversed, Count::One, true)), ["Charlie"]);
+
+        let notes = list_notes(&db, &Filter::empty(), order, Count::All, true);
+        let quiet = format_notes(&notes, CliVerbosity::Quiet);
+        assert_eq!(quiet, format!("{}\n{}\n{}\n", ids[1], ids[2], ids[0]));
+        let normal = format_notes(&notes, CliVerbosity::Normal);
+        assert_eq!(normal.lines().count(), 3);
+        for (line, note) in normal.lines().zip(&notes) {
+            assert!(line.starts_with(&format!("{}  ", note.id())), "bad line `{}`", line);
+            assert!(line.contains(note.title()), "bad line `{}`", line);
+        }
+        let verbose = format_notes(&notes, CliVerbosity::Verbose);
+        assert!(verbose.contains("Content: Carrots"));
+        assert!(format_notes(&[], CliVerbosity::Normal).is_empty());
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn output_formats() {
+        use crate::util::dtf::DEFAULT_DATE_FORMAT;
+        use unicode_width::UnicodeWidthStr;
+
+        let notes = vec![
+            Note::create(("Short", "", vec!["a"])),
+            Note::create(("A much longer title than the others, long enough to be truncated", "", vec!["a", "b"])),
+            Note::create(("Ünïcödé", "", vec![])),
+        ];
+        let refs = notes.iter().collect::<Vec<_>>();
+
+        let json = render_notes(&refs, OutputFormat::Json, CliVerbosity::Normal, DEFAULT_DATE_FORMAT).expect("Unable to render json");
+        let parsed: serde_json::Value = serde_json::from_str(&json).expect("Json output is not valid json");
+        assert_eq!(parsed.as_array().map(Vec::len), Some(3));
+
+        let table = render_notes(&refs, OutputFormat::Table, CliVerbosity::Normal, DEFAULT_DATE_FORMAT).expect("Unable to render table");
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4, "expected a header and a row per note:\n{}", table);
+        assert!(lines[0].starts_with("ID"));
+        assert!(lines[2].contains('…'), "long title was not truncated: {}", lines[2]);
+        assert!(!lines[2].contains("truncated"));
+
+        // Every column starts at the same display column on every line.
+        let column_of = |line: &str, text: &str| line[..line.find(text).expect("missing cell")].width();
+        let tags_column = column_of(lines[0], "TAGS");
+        let updated_column = column_of(lines[0], "UPDATED");
+        for (line, note) in lines[1..].iter().zip(&notes) {
+            assert_eq!(column_of(line, &note.id().to_string()), 0);
+            assert_eq!(column_of(line, &format!("  {}  ", note.tags().len())) + 2, tags_column, "bad line `{}`", line);
+            let updated = crate::util::dtf::format(note.updated(), DEFAULT_DATE_FORMAT);
+            assert_eq!(column_of(line, &updated), updated_column, "bad line `{}`", line);
+        }
+
+        let plain = render_notes(&refs, OutputFormat::Plain, CliVerbosity::Normal, DEFAULT_DATE_FORMAT).unwrap();
+        assert_eq!(plain, format_notes(&refs, CliVerbosity::Normal));
+        let verbose = render_notes(&refs, OutputFormat::Table, CliVerbosity::Verbose, DEFAULT_DATE_FORMAT).unwrap();
+        assert_eq!(verbose, format_notes(&refs, CliVerbosity::Verbose));
+        assert!(render_notes(&[], OutputFormat::Table, CliVerbosity::Normal, DEFAULT_DATE_FORMAT)
+            .unwrap()
+            .is_empty());
+
+        // Timestamps follow the configured date format.
+        let iso = render_notes(&refs, OutputFormat::Table, CliVerbosity::Normal, "iso-24h").unwrap();
+        assert!(iso.contains(&crate::util::dtf::format(notes[0].updated(), "iso-24h")));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn json_output_is_only_json() {
+        let (path, _) = seeded_database("json_output_is_only_json");
+        let db = Database::load(&path).expect("Unable to load database");
+        let config = CliConfig::default();
 
-    (ordering, count_num.into())
+        for command in ["list", "find"] {
+            let mut args = vec!["noted", "-v", command];
+            if command == "find" {
+                args.push("apples");
+            }
+            let input = app::create_app()
+                .try_get_matches_from(args.iter().chain(&["--format", "json"]))
+                .expect("Unable to parse args");
+            let (_, command_args) = input.subcommand().unwrap();
+            let format = config.output_format(command_args);
+            assert_eq!(format, OutputFormat::Json);
+
+            let notes = if command == "find" {
+                find_notes(&db, "apples", &Filter::empty(), true, Ordering::default(), Count::All)
+            } else {
+                list_notes(&db, &Filter::empty(), Ordering::default(), Count::All, true)
+            };
+            let verbosity = config.verbosity(&input);
+            let output = query_output(command, &notes, format, false, verbosity, config.date_format())
+                .expect("Unable to build output");
+            let parsed: serde_json::Value = serde_json::from_str(&output)
+                .unwrap_or_else(|err| panic!("`{}` output is not valid json ({}):\n{}", command, err, output));
+            assert_eq!(parsed.as_array().map(Vec::len), Some(notes.len()));
+
+            let table = query_output(command, &notes, OutputFormat::Table, false, verbosity, config.date_format())
+                .expect("Unable to build output");
+            assert!(table.starts_with(&format!("Running `{}` command", command)));
+        }
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn find_command() {
+        use crate::types::api::StringSearch;
+
+        let (path, ids) = seeded_database("find_command");
+        let db = Database::load(&path).expect("Unable to load database");
+        let order = Ordering::default();
+        let found_ids = |notes: Vec<&Note>| {
+            let mut found = notes.iter().map(|n| n.id()).collect::<Vec<_>>();
+            found.sort_by_cached_key(ToString::to_string);
+            found
+        };
+        let expected = |mut expected: Vec<TinyId>| {
+            expected.sort_by_cached_key(ToString::to_string);
+            expected
+        };
+
+        let fts = find_notes(&db, "apples", &Filter::empty(), true, order, Count::All);
+        assert_eq!(found_ids(fts), expected(vec![ids[0], ids[2]]));
+
+        let mut by_tag = Filter::empty();
+        by_tag.add_filter(NoteFilter::tag(StringSearch::contains("veg".to_string(), false)));
+        let tagged = find_notes(&db, "veg", &by_tag, false, order, Count::All);
+        assert_eq!(found_ids(tagged), expected(vec![ids[1], ids[2]]));
+
+        let limited = find_notes(&db, "apples", &Filter::empty(), true, order, Count::One);
+        assert_eq!(limited.len(), 1);
+        assert!(find_notes(&db, "nothing", &Filter::empty(), true, order, Count::All).is_empty());
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn delete_command() {
+        let (path, ids) = seeded_database("delete_command");
+
+        let deleted = delete_note(&path, ids[1].into()).expect("Unable to delete note");
+        assert_eq!(deleted.id(), ids[1]);
+        assert_eq!(deleted.title(), "Alpha");
+
+        let db = Database::load(&path).expect("Unable to reload database");
+        assert_eq!(db.len(), 2);
+        assert!(db.get(ids[1]).is_err());
+        assert!(db.get(ids[0]).is_ok());
+
+        assert!(delete_note(&path, ids[1].into()).is_err(), "deleting twice should fail");
+        assert!(delete_note(&path, TinyId::random().into()).is_err());
+        assert_eq!(Database::load(&path).unwrap().len(), 2);
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn export_subset() {
+        let (path, ids) = seeded_database("export_subset");
+        let db = Database::load(&path).expect("Unable to load database");
+        let export = |args: &[&str]| {
+            let matches = app::create_app()
+                .try_get_matches_from(["noted", "export"].iter().chain(args))
+                .expect("Unable to parse args");
+            let (_, export_args) = matches.subcommand().expect("export subcommand is missing");
+            let (order, count) = parse_order_count(export_args);
+            let notes = db.query_owned(&parse_filter(export_args), order, count);
+            let json = export_notes(&notes, parse_export_format(export_args), None).expect("Unable to export notes");
+            serde_json::from_str::<Vec<Note>>(&json).expect("Export is not valid json")
+        };
+        let titles = |notes: Vec<Note>| notes.iter().map(|n| n.title().to_string()).collect::<Vec<_>>();
+
+        assert_eq!(export(&[]).len(), 3);
+        assert_eq!(titles(export(&["--tags", "fruit", "--order", "title"])), ["Bravo", "Charlie"]);
+        assert_eq!(titles(export(&["--tags", "fruit", "--order", "title", "--reverse"])), ["Charlie", "Bravo"]);
+        assert_eq!(titles(export(&["--tags", "fruit,veg"])), ["Bravo"]);
+        assert_eq!(titles(export(&["-T", "veg", "-O", "title", "--count", "1"])), ["Alpha"]);
+        assert_eq!(titles(export(&["--filter", "har"])), ["Charlie"]);
+        assert!(export(&["--tags", "missing"]).is_empty());
+
+        let only = export(&["--filter", "Alpha"]);
+        assert_eq!(only.len(), 1);
+        assert_eq!(only[0].id(), ids[1]);
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn export_formats() {
+        use time::format_description::well_known::Rfc3339;
+
+        let notes = vec![
+            Note::create(("Groceries, \"urgent\"", "- Milk\n- Eggs", vec!["home", "food"])),
+            Note::create(("Ideas", "Write more notes.")),
+        ];
+        let db = Database::from_notes_vec(notes.clone()).expect("Unable to create database");
+        let rfc3339 = |ts: &time::OffsetDateTime| ts.format(&Rfc3339).unwrap();
+
+        let json = export_notes(db.get_all(), ExportFormat::Json, None).expect("Unable to export json");
+        let parsed: Vec<Note> = serde_json::from_str(&json).expect("Unable to parse exported json");
+        assert_eq!(parsed.len(), 2);
+        for (parsed, original) in parsed.iter().zip(&notes) {
+            assert_eq!(parsed.id(), original.id());
+            assert_eq!(parsed.title(), original.title());
+            assert_eq!(parsed.content(), original.content());
+            assert_eq!(parsed.tags(), original.tags());
+        }
+
+        let markdown = export_notes(db.get_all(), ExportFormat::Markdown, None).expect("Unable to export markdown");
+        assert_eq!(
+            markdown,
+            "# Groceries, \"urgent\"\n\n- Milk\n- Eggs\n\nTags: home, food\n\n# Ideas\n\nWrite more notes.\n"
+        );
+
+        let csv = export_notes(db.get_all(), ExportFormat::Csv, None).expect("Unable to export csv");
+        let expected = format!(
+            "id,title,tags,created,updated\n{},\"Groceries, \"\"urgent\"\"\",home;food,{},{}\n{},Ideas,,{},{}\n",
+            notes[0].id(),
+            rfc3339(notes[0].created()),
+            rfc3339(notes[0].updated()),
+            notes[1].id(),
+            rfc3339(notes[1].created()),
+            rfc3339(notes[1].updated()),
+        );
+        assert_eq!(csv, expected);
+
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(export_notes(&[], ExportFormat::Csv, None).unwrap(), "id,title,tags,created,updated\n");
+
+        let long = Note::create(("Long", "one two three four five six seven"));
+        let wrapped = export_notes(&[long], ExportFormat::Markdown, Some(10)).unwrap();
+        assert_eq!(wrapped, "# Long\n\none two\nthree four\nfive six\nseven\n");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn import_json() {
+        let tempfile = |name: &str| {
+            std::env::temp_dir().join(format!("cli-tests-import_json-{}-{:010}.tmp", name, fastrand::u32(..)))
+        };
+        let (db_path, file) = (tempfile("db"), tempfile("json"));
+        let existing = Database::from_notes_vec(vec![Note::create(("Existing", ""))]).unwrap();
+        existing.save(&db_path).expect("Unable to save database");
+        std::fs::write(
+            &file,
+            r#"[
+                { "title": "First", "content": "Some content", "tags": ["a", "b"] },
+                { "title": 42 },
+                { "title": "Second" },
+                { "title": "", "content": "No title" },
+                { "title": "Third", "colour": "red" }
+            ]"#,
+        )
+        .expect("Unable to write import file");
+
+        let report = import_file(&db_path, &file, ImportFormat::Json).expect("Import failed");
+        let titles = report.imported.iter().map(Note::title).collect::<Vec<_>>();
+        assert_eq!(titles, ["First", "Second"]);
+        assert_eq!(report.skipped.len(), 3, "{:#?}", report.skipped);
+        assert!(report.skipped[0].starts_with("Skipping entry 2,"), "{}", report.skipped[0]);
+        assert!(report.skipped[1].starts_with("Skipping entry 5,"), "{}", report.skipped[1]);
+        assert!(report.skipped[2].starts_with("Skipping entry 4,"), "{}", report.skipped[2]);
+
+        let db = Database::load(&db_path).expect("Unable to reload database");
+        assert_eq!(db.len(), 3);
+        let first = db.get(report.imported[0].id()).expect("First note missing after reload");
+        assert_eq!(first.content(), "Some content");
+        assert_eq!(first.tags(), &["a", "b"]);
+        assert!(db.get_all().iter().any(|n| n.title() == "Existing"));
+        assert!(!db.get_all().iter().any(|n| n.title() == "Third"));
+
+        std::fs::write(&file, r#"{ "title": "Not an array" }"#).unwrap();
+        assert!(import_file(&db_path, &file, ImportFormat::Json).is_err());
+        assert_eq!(Database::load(&db_path).unwrap().len(), 3);
+
+        for path in [db_path, file] {
+            std::fs::remove_file(path).expect("Unable to delete tempfile");
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn import_markdown() {
+        let text = "stray line\n# First\n\nLine one\n#not-a-heading\n\nTags: a, b\n\n# \nOrphaned\n# Second\n";
+        let (dtos, warnings) = parse_import(text, ImportFormat::Markdown).expect("Unable to parse markdown");
+        assert_eq!(dtos.len(), 2);
+        assert_eq!(dtos[0].title(), Some("First"));
+        assert_eq!(dtos[0].content(), Some("Line one\n#not-a-heading"));
+        assert_eq!(dtos[0].tags(), &["a", "b"]);
+        assert_eq!(dtos[1].title(), Some("Second"));
+        assert!(dtos[1].tags().is_empty());
+        assert_eq!(warnings.len(), 2, "{:#?}", warnings);
+        assert!(warnings[0].starts_with("Skipping line 1,"), "{}", warnings[0]);
+        assert!(warnings[1].starts_with("Skipping heading on line 9,"), "{}", warnings[1]);
+
+        // What `export` writes can be imported again.
+        let exported = export_notes(
+            &[Note::create(("Exported", "Body", vec!["x"])), Note::create(("Untagged", "Text"))],
+            ExportFormat::Markdown,
+            None,
+        )
+        .unwrap();
+        let (dtos, warnings) = parse_import(&exported, ImportFormat::Markdown).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(dtos.len(), 2);
+        assert_eq!((dtos[0].title(), dtos[0].content(), dtos[0].tags()), (Some("Exported"), Some("Body"), &["x".to_string()][..]));
+        assert_eq!((dtos[1].title(), dtos[1].content()), (Some("Untagged"), Some("Text")));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn add_from_stdin() {
+        let dto_for = |args: &[&str], stdin: &[u8]| {
+            let matches = app::create_app()
+                .try_get_matches_from(["noted", "add"].iter().chain(args))
+                .expect("Unable to parse args");
+            let (_, add_args) = matches.subcommand().expect("add subcommand is missing");
+            create_dto_from_args(add_args, &mut &stdin[..]).expect("Unable to build dto")
+        };
+
+        let dto = dto_for(&["--title", "Piped", "--content", "-", "--tags", "a,b"], b"Line one\nLine two\n");
+        assert_eq!(dto.title(), Some("Piped"));
+        assert_eq!(dto.content(), Some("Line one\nLine two\n"));
+        assert_eq!(dto.tags(), &["a", "b"]);
+
+        let dto = dto_for(&["--title", "Flag", "--stdin"], "Unicode ✓".as_bytes());
+        assert_eq!(dto.content(), Some("Unicode ✓"));
+
+        let dto = dto_for(&["--title", "Empty", "--stdin"], b"");
+        assert_eq!(dto.title(), Some("Empty"));
+        assert_eq!(dto.content(), Some(""), "empty stdin should give empty content");
+
+        let dto = dto_for(&["--title", "Inline", "--content", "Not piped"], b"ignored");
+        assert_eq!(dto.content(), Some("Not piped"));
+
+        let dto = dto_for(&["--json", "-"], br#"{ "title": "Json", "content": "From stdin", "tags": ["x"] }"#);
+        assert_eq!(dto.title(), Some("Json"));
+        assert_eq!(dto.content(), Some("From stdin"));
+        assert_eq!(dto.tags(), &["x"]);
+
+        let matches = app::create_app()
+            .try_get_matches_from(["noted", "add", "--json", "-"])
+            .unwrap();
+        let (_, add_args) = matches.subcommand().unwrap();
+        assert!(create_dto_from_args(add_args, &mut &b"not json"[..]).is_err());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn add_from_json_arg() {
+        let matches_for = |json: &str| app::create_app().try_get_matches_from(["noted", "add", "--json", json]);
+        let dto_for = |json: &str| {
+            let matches = matches_for(json).expect("Unable to parse args");
+            let (_, add_args) = matches.subcommand().expect("add subcommand is missing");
+            create_dto_from_args(add_args, &mut &b""[..]).expect("Unable to build dto")
+        };
+
+        // Missing fields are defaulted, the same as deserializing a `CreateNote` directly.
+        for json in [
+            r#"{ "title": "Only a title" }"#,
+            r#"{ "content": "Only content" }"#,
+            r#"{ "title": "Tagged", "tags": ["a", "b"] }"#,
+        ] {
+            let dto = dto_for(json);
+            let direct = serde_json::from_str::<CreateNote>(json).expect("Unable to parse CreateNote");
+            assert_eq!(dto.title().unwrap_or_default(), direct.title.as_deref().unwrap_or_default(), "{}", json);
+            assert_eq!(dto.content().unwrap_or_default(), direct.content.as_deref().unwrap_or_default(), "{}", json);
+            assert_eq!(dto.tags(), direct.tags.as_slice(), "{}", json);
+        }
+
+        // The CLI still needs a title or content, and rejects fields it doesn't know about.
+        assert!(matches_for("{}").is_err());
+        assert!(matches_for(r#"{ "title": "", "content": "" }"#).is_err());
+        assert!(matches_for(r#"{ "title": "Pinned", "pinned": true }"#).is_err());
+        assert!(matches_for("not json").is_err());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn pin_and_archive() {
+        let path = std::env::temp_dir().join(format!(
+            "cli-tests-pin_and_archive-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        let notes = vec![
+            Note::create(("One", "")),
+            Note::create(("Two", "")),
+            Note::create(("Three", "")),
+        ];
+        let (first, second) = (notes[0].id(), notes[1].id());
+        let db = Database::from_notes_vec(notes).expect("Unable to create database");
+        db.save(&path).expect("Unable to save database");
+
+        let pinned = set_note_flag(&path, FlagNote::new(first, NoteFlag::Pinned, true))
+            .expect("Unable to pin note");
+        assert!(pinned.is_pinned());
+        assert!(!pinned.is_archived());
+        assert!(Database::load(&path).unwrap().get(first).unwrap().is_pinned());
+
+        let archived = set_note_flag(&path, FlagNote::new(second, NoteFlag::Archived, true))
+            .expect("Unable to archive note");
+        assert!(archived.is_archived());
+        assert!(set_note_flag(&path, FlagNote::new(TinyId::random(), NoteFlag::Pinned, true)).is_err());
+
+        let db = Database::load(&path).expect("Unable to load database");
+        let order = Ordering::default();
+        let visible = list_notes(&db, &Filter::empty(), order, Count::All, false);
+        assert_eq!(visible.len(), 2);
+        assert!(visible.iter().all(|n| n.id() != second));
+        let all = list_notes(&db, &Filter::empty(), order, Count::All, true);
+        assert_eq!(all.len(), 3);
+
+        std::fs::remove_file(path).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn stats_json() {
+        use crate::types::Reminder;
+        use time::macros::datetime;
+
+        let reminders = vec![
+            Reminder::existing(TinyId::random(), String::from("Fired"), datetime!(2022-01-01 09:00 UTC), true),
+            Reminder::existing(TinyId::random(), String::from("Pending"), datetime!(2099-01-01 09:00 UTC), false),
+        ];
+        let mut notes = vec![
+            Note::create(("One", "three words here", vec!["a", "b"], reminders)),
+            Note::create(("Two", "two words", vec!["b"])),
+            Note::create(("Three", "", vec!["c"])),
+        ];
+        let (first, third) = (notes[0].id(), notes[2].id());
+        notes[0].flag(FlagNote::new(first, NoteFlag::Pinned, true));
+        notes[2].flag(FlagNote::new(third, NoteFlag::Archived, true));
+        let stats = Database::from_notes_vec(notes).expect("Unable to create database").stats();
+
+        let json = format_stats(&stats, true).expect("Unable to format stats as json");
+        let parsed: DatabaseStats = serde_json::from_str(&json).expect("Unable to parse stats json");
+        assert_eq!(parsed, stats);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let object = value.as_object().expect("stats json should be an object");
+        for field in [
+            "notes",
+            "pinned",
+            "archived",
+            "tags",
+            "reminders",
+            "pending_reminders",
+            "words",
+            "oldest_created",
+            "last_updated",
+        ] {
+            assert!(object.contains_key(field), "missing field `{}`", field);
+        }
+        assert!(object["oldest_created"].is_string());
+
+        let human = format_stats(&stats, false).expect("Unable to format stats");
+        let human_count = |label: &str| {
+            human
+                .lines()
+                .find_map(|line| line.strip_prefix(label)?.strip_prefix(": "))
+                .and_then(|count| count.parse::<u64>().ok())
+                .unwrap_or_else(|| panic!("missing count for `{}`", label))
+        };
+        for (label, field, expected) in [
+            ("Notes", "notes", 3),
+            ("Pinned", "pinned", 1),
+            ("Archived", "archived", 1),
+            ("Tags", "tags", 3),
+            ("Reminders", "reminders", 2),
+            ("Pending Reminders", "pending_reminders", 1),
+            ("Words", "words", 5),
+        ] {
+            assert_eq!(human_count(label), expected, "human `{}`", label);
+            assert_eq!(object[field].as_u64(), Some(expected), "json `{}`", field);
+        }
+
+        let empty = format_stats(&DatabaseStats::default(), true).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&empty).unwrap();
+        assert!(value["last_updated"].is_null());
+        assert!(format_stats(&DatabaseStats::default(), false).unwrap().ends_with("Last Updated: never"));
+    }
 }
@@ -11,13 +11,15 @@ use eframe::egui;
 use egui_toast::{Toast, ToastKind, ToastOptions, Toasts};
 use tinyid::TinyId;
 
-use crate::types::Note;
+use crate::types::{api::Filter, Note};
 
 use super::{
     backend::{Backend, ToBackend, ToFrontend},
+    clipboard::{self, Clipboard},
     hotkey::{HotkeyEditor, HotkeyState, Hotkeys},
+    reminders,
     settings::{AppSettings, AppSettingsUi},
-    widgets::{NoteEditor, NoteList, SimplePrompt, ToApp},
+    widgets::{NoteEditor, NoteList, SimplePrompt, TagPanel, ToApp},
 };
 
 fn default_toast_options() -> ToastOptions {
@@ -69,6 +71,7 @@ impl DeletingState {
 
 pub struct GuiApp {
     note_list: NoteList,
+    tag_panel: TagPanel,
     state: AppState,
     settings: AppSettings,
     settings_open: bool,
@@ -76,6 +79,8 @@ pub struct GuiApp {
     back_rx: Receiver<ToFrontend>,
     error_log: Vec<String>,
     exit_state: ExitState,
+    /// Whether the open database has changes that have not been saved yet, as reported by the backend.
+    db_modified: bool,
     note_editor: NoteEditor,
     widget_rx: Receiver<ToApp>,
     widget_tx: Sender<ToApp>,
@@ -84,6 +89,7 @@ pub struct GuiApp {
     deleting_state: DeletingState,
     time: f64,
     hotkeys: Hotkeys,
+    clipboard: Clipboard,
 }
 
 impl GuiApp {
@@ -108,6 +114,11 @@ impl GuiApp {
         front_tx
             .send(ToBackend::Startup)
             .expect("Unable to send startup message to backend");
+        front_tx
+            .send(ToBackend::SetTagNormalizer {
+                normalizer: settings.tag_normalizer,
+            })
+            .expect("Unable to send tag normalizer message to backend");
 
         if settings.load_default_on_start {
             if settings.default_database.exists() {
@@ -120,6 +131,7 @@ impl GuiApp {
                 front_tx
                     .send(ToBackend::CreateDatabase {
                         path: settings.default_database.clone(),
+                        seed_welcome: settings.seed_welcome_note,
                     })
                     .expect("Unable to send open database message to backend");
             }
@@ -135,6 +147,7 @@ impl GuiApp {
 
         Self {
             note_list,
+            tag_panel: TagPanel::new(),
             front_tx,
             back_rx,
             state: AppState::NoDatabase,
@@ -142,6 +155,7 @@ impl GuiApp {
             settings_open: false,
             error_log,
             exit_state: ExitState::Running,
+            db_modified: false,
             note_editor,
             widget_rx,
             widget_tx,
@@ -150,6 +164,7 @@ impl GuiApp {
             toast_rx,
             toast_tx,
             hotkeys: Hotkeys::default(),
+            clipboard: Clipboard::new(),
         }
     }
 
@@ -194,7 +209,10 @@ impl GuiApp {
 
     fn new_db(&self, path: PathBuf) {
         self.front_tx
-            .send(ToBackend::CreateDatabase { path })
+            .send(ToBackend::CreateDatabase {
+                path,
+                seed_welcome: self.settings.seed_welcome_note,
+            })
             .expect("Unable to send create database message to backend");
     }
 
@@ -218,7 +236,7 @@ impl GuiApp {
     }
 
     fn needs_save(&self) -> bool {
-        self.note_editor.has_changes()
+        self.db_modified || self.note_editor.has_changes()
     }
 
     fn new_note(&mut self) {
@@ -231,6 +249,7 @@ impl GuiApp {
 
     fn change_active_note(&mut self, note: Option<Note>) {
         self.update_active_note();
+        self.note_list.set_selected(note.as_ref().map(Note::id));
         self.note_editor.set_note(note);
     }
 
@@ -254,6 +273,43 @@ impl GuiApp {
             .expect("Unable to send delete note message to backend");
     }
 
+    /// Copies the active note to the clipboard as JSON, returning whether there was a note to copy and it was
+    /// copied successfully.
+    fn copy_note(&mut self) -> bool {
+        let Some(note) = self.note_editor.get_active_note() else {
+            return false;
+        };
+
+        match clipboard::note_to_clipboard(note).and_then(|json| self.clipboard.set(json)) {
+            Ok(()) => true,
+            Err(err) => {
+                self.error_log.push(err.to_string());
+                false
+            }
+        }
+    }
+
+    /// Creates a new note from the clipboard if it holds a copied note, otherwise inserts the clipboard text
+    /// into the active note.
+    fn paste(&mut self) {
+        let text = match self.clipboard.get() {
+            Ok(text) => text,
+            Err(err) => {
+                self.error_log.push(err.to_string());
+                return;
+            }
+        };
+
+        if let Some(dto) = clipboard::note_from_clipboard(&text) {
+            self.front_tx
+                .send(ToBackend::CreateNote { dto })
+                .expect("Unable to send new note message to backend");
+        } else if !self.note_editor.insert_text(&text) {
+            self.error_log
+                .push("The clipboard does not hold a note and there is no note to paste into.".to_string());
+        }
+    }
+
     fn check_hotkeys(&mut self, ctx: &egui::Context) {
         let state = self.hotkeys.check_hotkeys(ctx);
         if state.new_note {
@@ -276,31 +332,40 @@ impl GuiApp {
         if state.save {
             self.save_data();
         }
-        if state.copy {
-            // TODO: Implement copying for text AND notes
-            self.error_log.push("COPY hotkey pressed.".to_string());
+        if state.find {
+            self.note_editor.toggle_find();
         }
-        if state.cut {
-            // TODO: Implement cutting for text AND notes
-            self.error_log.push("CUT hotkey pressed.".to_string());
+        if state.toggle_preview {
+            self.note_editor.toggle_preview();
         }
-        if state.paste {
-            // TODO: Implement pasting for text AND notes
-            self.error_log.push("PASTE hotkey pressed.".to_string());
+        // While a text field has focus egui copies, cuts and pastes its text itself, so the hotkeys only act
+        // on whole notes when nothing is being edited.
+        if !ctx.wants_keyboard_input() {
+            if state.copy {
+                self.copy_note();
+            }
+            if state.cut && self.copy_note() {
+                if let Some(id) = self.note_editor.get_active_note().map(Note::id) {
+                    self.delete_note(id);
+                }
+            }
+            if state.paste {
+                self.paste();
+            }
         }
-        if state.undo {
-            // TODO: Implement undo/redo
-            self.error_log.push("UNDO hotkey pressed.".to_string());
+        // Text fields have their own undo history, so the note history is only used outside of them.
+        if state.undo && !ctx.wants_keyboard_input() {
+            self.note_editor.undo();
         }
-        if state.redo {
-            // TODO: Implement undo/redo
-            self.error_log.push("REDO hotkey pressed.".to_string());
+        if state.redo && !ctx.wants_keyboard_input() {
+            self.note_editor.redo();
         }
     }
 
     fn render_db_loaded(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // let mut delete_requested = None;
         let mut change_active = None;
+        let unsaved = self.needs_save();
 
         let mut side_panel = egui::SidePanel::left("note_list_panel")
             .width_range(50.0..=200.0)
@@ -319,12 +384,18 @@ impl GuiApp {
                         ui.available_size(),
                         egui::Layout::centered_and_justified(egui::Direction::TopDown),
                         |ui| {
-                            ui.heading(egui::RichText::new("Notes").heading());
+                            let heading = if unsaved { "Notes*" } else { "Notes" };
+                            ui.heading(egui::RichText::new(heading).heading())
+                                .on_hover_text(if unsaved { "There are unsaved changes" } else { "Saved" });
                         },
                     );
                 },
             );
             ui.separator();
+            if self.tag_panel.render(ui) {
+                self.note_list.set_tag_filter(self.tag_panel.filter());
+            }
+            ui.separator();
             self.note_list.render(ui);
         });
 
@@ -398,6 +469,11 @@ impl GuiApp {
                     self.error_log.push(err.to_string());
                 }
                 self.note_editor.settings_updated(&self.settings);
+                self.front_tx
+                    .send(ToBackend::SetTagNormalizer {
+                        normalizer: self.settings.tag_normalizer,
+                    })
+                    .expect("Unable to send tag normalizer message to backend");
             }
 
             //ui.separator();
@@ -576,6 +652,11 @@ impl eframe::App for GuiApp {
                         })
                         .expect("Unable to send toast");
                 }
+                ToFrontend::TagCounts { tags } => {
+                    if self.tag_panel.update_tags(tags) {
+                        self.note_list.set_tag_filter(self.tag_panel.filter());
+                    }
+                }
                 ToFrontend::Error { error_msg } => {
                     self.toast_tx
                         .send(Toast {
@@ -596,13 +677,19 @@ impl eframe::App for GuiApp {
                         })
                         .expect("Unable to send toast");
                 }
-                ToFrontend::DatabaseLoaded { notes } => {
+                ToFrontend::DatabaseLoaded { notes, due_reminders } => {
                     self.state = AppState::DatabaseOpen;
+                    for toast in reminders::due_reminder_toasts(&due_reminders) {
+                        self.toast_tx.send(toast).expect("Unable to send toast");
+                    }
                     self.note_list.update_note_list(notes);
                 }
+                ToFrontend::ModifiedChanged { modified } => self.db_modified = modified,
                 ToFrontend::DatabaseClosed => {
                     self.state = AppState::NoDatabase;
                     self.note_list.clear_note_list();
+                    self.note_list.set_tag_filter(Filter::empty());
+                    self.tag_panel.clear();
                     self.note_editor.clear_note();
                 }
             },
@@ -629,6 +716,18 @@ impl eframe::App for GuiApp {
                         }
                     }
                     ToApp::SaveRequested => self.save_data(),
+                    ToApp::ApplyUpdate(update) => {
+                        self.front_tx
+                            .send(ToBackend::ApplyUpdate { update })
+                            .expect("Unable to send update message to backend");
+                    }
+                    ToApp::NoteListSorted(ordering) => {
+                        self.settings.note_list_order_by = ordering.field();
+                        self.settings.note_list_direction = ordering.direction();
+                        if let Err(err) = self.settings.save_default() {
+                            self.error_log.push(err.to_string());
+                        }
+                    }
                     ToApp::Toast(kind, text) => {
                         self.toast_tx
                             .send(Toast {
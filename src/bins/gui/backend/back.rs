@@ -18,6 +18,9 @@ static LOG_CHANNEL_CLOSED: Once = Once::new();
 pub struct Backend {
     db: Option<crate::db::Database>,
     db_path: Option<PathBuf>,
+    tag_normalizer: crate::types::TagNormalizer,
+    /// The last [`ToFrontend::ModifiedChanged`] sent, see [`Backend::send_modified`].
+    modified: bool,
     back_tx: Sender<ToFrontend>,
     front_rx: Receiver<ToBackend>,
     egui_context: egui::Context,
@@ -32,6 +35,8 @@ impl Backend {
         Self {
             db: None,
             db_path: None,
+            tag_normalizer: crate::types::TagNormalizer::default(),
+            modified: false,
             back_tx,
             front_rx,
             egui_context,
@@ -50,6 +55,7 @@ impl Backend {
                     Ok(mut msg) => {
                         match msg {
                             ToBackend::UpdateNote { ref mut note } => self.update_note(note),
+                            ToBackend::ApplyUpdate { ref update } => self.apply_update(update),
                             ToBackend::CreateNote { ref dto } => self.create_note(dto),
                             ToBackend::DeleteNote { id } => self.delete_note(id),
                             ToBackend::SaveData => self.save_data(),
@@ -60,10 +66,12 @@ impl Backend {
                                 // self.save_data();
                                 info!("Backend shutting down");
                             },
-                            ToBackend::CreateDatabase { path } => self.create_db(path),
+                            ToBackend::CreateDatabase { path, seed_welcome } => self.create_db(path, seed_welcome),
                             ToBackend::OpenDatabase { path } => self.open_db(path),
                             ToBackend::CloseDatabase => self.close_db(),
+                            ToBackend::SetTagNormalizer { normalizer } => self.set_tag_normalizer(normalizer),
                         }
+                        self.send_modified();
                     }
                     Err(error) => {
                          // As the only reason this will error out is if the channel is closed (sender is dropped) a one time log of the error is enough
@@ -90,28 +98,51 @@ impl Backend {
         }
     }
 
-    fn create_db<P: AsRef<Path>>(&mut self, path: P) {
+    fn create_db<P: AsRef<Path>>(&mut self, path: P, seed_welcome: bool) {
         let path = path.as_ref();
         self.close_db();
-        let db = crate::db::Database::empty();
+        let mut db = crate::db::Database::empty();
+        db.set_tag_normalizer(self.tag_normalizer);
+        if seed_welcome {
+            if let Err(error) = db.seed_welcome_if_empty() {
+                error!(%error, "Error while creating welcome note:");
+                self.send_error(error);
+            }
+        }
         info!(?path, "Database opened at path");
-        let notes = Vec::new();
+        let loaded = Self::database_loaded(&db);
         self.db = Some(db);
         self.db_path = Some(path.to_path_buf());
         self.save_data();
-        self.send_msg(ToFrontend::DatabaseLoaded { notes });
+        self.send_msg(loaded);
+        self.send_tag_counts();
+    }
+
+    /// The [`ToFrontend::DatabaseLoaded`] message for a freshly opened `db`.
+    fn database_loaded(db: &crate::db::Database) -> ToFrontend {
+        let due_reminders = db
+            .due_reminders(time::OffsetDateTime::now_utc())
+            .into_iter()
+            .map(|(note, reminder)| (note.clone(), reminder.clone()))
+            .collect();
+        ToFrontend::DatabaseLoaded {
+            notes: db.get_all().to_vec(),
+            due_reminders,
+        }
     }
 
     fn open_db<P: AsRef<Path>>(&mut self, path: P) {
         let path = path.as_ref();
         self.close_db();
         match crate::db::Database::load(path) {
-            Ok(db) => {
+            Ok(mut db) => {
                 info!(?path, "Database opened at path");
-                let notes = db.get_all().to_vec();
+                db.set_tag_normalizer(self.tag_normalizer);
+                let loaded = Self::database_loaded(&db);
                 self.db = Some(db);
                 self.db_path = Some(path.to_path_buf());
-                self.send_msg(ToFrontend::DatabaseLoaded { notes });
+                self.send_msg(loaded);
+                self.send_tag_counts();
             }
             Err(error) => {
                 error!(%error, ?path, "Error while opening database:");
@@ -121,6 +152,13 @@ impl Backend {
         self.egui_context.request_repaint();
     }
 
+    fn set_tag_normalizer(&mut self, normalizer: crate::types::TagNormalizer) {
+        self.tag_normalizer = normalizer;
+        if let Some(db) = &mut self.db {
+            db.set_tag_normalizer(normalizer);
+        }
+    }
+
     fn close_db(&mut self) {
         if self.db.is_some() {
             self.save_data();
@@ -138,6 +176,7 @@ impl Backend {
                 notes: db.get_all().to_vec(),
             };
             self.send_msg(msg);
+            self.send_tag_counts();
         } else {
             error!("UpdateNote received but no database is open");
             self.send_error_msg(format!(
@@ -148,6 +187,45 @@ impl Backend {
         self.egui_context.request_repaint();
     }
 
+    fn apply_update(&mut self, update: &crate::types::UpdateNote) {
+        if let Some(db) = &mut self.db {
+            match db.apply_update(update.clone()) {
+                Ok(_) => {
+                    self.send_msg(ToFrontend::RefreshNoteList {
+                        notes: db.get_all().to_vec(),
+                    });
+                    self.send_tag_counts();
+                }
+                Err(error) => {
+                    error!(%error, ?update, "Error while applying note update:");
+                    self.send_error(error);
+                }
+            }
+        } else {
+            error!("ApplyUpdate received but no database is open");
+            self.send_error_msg("Update requested but no database is open!");
+        }
+        self.egui_context.request_repaint();
+    }
+
+    /// Sends every tag in the open database, and how many notes carry it, to the frontend.
+    fn send_tag_counts(&self) {
+        if let Some(db) = &self.db {
+            self.send_msg(ToFrontend::TagCounts {
+                tags: db.get_all_tags_and_counts(),
+            });
+        }
+    }
+
+    /// Tells the frontend whether the database has unsaved changes, if that changed since it was last told.
+    fn send_modified(&mut self) {
+        let modified = self.db.as_ref().map_or(false, crate::db::Database::is_modified);
+        if modified != self.modified {
+            self.modified = modified;
+            self.send_msg(ToFrontend::ModifiedChanged { modified });
+        }
+    }
+
     fn send_msg(&self, msg: ToFrontend) {
         self.back_tx
             .send(msg)
@@ -189,6 +267,7 @@ impl Backend {
                     notes: db.get_all().to_vec(),
                 })
                 .expect("Unable to send message to frontend");
+            self.send_tag_counts();
             self.egui_context.request_repaint();
         } else {
             error!("CreateNote received but no database is open");
@@ -200,16 +279,14 @@ impl Backend {
         if let Some(db) = &mut self.db {
             match db.apply_delete(id) {
                 Ok(deleted) => {
-                    if deleted {
-                        self.back_tx
-                            .send(ToFrontend::RefreshNoteList {
-                                notes: db.get_all().to_vec(),
-                            })
-                            .expect("Unable to send message to frontend");
-                        self.egui_context.request_repaint();
-                    } else {
-                        self.send_error_msg(format!("Note with id '{}' not found", id));
-                    }
+                    info!(id = %deleted.id(), title = deleted.title(), "Note deleted");
+                    self.back_tx
+                        .send(ToFrontend::RefreshNoteList {
+                            notes: db.get_all().to_vec(),
+                        })
+                        .expect("Unable to send message to frontend");
+                    self.send_tag_counts();
+                    self.egui_context.request_repaint();
                 }
                 Err(err) => self
                     .back_tx
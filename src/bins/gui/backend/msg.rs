@@ -8,26 +8,35 @@ use std::path::PathBuf;
 
 use tinyid::TinyId;
 
-use crate::types::{CreateNote, Note};
+use crate::types::{CreateNote, Note, Reminder, TagNormalizer, UpdateNote};
 
 use super::BackendError;
 
 pub enum ToFrontend {
     RefreshNoteList { notes: Vec<Note> },
+    TagCounts { tags: Vec<(String, usize)> },
     Error { error_msg: String },
     NoteCreated { note: Note },
-    DatabaseLoaded { notes: Vec<Note> },
+    DatabaseLoaded {
+        notes: Vec<Note>,
+        /// The reminders that were due when the database was loaded, along with the note each belongs to.
+        due_reminders: Vec<(Note, Reminder)>,
+    },
     DatabaseClosed,
+    /// Whether the open database has changes that have not been saved yet, sent whenever that changes.
+    ModifiedChanged { modified: bool },
 }
 
 pub enum ToBackend {
     UpdateNote { note: Note },
+    ApplyUpdate { update: UpdateNote },
     CreateNote { dto: CreateNote },
     DeleteNote { id: TinyId },
     SaveData,
     Startup,
     Shutdown,
-    CreateDatabase { path: PathBuf },
+    CreateDatabase { path: PathBuf, seed_welcome: bool },
     OpenDatabase { path: PathBuf },
     CloseDatabase,
+    SetTagNormalizer { normalizer: TagNormalizer },
 }
@@ -0,0 +1,141 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Copying, cutting and pasting of whole notes (as JSON) and of text through the system clipboard.
+
+use copypasta::{ClipboardContext, ClipboardProvider};
+
+use crate::types::{CreateNote, Note};
+
+/// The system clipboard, or nothing at all if it could not be opened, in which case every operation fails.
+pub struct Clipboard {
+    context: Option<ClipboardContext>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        Self {
+            context: ClipboardContext::new().ok(),
+        }
+    }
+
+    /// Gets the text currently on the clipboard.
+    ///
+    /// ## Errors
+    /// - If the clipboard could not be opened, or holds no text.
+    pub fn get(&mut self) -> crate::Result<String> {
+        let context = self
+            .context
+            .as_mut()
+            .ok_or_else(|| crate::Error::ui("The system clipboard is not available"))?;
+        context
+            .get_contents()
+            .map_err(|err| crate::Error::ui(format!("Unable to read the clipboard: {}", err)))
+    }
+
+    /// Puts `text` on the clipboard.
+    ///
+    /// ## Errors
+    /// - If the clipboard could not be opened or written to.
+    pub fn set(&mut self, text: String) -> crate::Result {
+        let context = self
+            .context
+            .as_mut()
+            .ok_or_else(|| crate::Error::ui("The system clipboard is not available"))?;
+        context
+            .set_contents(text)
+            .map_err(|err| crate::Error::ui(format!("Unable to write to the clipboard: {}", err)))
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializes `note` for copying it to the clipboard, see [`note_from_clipboard`].
+///
+/// ## Errors
+/// - If the note cannot be serialized.
+pub fn note_to_clipboard(note: &Note) -> crate::Result<String> {
+    serde_json::to_string_pretty(note).map_err(crate::Error::json)
+}
+
+/// Parses text copied by [`note_to_clipboard`] into a [`CreateNote`] for a copy of the note. The copy gets the
+/// title, content, tags and reminders of the original but not its id or timestamps, so pasting the same note
+/// twice creates two notes. Returns `None` if `text` is not a serialized note.
+pub fn note_from_clipboard(text: &str) -> Option<CreateNote> {
+    let note = serde_json::from_str::<Note>(text.trim()).ok()?;
+    Some(
+        CreateNote::empty()
+            .with_title(Some(note.title().to_string()))
+            .with_content(Some(note.content().to_string()))
+            .with_tags(note.tags().to_vec())
+            .with_reminders(note.reminders().to_vec()),
+    )
+}
+
+/// Inserts `text` into `content` at the character index `cursor`, or at the end if `cursor` is past it.
+/// Returns the new content and the character index just after the inserted text.
+pub fn insert_at(content: &str, cursor: usize, text: &str) -> (String, usize) {
+    let byte_index = content
+        .char_indices()
+        .nth(cursor)
+        .map_or(content.len(), |(index, _)| index);
+    let mut result = String::with_capacity(content.len() + text.len());
+    result.push_str(&content[..byte_index]);
+    result.push_str(text);
+    result.push_str(&content[byte_index..]);
+    let cursor = content[..byte_index].chars().count() + text.chars().count();
+    (result, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Reminder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn note_round_trip() {
+        let due = time::OffsetDateTime::now_utc() + time::Duration::days(1);
+        let note = Note::create((
+            "Title",
+            "Some \"quoted\"\ncontent",
+            vec!["one", "two"],
+            vec![Reminder::new(&"Soon", due)],
+        ));
+
+        let json = note_to_clipboard(&note).expect("Unable to serialize note");
+        let dto = note_from_clipboard(&json).expect("Unable to parse copied note");
+        assert_eq!(dto.title.as_deref(), Some(note.title()));
+        assert_eq!(dto.content.as_deref(), Some(note.content()));
+        assert_eq!(dto.tags, note.tags());
+        assert_eq!(dto.reminders, note.reminders());
+
+        // Surrounding whitespace from other applications is ignored.
+        assert_eq!(note_from_clipboard(&format!("\n  {}\n", json)), Some(dto));
+
+        assert_eq!(note_from_clipboard("Just some text"), None);
+        assert_eq!(note_from_clipboard("{\"title\": \"Not a full note\"}"), None);
+        assert_eq!(note_from_clipboard(""), None);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn text_insertion() {
+        assert_eq!(insert_at("Hello world", 5, ","), ("Hello, world".to_string(), 6));
+        assert_eq!(insert_at("Hello", 0, ">> "), (">> Hello".to_string(), 3));
+        assert_eq!(insert_at("Hello", 5, "!"), ("Hello!".to_string(), 6));
+        assert_eq!(insert_at("Hello", 99, "!"), ("Hello!".to_string(), 6), "past the end appends");
+        assert_eq!(insert_at("", 0, "text"), ("text".to_string(), 4));
+        // The cursor counts characters, not bytes.
+        assert_eq!(insert_at("héllo wörld", 6, "wide "), ("héllo wide wörld".to_string(), 11));
+        assert_eq!(insert_at("ab", 1, "ü"), ("aüb".to_string(), 2));
+    }
+}
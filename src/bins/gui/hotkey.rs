@@ -264,6 +264,8 @@ pub struct Hotkeys {
     quit: Option<Binding>,
     undo: Option<Binding>,
     redo: Option<Binding>,
+    find: Option<Binding>,
+    toggle_preview: Option<Binding>,
 }
 
 impl Default for Hotkeys {
@@ -380,6 +382,26 @@ impl Default for Hotkeys {
                     command: false,
                 },
             }),
+            find: Some(Binding {
+                variant: BindVariant::Keyboard(Key::F),
+                modifiers: Modifiers {
+                    alt: false,
+                    ctrl: true,
+                    shift: false,
+                    mac_cmd: false,
+                    command: false,
+                },
+            }),
+            toggle_preview: Some(Binding {
+                variant: BindVariant::Keyboard(Key::P),
+                modifiers: Modifiers {
+                    alt: false,
+                    ctrl: true,
+                    shift: false,
+                    mac_cmd: false,
+                    command: false,
+                },
+            }),
         }
     }
 }
@@ -443,6 +465,16 @@ impl Hotkeys {
                 is_valid = false;
             }
         }
+        if let Some(f) = self.find {
+            if !bindings.insert(f.into()) {
+                is_valid = false;
+            }
+        }
+        if let Some(p) = self.toggle_preview {
+            if !bindings.insert(p.into()) {
+                is_valid = false;
+            }
+        }
 
         if !is_valid {
             *self = Self::default();
@@ -491,6 +523,14 @@ impl Hotkeys {
             .redo
             .map(|b| b.pressed(ctx.input()))
             .unwrap_or_default();
+        let find = self
+            .find
+            .map(|b| b.pressed(ctx.input()))
+            .unwrap_or_default();
+        let toggle_preview = self
+            .toggle_preview
+            .map(|b| b.pressed(ctx.input()))
+            .unwrap_or_default();
         HotkeyState {
             new_note,
             copy,
@@ -503,6 +543,8 @@ impl Hotkeys {
             quit,
             undo,
             redo,
+            find,
+            toggle_preview,
         }
     }
 }
@@ -521,6 +563,8 @@ pub struct HotkeyState {
     pub quit: bool,
     pub undo: bool,
     pub redo: bool,
+    pub find: bool,
+    pub toggle_preview: bool,
 }
 
 pub struct HotkeyEditor;
@@ -580,6 +624,16 @@ impl HotkeyEditor {
                     Hotkey::new(&mut hotkeys.save).ui(ui);
                     ui.end_row();
 
+                    ui.label(RichText::new("Find in Note").font(FontId::proportional(LABEL_SIZE)));
+                    Hotkey::new(&mut hotkeys.find).ui(ui);
+                    ui.end_row();
+
+                    ui.label(
+                        RichText::new("Toggle Markdown Preview").font(FontId::proportional(LABEL_SIZE)),
+                    );
+                    Hotkey::new(&mut hotkeys.toggle_preview).ui(ui);
+                    ui.end_row();
+
                     ui.label(
                         RichText::new("Close Note Editor").font(FontId::proportional(LABEL_SIZE)),
                     );
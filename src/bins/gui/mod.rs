@@ -6,7 +6,9 @@
 
 mod app;
 mod backend;
+mod clipboard;
 mod hotkey;
+mod reminders;
 mod settings;
 mod theme;
 mod widgets;
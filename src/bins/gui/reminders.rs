@@ -0,0 +1,102 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Surfacing due reminders as toasts when a database is opened.
+
+use egui_toast::{Toast, ToastKind, ToastOptions};
+
+use crate::types::{Note, Reminder};
+
+/// How long the toast for a due reminder stays up, longer than other toasts so it isn't missed.
+const REMINDER_TOAST_SECS: u64 = 15;
+
+/// The toast shown for a due `reminder` on `note`.
+pub fn reminder_toast(note: &Note, reminder: &Reminder) -> Toast {
+    Toast {
+        kind: ToastKind::Warning,
+        text: format!(
+            "Reminder: {} ({}, due {} {})",
+            reminder.text(),
+            note.title(),
+            reminder.date_display(),
+            reminder.time_display()
+        )
+        .into(),
+        options: ToastOptions {
+            show_icon: true,
+            expires_at: Some(std::time::Instant::now() + std::time::Duration::from_secs(REMINDER_TOAST_SECS)),
+        },
+    }
+}
+
+/// The toasts for the `due` reminders sent by the backend, see
+/// [`Database::due_reminders`](crate::db::Database::due_reminders).
+pub fn due_reminder_toasts(due: &[(Note, Reminder)]) -> Vec<Toast> {
+    due.iter().map(|(note, reminder)| reminder_toast(note, reminder)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use time::{macros::datetime, OffsetDateTime};
+    use tinyid::TinyId;
+
+    use crate::db::Database;
+
+    #[test]
+    #[no_coverage]
+    fn due_reminders_to_toasts() {
+        let now = datetime!(2022-06-01 10:30 UTC);
+        let reminder = |text: &str, due: OffsetDateTime, fired: bool| {
+            Reminder::existing(TinyId::random(), text.to_string(), due, fired)
+        };
+        let db = Database::from_notes_vec(vec![
+            Note::create((
+                "Errands",
+                "",
+                vec![],
+                vec![
+                    reminder("Groceries", datetime!(2022-06-01 09:00 UTC), false),
+                    reminder("Tomorrow", datetime!(2022-06-02 09:00 UTC), false),
+                    reminder("Already seen", datetime!(2022-05-30 09:00 UTC), true),
+                ],
+            )),
+            Note::create(("Work", "", vec![], vec![reminder("Standup", datetime!(2022-05-31 09:00 UTC), false)])),
+            Note::create(("Empty", "")),
+        ])
+        .unwrap();
+        let owned = |due: Vec<(&Note, &Reminder)>| {
+            due.into_iter()
+                .map(|(note, reminder)| (note.clone(), reminder.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let due = db.due_reminders(now);
+        let texts = due
+            .iter()
+            .map(|(note, reminder)| (note.title(), reminder.text()))
+            .collect::<Vec<_>>();
+        assert_eq!(texts, [("Work", "Standup"), ("Errands", "Groceries")]);
+
+        let toasts = due_reminder_toasts(&owned(due.clone()));
+        assert_eq!(toasts.len(), 2);
+        let (note, reminder) = due[0];
+        assert!(matches!(toasts[0].kind, ToastKind::Warning));
+        assert_eq!(
+            toasts[0].text.text(),
+            format!(
+                "Reminder: Standup (Work, due {} {})",
+                reminder.date_display(),
+                reminder.time_display()
+            )
+        );
+        assert_eq!(toasts[0].text.text(), reminder_toast(note, reminder).text.text());
+        assert!(toasts[1].text.text().starts_with("Reminder: Groceries (Errands, due "));
+
+        assert!(due_reminder_toasts(&owned(db.due_reminders(datetime!(2022-05-01 00:00 UTC)))).is_empty());
+    }
+}
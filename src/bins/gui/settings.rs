@@ -10,6 +10,11 @@ use directories::ProjectDirs;
 use eframe::egui;
 use serde::{Deserialize, Serialize};
 
+use crate::types::{
+    api::{OrderBy, OrderDirection, Ordering},
+    TagNormalizer,
+};
+
 pub enum ValueType {
     Path,
     String,
@@ -26,6 +31,10 @@ pub enum AppSettingKind {
     AutosaveEnabled,
     AutosaveInterval,
     HumanizeDates,
+    LowercaseTags,
+    DashTagSpaces,
+    SeedWelcomeNote,
+    DateFormat,
 }
 
 impl AppSettingKind {
@@ -34,8 +43,12 @@ impl AppSettingKind {
             AppSettingKind::DefaultDatabase | AppSettingKind::DefaultSavePath => ValueType::Path,
             AppSettingKind::LoadDefaultOnStart
             | AppSettingKind::AutosaveEnabled
-            | AppSettingKind::HumanizeDates => ValueType::Bool,
+            | AppSettingKind::HumanizeDates
+            | AppSettingKind::LowercaseTags
+            | AppSettingKind::DashTagSpaces
+            | AppSettingKind::SeedWelcomeNote => ValueType::Bool,
             AppSettingKind::AutosaveInterval => ValueType::Number,
+            AppSettingKind::DateFormat => ValueType::String,
         }
     }
 }
@@ -44,6 +57,22 @@ fn default_humanize() -> bool {
     true
 }
 
+fn default_seed_welcome() -> bool {
+    true
+}
+
+fn default_date_format() -> String {
+    crate::util::dtf::DEFAULT_DATE_FORMAT.to_string()
+}
+
+fn default_note_list_order_by() -> OrderBy {
+    OrderBy::Created
+}
+
+fn default_note_list_direction() -> OrderDirection {
+    OrderDirection::Ascending
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AppSettings {
     /// The default database location
@@ -59,6 +88,21 @@ pub struct AppSettings {
     /// Whether dates should be humanized or displayed as raw timestamps
     #[serde(default = "default_humanize")]
     pub humanize_dates: bool,
+    /// How tags are cleaned up when notes are created or updated
+    #[serde(default)]
+    pub tag_normalizer: TagNormalizer,
+    /// Whether newly created databases start with a "getting started" note
+    #[serde(default = "default_seed_welcome")]
+    pub seed_welcome_note: bool,
+    /// How timestamps are displayed when dates are not humanized, see [`crate::util::dtf::format`]
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// The field the note list is sorted by
+    #[serde(default = "default_note_list_order_by")]
+    pub note_list_order_by: OrderBy,
+    /// Whether the note list is sorted ascending or descending
+    #[serde(default = "default_note_list_direction")]
+    pub note_list_direction: OrderDirection,
 }
 
 impl AppSettings {
@@ -83,11 +127,21 @@ impl AppSettings {
             load_default_on_start: true,
             autosave_enabled: true,
             humanize_dates: true,
+            tag_normalizer: TagNormalizer::default(),
+            seed_welcome_note: true,
+            date_format: default_date_format(),
+            note_list_order_by: default_note_list_order_by(),
+            note_list_direction: default_note_list_direction(),
         };
 
         Ok(config)
     }
 
+    /// The order of the notes in the note list.
+    pub fn note_list_ordering(&self) -> Ordering {
+        Ordering::new(self.note_list_order_by, self.note_list_direction)
+    }
+
     pub fn load_or_create() -> crate::Result<Self> {
         if let Ok(config) = Self::load_default() {
             return Ok(config);
@@ -146,6 +200,10 @@ impl AppSettings {
             AppSettingKind::AutosaveInterval => self.autosave_interval.to_string(),
             AppSettingKind::AutosaveEnabled => self.autosave_enabled.to_string(),
             AppSettingKind::HumanizeDates => self.humanize_dates.to_string(),
+            AppSettingKind::LowercaseTags => self.tag_normalizer.lowercase().to_string(),
+            AppSettingKind::DashTagSpaces => self.tag_normalizer.dash_spaces().to_string(),
+            AppSettingKind::SeedWelcomeNote => self.seed_welcome_note.to_string(),
+            AppSettingKind::DateFormat => self.date_format.clone(),
         }
     }
 
@@ -175,6 +233,22 @@ impl AppSettings {
                 self.humanize_dates = value.parse().ok()?;
                 Some(())
             }
+            AppSettingKind::LowercaseTags => {
+                self.tag_normalizer.set_lowercase(value.parse().ok()?);
+                Some(())
+            }
+            AppSettingKind::DashTagSpaces => {
+                self.tag_normalizer.set_dash_spaces(value.parse().ok()?);
+                Some(())
+            }
+            AppSettingKind::SeedWelcomeNote => {
+                self.seed_welcome_note = value.parse().ok()?;
+                Some(())
+            }
+            AppSettingKind::DateFormat => {
+                self.date_format = value.to_string();
+                Some(())
+            }
         }
     }
 
@@ -237,6 +311,39 @@ impl AppSettingsUi {
                 has_changed = true;
             }
 
+            ui.add_enabled_ui(!settings.humanize_dates, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Date Format:");
+                    egui::ComboBox::from_id_source(id.with("date_format_presets"))
+                        .selected_text(settings.date_format.as_str())
+                        .show_ui(ui, |ui| {
+                            for (name, _) in crate::util::dtf::DATE_FORMAT_PRESETS {
+                                if ui
+                                    .selectable_label(settings.date_format == *name, *name)
+                                    .clicked()
+                                {
+                                    settings.date_format = (*name).to_string();
+                                    has_changed = true;
+                                }
+                            }
+                        });
+                    if ui
+                        .text_edit_singleline(&mut settings.date_format)
+                        .on_hover_text("A preset name or a format such as '[year]-[month]-[day] [hour]:[minute]'")
+                        .changed()
+                    {
+                        has_changed = true;
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Preview: {}",
+                        crate::util::dtf::format(&time::OffsetDateTime::now_utc(), &settings.date_format)
+                    ))
+                    .weak(),
+                );
+            });
+
             let ldos_res = ui.checkbox(
                 &mut settings.load_default_on_start,
                 "Load default database on application start",
@@ -306,6 +413,37 @@ impl AppSettingsUi {
             if asi_res.changed() {
                 has_changed = true;
             }
+
+            let mut lowercase_tags = settings.tag_normalizer.lowercase();
+            if ui
+                .checkbox(&mut lowercase_tags, "Lowercase tags when saving notes")
+                .changed()
+            {
+                settings.tag_normalizer.set_lowercase(lowercase_tags);
+                has_changed = true;
+            }
+
+            let mut dash_tag_spaces = settings.tag_normalizer.dash_spaces();
+            if ui
+                .checkbox(
+                    &mut dash_tag_spaces,
+                    "Replace spaces in tags with dashes when saving notes",
+                )
+                .changed()
+            {
+                settings.tag_normalizer.set_dash_spaces(dash_tag_spaces);
+                has_changed = true;
+            }
+
+            if ui
+                .checkbox(
+                    &mut settings.seed_welcome_note,
+                    "Add a getting started note to new databases",
+                )
+                .changed()
+            {
+                has_changed = true;
+            }
         });
 
         has_changed
@@ -6,17 +6,88 @@
 
 use crossbeam_channel::Sender;
 use eframe::{
-    egui::{Button, Grid, Layout, ScrollArea, Ui},
+    egui::{Button, Grid, Layout, ScrollArea, TextEdit, TextStyle, Ui},
     emath::Align,
 };
 use egui_toast::Toast;
+use tinyid::TinyId;
 
-use crate::types::Note;
+use crate::types::{
+    api::{Filter, FilterExpr, NoteFilter, NoteStringField, OrderBy, OrderDirection, Ordering, StringSearch},
+    Note,
+};
 
 use super::{super::settings::AppSettings, ToApp};
 
+/// The filter used by the search box of the [`NoteList`]: notes whose title, content or tags contain `query`,
+/// ignoring case.
+fn search_filter(query: &str) -> FilterExpr {
+    FilterExpr::or(
+        [NoteStringField::Title, NoteStringField::Content, NoteStringField::Tags]
+            .into_iter()
+            .map(|field| {
+                Filter::single(NoteFilter::String(field, StringSearch::contains_ci(query.to_string(), false))).into()
+            })
+            .collect(),
+    )
+}
+
+/// The fields the [`NoteList`] can be sorted by, and their labels.
+const SORT_FIELDS: [(OrderBy, &str); 3] = [
+    (OrderBy::Title, "Title"),
+    (OrderBy::Created, "Created"),
+    (OrderBy::Updated, "Updated"),
+];
+
+/// The notes shown by the [`NoteList`] for the search `query` that also pass the `tags` filter, sorted by
+/// `ordering`. An empty (or blank) query doesn't filter anything out.
+fn visible_notes<'n>(notes: &'n [Note], query: &str, tags: &Filter, ordering: Ordering) -> Vec<&'n Note> {
+    let query = query.trim();
+    let search = if query.is_empty() {
+        Filter::empty().to_expr()
+    } else {
+        search_filter(query)
+    };
+
+    let predicate = FilterExpr::and(vec![search, tags.to_expr()]).predicate();
+    let mut visible = notes.iter().filter(|note| predicate(note)).collect::<Vec<_>>();
+    let comparison = ordering.comparison();
+    visible.sort_by(|a, b| comparison(a, b));
+    visible
+}
+
+/// The ordering after clicking the sort control for `field`: the same field again flips the direction, a
+/// different field is sorted ascending.
+fn next_ordering(mut current: Ordering, field: OrderBy) -> Ordering {
+    if current.field() == field {
+        current.reverse();
+        current
+    } else {
+        Ordering::ascending(field)
+    }
+}
+
+/// How many characters of a [`Note::summary_line`] fit on a button `width` points wide, assuming every
+/// character is `char_width` points wide.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn summary_width(width: f32, char_width: f32) -> usize {
+    if char_width <= 0. {
+        return 0;
+    }
+    (width / char_width).floor().max(0.) as usize
+}
+
+/// Keeps the `selected` note selected only if it is still one of the `visible` notes.
+fn retain_selection(selected: Option<TinyId>, visible: &[&Note]) -> Option<TinyId> {
+    selected.filter(|id| visible.iter().any(|note| note.id() == *id))
+}
+
 pub struct NoteList {
     notes: Vec<Note>,
+    query: String,
+    tag_filter: Filter,
+    ordering: Ordering,
+    selected: Option<TinyId>,
     app_sender: Sender<ToApp>,
     toast_sender: Sender<Toast>,
 }
@@ -29,6 +100,10 @@ impl NoteList {
     ) -> Self {
         Self {
             notes: Vec::new(),
+            query: String::new(),
+            tag_filter: Filter::empty(),
+            ordering: settings.note_list_ordering(),
+            selected: None,
             app_sender,
             toast_sender,
         }
@@ -42,22 +117,45 @@ impl NoteList {
     ) -> Self {
         Self {
             notes,
+            query: String::new(),
+            tag_filter: Filter::empty(),
+            ordering: settings.note_list_ordering(),
+            selected: None,
             app_sender,
             toast_sender,
         }
     }
 
     pub fn render(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.query).hint_text("Search notes..."));
+            if !self.query.is_empty() && ui.small_button("x").on_hover_text("Clear search").clicked() {
+                self.query.clear();
+            }
+        });
+        self.render_sort_controls(ui);
+        ui.separator();
+
+        let visible = visible_notes(&self.notes, &self.query, &self.tag_filter, self.ordering);
+        self.selected = retain_selection(self.selected, &visible);
+
         ScrollArea::vertical().show(ui, |ui| {
             let max_width = ui.available_width();
+            // The widest glyph is used so that a summary line never has to wrap.
+            let font_id = TextStyle::Button.resolve(ui.style());
+            let char_width = ui.fonts().glyph_width(&font_id, 'M');
+            let summary_chars = summary_width(max_width - 2. * ui.spacing().button_padding.x, char_width);
             Grid::new("note_selection_grid")
                 .num_columns(1)
                 .max_col_width(max_width)
                 .min_col_width(max_width)
                 .min_row_height(15.)
                 .show(ui, |ui| {
-                    for (i, note) in self.notes.iter().enumerate() {
-                        let button = Button::new(note.title()).wrap(true);
+                    for &note in &visible {
+                        let mut button = Button::new(note.summary_line(summary_chars)).wrap(false);
+                        if self.selected == Some(note.id()) {
+                            button = button.fill(ui.visuals().selection.bg_fill);
+                        }
                         ui.allocate_ui_with_layout(
                             [max_width, 75.].into(),
                             Layout::top_down_justified(Align::Center),
@@ -85,6 +183,40 @@ impl NoteList {
         });
     }
 
+    fn render_sort_controls(&mut self, ui: &mut Ui) {
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            ui.label("Sort:");
+            for (field, label) in SORT_FIELDS {
+                let is_current = self.ordering.field() == field;
+                let text = match (is_current, self.ordering.direction()) {
+                    (false, _) => label.to_string(),
+                    (true, OrderDirection::Ascending) => format!("{} \u{25b2}", label),
+                    (true, OrderDirection::Descending) => format!("{} \u{25bc}", label),
+                };
+                let hover = if is_current { "Reverse the sort direction" } else { "Sort by this field" };
+                if ui.selectable_label(is_current, text).on_hover_text(hover).clicked() {
+                    clicked = Some(field);
+                }
+            }
+        });
+
+        if let Some(field) = clicked {
+            self.ordering = next_ordering(self.ordering, field);
+            Self::send_app_msg(&self.app_sender, ToApp::NoteListSorted(self.ordering));
+        }
+    }
+
+    /// Only shows the notes passing `filter`, in addition to the search box, see [`super::TagPanel::filter`].
+    pub fn set_tag_filter(&mut self, filter: Filter) {
+        self.tag_filter = filter;
+    }
+
+    /// Marks the note with the given id as the selected note, e.g. when it is opened in the editor.
+    pub fn set_selected(&mut self, id: Option<TinyId>) {
+        self.selected = id;
+    }
+
     pub fn update_note_list(&mut self, notes: Vec<Note>) {
         self.notes = notes;
     }
@@ -97,3 +229,98 @@ impl NoteList {
         sender.send(msg).expect("Unable to send message to GuiApp");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn search_filters_notes() {
+        let notes = vec![
+            Note::create(("Groceries", "Milk, eggs and BREAD", vec!["errands"])),
+            Note::create(("Bread recipe", "Flour, water, salt", vec!["baking"])),
+            Note::create(("Meeting", "Discuss the roadmap", vec!["work"])),
+        ];
+        let no_tags = Filter::empty();
+        let order = Ordering::default();
+        let titles = |query: &str| {
+            visible_notes(&notes, query, &no_tags, order)
+                .iter()
+                .map(|n| n.title().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(titles(""), ["Groceries", "Bread recipe", "Meeting"]);
+        assert_eq!(titles("   "), ["Groceries", "Bread recipe", "Meeting"]);
+        assert_eq!(titles("bread"), ["Groceries", "Bread recipe"], "title and content, ignoring case");
+        assert_eq!(titles("WORK"), ["Meeting"], "tags");
+        assert_eq!(titles(" roadmap "), ["Meeting"]);
+        assert!(titles("nothing matches").is_empty());
+
+        let baking = Filter::single(NoteFilter::any_tag(StringSearch::matches("baking".to_string(), false)));
+        assert_eq!(visible_notes(&notes, "", &baking, order).len(), 1);
+        assert_eq!(visible_notes(&notes, "bread", &baking, order)[0].id(), notes[1].id());
+        assert!(visible_notes(&notes, "meeting", &baking, order).is_empty());
+
+        let selected = Some(notes[1].id());
+        assert_eq!(retain_selection(selected, &visible_notes(&notes, "bread", &no_tags, order)), selected);
+        assert_eq!(retain_selection(selected, &visible_notes(&notes, "meeting", &no_tags, order)), None);
+        assert_eq!(retain_selection(selected, &visible_notes(&notes, "", &no_tags, order)), selected);
+        assert_eq!(retain_selection(None, &visible_notes(&notes, "", &no_tags, order)), None);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn sorted_by_ordering() {
+        let notes = vec![
+            Note::create(("banana", "", vec![])),
+            Note::create(("Cherry", "", vec![])),
+            Note::create(("apple", "", vec![])),
+            Note::create(("Apple", "", vec![])),
+        ];
+        let no_tags = Filter::empty();
+
+        let ordering = Ordering::ascending(OrderBy::Title);
+        let comparison = ordering.comparison();
+        let mut expected = notes.iter().collect::<Vec<_>>();
+        expected.sort_by(|a, b| comparison(a, b));
+        let displayed = visible_notes(&notes, "", &no_tags, ordering);
+        assert_eq!(
+            displayed.iter().map(|n| n.id()).collect::<Vec<_>>(),
+            expected.iter().map(|n| n.id()).collect::<Vec<_>>()
+        );
+        assert!(displayed.windows(2).all(|w| comparison(w[0], w[1]) != std::cmp::Ordering::Greater));
+
+        let descending = visible_notes(&notes, "", &no_tags, Ordering::descending(OrderBy::Title));
+        assert_eq!(
+            descending.iter().rev().map(|n| n.title()).collect::<Vec<_>>(),
+            displayed.iter().map(|n| n.title()).collect::<Vec<_>>()
+        );
+
+        // Sorting applies to the search results too.
+        let searched = visible_notes(&notes, "apple", &no_tags, ordering);
+        assert_eq!(searched.iter().map(|n| n.title()).collect::<Vec<_>>(), ["Apple", "apple"]);
+
+        let title = Ordering::ascending(OrderBy::Title);
+        assert_eq!(next_ordering(title, OrderBy::Title), Ordering::descending(OrderBy::Title));
+        assert_eq!(next_ordering(Ordering::descending(OrderBy::Title), OrderBy::Title), title);
+        assert_eq!(next_ordering(title, OrderBy::Updated), Ordering::ascending(OrderBy::Updated));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn summary_width_fits_the_button() {
+        assert_eq!(summary_width(100., 10.), 10);
+        assert_eq!(summary_width(105., 10.), 10);
+        assert_eq!(summary_width(5., 10.), 0);
+        assert_eq!(summary_width(-20., 10.), 0);
+        assert_eq!(summary_width(100., 0.), 0);
+
+        let note = Note::create(("A title that is much too long for a narrow list", "", vec!["a", "b"]));
+        let line = note.summary_line(summary_width(120., 8.));
+        assert!(line.chars().count() <= 15, "'{}' does not fit", line);
+        assert!(line.ends_with("#2"));
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Converts markdown into an egui [`LayoutJob`] for the markdown preview of the [`super::NoteEditor`].
+
+use eframe::{
+    egui::{self, text::LayoutJob, Color32, FontId, Stroke, TextFormat},
+    epaint::FontFamily,
+};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+
+/// The fonts and colors used by [`layout_markdown`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownStyle {
+    pub body: FontId,
+    pub code: FontId,
+    pub text_color: Color32,
+    pub strong_color: Color32,
+    pub weak_color: Color32,
+    pub link_color: Color32,
+    pub code_background: Color32,
+}
+
+impl MarkdownStyle {
+    /// Takes the fonts and colors from the given egui [`egui::Style`].
+    pub fn from_style(style: &egui::Style) -> Self {
+        Self {
+            body: egui::TextStyle::Body.resolve(style),
+            code: egui::TextStyle::Monospace.resolve(style),
+            text_color: style.visuals.text_color(),
+            strong_color: style.visuals.strong_text_color(),
+            weak_color: style.visuals.weak_text_color(),
+            link_color: style.visuals.hyperlink_color,
+            code_background: style.visuals.code_bg_color,
+        }
+    }
+
+    /// The font size of a heading, relative to the body font.
+    pub fn heading_size(&self, level: HeadingLevel) -> f32 {
+        let scale = match level {
+            HeadingLevel::H1 => 2.0,
+            HeadingLevel::H2 => 1.6,
+            HeadingLevel::H3 => 1.35,
+            HeadingLevel::H4 => 1.2,
+            HeadingLevel::H5 | HeadingLevel::H6 => 1.1,
+        };
+        self.body.size * scale
+    }
+}
+
+impl Default for MarkdownStyle {
+    fn default() -> Self {
+        Self::from_style(&egui::Style::default())
+    }
+}
+
+/// Lays out the markdown in `text` as formatted egui text, wrapped at `wrap_width`.
+///
+/// Headings are larger and stronger, bold text is stronger, italic text is slanted, list items get a bullet (or
+/// their number) and are indented by nesting level, and inline code and code blocks use the monospace font on
+/// the code background. Blocks are separated by a blank line.
+pub fn layout_markdown(text: &str, style: &MarkdownStyle, wrap_width: f32) -> LayoutJob {
+    let mut renderer = Renderer::new(style);
+    renderer.job.wrap_width = wrap_width;
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(text, options) {
+        renderer.event(event);
+    }
+    renderer.job
+}
+
+/// The state of a single [`layout_markdown`] call.
+struct Renderer<'style> {
+    style: &'style MarkdownStyle,
+    job: LayoutJob,
+    heading: Option<HeadingLevel>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    link: usize,
+    quote: usize,
+    /// The lists currently open, innermost last, holding the number of the next item of ordered lists.
+    lists: Vec<Option<u64>>,
+    /// The contents of the code block currently open, laid out once the block ends so the trailing newline
+    /// can be dropped.
+    code_block: Option<String>,
+}
+
+impl<'style> Renderer<'style> {
+    fn new(style: &'style MarkdownStyle) -> Self {
+        Self {
+            style,
+            job: LayoutJob::default(),
+            heading: None,
+            strong: 0,
+            emphasis: 0,
+            strikethrough: 0,
+            link: 0,
+            quote: 0,
+            lists: Vec::new(),
+            code_block: None,
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code_block {
+                Some(code) => code.push_str(&text),
+                None => self.push(&text, self.format()),
+            },
+            Event::Code(code) => self.push(&code, self.code_format()),
+            Event::Html(html) => self.push(&html, self.code_format()),
+            Event::FootnoteReference(name) => self.push(&format!("[^{}]", name), self.format()),
+            Event::SoftBreak => self.push(" ", self.format()),
+            Event::HardBreak => self.push("\n", self.format()),
+            Event::Rule => {
+                self.block_break();
+                let format = TextFormat::simple(self.style.body.clone(), self.style.weak_color);
+                self.push("――――――――――", format);
+            }
+            Event::TaskListMarker(checked) => {
+                self.push(if checked { "[x] " } else { "[ ] " }, self.format());
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::Table(_) | Tag::FootnoteDefinition(_) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            Tag::Heading(level, ..) => {
+                self.block_break();
+                self.heading = Some(level);
+            }
+            Tag::BlockQuote => {
+                self.block_break();
+                self.quote += 1;
+            }
+            Tag::CodeBlock(_) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.newline();
+                }
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.newline();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.newline();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}{}. ", indent, *number - 1)
+                    }
+                    _ => format!("{}• ", indent),
+                };
+                let format = TextFormat::simple(self.style.body.clone(), self.style.text_color);
+                self.push(&marker, format);
+            }
+            Tag::TableHead | Tag::TableRow => self.newline(),
+            Tag::TableCell => self.push(" | ", self.format()),
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link(..) | Tag::Image(..) => self.link += 1,
+        }
+    }
+
+    fn end(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading(..) => self.heading = None,
+            Tag::BlockQuote => self.quote = self.quote.saturating_sub(1),
+            Tag::CodeBlock(kind) => {
+                let code = self.code_block.take().unwrap_or_default();
+                if let CodeBlockKind::Fenced(lang) = kind && !lang.is_empty() {
+                    let format = TextFormat::simple(self.style.code.clone(), self.style.weak_color);
+                    self.push(&format!("{}\n", lang), format);
+                }
+                self.push(code.trim_end_matches('\n'), self.code_format());
+            }
+            Tag::List(_) => {
+                self.lists.pop();
+            }
+            Tag::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            Tag::Strong => self.strong = self.strong.saturating_sub(1),
+            Tag::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+            Tag::Link(..) | Tag::Image(..) => self.link = self.link.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    /// The format of regular text given the currently open tags.
+    fn format(&self) -> TextFormat {
+        let mut format = TextFormat::simple(self.style.body.clone(), self.style.text_color);
+        if let Some(level) = self.heading {
+            format.font_id.size = self.style.heading_size(level);
+            format.color = self.style.strong_color;
+        }
+        if self.quote > 0 {
+            format.color = self.style.weak_color;
+            format.italics = true;
+        }
+        if self.strong > 0 {
+            format.color = self.style.strong_color;
+        }
+        if self.emphasis > 0 {
+            format.italics = true;
+        }
+        if self.strikethrough > 0 {
+            format.strikethrough = Stroke::new(1.0, format.color);
+        }
+        if self.link > 0 {
+            format.color = self.style.link_color;
+            format.underline = Stroke::new(1.0, self.style.link_color);
+        }
+        format
+    }
+
+    fn code_format(&self) -> TextFormat {
+        let mut font_id = self.style.code.clone();
+        if let Some(level) = self.heading {
+            font_id = FontId::new(self.style.heading_size(level), FontFamily::Monospace);
+        }
+        TextFormat {
+            background: self.style.code_background,
+            ..TextFormat::simple(font_id, self.style.text_color)
+        }
+    }
+
+    fn push(&mut self, text: &str, format: TextFormat) {
+        self.job.append(text, 0.0, format);
+    }
+
+    /// Starts a new line, unless nothing has been laid out yet or the last line is already empty.
+    fn newline(&mut self) {
+        if !self.job.text.is_empty() && !self.job.text.ends_with('\n') {
+            self.push("\n", self.format());
+        }
+    }
+
+    /// Separates the next block from the previous one by a blank line.
+    fn block_break(&mut self) {
+        self.newline();
+        if !self.job.text.is_empty() && !self.job.text.ends_with("\n\n") {
+            self.push("\n", TextFormat::simple(self.style.body.clone(), self.style.text_color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn layout(text: &str) -> LayoutJob {
+        layout_markdown(text, &MarkdownStyle::default(), f32::INFINITY)
+    }
+
+    fn format_of<'job>(job: &'job LayoutJob, text: &str) -> &'job TextFormat {
+        job.sections
+            .iter()
+            .find(|section| &job.text[section.byte_range.clone()] == text)
+            .map(|section| &section.format)
+            .unwrap_or_else(|| panic!("no section laid out as '{}' in {:?}", text, job.text))
+    }
+
+    #[test]
+    #[no_coverage]
+    fn heading() {
+        let style = MarkdownStyle::default();
+        let job = layout("# Title\n\nSome *body* text.\n\n### Smaller");
+        assert_eq!(job.text, "Title\n\nSome body text.\n\nSmaller");
+
+        let title = format_of(&job, "Title");
+        assert!((title.font_id.size - style.heading_size(HeadingLevel::H1)).abs() < f32::EPSILON);
+        assert!(title.font_id.size > style.body.size);
+        assert_eq!(title.color, style.strong_color);
+        assert!(format_of(&job, "Smaller").font_id.size < title.font_id.size);
+
+        assert_eq!(format_of(&job, "Some ").font_id, style.body);
+        assert!(format_of(&job, "body").italics);
+        assert!(!format_of(&job, " text.").italics);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn list() {
+        let job = layout("- one\n- **two**\n  - nested\n\n3. third\n4. fourth");
+        assert_eq!(job.text, "• one\n• two\n  • nested\n\n3. third\n4. fourth");
+        assert_eq!(format_of(&job, "two").color, MarkdownStyle::default().strong_color);
+
+        let job = layout("Intro\n\n- [x] done\n- [ ] todo");
+        assert_eq!(job.text, "Intro\n\n• [x] done\n• [ ] todo");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn inline_code() {
+        let style = MarkdownStyle::default();
+        let job = layout("Run `cargo test` first.");
+        assert_eq!(job.text, "Run cargo test first.");
+
+        let code = format_of(&job, "cargo test");
+        assert_eq!(code.font_id.family, FontFamily::Monospace);
+        assert_eq!(code.background, style.code_background);
+        let text = format_of(&job, "Run ");
+        assert_eq!(text.font_id.family, FontFamily::Proportional);
+        assert_eq!(text.background, Color32::TRANSPARENT);
+
+        let job = layout("Before\n\n```rust\nfn main() {}\n```\n\nAfter");
+        assert_eq!(job.text, "Before\n\nrust\nfn main() {}\n\nAfter");
+        assert_eq!(format_of(&job, "fn main() {}").font_id, style.code);
+    }
+}
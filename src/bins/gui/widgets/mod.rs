@@ -5,19 +5,25 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 mod list;
+mod markdown;
 mod msg;
 mod note_editor;
 mod panels;
+mod reminder_picker;
 mod screens;
 mod simple_prompt;
+mod tag_panel;
 mod time;
 mod toaster;
 
 pub use self::time::{DatePicker, HourMinutePicker};
 pub use list::NoteList;
+pub use markdown::{layout_markdown, MarkdownStyle};
 pub use msg::ToApp;
 pub use note_editor::NoteEditor;
+pub use reminder_picker::{local_offset, remove_reminder_update, PickerAction, ReminderPicker};
 pub use simple_prompt::SimplePrompt;
+pub use tag_panel::TagPanel;
 pub use toaster::{Toast, ToastKind, Toaster};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -6,7 +6,7 @@
 
 use egui_toast::ToastKind;
 
-use crate::types::Note;
+use crate::types::{api::Ordering, Note, UpdateNote};
 
 pub enum ToApp {
     CreateNewNote,
@@ -14,6 +14,10 @@ pub enum ToApp {
     DeleteNote(Note),
     DeleteActiveNote,
     SaveRequested,
+    /// An update to a single note, e.g. its reminders, that should be applied by the backend.
+    ApplyUpdate(UpdateNote),
+    /// The note list has been sorted differently, and the new order should be saved to the settings.
+    NoteListSorted(Ordering),
     Toast(ToastKind, String),
     Error(String),
     Debug(String),
@@ -4,26 +4,26 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use std::sync::Once;
+use std::{collections::VecDeque, ops::Range};
 
-use chrono::Datelike;
 use crossbeam_channel::Sender;
-use eframe::egui::{self, TextEdit, Ui};
-use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
-use egui_extras::DatePickerButton;
+use eframe::{
+    egui::{self, text::LayoutJob, TextEdit, TextFormat, Ui},
+    epaint::text::cursor::CCursor,
+};
 use egui_toast::{Toast, ToastKind};
 use time::OffsetDateTime;
 use tinyid::TinyId;
 
 use crate::{
     bins::gui::app,
-    types::{
-        time::{Hour, Hour12, Minute, TimePeriod},
-        Note, Reminder,
-    },
+    types::{Note, UpdateNote},
 };
 
-use super::{super::settings::AppSettings, ToApp, WidgetState};
+use super::{
+    super::settings::AppSettings, layout_markdown, local_offset, remove_reminder_update, MarkdownStyle, PickerAction,
+    ReminderPicker, ToApp, WidgetState,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 enum PreviewState {
@@ -31,25 +31,194 @@ enum PreviewState {
     Closed,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum ReminderFocus {
-    Date,
-    Time,
-    Text,
+/// State for the find bar shown above the content editor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FindState {
+    open: bool,
+    query: String,
+    /// The matches for `query`, as found by [`Note::match_ranges`].
+    matches: Vec<Range<usize>>,
+    /// The index into `matches` of the current match.
+    current: Option<usize>,
+    /// Set when the current match changes so the editor is scrolled to it on the next frame.
+    scroll_pending: bool,
+    request_focus: bool,
+}
+
+impl FindState {
+    /// Recalculates the matches for `content`, keeping the current match if there still is one.
+    fn update_matches(&mut self, content: &str) {
+        let matches = Note::match_ranges(content, &self.query);
+        if matches != self.matches {
+            self.current = match self.current {
+                _ if matches.is_empty() => None,
+                Some(current) => Some(current.min(matches.len() - 1)),
+                None => {
+                    self.scroll_pending = true;
+                    Some(0)
+                }
+            };
+            self.matches = matches;
+        }
+    }
+
+    fn next(&mut self) {
+        self.current = cycle_match(self.current, self.matches.len(), true);
+        self.scroll_pending = true;
+    }
+
+    fn previous(&mut self) {
+        self.current = cycle_match(self.current, self.matches.len(), false);
+        self.scroll_pending = true;
+    }
+
+    fn current_range(&self) -> Option<Range<usize>> {
+        self.current.and_then(|current| self.matches.get(current)).cloned()
+    }
+}
+
+/// The parts of a [`Note`] that can be undone, see [`EditHistory`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NoteSnapshot {
+    title: String,
+    content: String,
+    tags: Vec<String>,
+}
+
+impl NoteSnapshot {
+    fn of(note: &Note) -> Self {
+        Self {
+            title: note.title().to_string(),
+            content: note.content().to_string(),
+            tags: note.tags().to_vec(),
+        }
+    }
+
+    fn restore(self, note: &mut Note) {
+        note.set_title(&self.title);
+        note.set_content(&self.content);
+        note.set_tags(self.tags);
+    }
+}
+
+/// The undo and redo history of the active note, holding at most [`EditHistory::CAPACITY`] undo steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct EditHistory {
+    undo: VecDeque<NoteSnapshot>,
+    redo: Vec<NoteSnapshot>,
+}
+
+impl EditHistory {
+    const CAPACITY: usize = 100;
+
+    /// Records the state of the note from before an edit. Any undone edits can no longer be redone.
+    fn record(&mut self, before: NoteSnapshot) {
+        self.redo.clear();
+        if self.undo.back() == Some(&before) {
+            return;
+        }
+        if self.undo.len() == Self::CAPACITY {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(before);
+    }
+
+    /// Returns the state to restore to undo the last edit, given the `current` state of the note.
+    fn undo(&mut self, current: NoteSnapshot) -> Option<NoteSnapshot> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Returns the state to restore to redo the last undone edit, given the `current` state of the note.
+    fn redo(&mut self, current: NoteSnapshot) -> Option<NoteSnapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(next)
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// Moves from the `current` match to the next (or previous) of `len` matches, wrapping around at either end.
+/// With no current match this starts at the first (or last) match, and with no matches at all there is
+/// never a current match.
+fn cycle_match(current: Option<usize>, len: usize, forward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+
+    let next = match (current, forward) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(current), true) => (current.min(len - 1) + 1) % len,
+        (Some(current), false) => (current.min(len - 1) + len - 1) % len,
+    };
+    Some(next)
+}
+
+/// The footer shown below the note being edited: its word count, character count and estimated reading time.
+fn footer_text(note: &Note) -> String {
+    let words = note.word_count();
+    let chars = note.char_count();
+    format!(
+        "{} word{} | {} character{} | {} min read",
+        words,
+        if words == 1 { "" } else { "s" },
+        chars,
+        if chars == 1 { "" } else { "s" },
+        note.reading_time_minutes()
+    )
+}
+
+/// Lays out `text` for the content editor, highlighting every range in `matches` and the `current` one
+/// more strongly.
+fn highlight_layout(ui: &Ui, text: &str, wrap_width: f32, matches: &[Range<usize>], current: Option<usize>) -> LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let normal = TextFormat::simple(font_id, ui.visuals().text_color());
+    let highlighted = TextFormat {
+        background: egui::Color32::from_rgba_unmultiplied(255, 210, 0, 48),
+        ..normal.clone()
+    };
+    let selected = TextFormat {
+        background: ui.visuals().selection.bg_fill,
+        ..normal.clone()
+    };
+
+    let mut job = LayoutJob::default();
+    job.wrap_width = wrap_width;
+    let mut last = 0;
+    for (i, range) in matches.iter().enumerate() {
+        job.append(&text[last..range.start], 0.0, normal.clone());
+        let format = if Some(i) == current { &selected } else { &highlighted };
+        job.append(&text[range.clone()], 0.0, format.clone());
+        last = range.end;
+    }
+    job.append(&text[last..], 0.0, normal);
+    job
 }
 
 pub struct NoteEditor {
     active: WidgetState,
     active_note: Option<Note>,
     active_tag: Option<usize>,
-    active_reminder: Option<(usize, ReminderFocus)>,
-    md_cache: CommonMarkCache,
+    /// The form for adding or editing a reminder, if it is open.
+    reminder_picker: Option<ReminderPicker>,
     has_changes: bool,
     preview_state: PreviewState,
     app_sender: Sender<ToApp>,
     toast_sender: Sender<Toast>,
     humanize_dates: bool,
+    date_format: String,
     force_save: bool,
+    find: FindState,
+    /// The character index of the cursor in the content editor when it was last shown, used by
+    /// [`NoteEditor::insert_text`].
+    content_cursor: Option<usize>,
+    history: EditHistory,
 }
 
 impl NoteEditor {
@@ -65,13 +234,16 @@ impl NoteEditor {
             active: active.into(),
             active_note: note,
             active_tag: None,
-            active_reminder: None,
+            reminder_picker: None,
             has_changes: false,
             preview_state: PreviewState::Closed,
-            md_cache: CommonMarkCache::default(),
             humanize_dates: true,
+            date_format: settings.date_format.clone(),
             toast_sender,
             force_save: false,
+            find: FindState::default(),
+            content_cursor: None,
+            history: EditHistory::default(),
         }
     }
 
@@ -105,15 +277,26 @@ impl NoteEditor {
         ui.add(egui::Separator::default().horizontal().spacing(25.));
         changes = changes || self.render_tags_editor(ui, &mut active_note);
         ui.add(egui::Separator::default().horizontal().spacing(25.));
-        changes = changes || self.render_reminders(ui, &mut active_note);
+        let reminders_update = self.render_reminders(ui, &active_note);
         ui.add(egui::Separator::default().horizontal().spacing(25.));
         self.render_metadata(ui, &mut active_note);
 
         if changes {
+            if let Some(before) = &self.active_note {
+                self.history.record(NoteSnapshot::of(before));
+            }
             self.active_note = Some(active_note);
             self.has_changes = true;
         }
 
+        if let Some(update) = reminders_update {
+            if let Some(note) = &mut self.active_note {
+                note.update(update.clone());
+            }
+            Self::send_app_msg(&self.app_sender, ToApp::ApplyUpdate(update));
+            self.force_save = true;
+        }
+
         if self.force_save {
             self.force_save = false;
             Self::send_app_msg(&self.app_sender, ToApp::SaveRequested);
@@ -135,9 +318,82 @@ impl NoteEditor {
     pub fn clear_note(&mut self) {
         self.active_tag = None;
         self.active_note = None;
-        self.active_reminder = None;
+        self.reminder_picker = None;
         self.has_changes = false;
         self.preview_state = PreviewState::Closed;
+        self.find = FindState::default();
+        self.content_cursor = None;
+        self.history.clear();
+    }
+
+    /// Opens the find bar for the active note, or closes it if it is already open.
+    pub fn toggle_find(&mut self) {
+        if self.find.open {
+            self.find.open = false;
+        } else if self.active_note.is_some() {
+            self.find.open = true;
+            self.find.request_focus = true;
+            self.preview_state = PreviewState::Closed;
+        }
+    }
+
+    /// Inserts `text` into the content of the active note at the last position of the content editor's cursor,
+    /// or at the end of the content if the editor has not had a cursor yet. Returns `false` if there is no
+    /// active note.
+    pub fn insert_text(&mut self, text: &str) -> bool {
+        let Some(note) = &mut self.active_note else {
+            return false;
+        };
+
+        self.history.record(NoteSnapshot::of(note));
+        let cursor = self.content_cursor.unwrap_or(usize::MAX);
+        let (content, cursor) = crate::bins::gui::clipboard::insert_at(note.content(), cursor, text);
+        note.set_content(&content);
+        self.content_cursor = Some(cursor);
+        self.has_changes = true;
+        true
+    }
+
+    /// Undoes the last edit of the active note, returning `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(note) = &mut self.active_note else {
+            return false;
+        };
+        let Some(previous) = self.history.undo(NoteSnapshot::of(note)) else {
+            return false;
+        };
+
+        previous.restore(note);
+        self.has_changes = true;
+        true
+    }
+
+    /// Redoes the last undone edit of the active note, returning `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(note) = &mut self.active_note else {
+            return false;
+        };
+        let Some(next) = self.history.redo(NoteSnapshot::of(note)) else {
+            return false;
+        };
+
+        next.restore(note);
+        self.has_changes = true;
+        true
+    }
+
+    /// Switches the content editor between editing and the markdown preview, if there is an active note.
+    pub fn toggle_preview(&mut self) {
+        if self.active_note.is_none() {
+            return;
+        }
+        self.preview_state = match self.preview_state {
+            PreviewState::Open => PreviewState::Closed,
+            PreviewState::Closed => {
+                self.find.open = false;
+                PreviewState::Open
+            }
+        };
     }
 
     pub fn clear_if_active(&mut self, note: &Note) {
@@ -165,9 +421,12 @@ impl NoteEditor {
 
         self.active_note = note;
         self.active_tag = None;
-        self.active_reminder = None;
+        self.reminder_picker = None;
         self.has_changes = false;
         self.preview_state = PreviewState::Closed;
+        self.find = FindState::default();
+        self.content_cursor = None;
+        self.history.clear();
     }
 
     pub fn is_active(&self) -> bool {
@@ -184,6 +443,7 @@ impl NoteEditor {
 
     pub fn settings_updated(&mut self, settings: &AppSettings) {
         self.humanize_dates = settings.humanize_dates;
+        self.date_format = settings.date_format.clone();
     }
 }
 
@@ -234,15 +494,52 @@ impl NoteEditor {
 
         match self.preview_state {
             PreviewState::Open => {
-                CommonMarkViewer::new("note_content_viewer").show(
-                    ui,
-                    &mut self.md_cache,
-                    note_content.as_str(),
-                );
+                // Laid out from the content every frame, so the preview always reflects the latest edits.
+                let style = MarkdownStyle::from_style(ui.style());
+                let job = layout_markdown(&note_content, &style, ui.available_width());
+                egui::ScrollArea::vertical()
+                    .id_source("note_content_preview")
+                    .show(ui, |ui| ui.add(egui::Label::new(job)));
             }
             PreviewState::Closed => {
-                let content_response = ui.code_editor(&mut note_content);
-                if content_response.changed() {
+                if self.find.open {
+                    self.find.update_matches(&note_content);
+                    self.render_find_bar(ui);
+                }
+
+                let output = if self.find.open {
+                    let (query, current) = (self.find.query.as_str(), self.find.current);
+                    let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                        // The layouter is given the text as edited this frame, so the matches are found again here
+                        // rather than using the ones found before the edit.
+                        let matches = Note::match_ranges(text, query);
+                        ui.fonts().layout_job(highlight_layout(ui, text, wrap_width, &matches, current))
+                    };
+                    TextEdit::multiline(&mut note_content)
+                        .code_editor()
+                        .layouter(&mut layouter)
+                        .show(ui)
+                } else {
+                    TextEdit::multiline(&mut note_content).code_editor().show(ui)
+                };
+
+                if self.find.scroll_pending {
+                    self.find.scroll_pending = false;
+                    if let Some(range) = self.find.current_range() {
+                        let ccursor = CCursor::new(note_content[..range.start].chars().count());
+                        let rect = output
+                            .galley
+                            .pos_from_ccursor(ccursor)
+                            .translate(output.response.rect.min.to_vec2());
+                        ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                    }
+                }
+
+                if let Some(range) = output.cursor_range {
+                    self.content_cursor = Some(range.primary.ccursor.index);
+                }
+
+                if output.response.changed() {
                     note.set_content(note_content.as_str());
                     return true;
                 }
@@ -252,6 +549,46 @@ impl NoteEditor {
         false
     }
 
+    /// Renders the find bar, handling the query input and the next/previous/close controls.
+    fn render_find_bar(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Find:");
+            let query_response = ui.text_edit_singleline(&mut self.find.query);
+            if self.find.request_focus {
+                self.find.request_focus = false;
+                query_response.request_focus();
+            }
+
+            let (enter, shift, escape) = {
+                let input = ui.input();
+                (input.key_pressed(egui::Key::Enter), input.modifiers.shift, input.key_pressed(egui::Key::Escape))
+            };
+            if query_response.lost_focus() && enter {
+                if shift {
+                    self.find.previous();
+                } else {
+                    self.find.next();
+                }
+                query_response.request_focus();
+            }
+
+            ui.label(match self.find.current {
+                Some(current) => format!("{} of {}", current + 1, self.find.matches.len()),
+                None if self.find.query.is_empty() => String::new(),
+                None => "No matches".to_string(),
+            });
+            if ui.small_button("⏶").on_hover_text("Previous match (Shift+Enter)").clicked() {
+                self.find.previous();
+            }
+            if ui.small_button("⏷").on_hover_text("Next match (Enter)").clicked() {
+                self.find.next();
+            }
+            if ui.small_button("x").on_hover_text("Close (Esc)").clicked() || escape {
+                self.find.open = false;
+            }
+        });
+    }
+
     #[allow(clippy::collapsible_if)]
     #[allow(clippy::collapsible_else_if)]
     fn render_tags_editor(&mut self, ui: &mut egui::Ui, note: &mut Note) -> bool {
@@ -330,85 +667,60 @@ impl NoteEditor {
         false
     }
 
-    /// Renders the list of reminders for the note editor. If the reminders are changed the active Note will be updated
-    /// and `true` will be returned. This will also set `self.active_reminder` based on user interactions.
-    fn render_reminders(&mut self, ui: &mut egui::Ui, note: &mut Note) -> bool {
-        type ChronoUtcDate = chrono::Date<chrono::Utc>;
-        type TimeDate = time::Date;
-        use time::{Date, Time, UtcOffset};
-
-        let mut reminders = note.reminders().to_vec();
-        let mut removals: Vec<usize> = Vec::new();
-        let mut reminders_changed = false;
+    /// Renders the reminders of the active note, each with buttons to edit or remove it, along with the
+    /// [`ReminderPicker`] used to add or edit one. Returns the update to send to the backend if the reminders
+    /// were changed.
+    fn render_reminders(&mut self, ui: &mut egui::Ui, note: &Note) -> Option<UpdateNote> {
+        let mut update = None;
         ui.add(egui::Label::new(
             egui::RichText::new("Reminders:").underline(),
         ));
         ui.add_space(10.);
 
-        if reminders.is_empty() {
+        if note.reminders().is_empty() {
             ui.add(egui::Label::new(
                 egui::RichText::new("No reminders set.").weak(),
             ));
         } else {
-            let grid_id = egui::Id::new("note_reminders_grid");
-
-            match self.active_reminder {
-                Some((idx, state)) => {
-                    egui::Grid::new(grid_id)
-                        .num_columns(4)
-                        .min_col_width(20.)
-                        .max_col_width(60.)
-                        .show(ui, |ui| {
-                            for (i, reminder) in reminders.iter_mut().enumerate() {
-                                if i == idx {
-                                    reminders_changed |=
-                                        self.render_enabled_reminder_line(ui, reminder, i, state);
-                                } else {
-                                    self.render_disabled_reminder_line(ui, reminder, i);
-                                }
-
-                                if ui.small_button("x").clicked() {
-                                    removals.push(i);
-                                }
-                                ui.end_row();
-                            }
-                        });
-                }
-                None => {
-                    egui::Grid::new(grid_id).num_columns(4).show(ui, |ui| {
-                        for (i, reminder) in reminders.iter_mut().enumerate() {
-                            self.render_disabled_reminder_line(ui, reminder, i);
-                            if ui.small_button("x").clicked() {
-                                removals.push(i);
-                            }
-                            ui.end_row();
-                        }
-                    });
+            egui::Grid::new("note_reminders_grid").num_columns(5).show(ui, |ui| {
+                for reminder in note.reminders() {
+                    ui.label(reminder.date_display());
+                    ui.label(reminder.time_display());
+                    ui.label(reminder.text());
+                    if ui.small_button("✏").on_hover_text("Edit reminder").clicked() {
+                        self.reminder_picker = Some(ReminderPicker::edit(reminder, local_offset()));
+                    }
+                    if ui.small_button("x").on_hover_text("Remove reminder").clicked() {
+                        update = Some(remove_reminder_update(note, reminder.id()));
+                    }
+                    ui.end_row();
                 }
-            }
-        }
-
-        if ui.small_button("Create Reminder").clicked() {
-            reminders.push(Reminder::default());
-            self.active_reminder = Some((reminders.len() - 1, ReminderFocus::Text));
-            reminders_changed = true;
+            });
         }
 
-        if !removals.is_empty() {
-            removals.sort_unstable();
-            removals.reverse();
-            for i in removals {
-                reminders.remove(i);
+        match &mut self.reminder_picker {
+            Some(picker) => match picker.render(ui) {
+                PickerAction::Save => match picker.build_update(note, local_offset()) {
+                    Ok(built) => {
+                        update = Some(built);
+                        self.reminder_picker = None;
+                    }
+                    Err(error) => {
+                        Self::send_app_msg(&self.app_sender, ToApp::Toast(ToastKind::Error, error.to_string()));
+                    }
+                },
+                PickerAction::Cancel => self.reminder_picker = None,
+                PickerAction::None => {}
+            },
+            None => {
+                if ui.small_button("Create Reminder").clicked() {
+                    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+                    self.reminder_picker = Some(ReminderPicker::new(now));
+                }
             }
-            reminders_changed = true;
         }
 
-        if reminders_changed {
-            note.set_reminders(reminders);
-            self.force_save = true;
-        }
-
-        reminders_changed
+        update
     }
 
     #[allow(clippy::unused_self)]
@@ -424,219 +736,146 @@ impl NoteEditor {
                 } else {
                     ui.label(format!(
                         "Created: {}",
-                        crate::util::dtf::timestamp_to_string(note.created())
+                        crate::util::dtf::format(note.created(), &self.date_format)
                     ));
                     ui.label("|");
                     ui.label(format!(
                         "Updated: {}",
-                        crate::util::dtf::timestamp_to_string(note.updated())
+                        crate::util::dtf::format(note.updated(), &self.date_format)
                     ));
                 }
+                ui.label("|");
+                ui.label(footer_text(note));
             });
         });
     }
 
-    fn render_enabled_reminder_line(
-        &mut self,
-        ui: &mut Ui,
-        reminder: &mut Reminder,
-        idx: usize,
-        focus: ReminderFocus,
-    ) -> bool {
-        let mut changes = false;
-        let (mut hour, mut min, mut period) = reminder.get_due_time();
-        let mut date = reminder
-            .get_due_date_chrono_local()
-            .with_timezone(&chrono::Utc);
-
-        if focus == ReminderFocus::Date {
-            let picker = egui_extras::DatePickerButton::new(&mut date);
-
-            let dp_res = ui.add(picker);
-
-            if dp_res.changed() || dp_res.lost_focus() {
-                changes = true;
-                reminder.set_due_date_chrono(&date);
-            }
-
-            if dp_res.lost_focus() {
-                self.active_reminder = None;
-            }
-        } else {
-            let dp_res =
-                ui.add(egui::Label::new(reminder.date_display()).sense(egui::Sense::click()));
-            if dp_res.double_clicked() {
-                self.active_reminder = Some((idx, ReminderFocus::Date));
-            }
-        }
-
-        if focus == ReminderFocus::Time {
-            let mut lost_focus = false;
-
-            changes |=
-                Self::render_time_picker(ui, reminder, idx, &mut lost_focus, &self.app_sender);
-
-            if lost_focus {
-                self.active_reminder = None;
-            }
-        } else {
-            let res = ui.add(egui::Label::new(reminder.time_display()).sense(egui::Sense::click()));
-            if res.double_clicked() {
-                self.active_reminder = Some((idx, ReminderFocus::Time));
-            }
-        }
-
-        if focus == ReminderFocus::Text {
-            let mut reminder_text = reminder.text().to_string();
-            let text_res = ui.text_edit_singleline(&mut reminder_text);
-
-            if text_res.changed() {
-                changes = true;
-                reminder.set_text(&reminder_text);
-            }
-
-            if text_res.lost_focus() {
-                self.active_reminder = None;
-            }
-        } else {
-            let res = ui.add(egui::Label::new(reminder.text()));
-            if res.double_clicked() {
-                self.active_reminder = Some((idx, ReminderFocus::Text));
-            }
-        }
-
-        changes
+    fn send_app_msg(sender: &Sender<ToApp>, msg: ToApp) {
+        sender.send(msg).expect("Unable to send message to GuiApp");
     }
+}
 
-    fn render_disabled_reminder_line(&mut self, ui: &mut Ui, reminder: &mut Reminder, idx: usize) {
-        let dp_res = ui.add(egui::Label::new(reminder.date_display()).sense(egui::Sense::click()));
-        if dp_res.double_clicked() {
-            self.active_reminder = Some((idx, ReminderFocus::Date));
-        }
-
-        let res = ui.add(egui::Label::new(reminder.time_display()).sense(egui::Sense::click()));
-        if res.double_clicked() {
-            self.active_reminder = Some((idx, ReminderFocus::Time));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn cycle_match_wraps() {
+        assert_eq!(cycle_match(None, 0, true), None);
+        assert_eq!(cycle_match(Some(2), 0, false), None);
+
+        assert_eq!(cycle_match(None, 3, true), Some(0));
+        assert_eq!(cycle_match(None, 3, false), Some(2));
+        assert_eq!(cycle_match(Some(0), 3, true), Some(1));
+        assert_eq!(cycle_match(Some(2), 3, true), Some(0));
+        assert_eq!(cycle_match(Some(0), 3, false), Some(2));
+        assert_eq!(cycle_match(Some(1), 3, false), Some(0));
+        assert_eq!(cycle_match(Some(0), 1, true), Some(0));
+        assert_eq!(cycle_match(Some(7), 3, true), Some(0), "out of range should clamp to the last match");
+    }
 
-        let res = ui.add(egui::Label::new(reminder.text()).sense(egui::Sense::click()));
-        if res.double_clicked() {
-            self.active_reminder = Some((idx, ReminderFocus::Text));
+    #[test]
+    #[no_coverage]
+    fn find_state_matches() {
+        let note = Note::create(("Title", "One fish, two FISH, red fish, blue fish.", vec![]));
+        let mut find = FindState {
+            open: true,
+            query: "fish".to_string(),
+            ..FindState::default()
+        };
+        find.update_matches(note.content());
+        assert_eq!(find.matches, note.highlight_ranges("fish"));
+        assert_eq!(find.matches.len(), 4);
+        assert_eq!(find.current, Some(0));
+        assert_eq!(find.current_range(), Some(4..8));
+
+        let mut visited = Vec::new();
+        for _ in 0..5 {
+            find.next();
+            visited.push(find.current.unwrap());
         }
+        assert_eq!(visited, [1, 2, 3, 0, 1]);
+        find.previous();
+        find.previous();
+        assert_eq!(find.current, Some(3));
+        assert_eq!(find.current_range(), find.matches.last().cloned());
+
+        // Editing the content keeps the current match where possible.
+        find.update_matches("fish fish");
+        assert_eq!(find.matches, Note::match_ranges("fish fish", "fish"));
+        assert_eq!(find.current, Some(1));
+
+        find.query = "whale".to_string();
+        find.update_matches(note.content());
+        assert!(find.matches.is_empty());
+        assert_eq!(find.current, None);
+        assert_eq!(find.current_range(), None);
     }
 
-    fn render_time_picker(
-        ui: &mut Ui,
-        reminder: &mut Reminder,
-        idx: usize,
-        lost_focus: &mut bool,
-        sender: &Sender<ToApp>,
-    ) -> bool {
-        static ONCE_HR: Once = Once::new();
-        static ONCE_MIN: Once = Once::new();
-        static ONCE_HOR: Once = Once::new();
-        const HOURS: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
-        const MINS: &[u8] = &[0, 15, 30, 45];
-
-        let mut changes = false;
-
-        let (mut hour, mut minute, mut period) = reminder.get_due_time();
-
-        let res = ui.horizontal(|ui| {
-            let hr_res = egui::ComboBox::from_id_source("time_picker_hour")
-                .width(20.)
-                .selected_text(hour.to_string())
-                .show_ui(ui, |ui| {
-                    HOURS
-                        .iter()
-                        .map(|&hr| {
-                            ui.selectable_value(&mut hour, Hour12::from_u8(hr), hr.to_string())
-                        })
-                        .collect::<Vec<_>>()
-                    // for hr in HOURS {
-                    //     ui.selectable_value(&mut hour, Hour::from_u8(*hr), hr.to_string());
-                    // }
-                });
-
-            if let Some(resps) = &hr_res.inner && resps.iter().any(egui::Response::changed) {
-            // if hr_res.response.changed() {
-                ONCE_HR.call_once(|| {
-                    sender
-                        .send(ToApp::Debug(format!(
-                            "Hour Response: {:#?}",
-                            hr_res.response
-                        )))
-                        .expect("Unable to send debug message to app");
-                });
-            }
+    #[test]
+    #[no_coverage]
+    fn footer_counts() {
+        let note = Note::create(("Title", "The quick brown fox\njumps over  the lazy dog.", vec![]));
+        assert_eq!(footer_text(&note), "9 words | 45 characters | 1 min read");
 
-            changes |= hr_res.response.changed();
-
-            // if hr_res.inner.is_none() {
-            //     *lost_focus = true;
-            // }
-
-            let min_res = egui::ComboBox::from_id_source("time_picker_min")
-                .width(20.)
-                .selected_text(minute.to_string())
-                .show_ui(ui, |ui| {
-                    MINS.iter()
-                        .map(|&min| {
-                            ui.selectable_value(&mut minute, Minute::from_u8(min), min.to_string())
-                        })
-                        .collect::<Vec<_>>()
-                    // for min in MINS {
-                    //     ui.selectable_value(&mut minute, Minute::from_u8(*min), min.to_string());
-                    // }
-                });
-
-            if let Some(resps) = &min_res.inner && resps.iter().any(egui::Response::changed) {
-            // if min_res.response.changed() {
-                ONCE_MIN.call_once(|| {
-                    sender
-                        .send(ToApp::Debug(format!(
-                            "Minute Response: {:#?}",
-                            min_res.response
-                        )))
-                        .expect("Unable to send debug message to app");
-                });
-            }
-
-            let mut is_am = period == TimePeriod::Am;
-            if ui.toggle_value(&mut is_am, period.to_string()).clicked() {
-                period.toggle();
-                changes = true;
-            }
+        let empty = Note::create(("Title", "", vec![]));
+        assert_eq!(footer_text(&empty), "0 words | 0 characters | 0 min read");
 
-            // if min_res.inner.is_none() {
-            //     *lost_focus = true;
-            // }
-            (hr_res, min_res)
-        });
+        let single = Note::create(("Title", "é", vec![]));
+        assert_eq!(footer_text(&single), "1 word | 1 character | 1 min read");
 
-        if res.response.changed() {
-            ONCE_HOR.call_once(|| {
-                sender
-                    .send(ToApp::Debug(format!(
-                        "Horizontal Response: {:#?}",
-                        res.response
-                    )))
-                    .expect("Unable to send debug message to app");
-            });
-        }
+        let long = Note::create(("Title", "word ".repeat(Note::WORDS_PER_MINUTE * 2 + 1).trim_end(), vec![]));
+        assert_eq!(footer_text(&long), "401 words | 2004 characters | 3 min read");
+    }
 
-        if res.response.clicked_elsewhere() {
-            *lost_focus = true;
-        }
+    #[test]
+    #[no_coverage]
+    fn edit_history() {
+        let mut note = Note::create(("Title", "", vec!["tag"]));
+        let mut history = EditHistory::default();
+        let edit = |history: &mut EditHistory, note: &mut Note, content: &str| {
+            history.record(NoteSnapshot::of(note));
+            note.set_content(content);
+        };
 
-        if changes || *lost_focus {
-            reminder.set_due_hmp(hour, minute, period);
+        edit(&mut history, &mut note, "H");
+        edit(&mut history, &mut note, "Hi");
+        edit(&mut history, &mut note, "Hi!");
+
+        // Undo restores the previous text, redo reapplies it.
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(note.content(), "Hi");
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(note.content(), "H");
+        history.redo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(note.content(), "Hi");
+
+        // A new edit after undoing truncates the redo branch.
+        edit(&mut history, &mut note, "Hey");
+        assert_eq!(history.redo(NoteSnapshot::of(&note)), None);
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(note.content(), "Hi");
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(note.content(), "");
+        assert_eq!(history.undo(NoteSnapshot::of(&note)), None);
+
+        // Titles and tags are restored along with the content.
+        let before = NoteSnapshot::of(&note);
+        history.record(before.clone());
+        note.set_title("Renamed");
+        note.set_tags(vec!["other".to_string()]);
+        history.undo(NoteSnapshot::of(&note)).unwrap().restore(&mut note);
+        assert_eq!(NoteSnapshot::of(&note), before);
+
+        // The history is bounded, dropping the oldest steps first.
+        history.clear();
+        for i in 0..EditHistory::CAPACITY + 10 {
+            edit(&mut history, &mut note, &i.to_string());
         }
-
-        changes
-    }
-
-    fn send_app_msg(sender: &Sender<ToApp>, msg: ToApp) {
-        sender.send(msg).expect("Unable to send message to GuiApp");
+        assert_eq!(history.undo.len(), EditHistory::CAPACITY);
+        assert_eq!(history.undo.front().unwrap().content, "9");
     }
 }
@@ -0,0 +1,242 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use chrono::{Datelike, TimeZone};
+use eframe::egui::{ComboBox, Ui};
+use egui_extras::DatePickerButton;
+use time::{OffsetDateTime, UtcOffset};
+use tinyid::TinyId;
+
+use crate::types::{time::TimePeriod, Note, Reminder, UpdateNote};
+
+/// The minutes offered by the minute picker. Reminders with any other minute keep it until it is changed.
+const MINUTES: &[u8] = &[0, 5, 10, 15, 20, 25, 30, 35, 40, 45, 50, 55];
+
+/// What the user did with a [`ReminderPicker`] this frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PickerAction {
+    None,
+    Save,
+    Cancel,
+}
+
+/// The form used by the [`super::NoteEditor`] to add a reminder to the active note or to edit one of its
+/// reminders. The date and time are in the local offset the picker was opened with, see
+/// [`ReminderPicker::build_update`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReminderPicker {
+    pub text: String,
+    pub date: chrono::Date<chrono::Utc>,
+    /// The clock-face hour (1-12).
+    pub hour: u8,
+    pub minute: u8,
+    pub period: TimePeriod,
+    /// The id of the reminder being edited, or `None` when adding a new one.
+    pub editing: Option<TinyId>,
+}
+
+impl ReminderPicker {
+    /// A picker for a new reminder, due this time tomorrow (on the hour).
+    pub fn new(now: OffsetDateTime) -> Self {
+        Self {
+            minute: 0,
+            ..Self::from_due("New Reminder", now + time::Duration::days(1), None)
+        }
+    }
+
+    /// A picker for editing `reminder`, showing its due time in `offset`.
+    pub fn edit(reminder: &Reminder, offset: UtcOffset) -> Self {
+        Self::from_due(reminder.text(), reminder.due().to_offset(offset), Some(reminder.id()))
+    }
+
+    fn from_due(text: &str, due: OffsetDateTime, editing: Option<TinyId>) -> Self {
+        let (hour, period) = match due.hour() {
+            0 => (12, TimePeriod::Am),
+            hour @ 1..=11 => (hour, TimePeriod::Am),
+            12 => (12, TimePeriod::Pm),
+            hour => (hour - 12, TimePeriod::Pm),
+        };
+        Self {
+            text: text.to_string(),
+            date: chrono::Utc.ymd(due.year(), u8::from(due.month()).into(), due.day().into()),
+            hour,
+            minute: due.minute(),
+            period,
+            editing,
+        }
+    }
+
+    /// Renders the picker, returning whether it should be saved or closed.
+    pub fn render(&mut self, ui: &mut Ui) -> PickerAction {
+        let mut action = PickerAction::None;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Reminder:");
+                ui.text_edit_singleline(&mut self.text);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Due:");
+                ui.add(DatePickerButton::new(&mut self.date));
+                ComboBox::from_id_source("reminder_picker_hour")
+                    .width(40.)
+                    .selected_text(self.hour.to_string())
+                    .show_ui(ui, |ui| {
+                        for hour in 1..=12 {
+                            ui.selectable_value(&mut self.hour, hour, hour.to_string());
+                        }
+                    });
+                ComboBox::from_id_source("reminder_picker_minute")
+                    .width(40.)
+                    .selected_text(format!("{:02}", self.minute))
+                    .show_ui(ui, |ui| {
+                        for &minute in MINUTES {
+                            ui.selectable_value(&mut self.minute, minute, format!("{:02}", minute));
+                        }
+                    });
+                ui.selectable_value(&mut self.period, TimePeriod::Am, "AM");
+                ui.selectable_value(&mut self.period, TimePeriod::Pm, "PM");
+            });
+
+            ui.horizontal(|ui| {
+                let save = if self.editing.is_some() { "Save" } else { "Add" };
+                if ui.button(save).clicked() {
+                    action = PickerAction::Save;
+                }
+                if ui.button("Cancel").clicked() {
+                    action = PickerAction::Cancel;
+                }
+            });
+        });
+        action
+    }
+
+    /// Builds the [`Reminder`] described by the picker, treating its date and time as being in `offset`.
+    ///
+    /// ## Errors
+    /// - If the reminder text is empty.
+    /// - If the date or time is invalid, see [`crate::types::ReminderBuilder::build`].
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_reminder(&self, offset: UtcOffset) -> crate::Result<Reminder> {
+        if self.text.trim().is_empty() {
+            return Err(crate::Error::ui("The reminder text cannot be empty"));
+        }
+
+        Reminder::builder()
+            .text(self.text.trim())
+            .date(self.date.year(), self.date.month() as u8, self.date.day() as u8)
+            .time(self.hour, self.minute, self.period)
+            .offset(offset)
+            .build()
+    }
+
+    /// Builds the [`UpdateNote`] that adds the reminder described by the picker to `note`, or that changes the
+    /// text and due time of the reminder being edited while keeping its id, label and recurrence. The date and
+    /// time are treated as being in `offset`.
+    ///
+    /// ## Errors
+    /// - If the reminder cannot be built, see [`ReminderPicker::to_reminder`].
+    /// - If the reminder being edited is no longer on `note`.
+    pub fn build_update(&self, note: &Note, offset: UtcOffset) -> crate::Result<UpdateNote> {
+        let built = self.to_reminder(offset)?;
+        let mut reminders = note.reminders().to_vec();
+        match self.editing {
+            Some(id) => {
+                let reminder = reminders
+                    .iter_mut()
+                    .find(|reminder| reminder.id() == id)
+                    .ok_or_else(|| crate::Error::ui("The reminder being edited no longer exists"))?;
+                reminder.set_text(built.text());
+                reminder.set_due(built.due());
+            }
+            None => reminders.push(built),
+        }
+
+        Ok(UpdateNote::empty(note.id()).with_reminders(Some(reminders)))
+    }
+}
+
+/// Builds the [`UpdateNote`] that removes the reminder with the given `id` from `note`.
+pub fn remove_reminder_update(note: &Note, id: TinyId) -> UpdateNote {
+    let reminders = note
+        .reminders()
+        .iter()
+        .filter(|reminder| reminder.id() != id)
+        .cloned()
+        .collect();
+    UpdateNote::empty(note.id()).with_reminders(Some(reminders))
+}
+
+/// The offset the reminder pickers show times in: the local offset if it can be determined, otherwise UTC.
+pub fn local_offset() -> UtcOffset {
+    UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use time::macros::{datetime, offset};
+
+    #[test]
+    #[no_coverage]
+    fn build_update_from_picker() {
+        let mut note = Note::create(("Title", "Content"));
+        let mut picker = ReminderPicker::new(datetime!(2022-06-01 10:30 UTC));
+        assert_eq!(picker.date, chrono::Utc.ymd(2022, 6, 2));
+        assert_eq!((picker.hour, picker.minute, picker.period), (10, 0, TimePeriod::Am));
+        assert_eq!(picker.editing, None);
+
+        picker.text = "  Call back ".to_string();
+        picker.hour = 9;
+        picker.minute = 45;
+        picker.period = TimePeriod::Pm;
+        let update = picker.build_update(&note, UtcOffset::UTC).expect("Unable to build update");
+        assert_eq!(update.id(), &note.id());
+        assert_eq!((update.title(), update.content(), update.tags.as_ref()), (None, None, None));
+        let reminders = update.reminders.as_ref().expect("reminders should be set");
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].text(), "Call back");
+        assert_eq!(reminders[0].due(), datetime!(2022-06-02 21:45 UTC));
+
+        // The picker's date and time are in the given offset.
+        let update = picker.build_update(&note, offset!(+2)).unwrap();
+        assert_eq!(update.reminders.unwrap()[0].due(), datetime!(2022-06-02 19:45 UTC));
+
+        // Editing keeps the reminder's id and only changes the edited one.
+        assert!(note.update(picker.build_update(&note, UtcOffset::UTC).unwrap()));
+        picker.text = "Second".to_string();
+        assert!(note.update(picker.build_update(&note, UtcOffset::UTC).unwrap()));
+        let first = note.reminders()[0].clone();
+        let mut edit = ReminderPicker::edit(&first, UtcOffset::UTC);
+        assert_eq!(edit.text, "Call back");
+        assert_eq!(edit.date, chrono::Utc.ymd(2022, 6, 2));
+        assert_eq!((edit.hour, edit.minute, edit.period), (9, 45, TimePeriod::Pm));
+        assert_eq!(edit.editing, Some(first.id()));
+
+        edit.text = "Call back later".to_string();
+        edit.hour = 12;
+        edit.minute = 0;
+        edit.period = TimePeriod::Am;
+        let reminders = edit.build_update(&note, UtcOffset::UTC).unwrap().reminders.unwrap();
+        assert_eq!(reminders.len(), 2);
+        assert_eq!(reminders[0].id(), first.id());
+        assert_eq!(reminders[0].text(), "Call back later");
+        assert_eq!(reminders[0].due(), datetime!(2022-06-02 00:00 UTC));
+        assert_eq!(reminders[1].text(), "Second");
+
+        let reminders = remove_reminder_update(&note, first.id()).reminders.unwrap();
+        assert_eq!(reminders.iter().map(Reminder::text).collect::<Vec<_>>(), ["Second"]);
+
+        edit.text = "   ".to_string();
+        assert!(edit.build_update(&note, UtcOffset::UTC).is_err(), "empty text");
+        edit.text = "Text".to_string();
+        edit.hour = 13;
+        assert!(edit.build_update(&note, UtcOffset::UTC).is_err(), "invalid hour");
+        edit.hour = 1;
+        assert!(edit.build_update(&Note::create(("Other", "")), UtcOffset::UTC).is_err(), "missing reminder");
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::collections::BTreeSet;
+
+use eframe::egui::{CollapsingHeader, ScrollArea, Ui};
+
+use crate::types::api::{Filter, NoteFilter, StringSearch};
+
+/// The [`Filter`] for a set of selected tags: notes carrying every one of them. Selecting no tags gives the
+/// empty filter, which matches every note.
+pub fn tag_selection_filter<'t>(selected: impl IntoIterator<Item = &'t String>) -> Filter {
+    Filter::multiple(
+        selected
+            .into_iter()
+            .map(|tag| NoteFilter::any_tag(StringSearch::matches(tag.clone(), false)))
+            .collect(),
+    )
+}
+
+/// A collapsible list of every tag in the database along with the number of notes carrying it. Clicking a tag
+/// selects it (or deselects it if it was already selected), see [`TagPanel::filter`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagPanel {
+    tags: Vec<(String, usize)>,
+    selected: BTreeSet<String>,
+}
+
+impl TagPanel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the panel, returning whether the selected tags changed.
+    pub fn render(&mut self, ui: &mut Ui) -> bool {
+        let mut changed = false;
+        let header = if self.selected.is_empty() {
+            "Tags".to_string()
+        } else {
+            format!("Tags ({} selected)", self.selected.len())
+        };
+
+        CollapsingHeader::new(header)
+            .id_source("tag_panel")
+            .default_open(false)
+            .show(ui, |ui| {
+                if self.tags.is_empty() {
+                    ui.label("No tags yet.");
+                    return;
+                }
+
+                ScrollArea::vertical()
+                    .id_source("tag_panel_scroll")
+                    .max_height(200.)
+                    .show(ui, |ui| {
+                        let mut clicked = None;
+                        for (tag, count) in &self.tags {
+                            let is_selected = self.selected.contains(tag);
+                            if ui.selectable_label(is_selected, format!("{} ({})", tag, count)).clicked() {
+                                clicked = Some(tag.clone());
+                            }
+                        }
+                        if let Some(tag) = clicked {
+                            self.toggle(&tag);
+                            changed = true;
+                        }
+                    });
+
+                if !self.selected.is_empty() && ui.small_button("Clear").clicked() {
+                    self.selected.clear();
+                    changed = true;
+                }
+            });
+
+        changed
+    }
+
+    /// Replaces the listed tags, sorting them by name. Selected tags that no longer exist are deselected, and
+    /// the return value is whether that changed the selection.
+    pub fn update_tags(&mut self, mut tags: Vec<(String, usize)>) -> bool {
+        tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.tags = tags;
+
+        let before = self.selected.len();
+        let tags = &self.tags;
+        self.selected.retain(|selected| tags.iter().any(|(tag, _)| tag == selected));
+        self.selected.len() != before
+    }
+
+    /// Removes every tag, e.g. when the database is closed.
+    pub fn clear(&mut self) {
+        self.tags.clear();
+        self.selected.clear();
+    }
+
+    /// Selects `tag` if it isn't selected, otherwise deselects it.
+    pub fn toggle(&mut self, tag: &str) {
+        if !self.selected.remove(tag) {
+            self.selected.insert(tag.to_string());
+        }
+    }
+
+    pub fn selected(&self) -> &BTreeSet<String> {
+        &self.selected
+    }
+
+    /// The filter for the currently selected tags, see [`tag_selection_filter`].
+    pub fn filter(&self) -> Filter {
+        tag_selection_filter(&self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Note;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn selection_to_filter() {
+        let mut panel = TagPanel::new();
+        assert!(!panel.update_tags(vec![("work".to_string(), 2), ("home".to_string(), 1), ("urgent".to_string(), 1)]));
+        assert_eq!(panel.filter(), Filter::empty());
+
+        panel.toggle("work");
+        assert_eq!(
+            panel.filter(),
+            Filter::single(NoteFilter::any_tag(StringSearch::matches("work".to_string(), false)))
+        );
+
+        // Multiple tags are ANDed together, in a stable order.
+        panel.toggle("urgent");
+        assert_eq!(
+            panel.filter(),
+            Filter::multiple(vec![
+                NoteFilter::any_tag(StringSearch::matches("urgent".to_string(), false)),
+                NoteFilter::any_tag(StringSearch::matches("work".to_string(), false)),
+            ])
+        );
+
+        let notes = vec![
+            Note::create(("First", "", vec!["work", "urgent"])),
+            Note::create(("Second", "", vec!["work"])),
+            Note::create(("Third", "", vec!["homework", "urgent"])),
+        ];
+        let titles = |filter: &Filter| {
+            let predicate = filter.predicate();
+            notes
+                .iter()
+                .filter(|note| predicate(note))
+                .map(|note| note.title().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles(&panel.filter()), ["First"]);
+
+        // Clicking a selected tag again deselects it. Tags match exactly, so "work" does not match "homework".
+        panel.toggle("urgent");
+        assert_eq!(panel.selected().iter().collect::<Vec<_>>(), ["work"]);
+        assert_eq!(titles(&panel.filter()), ["First", "Second"]);
+        panel.toggle("work");
+        assert_eq!(titles(&panel.filter()), ["First", "Second", "Third"]);
+
+        // Selected tags that disappear from the database are deselected.
+        panel.toggle("home");
+        assert!(!panel.update_tags(vec![("home".to_string(), 3)]));
+        assert!(panel.update_tags(vec![("work".to_string(), 3)]));
+        assert!(panel.selected().is_empty());
+    }
+}
@@ -10,18 +10,57 @@
 //! with a full interface and whatnot, but it will query the user in a pretty way, prompting for information until a request or
 //! command has been "built", at which point it will execute and display results, very much like the normal `cli`.
 
-use crate::{flame_dump, flame_guard, types::Note};
+use crate::{flame_dump, flame_guard, services::NoteService, types::Note};
 
 mod parts;
 
+/// Lets the user pick one of the given `notes`, usually the results of a `cli` query, and then view, edit, or
+/// delete it. Changes are made through `service`, which saves them if it was opened from a file.
+///
+/// ## Errors
+/// - Any error returned by the prompts or while applying a change through `service`.
+pub fn pick_and_act(service: &mut NoteService, notes: &[Note]) -> crate::Result {
+    flame_guard!("bins", "icli", "pick_and_act");
+
+    let backend = parts::Backend::Inquire;
+    let Some(note) = parts::pick_note_from(notes, backend, &parts::pick_note::PickNoteOptions::default())? else {
+        return Ok(());
+    };
+
+    match backend.select_str("Action:", &["View", "Edit", "Delete", "Cancel"])? {
+        "View" => parts::view_note_with(service, backend, Some(note))?,
+        "Edit" => parts::edit_note_with(service, backend, &Some(note))?,
+        "Delete" => {
+            if backend.confirm(&format!("Delete note '{}'?", note.title()))? {
+                service.delete(note.id())?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// # Arguments
+/// - `--no-welcome`: never offer to add the getting started note to an empty database, the same opt-out as
+///   the GUI's "Add a getting started note to new databases" setting.
 /// # Errors
 /// - All the fucking time!
+/// - When given an argument other than the ones above.
 /// # Panics
 /// - Exactly 5% of the time it is called, on a completely random basis. Suck it.
-pub fn execute() -> crate::Result {
+pub fn execute(args: std::env::Args) -> crate::Result {
     flame_guard!("bins", "icli", "execute");
 
+    let mut seed_welcome = true;
+    for arg in args.skip(1) {
+        match arg.as_str() {
+            "--no-welcome" => seed_welcome = false,
+            _ => return Err(crate::Error::ui(format!("Unknown argument '{}'", arg))),
+        }
+    }
     let mut dev_db = false;
+    // A freshly generated database is worth saving even though nothing has changed since it was created.
+    let mut generated = false;
     let backend =
         match inquire::Select::new("Choose Backend:", vec!["Dialoguer", "Inquire"]).prompt()? {
             "Dialoguer" => parts::Backend::Dialoguer,
@@ -36,11 +75,20 @@ pub fn execute() -> crate::Result {
             dev_db = true;
             crate::db::Database::load_dev()?
         }
-        "Create New Dev Db" => crate::db::Database::create_random(),
+        "Create New Dev Db" => {
+            generated = true;
+            crate::db::Database::create_random()
+        }
         "Empty" => crate::db::Database::empty(),
         _ => unreachable!(),
     };
 
+    if seed_welcome && db.is_empty() && backend.confirm("Database is empty, add a getting started note?")? {
+        db.seed_welcome()?;
+    }
+    // Nothing is saved until the end of the session, when the user is asked where to.
+    let mut service = NoteService::new(db);
+
     let should_loop = backend.confirm(
         "Run as REPL? (i.e. continously until exit is chosen, vs. only one command and then exit)",
     )?;
@@ -52,41 +100,47 @@ pub fn execute() -> crate::Result {
 
         match choice {
             parts::menu::MenuOptions::CreateNote => {
-                parts::add_note(&mut db, backend)?;
+                parts::add_note(&mut service, backend)?;
             }
             parts::menu::MenuOptions::ViewNote => {
-                let choice = parts::pick_note(&mut db, backend)?;
-                parts::view_note_with(&mut db, backend, choice)?;
+                let choice = parts::pick_note(&mut service, backend)?;
+                parts::view_note_with(&mut service, backend, choice)?;
             }
             parts::menu::MenuOptions::ListNotes => {
-                let result = parts::list_notes(&mut db, backend)?;
+                let result = parts::list_notes(&mut service, backend)?;
                 if let Some(note) = result {
                     println!("You chose note:\n{}", note);
                 }
             }
+            parts::menu::MenuOptions::Search => {
+                let choice = parts::search_notes(&mut service, backend)?;
+                parts::view_note_with(&mut service, backend, choice)?;
+            }
             parts::menu::MenuOptions::UpdateNote => {
-                let choice = parts::pick_note(&mut db, backend)?;
-                parts::edit_note_with(&mut db, backend, &choice)?;
+                let choice = parts::pick_note(&mut service, backend)?;
+                parts::edit_note_with(&mut service, backend, &choice)?;
             }
             parts::menu::MenuOptions::ViewTags => {
-                /// TODO: Something is broken here, the list of notes is never populated...
-                let tag = if let Some(tag) = parts::list_tags(&mut db, backend)? {
+                let tag = if let Some(tag) = parts::list_tags(&mut service, backend)? {
                     tag
                 } else {
                     return Ok(());
                 };
                 let choice = parts::pick_note_with(
-                    &mut db,
+                    &mut service,
                     backend,
                     parts::pick_note::PickNoteOptions {
-                        filter: Some(box move |n: &Note| n.tag_matches(&tag)),
+                        filter: Some(parts::list_tags::tag_filter(tag)),
                         ..Default::default()
                     },
                 )?;
-                parts::view_note_with(&mut db, backend, choice)?;
+                parts::view_note_with(&mut service, backend, choice)?;
             }
             parts::menu::MenuOptions::DeleteNote => {
-                parts::delete_note(&mut db, backend)?;
+                parts::delete_note(&mut service, backend)?;
+            }
+            parts::menu::MenuOptions::SetReminder => {
+                parts::set_reminder(&mut service, backend)?;
             }
             parts::menu::MenuOptions::Exit => {
                 println!("Exiting application...");
@@ -99,7 +153,8 @@ pub fn execute() -> crate::Result {
         }
     }
 
-    if backend.confirm("Save Database?")? {
+    let db = service.into_database();
+    if (generated || db.is_modified()) && backend.confirm("Save Database?")? {
         if dev_db {
             db.save_dev()?;
         } else {
@@ -6,8 +6,7 @@
 
 use crate::types::CreateNote;
 
-/// TODO: Turn this into a trait that limits access to [`Database`] methods.
-pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::Result {
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result {
     crate::flame_guard!("bins", "icli", "parts", "add_note", "execute");
 
     let title = backend.text("Title:", None)?;
@@ -24,7 +23,7 @@ pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::
     let mut dto: CreateNote = (title, content, tags).into();
 
     if backend.confirm("Are you sure you want to create this note?")? {
-        let note = db.apply_create(dto)?;
+        let note = service.create(dto)?;
         println!("Created note:\n{}", note);
     }
 
@@ -35,11 +34,11 @@ struct AddNoteComponent;
 
 impl super::Component for AddNoteComponent {
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
         _options: super::NoOptions,
     ) -> crate::Result<Self::Output> {
-        self::execute(db, backend)
+        self::execute(service, backend)
     }
 
     type Output = ();
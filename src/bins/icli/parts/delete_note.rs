@@ -4,24 +4,24 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::Result<()> {
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result<()> {
     crate::flame_guard!("bins", "icli", "parts", "delete_note", "execute");
 
-    execute_with(db, backend, super::NoOptions::default())
+    execute_with(service, backend, super::NoOptions::default())
 }
 
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
     _options: super::NoOptions,
 ) -> crate::Result<()> {
     crate::flame_guard!("bins", "icli", "parts", "delete_note", "execute_with");
 
-    let choice = super::pick_note(db, backend)?;
+    let choice = super::pick_note(service, backend)?;
     println!("Are you sure you want to delete this note?");
-    super::view_note_with(db, backend, choice.clone())?;
+    super::view_note_with(service, backend, choice.clone())?;
     if let Some(note) = choice && backend.confirm("Delete note?")? {
-        db.apply_delete(note)?;
+        service.delete(note.id())?;
         println!("Note deleted!");
     }
     Ok(())
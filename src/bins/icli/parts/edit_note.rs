@@ -6,14 +6,14 @@
 
 use crate::types::Note;
 
-pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::Result<()> {
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result<()> {
     crate::flame_guard!("bins", "icli", "parts", "edit_note", "execute");
 
-    execute_with(db, backend, &None)
+    execute_with(service, backend, &None)
 }
 
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
     options: &Option<Note>,
 ) -> crate::Result<()> {
@@ -38,7 +38,7 @@ pub fn execute_with(
     loop {
         if match backend.select_str("Edit Note:", CMDS)? {
             "View" => {
-                super::view_note_with(db, backend, Some(note.clone()))?;
+                super::view_note_with(service, backend, Some(note.clone()))?;
                 continue;
             }
             "Edit Title" => edit_title(&mut note, backend)?,
@@ -50,7 +50,7 @@ pub fn execute_with(
             }
             _ => unreachable!(),
         } {
-            db.ensure_sync(&mut note);
+            service.sync(&mut note)?;
         }
     }
 
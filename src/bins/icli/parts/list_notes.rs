@@ -55,9 +55,9 @@ impl std::fmt::Display for CompactNote<'_> {
 mod with_d {
     use dialoguer::{theme::ColorfulTheme, Select};
 
-    pub fn execute(db: &mut crate::db::Database) -> crate::Result<Option<super::Note>> {
+    pub fn execute(service: &mut crate::services::NoteService) -> crate::Result<Option<super::Note>> {
         crate::flame_guard!("bins", "icli", "parts", "list_notes", "with_d", "execute");
-        let all_notes = db.get_all().to_vec();
+        let all_notes = service.database().get_all().to_vec();
         if all_notes.is_empty() {
             println!("There are no notes to display!");
             return Ok(None);
@@ -81,9 +81,9 @@ mod with_i {
 
     use crate::types::Note;
 
-    pub fn execute(db: &mut crate::db::Database) -> crate::Result<Option<super::Note>> {
+    pub fn execute(service: &mut crate::services::NoteService) -> crate::Result<Option<super::Note>> {
         crate::flame_guard!("bins", "icli", "parts", "list_notes", "with_i", "execute");
-        let all_notes = db.get_all().to_vec();
+        let all_notes = service.database().get_all().to_vec();
         if all_notes.is_empty() {
             println!("There are no notes to display!");
             return Ok(None);
@@ -99,13 +99,13 @@ mod with_i {
 }
 
 pub fn execute(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
 ) -> crate::Result<Option<Note>> {
     crate::flame_guard!("bins", "icli", "parts", "list_notes", "execute");
     match backend {
-        super::Backend::Dialoguer => with_d::execute(db),
-        super::Backend::Inquire => with_i::execute(db),
+        super::Backend::Dialoguer => with_d::execute(service),
+        super::Backend::Inquire => with_i::execute(service),
     }
 }
 
@@ -115,10 +115,10 @@ impl super::Component for ListNoteComponent {
     type Output = Option<Note>;
 
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
         _options: super::NoOptions,
     ) -> crate::Result<Self::Output> {
-        self::execute(db, backend)
+        self::execute(service, backend)
     }
 }
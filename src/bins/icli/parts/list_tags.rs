@@ -4,33 +4,110 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use tinyid::TinyId;
-
 use crate::types::Note;
 
+/// A single item shown by the tag picker. It is displayed as `tag (count)`, but only the bare tag is returned
+/// from [`execute_with`], so that it can be compared against [`Note::tags`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagChoice {
+    tag: String,
+    count: usize,
+}
+
+impl TagChoice {
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl std::fmt::Display for TagChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.tag, self.count)
+    }
+}
+
+/// Every tag in `db` along with the number of notes carrying it, sorted by tag.
+pub fn tag_choices(db: &crate::db::Database) -> Vec<TagChoice> {
+    crate::flame_guard!("bins", "icli", "parts", "list_tags", "tag_choices");
+    let mut choices = db
+        .get_all_tags_and_counts()
+        .into_iter()
+        .map(|(tag, count)| TagChoice { tag, count })
+        .collect::<Vec<_>>();
+    choices.sort_by(|a, b| a.tag.cmp(&b.tag));
+    choices
+}
+
+/// A [`super::pick_note::PickNoteOptions::filter`] that keeps only the notes carrying `tag`.
+pub fn tag_filter(tag: String) -> Box<dyn Fn(&Note) -> bool> {
+    crate::flame_guard!("bins", "icli", "parts", "list_tags", "tag_filter");
+    box move |note: &Note| note.tag_matches(&tag)
+}
+
 pub fn execute(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
 ) -> crate::Result<Option<String>> {
-    execute_with(db, backend, super::NoOptions::default())
+    crate::flame_guard!("bins", "icli", "parts", "list_tags", "execute");
+    execute_with(service, backend, super::NoOptions::default())
 }
 
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
-    options: super::NoOptions,
+    _options: super::NoOptions,
 ) -> crate::Result<Option<String>> {
-    let tag_counts = db.get_all_tags_and_counts();
-    if tag_counts.is_empty() {
+    crate::flame_guard!("bins", "icli", "parts", "list_tags", "execute_with");
+    let choices = tag_choices(service.database());
+    if choices.is_empty() {
         println!("There are no tags to display!");
         return Ok(None);
     }
 
-    let labels = tag_counts
-        .iter()
-        .map(|(tag, count)| format!("{} ({})", tag, count))
-        .collect::<Vec<_>>();
+    let choice = backend.select("Tag (Occurrences):", &choices)?;
+    Ok(Some(choice.tag))
+}
 
-    let choice = backend.select("Tag (Occurrences):", &labels)?;
-    Ok(Some(choice))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bins::icli::parts::pick_note::{picker_options, PartialNote, PickNoteOptions};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn chosen_tag_filters_notes() {
+        let notes = vec![
+            Note::create(("First", "", vec!["work", "urgent"])),
+            Note::create(("Second", "", vec!["home"])),
+            Note::create(("Third", "", vec!["work"])),
+        ];
+        let db = crate::db::Database::from_notes(&notes).expect("Unable to create database");
+
+        let choices = tag_choices(&db);
+        let labels = choices.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(labels, ["home (1)", "urgent (1)", "work (2)"]);
+
+        let work = choices.iter().find(|c| c.count() == 2).expect("work should be a choice");
+        assert_eq!(work.tag(), "work");
+        let options = PickNoteOptions {
+            filter: Some(tag_filter(work.tag().to_string())),
+            ..PickNoteOptions::default()
+        };
+        let ids = picker_options(db.get_all(), &options)
+            .iter()
+            .map(PartialNote::id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [notes[0].id(), notes[2].id()]);
+
+        let options = PickNoteOptions {
+            filter: Some(tag_filter(labels[0].clone())),
+            ..PickNoteOptions::default()
+        };
+        assert!(picker_options(db.get_all(), &options).is_empty(), "the label is not the tag");
+    }
 }
@@ -11,9 +11,11 @@ pub enum MenuOptions {
     CreateNote,
     ViewNote,
     ListNotes,
+    Search,
     UpdateNote,
     ViewTags,
     DeleteNote,
+    SetReminder,
     Exit,
 }
 
@@ -25,9 +27,11 @@ impl MenuOptions {
             Self::CreateNote,
             Self::ViewNote,
             Self::ListNotes,
+            Self::Search,
             Self::UpdateNote,
             Self::ViewTags,
             Self::DeleteNote,
+            Self::SetReminder,
             Self::Exit,
         ]
     }
@@ -47,10 +51,12 @@ impl From<MenuOptions> for usize {
             MenuOptions::CreateNote => 0,
             MenuOptions::ViewNote => 1,
             MenuOptions::ListNotes => 2,
-            MenuOptions::UpdateNote => 3,
-            MenuOptions::ViewTags => 4,
-            MenuOptions::DeleteNote => 5,
-            MenuOptions::Exit => 6,
+            MenuOptions::Search => 3,
+            MenuOptions::UpdateNote => 4,
+            MenuOptions::ViewTags => 5,
+            MenuOptions::DeleteNote => 6,
+            MenuOptions::SetReminder => 7,
+            MenuOptions::Exit => 8,
         }
     }
 }
@@ -69,9 +75,11 @@ impl From<usize> for MenuOptions {
             0 => MenuOptions::CreateNote,
             1 => MenuOptions::ViewNote,
             2 => MenuOptions::ListNotes,
-            3 => MenuOptions::UpdateNote,
-            4 => MenuOptions::ViewTags,
-            5 => MenuOptions::DeleteNote,
+            3 => MenuOptions::Search,
+            4 => MenuOptions::UpdateNote,
+            5 => MenuOptions::ViewTags,
+            6 => MenuOptions::DeleteNote,
+            7 => MenuOptions::SetReminder,
             _ => MenuOptions::Exit,
         }
     }
@@ -91,9 +99,11 @@ impl AsRef<str> for MenuOptions {
             MenuOptions::CreateNote => "Create Note",
             MenuOptions::ViewNote => "View Note",
             MenuOptions::ListNotes => "List Notes",
+            MenuOptions::Search => "Search Notes",
             MenuOptions::UpdateNote => "Update Note",
             MenuOptions::ViewTags => "View Tags",
             MenuOptions::DeleteNote => "Delete Note",
+            MenuOptions::SetReminder => "Set Reminder",
             MenuOptions::Exit => "Exit",
         }
     }
@@ -116,7 +126,7 @@ impl super::Component for MenuComponent {
     type Output = MenuOptions;
 
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
         _options: super::NoOptions,
     ) -> crate::Result<Self::Output> {
@@ -11,6 +11,8 @@ pub mod list_notes;
 pub mod list_tags;
 pub mod menu;
 pub mod pick_note;
+pub mod search_notes;
+pub mod set_reminder;
 pub mod update_note;
 pub mod view_note;
 
@@ -20,12 +22,12 @@ pub struct NoOptions;
 pub trait Component {
     type Output = ();
     type Options: Default = NoOptions;
-    fn execute(db: &mut crate::db::Database, backend: Backend) -> crate::Result<Self::Output> {
-        Self::execute_with(db, backend, Self::Options::default())
+    fn execute(service: &mut crate::services::NoteService, backend: Backend) -> crate::Result<Self::Output> {
+        Self::execute_with(service, backend, Self::Options::default())
     }
 
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: Backend,
         options: Self::Options,
     ) -> crate::Result<Self::Output>;
@@ -186,6 +188,10 @@ pub use edit_note::{execute as edit_note, execute_with as edit_note_with};
 pub use list_notes::execute as list_notes;
 pub use list_tags::execute as list_tags;
 pub use menu::execute as menu;
-pub use pick_note::{execute as pick_note, execute_with as pick_note_with};
+pub use pick_note::{
+    execute as pick_note, execute_from as pick_note_from, execute_with as pick_note_with,
+};
+pub use search_notes::execute as search_notes;
+pub use set_reminder::{execute as set_reminder, execute_with as set_reminder_with};
 pub use update_note::execute_with as update_note_with;
 pub use view_note::execute_with as view_note_with;
@@ -143,8 +143,9 @@ impl Default for PickNoteOptions {
     }
 }
 
+/// A single item shown by the note picker: the id of a [`Note`] and the text rendered for it by a [`NoteVisibility`].
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct PartialNote {
+pub struct PartialNote {
     id: TinyId,
     text: String,
 }
@@ -171,6 +172,14 @@ impl PartialNote {
             text,
         }
     }
+
+    pub fn id(&self) -> TinyId {
+        self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl std::fmt::Display for PartialNote {
@@ -179,36 +188,39 @@ impl std::fmt::Display for PartialNote {
     }
 }
 
+/// Converts a set of notes into the items shown by the picker, keeping their order and dropping any that
+/// don't pass `options.filter`.
+pub fn picker_options(notes: &[Note], options: &PickNoteOptions) -> Vec<PartialNote> {
+    crate::flame_guard!("bins", "icli", "parts", "pick_note", "picker_options");
+    notes
+        .iter()
+        .filter(|n| options.filter.as_ref().map_or(true, |filter| filter(n)))
+        .map(|n| PartialNote::from_note(n, &options.field_visibility))
+        .collect()
+}
+
 mod with_d {
     use dialoguer::{theme::ColorfulTheme, Select};
 
     pub fn execute(
-        db: &mut crate::db::Database,
+        notes: &[super::Note],
         options: &super::PickNoteOptions,
     ) -> crate::Result<Option<super::Note>> {
         crate::flame_guard!("bins", "icli", "parts", "pick_note", "with_d", "execute");
-        let mut all_notes = db.get_all().to_vec();
-
-        if let Some(filter) = &options.filter {
-            all_notes.retain(filter);
-        }
+        let compact = super::picker_options(notes, options);
 
-        if all_notes.is_empty() {
+        if compact.is_empty() {
             println!("There are no notes to display (or none that match the given filter)!");
             return Ok(None);
         }
 
-        let compact = all_notes
-            .iter()
-            .map(|n| super::PartialNote::from_note(n, &options.field_visibility))
-            .collect::<Vec<_>>();
         let choice = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Notes:")
             .max_length(10)
             .items(&compact)
             .interact_opt()?;
 
-        Ok(choice.and_then(|i| all_notes.iter().find(|n| n.id() == compact[i].id).cloned()))
+        Ok(choice.and_then(|i| notes.iter().find(|n| n.id() == compact[i].id()).cloned()))
     }
 }
 
@@ -218,49 +230,52 @@ mod with_i {
     use crate::types::Note;
 
     pub fn execute(
-        db: &mut crate::db::Database,
+        notes: &[Note],
         options: &super::PickNoteOptions,
     ) -> crate::Result<Option<super::Note>> {
         crate::flame_guard!("bins", "icli", "parts", "pick_note", "with_i", "execute");
-        let mut all_notes = db.get_all().to_vec();
-
-        if let Some(filter) = &options.filter {
-            all_notes.retain(filter);
-        }
+        let compact = super::picker_options(notes, options);
 
-        if all_notes.is_empty() {
+        if compact.is_empty() {
             println!("There are no notes to display (or none that match the given filter)!");
             return Ok(None);
         }
 
-        let compact = all_notes
-            .iter()
-            .map(|n| super::PartialNote::from_note(n, &options.field_visibility))
-            .collect::<Vec<_>>();
         let mut select = Select::new("Notes:", compact).with_page_size(10);
         let choice = select.prompt_skippable()?;
-        Ok(choice.and_then(|cn| all_notes.iter().find(|n| n.id() == cn.id).cloned()))
+        Ok(choice.and_then(|cn| notes.iter().find(|n| n.id() == cn.id()).cloned()))
     }
 }
 
 pub fn execute(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
 ) -> crate::Result<Option<Note>> {
     crate::flame_guard!("bins", "icli", "parts", "pick_note", "execute");
-    execute_with(db, backend, PickNoteOptions::default())
+    execute_with(service, backend, PickNoteOptions::default())
 }
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
     options: PickNoteOptions,
 ) -> crate::Result<Option<Note>> {
     crate::flame_guard!("bins", "icli", "parts", "pick_note", "execute_with");
+    execute_from(service.database().get_all(), backend, &options)
+}
+
+/// Lets the user pick from an already queried set of `notes` (e.g. search results) rather than from every
+/// note in the database.
+pub fn execute_from(
+    notes: &[Note],
+    backend: super::Backend,
+    options: &PickNoteOptions,
+) -> crate::Result<Option<Note>> {
+    crate::flame_guard!("bins", "icli", "parts", "pick_note", "execute_from");
     match backend {
-        super::Backend::Dialoguer => with_d::execute(db, &options),
-        super::Backend::Inquire => with_i::execute(db, &options),
+        super::Backend::Dialoguer => with_d::execute(notes, options),
+        super::Backend::Inquire => with_i::execute(notes, options),
     }
 }
 
@@ -272,17 +287,53 @@ impl super::Component for PickNoteComponent {
     type Options = PickNoteOptions;
 
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
         options: Self::Options,
     ) -> crate::Result<Self::Output> {
-        execute_with(db, backend, options)
+        execute_with(service, backend, options)
     }
 
     fn execute(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
     ) -> crate::Result<Self::Output> {
-        execute(db, backend)
+        execute(service, backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn picker_options_from_results() {
+        let notes = vec![
+            Note::create(("First", "", vec!["keep"])),
+            Note::create(("Second", "", vec!["drop"])),
+            Note::create(("Third", "", vec!["keep"])),
+        ];
+
+        let items = picker_options(&notes, &PickNoteOptions::default());
+        assert_eq!(items.len(), 3);
+        for (item, note) in items.iter().zip(&notes) {
+            assert_eq!(item.id(), note.id());
+            assert_eq!(item.text(), format!("{} | {}", note.id(), note.title()));
+            assert_eq!(item.to_string(), item.text());
+        }
+
+        let options = PickNoteOptions {
+            field_visibility: NoteVisibility(vec![NoteDisplay::Field(NoteField::Title)]),
+            filter: Some(box |note: &Note| note.tag_matches("keep")),
+            ..PickNoteOptions::default()
+        };
+        let items = picker_options(&notes, &options);
+        let texts = items.iter().map(PartialNote::text).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["First", "Third"]);
+        assert_eq!(items[1].id(), notes[2].id());
+
+        assert!(picker_options(&[], &PickNoteOptions::default()).is_empty());
     }
 }
@@ -0,0 +1,118 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::types::{
+    api::{Filter, FilterExpr, NoteFilter, NoteStringField, StringSearch},
+    Note,
+};
+
+use super::pick_note::PickNoteOptions;
+
+/// The choices offered by the "Search in" prompt, see [`build_filter`].
+pub const FIELD_CHOICES: &[&str] = &["All", "Title", "Content", "Tags"];
+
+/// Builds the filter for a search of `query` in the field chosen from [`FIELD_CHOICES`]. Each field gets a
+/// [`Filter`] using [`StringSearch::contains`], and a note matches if any of them do, so "All" finds the query
+/// in the title, the content, or the tags. Anything other than a single field searches all of them.
+pub fn build_filter(query: &str, field: &str) -> FilterExpr {
+    crate::flame_guard!("bins", "icli", "parts", "search_notes", "build_filter");
+    let fields: &[NoteStringField] = match field {
+        "Title" => &[NoteStringField::Title],
+        "Content" => &[NoteStringField::Content],
+        "Tags" => &[NoteStringField::Tags],
+        _ => &[NoteStringField::Title, NoteStringField::Content, NoteStringField::Tags],
+    };
+
+    FilterExpr::or(
+        fields
+            .iter()
+            .map(|&field| {
+                Filter::single(NoteFilter::String(field, StringSearch::contains(query.to_string(), false))).into()
+            })
+            .collect(),
+    )
+}
+
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result<Option<Note>> {
+    crate::flame_guard!("bins", "icli", "parts", "search_notes", "execute");
+    let query = backend.text("Search for:", None)?;
+    if query.is_empty() {
+        println!("Nothing to search for!");
+        return Ok(None);
+    }
+    let field = backend.select("Search in:", FIELD_CHOICES)?;
+
+    let predicate = build_filter(&query, field).predicate();
+    let matches = service.database().find(|note| predicate(note)).len();
+    if matches == 0 {
+        println!("No notes found matching '{}'.", query);
+        return Ok(None);
+    }
+    println!("Found {} note(s) matching '{}'.", matches, query);
+
+    super::pick_note_with(
+        service,
+        backend,
+        PickNoteOptions {
+            filter: Some(box move |note: &Note| predicate(note)),
+            ..PickNoteOptions::default()
+        },
+    )
+}
+
+pub struct SearchNotesComponent;
+
+impl super::Component for SearchNotesComponent {
+    type Output = Option<Note>;
+
+    fn execute_with(
+        service: &mut crate::services::NoteService,
+        backend: super::Backend,
+        _options: super::NoOptions,
+    ) -> crate::Result<Self::Output> {
+        execute(service, backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn build_filter_from_fields() {
+        let notes = vec![
+            Note::create(("Apple pie", "Bake at 180", vec!["recipe"])),
+            Note::create(("Shopping", "apples, pears", vec!["errands"])),
+            Note::create(("Orchard", "Trees", vec!["apple"])),
+            Note::create(("Unrelated", "Nothing here", vec![])),
+        ];
+        let search = |query: &str, field: &str| {
+            let predicate = build_filter(query, field).predicate();
+            notes
+                .iter()
+                .filter(|note| predicate(note))
+                .map(|note| note.title().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(search("Apple", "Title"), ["Apple pie"]);
+        assert_eq!(search("apple", "Content"), ["Shopping"]);
+        assert_eq!(search("apple", "Tags"), ["Orchard"]);
+        assert_eq!(search("apple", "All"), ["Shopping", "Orchard"]);
+        assert_eq!(search("pie", "All"), ["Apple pie"]);
+        assert!(search("missing", "All").is_empty());
+
+        match build_filter("x", "Title") {
+            FilterExpr::Or(filters) => assert_eq!(filters.len(), 1),
+            other => panic!("expected an or of filters, got {:?}", other),
+        }
+        for choice in FIELD_CHOICES {
+            assert!(matches!(build_filter("x", choice), FilterExpr::Or(_)));
+        }
+    }
+}
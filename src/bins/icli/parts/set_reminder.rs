@@ -0,0 +1,180 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use time::OffsetDateTime;
+
+use crate::{
+    types::{Note, Recurrence, Reminder, UpdateNote},
+    util::dtf,
+};
+
+/// The choices offered by the "Repeat" prompt, see [`ReminderAnswers::repeat`].
+pub const REPEAT_CHOICES: &[&str] = &["Never", "Daily", "Weekly", "Monthly", "Yearly"];
+
+/// The answers given to the prompts of [`execute_with`]. They are kept separate from the prompts so that the
+/// resulting [`UpdateNote`] can be built (and tested) without a terminal, see [`build_update`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReminderAnswers {
+    /// The text of the reminder.
+    pub text: String,
+    /// The day the reminder is due, in any form understood by [`dtf::parse_relative`].
+    pub when: String,
+    /// The time of day as `HH:MM` (24-hour), or empty to keep the time of day given by `when`.
+    pub at: String,
+    /// One of [`REPEAT_CHOICES`], weekly and monthly reminders repeat on the weekday and day of the month they
+    /// are first due.
+    pub repeat: String,
+}
+
+/// Builds the [`UpdateNote`] that adds the [`Reminder`] described by `answers` to `note`, keeping any
+/// reminders it already has. Relative dates in `answers` are relative to `now`.
+///
+/// ## Errors
+/// - If the reminder text is empty.
+/// - If `when` or `at` cannot be understood, or together they are before `now`.
+pub fn build_update(note: &Note, answers: &ReminderAnswers, now: OffsetDateTime) -> crate::Result<UpdateNote> {
+    crate::flame_guard!("bins", "icli", "parts", "set_reminder", "build_update");
+    let text = answers.text.trim();
+    if text.is_empty() {
+        return Err(crate::Error::ui("The reminder text cannot be empty"));
+    }
+
+    let mut due = dtf::parse_relative(&answers.when, now)
+        .ok_or_else(|| crate::Error::ui(format!("Unable to understand '{}' as a date", answers.when)))?;
+    if !answers.at.trim().is_empty() {
+        let at = time::Time::parse(answers.at.trim(), time::macros::format_description!("[hour]:[minute]"))
+            .map_err(|_| crate::Error::ui(format!("Unable to understand '{}' as a time, use HH:MM", answers.at)))?;
+        due = due.replace_time(at);
+    }
+    if due < now {
+        return Err(crate::Error::ui(format!("The reminder would be due in the past ({})", due)));
+    }
+
+    let recurrence = match answers.repeat.as_str() {
+        "Daily" => Recurrence::Daily,
+        "Weekly" => Recurrence::Weekly(due.weekday().into()),
+        "Monthly" => Recurrence::Monthly(due.day()),
+        "Yearly" => Recurrence::Yearly,
+        _ => Recurrence::None,
+    };
+    let mut reminders = note.reminders().to_vec();
+    reminders.push(Reminder::new(&text, due).with_recurrence(recurrence));
+
+    Ok(UpdateNote::empty(note.id()).with_reminders(Some(reminders)))
+}
+
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result {
+    crate::flame_guard!("bins", "icli", "parts", "set_reminder", "execute");
+    let choice = super::pick_note(service, backend)?;
+    execute_with(service, backend, choice)
+}
+
+pub fn execute_with(
+    service: &mut crate::services::NoteService,
+    backend: super::Backend,
+    option: Option<Note>,
+) -> crate::Result {
+    crate::flame_guard!("bins", "icli", "parts", "set_reminder", "execute_with");
+    let Some(note) = option else {
+        eprintln!("No note selected!");
+        return Ok(());
+    };
+
+    let update = loop {
+        let answers = ReminderAnswers {
+            text: backend.text("Reminder:", None)?,
+            when: backend.text("When? (e.g. 'tomorrow', 'in 2 hours', 'next friday', or 'YYYY-MM-DD')", None)?,
+            at: backend.text("At what time? (HH:MM, leave empty to keep)", None)?,
+            repeat: backend.select("Repeat:", REPEAT_CHOICES)?.to_string(),
+        };
+        let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+        match build_update(&note, &answers, now) {
+            Ok(update) => break update,
+            Err(error) => {
+                eprintln!("{}", error);
+                if !backend.confirm("Try again?")? {
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    let (note, _) = service.update(update)?;
+    if let Some(reminder) = note.reminders().last() {
+        println!("Added reminder '{}' due {} to '{}'", reminder.text(), reminder.due(), note.title());
+    }
+
+    Ok(())
+}
+
+pub struct SetReminderComponent;
+
+impl super::Component for SetReminderComponent {
+    type Output = ();
+    type Options = Option<Note>;
+
+    fn execute_with(
+        service: &mut crate::services::NoteService,
+        backend: super::Backend,
+        options: Self::Options,
+    ) -> crate::Result<Self::Output> {
+        execute_with(service, backend, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use time::macros::datetime;
+
+    fn answers(text: &str, when: &str, at: &str, repeat: &str) -> ReminderAnswers {
+        ReminderAnswers {
+            text: text.to_string(),
+            when: when.to_string(),
+            at: at.to_string(),
+            repeat: repeat.to_string(),
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn build_update_from_answers() {
+        // A Wednesday.
+        let now = datetime!(2022-06-01 10:30 UTC);
+        let note = Note::create(("Title", "Content"));
+
+        let update = build_update(&note, &answers("Call back", "tomorrow", "09:15", "Never"), now)
+            .expect("Unable to build update");
+        assert_eq!(update.id(), &note.id());
+        assert_eq!((update.title(), update.content(), update.tags.as_ref()), (None, None, None));
+        let reminders = update.reminders.as_ref().expect("reminders should be set");
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].text(), "Call back");
+        assert_eq!(reminders[0].due(), datetime!(2022-06-02 09:15 UTC));
+        assert_eq!(reminders[0].recurrence(), Recurrence::None);
+
+        let update = build_update(&note, &answers("Standup", "next monday", "", "Weekly"), now).unwrap();
+        let reminder = &update.reminders.as_ref().unwrap()[0];
+        assert_eq!(reminder.due(), datetime!(2022-06-06 10:30 UTC));
+        assert_eq!(reminder.recurrence(), Recurrence::Weekly(time::Weekday::Monday.into()));
+
+        let update = build_update(&note, &answers("Rent", "2022-06-28", "08:00", "Monthly"), now).unwrap();
+        assert_eq!(update.reminders.as_ref().unwrap()[0].recurrence(), Recurrence::Monthly(28));
+
+        // Existing reminders are kept.
+        let mut with_reminder = note.clone();
+        assert!(with_reminder.update(build_update(&note, &answers("First", "in 1 hour", "", "Never"), now).unwrap()));
+        let update = build_update(&with_reminder, &answers("Second", "in 2 days", "", "Daily"), now).unwrap();
+        let texts = update.reminders.unwrap().iter().map(|r| r.text().to_string()).collect::<Vec<_>>();
+        assert_eq!(texts, ["First", "Second"]);
+
+        assert!(build_update(&note, &answers("", "tomorrow", "", "Never"), now).is_err());
+        assert!(build_update(&note, &answers("Text", "someday", "", "Never"), now).is_err());
+        assert!(build_update(&note, &answers("Text", "tomorrow", "25:00", "Never"), now).is_err());
+        assert!(build_update(&note, &answers("Text", "today", "08:00", "Never"), now).is_err(), "due in the past");
+    }
+}
@@ -6,12 +6,12 @@
 
 use crate::types::Note;
 
-pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::Result<()> {
-    execute_with(db, backend, &None)
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result<()> {
+    execute_with(service, backend, &None)
 }
 
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
     option: &Option<Note>,
 ) -> crate::Result<()> {
@@ -65,7 +65,7 @@ pub fn execute_with(
 
     note.set_title(title.as_str());
     note.set_content(content.as_str());
-    db.ensure_sync(&mut note);
+    service.sync(&mut note)?;
 
     Ok(())
 }
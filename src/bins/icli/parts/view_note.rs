@@ -6,13 +6,13 @@
 
 use crate::types::Note;
 
-pub fn execute(db: &mut crate::db::Database, backend: super::Backend) -> crate::Result {
+pub fn execute(service: &mut crate::services::NoteService, backend: super::Backend) -> crate::Result {
     crate::flame_guard!("bins", "icli", "parts", "view_note", "execute");
-    execute_with(db, backend, None)
+    execute_with(service, backend, None)
 }
 
 pub fn execute_with(
-    db: &mut crate::db::Database,
+    service: &mut crate::services::NoteService,
     backend: super::Backend,
     options: Option<Note>,
 ) -> crate::Result {
@@ -47,7 +47,7 @@ Created: {} | Updated: {}"#,
 
     println!("{}", formatted);
 
-    db.ensure_sync(&mut note);
+    service.sync(&mut note)?;
 
     Ok(())
 }
@@ -59,10 +59,10 @@ impl super::Component for ViewNoteComponent {
     type Options = Option<Note>;
 
     fn execute_with(
-        db: &mut crate::db::Database,
+        service: &mut crate::services::NoteService,
         backend: super::Backend,
         options: Self::Options,
     ) -> crate::Result<Self::Output> {
-        execute_with(db, backend, options)
+        execute_with(service, backend, options)
     }
 }
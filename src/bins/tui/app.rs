@@ -0,0 +1,486 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! The two-pane terminal interface: a summary line for every note on the left, the selected note on the right.
+
+use std::{io, path::PathBuf};
+
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use tinyid::TinyId;
+
+use crate::{db::Database, types::Note};
+
+use super::{
+    keys::{Action, KeyDispatcher, KeyMode, Pending},
+    markdown::markdown_to_spans,
+};
+
+/// A list of items along with the [`ListState`] needed to render it with a selection, following the demo list
+/// in `term_ui`. Unlike the demo, every method is safe to call on an empty list, which never has a selection.
+pub struct StatefulList<T> {
+    state: ListState,
+    items: Vec<T>,
+}
+
+impl<T> StatefulList<T> {
+    /// Creates the list with its first item (if any) selected.
+    pub fn with_items(items: Vec<T>) -> Self {
+        let mut state = ListState::default();
+        state.select(if items.is_empty() { None } else { Some(0) });
+        Self { state, items }
+    }
+
+    /// Selects the next item, wrapping around to the first after the last.
+    pub fn next(&mut self) {
+        let next = match self.state.selected() {
+            _ if self.items.is_empty() => None,
+            Some(i) if i + 1 < self.items.len() => Some(i + 1),
+            _ => Some(0),
+        };
+        self.state.select(next);
+    }
+
+    /// Selects the previous item, wrapping around to the last before the first.
+    pub fn previous(&mut self) {
+        let previous = match self.state.selected() {
+            _ if self.items.is_empty() => None,
+            Some(i) if i > 0 && i < self.items.len() => Some(i - 1),
+            _ => Some(self.items.len() - 1),
+        };
+        self.state.select(previous);
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    /// Selects the item at `index`, or the last item if `index` is past the end.
+    pub fn select(&mut self, index: Option<usize>) {
+        let index = index.filter(|_| !self.items.is_empty()).map(|i| i.min(self.items.len() - 1));
+        self.state.select(index);
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+/// What key presses currently go to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Input {
+    /// Keys are turned into actions by the [`KeyDispatcher`].
+    Keys,
+    /// Keys edit the search query.
+    Search,
+    /// Keys answer whether the note with this id should be deleted.
+    ConfirmDelete(TinyId),
+}
+
+/// The state of the TUI.
+pub struct App {
+    db: Database,
+    /// Where `db` is saved to, if anywhere.
+    path: Option<PathBuf>,
+    /// The notes matching the search query.
+    notes: StatefulList<Note>,
+    keys: KeyDispatcher,
+    input: Input,
+    query: String,
+    /// A message for the user, shown in the footer until the next key press.
+    status: Option<String>,
+}
+
+impl App {
+    /// Creates the app showing every note in `db`, in the order they are stored, with the first one selected.
+    pub fn new(db: Database, path: Option<PathBuf>, mode: KeyMode) -> Self {
+        let notes = StatefulList::with_items(db.get_all().to_vec());
+        Self {
+            db,
+            path,
+            notes,
+            keys: KeyDispatcher::new(mode),
+            input: Input::Keys,
+            query: String::new(),
+            status: None,
+        }
+    }
+
+    pub fn notes(&self) -> &StatefulList<Note> {
+        &self.notes
+    }
+
+    pub fn selected_note(&self) -> Option<&Note> {
+        self.notes.selected_item()
+    }
+
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    /// The title of the note list: the number of notes listed, marked with `[+]` if the database has unsaved
+    /// changes.
+    pub fn list_title(&self) -> String {
+        let modified = if self.db.is_modified() { " [+]" } else { "" };
+        format!("Notes ({}){}", self.notes.items().len(), modified)
+    }
+
+    /// Handles a key press, returning `false` if the app should quit.
+    pub fn on_key(&mut self, key: KeyEvent) -> bool {
+        self.status = None;
+        match self.input {
+            Input::Keys => {
+                let action = self.keys.dispatch(key);
+                return self.perform(action);
+            }
+            Input::Search => match key.code {
+                KeyCode::Enter => self.input = Input::Keys,
+                KeyCode::Esc => {
+                    self.input = Input::Keys;
+                    self.query.clear();
+                    self.refresh();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.refresh();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.refresh();
+                }
+                _ => {}
+            },
+            Input::ConfirmDelete(id) => {
+                self.input = Input::Keys;
+                if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                    self.delete(id);
+                } else {
+                    self.status = Some("Delete cancelled".to_string());
+                }
+            }
+        }
+        true
+    }
+
+    /// Performs `action`, returning `false` if the app should quit.
+    fn perform(&mut self, action: Action) -> bool {
+        match action {
+            Action::Next => self.notes.next(),
+            Action::Previous => self.notes.previous(),
+            Action::StartSearch => self.input = Input::Search,
+            Action::Delete => {
+                if let Some((id, title)) = self.selected_note().map(|note| (note.id(), note.title().to_string())) {
+                    self.status = Some(format!("Delete '{}'? (y/n)", title));
+                    self.input = Input::ConfirmDelete(id);
+                }
+            }
+            Action::Save => {
+                self.save();
+            }
+            Action::SaveAndQuit => return !self.save(),
+            Action::Quit => return false,
+            Action::ToggleMode => self.status = Some(format!("Switched to {} keys", self.keys.mode())),
+            Action::UnknownCommand(command) => self.status = Some(format!("Unknown command ':{}'", command)),
+            Action::None | Action::Pending => {}
+        }
+        true
+    }
+
+    /// Saves the database, returning whether it was saved.
+    fn save(&mut self) -> bool {
+        let Some(path) = &self.path else {
+            self.status = Some("There is no file to save to".to_string());
+            return false;
+        };
+        match self.db.save(path) {
+            Ok(()) => {
+                self.status = Some(format!("Saved to {}", path.display()));
+                true
+            }
+            Err(error) => {
+                self.status = Some(format!("Unable to save: {}", error));
+                false
+            }
+        }
+    }
+
+    fn delete(&mut self, id: TinyId) {
+        match self.db.apply_delete(id) {
+            Ok(note) => self.status = Some(format!("Deleted '{}'", note.title())),
+            Err(error) => self.status = Some(format!("Unable to delete note: {}", error)),
+        }
+        self.refresh();
+    }
+
+    /// Rebuilds the list of notes from the database and the search query, keeping the selected note selected
+    /// if it is still listed.
+    fn refresh(&mut self) {
+        let selected = self.selected_note().map(Note::id);
+        let query = self.query.to_lowercase();
+        let notes = self
+            .db
+            .get_all()
+            .iter()
+            .filter(|note| query.is_empty() || note.full_text_search_lowercase(&query))
+            .cloned()
+            .collect::<Vec<_>>();
+        let index = selected.and_then(|id| notes.iter().position(|note| note.id() == id));
+        self.notes = StatefulList::with_items(notes);
+        if index.is_some() {
+            self.notes.select(index);
+        }
+    }
+
+    /// The text of the footer: the search query or command being typed, the status message, or the help for the
+    /// current key mode.
+    fn footer(&self) -> Spans<'static> {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        if self.input == Input::Search {
+            return Spans::from(format!("/{}", self.query));
+        }
+        if let Pending::Command(command) = self.keys.pending() {
+            return Spans::from(format!(":{}", command));
+        }
+        if let Some(status) = &self.status {
+            return Spans::from(status.clone());
+        }
+
+        let mut help = vec![Span::styled(format!("[{}] ", self.keys.mode()), bold)];
+        let keys: &[(&str, &str)] = match self.keys.mode() {
+            KeyMode::Default => &[("↑/↓", "select"), ("/", "search"), ("Del", "delete"), ("^S", "save"), ("q", "quit")],
+            KeyMode::Vim => &[("j/k", "select"), ("/", "search"), ("dd", "delete"), (":w", "save"), (":q", "quit")],
+        };
+        for (key, description) in keys.iter().chain(&[("F2", "switch keys")]) {
+            help.push(Span::styled(key.to_string(), bold));
+            help.push(Span::raw(format!(" {}  ", description)));
+        }
+        Spans::from(help)
+    }
+}
+
+/// Runs the TUI on `db` until the user quits, restoring the terminal afterwards. Saving writes `db` to `path`.
+///
+/// ## Errors
+/// - If the terminal cannot be set up, drawn to, or restored.
+pub fn execute(db: Database, path: Option<PathBuf>, mode: KeyMode) -> crate::Result {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_app(&mut terminal, App::new(db, path, mode));
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    res.map_err(Into::into)
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|f| ui(f, &mut app))?;
+
+        if let Event::Key(key) = event::read()? && !app.on_key(key) {
+            return Ok(());
+        }
+    }
+}
+
+fn ui<B: Backend>(f: &mut Frame<'_, B>, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
+        .split(f.size());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+        .split(rows[0]);
+
+    render_list(f, app, panes[0]);
+    render_note(f, app.selected_note(), panes[1]);
+
+    f.render_widget(Paragraph::new(app.footer()), rows[1]);
+}
+
+/// Drawn in front of the selected note in the list, every other note is indented by the same amount.
+const LIST_HIGHLIGHT_SYMBOL: &str = ">> ";
+
+/// How many characters of a [`Note::summary_line`] fit in a list drawn in `area`, after the borders and the
+/// [`LIST_HIGHLIGHT_SYMBOL`].
+fn list_item_width(area: Rect) -> usize {
+    usize::from(area.width).saturating_sub(2 + LIST_HIGHLIGHT_SYMBOL.chars().count())
+}
+
+fn render_list<B: Backend>(f: &mut Frame<'_, B>, app: &mut App, area: Rect) {
+    let width = list_item_width(area);
+    let items = app
+        .notes
+        .items()
+        .iter()
+        .map(|note| ListItem::new(note.summary_line(width)))
+        .collect::<Vec<_>>();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(app.list_title()))
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::LightGreen).add_modifier(Modifier::BOLD))
+        .highlight_symbol(LIST_HIGHLIGHT_SYMBOL);
+    f.render_stateful_widget(list, area, &mut app.notes.state);
+}
+
+fn render_note<B: Backend>(f: &mut Frame<'_, B>, note: Option<&Note>, area: Rect) {
+    let Some(note) = note else {
+        let empty = Paragraph::new("No notes to show.").block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let mut lines = Vec::new();
+    if !note.tags().is_empty() {
+        lines.push(Spans::from(Span::styled(
+            note.tags().iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" "),
+            Style::default().fg(Color::Cyan),
+        )));
+        lines.push(Spans::from(""));
+    }
+    lines.extend(markdown_to_spans(note.content()));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Span::styled(note.title(), Style::default().add_modifier(Modifier::BOLD))),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    #[no_coverage]
+    fn selection_moves_through_notes() {
+        let mut db = Database::empty();
+        for title in ["First", "Second", "Third"] {
+            db.apply_create((title, "Content")).expect("Unable to create note");
+        }
+        let mut app = App::new(db, None, KeyMode::Default);
+        let selected = |app: &App| app.selected_note().map(|note| note.title().to_string());
+        assert_eq!(app.notes().items().len(), 3);
+        assert_eq!(app.notes().selected(), Some(0));
+        assert_eq!(selected(&app).as_deref(), Some("First"));
+
+        assert!(app.on_key(key(KeyCode::Down)));
+        assert!(app.on_key(key(KeyCode::Down)));
+        assert_eq!(selected(&app).as_deref(), Some("Third"));
+        assert!(app.on_key(key(KeyCode::Down)));
+        assert_eq!(selected(&app).as_deref(), Some("First"), "down wraps to the first note");
+        assert!(app.on_key(key(KeyCode::Up)));
+        assert_eq!(selected(&app).as_deref(), Some("Third"), "up wraps to the last note");
+        assert!(app.on_key(key(KeyCode::Up)));
+        assert_eq!(app.notes().selected(), Some(1));
+
+        assert!(app.on_key(key(KeyCode::Char('x'))), "other keys are ignored");
+        assert_eq!(app.notes().selected(), Some(1));
+        assert!(!app.on_key(key(KeyCode::Char('q'))));
+        assert!(!app.on_key(key(KeyCode::Esc)));
+        assert!(!app.on_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+
+        // An empty database never has a selection.
+        let mut empty = App::new(Database::empty(), None, KeyMode::Vim);
+        assert_eq!(empty.notes().selected(), None);
+        empty.on_key(key(KeyCode::Down));
+        empty.on_key(key(KeyCode::Up));
+        empty.on_key(key(KeyCode::Char('d')));
+        empty.on_key(key(KeyCode::Char('d')));
+        assert_eq!(empty.input, Input::Keys, "there is nothing to delete");
+        assert_eq!(empty.notes().selected(), None);
+        assert!(empty.selected_note().is_none());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn vim_keys_drive_the_app() {
+        let mut db = Database::empty();
+        for (title, content) in [("Groceries", "Milk"), ("Work", "Standup at 9"), ("Garden", "Water the plants")] {
+            db.apply_create((title, content)).expect("Unable to create note");
+        }
+        let mut app = App::new(db, None, KeyMode::Vim);
+        let press = |app: &mut App, text: &str| text.chars().all(|c| app.on_key(key(KeyCode::Char(c))));
+        let titles = |app: &App| app.notes().items().iter().map(|note| note.title().to_string()).collect::<Vec<_>>();
+
+        assert!(press(&mut app, "jj"));
+        assert_eq!(app.selected_note().map(Note::title), Some("Garden"));
+        assert!(press(&mut app, "k"));
+        assert_eq!(app.selected_note().map(Note::title), Some("Work"));
+
+        // `dd` asks for confirmation, anything but `y` cancels.
+        assert!(press(&mut app, "dd"));
+        assert_eq!(app.status(), Some("Delete 'Work'? (y/n)"));
+        assert!(press(&mut app, "n"));
+        assert_eq!(app.status(), Some("Delete cancelled"));
+        assert_eq!(titles(&app), ["Groceries", "Work", "Garden"]);
+        assert!(press(&mut app, "ddy"));
+        assert_eq!(app.status(), Some("Deleted 'Work'"));
+        assert_eq!(titles(&app), ["Groceries", "Garden"]);
+        assert_eq!(app.list_title(), "Notes (2) [+]");
+
+        // Searching filters the list, keeping the selection when it still matches.
+        assert!(press(&mut app, "j/GAR"));
+        assert_eq!(titles(&app), ["Garden"]);
+        assert_eq!(app.selected_note().map(Note::title), Some("Garden"));
+        assert!(app.on_key(key(KeyCode::Enter)));
+        assert!(press(&mut app, "k"), "keys are dispatched again after the search");
+        assert!(press(&mut app, "/"));
+        assert!(app.on_key(key(KeyCode::Esc)));
+        assert_eq!(titles(&app), ["Groceries", "Garden"]);
+
+        // There is no file to save to, so `:wq` does not quit.
+        assert!(press(&mut app, ":w"));
+        assert!(app.on_key(key(KeyCode::Enter)));
+        assert_eq!(app.status(), Some("There is no file to save to"));
+        assert!(press(&mut app, ":wq"));
+        assert!(app.on_key(key(KeyCode::Enter)));
+        assert!(press(&mut app, ":q"));
+        assert!(!app.on_key(key(KeyCode::Enter)));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn list_items_fit_the_list() {
+        let area = Rect::new(0, 0, 25, 10);
+        assert_eq!(list_item_width(area), 20);
+        assert_eq!(list_item_width(Rect::new(0, 0, 3, 10)), 0);
+
+        let note = Note::create(("A title that is much too long for the list", "", vec!["a", "b", "c"]));
+        let line = note.summary_line(list_item_width(area));
+        assert!(line.chars().count() <= 20, "'{}' does not fit", line);
+        assert!(line.ends_with(" #3"));
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Turning key presses into [`Action`]s, kept apart from the rest of the TUI so the keybindings can be tested
+//! without a terminal.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The keybindings used by the TUI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyMode {
+    /// Arrow keys to move, `/` to search, `Delete` to delete the selected note, `Ctrl+S` to save and `q` to quit.
+    Default,
+    /// The default keys along with `j`/`k` to move, `dd` to delete the selected note, and the `:w`, `:q`, `:wq`
+    /// and `:x` commands.
+    Vim,
+}
+
+impl KeyMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Default => Self::Vim,
+            Self::Vim => Self::Default,
+        }
+    }
+}
+
+impl std::fmt::Display for KeyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "DEFAULT"),
+            Self::Vim => write!(f, "VIM"),
+        }
+    }
+}
+
+/// What the TUI should do in response to a key press.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Nothing, the key is not bound.
+    None,
+    /// The key started (or continued) a sequence of keys, such as `d` or `:w`, that is not finished yet.
+    Pending,
+    Next,
+    Previous,
+    StartSearch,
+    /// Delete the selected note, once the user confirms it.
+    Delete,
+    Save,
+    SaveAndQuit,
+    Quit,
+    ToggleMode,
+    /// A `:` command that isn't known.
+    UnknownCommand(String),
+}
+
+/// A sequence of keys that has been started but not finished.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pending {
+    None,
+    /// `d` has been pressed once, pressing it again deletes the selected note.
+    Delete,
+    /// A `:` command is being typed.
+    Command(String),
+}
+
+/// Turns key presses into [`Action`]s according to the current [`KeyMode`], remembering any unfinished
+/// sequence of keys between presses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyDispatcher {
+    mode: KeyMode,
+    pending: Pending,
+}
+
+impl KeyDispatcher {
+    pub fn new(mode: KeyMode) -> Self {
+        Self {
+            mode,
+            pending: Pending::None,
+        }
+    }
+
+    pub fn mode(&self) -> KeyMode {
+        self.mode
+    }
+
+    /// Switches to the other [`KeyMode`], dropping any unfinished sequence of keys.
+    pub fn toggle_mode(&mut self) {
+        self.mode = self.mode.toggled();
+        self.pending = Pending::None;
+    }
+
+    pub fn pending(&self) -> &Pending {
+        &self.pending
+    }
+
+    /// The action for `key`. `Ctrl+C` always quits and `F2` always switches between the key modes.
+    pub fn dispatch(&mut self, key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Action::Quit,
+            (KeyCode::F(2), _) => {
+                self.toggle_mode();
+                return Action::ToggleMode;
+            }
+            _ => {}
+        }
+
+        match self.mode {
+            KeyMode::Default => Self::dispatch_default(key),
+            KeyMode::Vim => self.dispatch_vim(key),
+        }
+    }
+
+    fn dispatch_default(key: KeyEvent) -> Action {
+        match (key.code, key.modifiers) {
+            (KeyCode::Down, _) => Action::Next,
+            (KeyCode::Up, _) => Action::Previous,
+            (KeyCode::Char('/'), _) => Action::StartSearch,
+            (KeyCode::Delete, _) => Action::Delete,
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => Action::Save,
+            (KeyCode::Char('q') | KeyCode::Esc, _) => Action::Quit,
+            _ => Action::None,
+        }
+    }
+
+    fn dispatch_vim(&mut self, key: KeyEvent) -> Action {
+        match std::mem::replace(&mut self.pending, Pending::None) {
+            Pending::Command(mut command) => match key.code {
+                KeyCode::Enter => match command.as_str() {
+                    "w" => Action::Save,
+                    "q" => Action::Quit,
+                    "wq" | "x" => Action::SaveAndQuit,
+                    _ => Action::UnknownCommand(command),
+                },
+                KeyCode::Esc => Action::None,
+                KeyCode::Backspace => {
+                    if command.pop().is_some() {
+                        self.pending = Pending::Command(command);
+                    }
+                    Action::Pending
+                }
+                KeyCode::Char(c) => {
+                    command.push(c);
+                    self.pending = Pending::Command(command);
+                    Action::Pending
+                }
+                _ => {
+                    self.pending = Pending::Command(command);
+                    Action::Pending
+                }
+            },
+            Pending::Delete if key.code == KeyCode::Char('d') => Action::Delete,
+            // Any other key cancels a pending delete and is handled as usual.
+            Pending::Delete | Pending::None => match key.code {
+                KeyCode::Char('j') | KeyCode::Down => Action::Next,
+                KeyCode::Char('k') | KeyCode::Up => Action::Previous,
+                KeyCode::Char('/') => Action::StartSearch,
+                KeyCode::Char('d') => {
+                    self.pending = Pending::Delete;
+                    Action::Pending
+                }
+                KeyCode::Char(':') => {
+                    self.pending = Pending::Command(String::new());
+                    Action::Pending
+                }
+                KeyCode::Char('q') => Action::Quit,
+                _ => Action::None,
+            },
+        }
+    }
+}
+
+impl Default for KeyDispatcher {
+    fn default() -> Self {
+        Self::new(KeyMode::Default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn keys(dispatcher: &mut KeyDispatcher, codes: &[KeyCode]) -> Vec<Action> {
+        codes
+            .iter()
+            .map(|&code| dispatcher.dispatch(KeyEvent::new(code, KeyModifiers::NONE)))
+            .collect()
+    }
+
+    fn chars(dispatcher: &mut KeyDispatcher, text: &str) -> Vec<Action> {
+        keys(dispatcher, &text.chars().map(KeyCode::Char).collect::<Vec<_>>())
+    }
+
+    #[test]
+    #[no_coverage]
+    fn vim_dispatch() {
+        let mut vim = KeyDispatcher::new(KeyMode::Vim);
+        assert_eq!(chars(&mut vim, "jjk"), [Action::Next, Action::Next, Action::Previous]);
+        assert_eq!(keys(&mut vim, &[KeyCode::Down, KeyCode::Up]), [Action::Next, Action::Previous]);
+        assert_eq!(chars(&mut vim, "/"), [Action::StartSearch]);
+
+        // `dd` deletes, anything else in between cancels the pending delete.
+        assert_eq!(chars(&mut vim, "d"), [Action::Pending]);
+        assert_eq!(vim.pending(), &Pending::Delete);
+        assert_eq!(chars(&mut vim, "d"), [Action::Delete]);
+        assert_eq!(vim.pending(), &Pending::None);
+        assert_eq!(chars(&mut vim, "djd"), [Action::Pending, Action::Next, Action::Pending]);
+        assert_eq!(keys(&mut vim, &[KeyCode::Esc]), [Action::None]);
+        assert_eq!(vim.pending(), &Pending::None);
+
+        // `:` commands run on enter.
+        assert_eq!(chars(&mut vim, ":w"), [Action::Pending, Action::Pending]);
+        assert_eq!(vim.pending(), &Pending::Command("w".to_string()));
+        assert_eq!(keys(&mut vim, &[KeyCode::Enter]), [Action::Save]);
+        chars(&mut vim, ":wq");
+        assert_eq!(keys(&mut vim, &[KeyCode::Enter]), [Action::SaveAndQuit]);
+        chars(&mut vim, ":qx");
+        assert_eq!(keys(&mut vim, &[KeyCode::Backspace, KeyCode::Enter]), [Action::Pending, Action::Quit]);
+        chars(&mut vim, ":nope");
+        assert_eq!(keys(&mut vim, &[KeyCode::Enter]), [Action::UnknownCommand("nope".to_string())]);
+        chars(&mut vim, ":w");
+        assert_eq!(keys(&mut vim, &[KeyCode::Esc, KeyCode::Char('j')]), [Action::None, Action::Next]);
+        // Backspacing past the `:` cancels the command.
+        chars(&mut vim, ":");
+        assert_eq!(keys(&mut vim, &[KeyCode::Backspace]), [Action::Pending]);
+        assert_eq!(vim.pending(), &Pending::None);
+
+        assert_eq!(chars(&mut vim, "q"), [Action::Quit]);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn mode_switching() {
+        let mut dispatcher = KeyDispatcher::default();
+        assert_eq!(dispatcher.mode(), KeyMode::Default);
+        assert_eq!(chars(&mut dispatcher, "jd"), [Action::None, Action::None]);
+        assert_eq!(keys(&mut dispatcher, &[KeyCode::Down, KeyCode::Delete]), [Action::Next, Action::Delete]);
+        assert_eq!(dispatcher.dispatch(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)), Action::Save);
+
+        assert_eq!(keys(&mut dispatcher, &[KeyCode::F(2)]), [Action::ToggleMode]);
+        assert_eq!(dispatcher.mode(), KeyMode::Vim);
+        assert_eq!(chars(&mut dispatcher, "jd"), [Action::Next, Action::Pending]);
+
+        // Switching modes drops the pending delete.
+        assert_eq!(keys(&mut dispatcher, &[KeyCode::F(2)]), [Action::ToggleMode]);
+        assert_eq!(dispatcher.pending(), &Pending::None);
+        assert_eq!(dispatcher.mode(), KeyMode::Default);
+        assert_eq!(dispatcher.dispatch(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Converts markdown into styled [`Spans`] for the note pane of the TUI.
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+};
+
+/// The style of a heading. Every heading is bold, top-level headings are also underlined.
+pub fn heading_style(level: HeadingLevel) -> Style {
+    let style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    match level {
+        HeadingLevel::H1 => style.add_modifier(Modifier::UNDERLINED),
+        _ => style,
+    }
+}
+
+/// The style of inline code and code blocks.
+pub fn code_style() -> Style {
+    Style::default().fg(Color::LightRed).bg(Color::Black)
+}
+
+/// The style of links (and the alt text of images).
+pub fn link_style() -> Style {
+    Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)
+}
+
+/// Converts the markdown in `text` to one [`Spans`] per line.
+///
+/// Headings are bold, bold and italic text keep their emphasis, list items get a bullet (or their number) and are
+/// indented by nesting level, block quotes are prefixed with a bar, and inline code and code blocks use
+/// [`code_style`]. Blocks are separated by a blank line. Anything that can't be shown in a terminal, like HTML,
+/// is shown as its plain text.
+pub fn markdown_to_spans(text: &str) -> Vec<Spans<'static>> {
+    let mut renderer = Renderer::default();
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS | Options::ENABLE_TABLES;
+    for event in Parser::new_ext(text, options) {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+/// Removes anything that looks like an HTML tag from `html`, leaving its text.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// The state of a single [`markdown_to_spans`] call.
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<Spans<'static>>,
+    current: Vec<Span<'static>>,
+    heading: Option<HeadingLevel>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    link: usize,
+    quote: usize,
+    /// The number of open paragraphs, headings, list items and table cells, outside of which HTML is a block.
+    text_blocks: usize,
+    /// Whether an HTML block is being shown, it ends at the next tag.
+    html_block: bool,
+    /// The lists currently open, innermost last, holding the number of the next item of ordered lists.
+    lists: Vec<Option<u64>>,
+    /// The contents of the code block currently open, split into lines once the block ends.
+    code_block: Option<String>,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code_block {
+                Some(code) => code.push_str(&text),
+                None => self.push(&text, self.style()),
+            },
+            Event::Code(code) => self.push(&code, code_style()),
+            Event::Html(html) => {
+                if self.text_blocks == 0 && !self.html_block {
+                    self.block_break();
+                    self.html_block = true;
+                }
+                self.push(&strip_tags(&html), self.style());
+            }
+            Event::FootnoteReference(name) => self.push(&format!("[{}]", name), self.style()),
+            Event::SoftBreak => self.push(" ", self.style()),
+            Event::HardBreak => self.end_line(),
+            Event::Rule => {
+                self.block_break();
+                self.push(&"─".repeat(20), Style::default().fg(Color::DarkGray));
+            }
+            Event::TaskListMarker(checked) => self.push(if checked { "[x] " } else { "[ ] " }, self.style()),
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        self.html_block = false;
+        if matches!(tag, Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::TableCell) {
+            self.text_blocks += 1;
+        }
+        match tag {
+            Tag::Paragraph | Tag::Table(_) | Tag::FootnoteDefinition(_) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            Tag::Heading(level, ..) => {
+                self.block_break();
+                self.heading = Some(level);
+            }
+            Tag::BlockQuote => {
+                self.block_break();
+                self.quote += 1;
+            }
+            Tag::CodeBlock(_) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.end_line();
+                }
+                self.code_block = Some(String::new());
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                } else {
+                    self.end_line();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.end_line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}{}. ", indent, *number - 1)
+                    }
+                    _ => format!("{}• ", indent),
+                };
+                self.push(&marker, Style::default());
+            }
+            Tag::TableHead | Tag::TableRow => self.end_line(),
+            Tag::TableCell => {
+                if !self.current.is_empty() {
+                    self.push(" | ", self.style());
+                }
+            }
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link(..) | Tag::Image(..) => self.link += 1,
+        }
+    }
+
+    fn end(&mut self, tag: Tag<'_>) {
+        if matches!(tag, Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::TableCell) {
+            self.text_blocks = self.text_blocks.saturating_sub(1);
+        }
+        match tag {
+            Tag::Heading(..) => self.heading = None,
+            Tag::BlockQuote => self.quote = self.quote.saturating_sub(1),
+            Tag::CodeBlock(_) => {
+                let code = self.code_block.take().unwrap_or_default();
+                for line in code.trim_end_matches('\n').split('\n') {
+                    self.end_line();
+                    self.current.push(Span::styled(line.to_string(), code_style()));
+                }
+            }
+            Tag::List(_) => {
+                self.lists.pop();
+            }
+            Tag::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            Tag::Strong => self.strong = self.strong.saturating_sub(1),
+            Tag::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+            Tag::Link(..) | Tag::Image(..) => self.link = self.link.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    /// The style of regular text given the currently open tags.
+    fn style(&self) -> Style {
+        let mut style = Style::default();
+        if let Some(level) = self.heading {
+            style = heading_style(level);
+        }
+        if self.quote > 0 {
+            style = style.fg(Color::DarkGray).add_modifier(Modifier::ITALIC);
+        }
+        if self.strong > 0 {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.emphasis > 0 {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if self.strikethrough > 0 {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        if self.link > 0 {
+            style = style.patch(link_style());
+        }
+        style
+    }
+
+    /// Adds `text` to the current line, starting new lines at any newlines in it.
+    fn push(&mut self, text: &str, style: Style) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.end_line();
+            }
+            if part.is_empty() {
+                continue;
+            }
+            if self.current.is_empty() && self.quote > 0 {
+                self.current.push(Span::styled("│ ".repeat(self.quote), Style::default().fg(Color::DarkGray)));
+            }
+            self.current.push(Span::styled(part.to_string(), style));
+        }
+    }
+
+    /// Finishes the current line, if anything has been added to it.
+    fn end_line(&mut self) {
+        if !self.current.is_empty() {
+            self.lines.push(Spans::from(std::mem::take(&mut self.current)));
+        }
+    }
+
+    /// Separates the next block from the previous one by a blank line.
+    fn block_break(&mut self) {
+        self.end_line();
+        if self.lines.last().map_or(false, |line| !line.0.is_empty()) {
+            self.lines.push(Spans::default());
+        }
+    }
+
+    fn finish(mut self) -> Vec<Spans<'static>> {
+        self.end_line();
+        self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn plain(text: &str) -> Span<'static> {
+        Span::raw(text.to_string())
+    }
+
+    fn styled(text: &str, style: Style) -> Span<'static> {
+        Span::styled(text.to_string(), style)
+    }
+
+    #[test]
+    #[no_coverage]
+    fn headings_and_inline_styles() {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let italic = Style::default().add_modifier(Modifier::ITALIC);
+        let lines = markdown_to_spans("# Title\n\nSome **bold**, *italic* and `code`.\n\n## Section");
+        assert_eq!(
+            lines,
+            vec![
+                Spans::from(vec![styled("Title", heading_style(HeadingLevel::H1))]),
+                Spans::default(),
+                Spans::from(vec![
+                    plain("Some "),
+                    styled("bold", bold),
+                    plain(", "),
+                    styled("italic", italic),
+                    plain(" and "),
+                    styled("code", code_style()),
+                    plain("."),
+                ]),
+                Spans::default(),
+                Spans::from(vec![styled("Section", heading_style(HeadingLevel::H2))]),
+            ]
+        );
+        assert!(heading_style(HeadingLevel::H2).add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn lists_and_code_blocks() {
+        let lines = markdown_to_spans("- one\n- two\n  - nested\n\n1. first\n2. [x] done\n\n```rust\nfn main() {\n\n}\n```");
+        assert_eq!(
+            lines,
+            vec![
+                Spans::from(vec![plain("• "), plain("one")]),
+                Spans::from(vec![plain("• "), plain("two")]),
+                Spans::from(vec![plain("  • "), plain("nested")]),
+                Spans::default(),
+                Spans::from(vec![plain("1. "), plain("first")]),
+                Spans::from(vec![plain("2. "), plain("[x] "), plain("done")]),
+                Spans::default(),
+                Spans::from(vec![styled("fn main() {", code_style())]),
+                Spans::from(vec![styled("", code_style())]),
+                Spans::from(vec![styled("}", code_style())]),
+            ]
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn unsupported_markup_is_plain_text() {
+        let quote = Style::default().fg(Color::DarkGray);
+        let lines = markdown_to_spans("> Quoted\n\nSome <b>html</b> here.\n\n<div>Block</div>\n\n[link](https://example.com)");
+        assert_eq!(
+            lines,
+            vec![
+                Spans::from(vec![styled("│ ", quote), styled("Quoted", quote.add_modifier(Modifier::ITALIC))]),
+                Spans::default(),
+                Spans::from(vec![plain("Some "), plain("html"), plain(" here.")]),
+                Spans::default(),
+                Spans::from(vec![plain("Block")]),
+                Spans::default(),
+                Spans::from(vec![styled("link", link_style())]),
+            ]
+        );
+        assert!(markdown_to_spans("").is_empty());
+    }
+}
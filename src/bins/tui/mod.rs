@@ -11,17 +11,33 @@
 //! to have these notes render as markdown (at least as much markdown as a terminal can support, think golang something like
 //! [`glow`](https://github.com/charmbracelet/glow) library / bin).
 
-/// Runs the Terminal User Interface for the notes application.
+mod app;
+mod keys;
+mod markdown;
+
+/// Runs the Terminal User Interface for the notes application. The first argument that isn't a flag is the path
+/// of the database to open, without one the development database is opened. Passing `--vim` starts the TUI with
+/// vim-style keybindings, see [`keys::KeyMode`].
 ///
 /// ## Errors
+/// - If given an unknown `--` flag.
+/// - If the database cannot be loaded.
 /// - If the underlying process errors.
 pub fn run_tui(args: std::env::Args) -> crate::Result {
-    println!("Noted TUI. Args: {}", args.collect::<Vec<_>>().join(" "));
-
-    if let Err(err) = crate::term_ui::list::execute() {
-        eprintln!("{}", err);
-        return crate::Error::unknown(err.to_string()).into();
+    let mut mode = keys::KeyMode::Default;
+    let mut path = None;
+    for arg in args.skip(1) {
+        match arg.as_str() {
+            "--vim" => mode = keys::KeyMode::Vim,
+            flag if flag.starts_with("--") => return Err(crate::Error::ui(format!("Unknown flag '{}'", flag))),
+            _ => path = Some(std::path::PathBuf::from(arg)),
+        }
     }
+    let path = match path {
+        Some(path) => path,
+        None => crate::db::Database::dev_path()?,
+    };
+    let db = crate::db::Database::load(&path)?;
 
-    Ok(())
+    app::execute(db, Some(path), mode)
 }
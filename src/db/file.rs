@@ -4,20 +4,51 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering as AtomicOrdering},
+};
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 use serde::{Deserialize, Serialize};
 use tinyid::TinyId;
 use uuid::Uuid;
 
 use crate::{
-    types::{CreateNote, DeleteNote, Note, NoteDto, UpdateNote},
-    util::{persist::Persistence, variadic::OneOrMore},
+    types::{
+        api::{Count, Filter, Ordering},
+        ChangedFields, CreateNote, DeleteNote, FixApplied, FlagNote, Note, NoteDto, NoteFlag, Reminder, TagNormalizer,
+        TagStyle, UpdateNote,
+    },
+    util::{
+        markdown::{self, FrontMatter},
+        persist::{Method, Persistence},
+        variadic::OneOrMore,
+    },
     DatabaseError, Error, Result, flame_guard,
 };
 
-use super::DatabaseMessage;
+use super::{
+    msg::{MessageChannel, SequencedMessage},
+    DatabaseMessage,
+};
+
+/// The title of the note created by [`Database::seed_welcome`].
+pub const WELCOME_TITLE: &str = "Welcome to Noted!";
+
+/// The content of the note created by [`Database::seed_welcome`].
+pub const WELCOME_CONTENT: &str = "\
+Noted keeps your notes, tags and reminders in a single database file.
+
+Some tips to get started:
+- Give notes tags to group them, and search or filter by tag later.
+- Notes support markdown, and the first line is used as a summary in lists.
+- Pin important notes to keep them at the top.
+- Add a reminder to a note and Noted will let you know when it is due.
+
+This note is just a regular note, so feel free to edit or delete it.";
 
 /// Intermediate type that is used to serialize [`Database`] so that the
 /// internal ID-list can be built from the notes as it is constructed and
@@ -25,17 +56,32 @@ use super::DatabaseMessage;
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 struct IntermediateDatabase {
     notes: Vec<Note>,
+    #[serde(default)]
+    tag_styles: HashMap<String, TagStyle>,
+}
+
+/// Serializes `map` with its entries sorted by key, so that the output does not depend on the hash order.
+fn serialize_sorted<S: serde::Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    map.iter().collect::<std::collections::BTreeMap<_, _>>().serialize(serializer)
 }
 
 impl TryFrom<IntermediateDatabase> for Database {
     type Error = Error;
 
     fn try_from(value: IntermediateDatabase) -> Result<Self> {
-        Self::from_notes_vec(value.notes)
+        let mut db = Self::from_notes_vec(value.notes)?;
+        db.tag_styles = value.tag_styles;
+        Ok(db)
     }
 }
 
 /// Implementation of a Database that stores data in a file.
+///
+/// Notes are kept (and saved) in the order they were added, call [`Database::canonicalize`] first if the saved
+/// bytes need to be reproducible. Tag styles are always saved sorted by tag.
 /// 
 /// State Changes:
 /// - 
@@ -43,19 +89,49 @@ impl TryFrom<IntermediateDatabase> for Database {
 #[serde(try_from = "IntermediateDatabase")]
 pub struct Database {
     notes: Vec<Note>,
+    /// Explicit display styles for tags, see [`Database::tag_style`].
+    #[serde(serialize_with = "serialize_sorted")]
+    tag_styles: HashMap<String, TagStyle>,
     #[serde(skip)]
     ids: HashSet<TinyId>,
+    /// The channel used to send [`DatabaseMessage`]s, or `None` if messaging is disabled. The channel is
+    /// unbounded so if nothing is draining [`Database::get_receiver`] messaging should be disabled.
+    #[serde(skip)]
+    channel: Option<MessageChannel>,
+    /// The path this [`Database`] was loaded from, if any.
+    #[serde(skip)]
+    source_path: Option<PathBuf>,
+    /// The [`Method`] used when saving this [`Database`], which matches the method it was loaded with.
+    #[serde(skip)]
+    method: Method,
+    /// Applied to the tags of every [`CreateNote`] and [`UpdateNote`], see [`Database::set_tag_normalizer`].
     #[serde(skip)]
-    sender: Sender<DatabaseMessage>,
+    tag_normalizer: TagNormalizer,
+    /// Whether this [`Database`] has been changed since it was last saved (or loaded), see
+    /// [`Database::is_modified`]. This is a [`ModifiedFlag`] so that [`Database::save`] can clear it through
+    /// `&self` without making the database `!Sync`.
     #[serde(skip)]
-    receiver: Receiver<DatabaseMessage>,
-    // TODO: I think I should have an option to deactivate message sending since it will not be necessary in all scenarios.
-    //       There are two ways to go about this I can see, add a separate `send_messages` field like below, OR we could simply
-    //       hold the `sender` and `receiver` fields as an `Option` which would save from initializing them if they won't be used.
-    // send_messages: bool,
-    // OR
-    // channel: Option<(Sender<DatabaseMessage>, Receiver<DatabaseMessage>)>,
-    //       The second form would ensure that I don't need to verify that two different fields are Some
+    modified: ModifiedFlag,
+}
+
+/// The flag behind [`Database::is_modified`], an [`AtomicBool`] that can be cloned along with the database.
+#[derive(Debug, Default)]
+struct ModifiedFlag(AtomicBool);
+
+impl ModifiedFlag {
+    fn get(&self) -> bool {
+        self.0.load(AtomicOrdering::Acquire)
+    }
+
+    fn set(&self, modified: bool) {
+        self.0.store(modified, AtomicOrdering::Release);
+    }
+}
+
+impl Clone for ModifiedFlag {
+    fn clone(&self) -> Self {
+        Self(AtomicBool::new(self.get()))
+    }
 }
 
 /// Constructors
@@ -65,15 +141,49 @@ impl Database {
     pub fn empty() -> Self {
         crate::profile_guard!("empty", "db::file::Database");
 
-        let (sender, receiver) = crossbeam_channel::unbounded();
-        
         Database {
             notes: Vec::new(),
+            tag_styles: HashMap::new(),
+            ids: HashSet::new(),
+            channel: Self::create_channel(),
+            source_path: None,
+            method: Persistence::DEFAULT_METHOD,
+            tag_normalizer: TagNormalizer::default(),
+            modified: ModifiedFlag::default(),
+        }
+    }
+
+    /// Creates an empty [`Database`] with messaging disabled, so no channel is ever allocated and no
+    /// [`DatabaseMessage`]s are sent. See [`Database::set_messaging`].
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn without_messaging() -> Self {
+        // crate::profile_guard!("without_messaging", "db::file::Database");
+
+        Database {
+            notes: Vec::new(),
+            tag_styles: HashMap::new(),
             ids: HashSet::new(),
-            sender, receiver,
+            channel: None,
+            source_path: None,
+            method: Persistence::DEFAULT_METHOD,
+            tag_normalizer: TagNormalizer::default(),
+            modified: ModifiedFlag::default(),
         }
     }
 
+    /// Creates an empty [`Database`] with room for at least `capacity` [`Note`]s before reallocating.
+    /// The capacity is only an optimization for bulk creation, not a limit.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        // crate::profile_guard!("with_capacity", "db::file::Database");
+
+        let mut db = Self::empty();
+        db.reserve(capacity);
+        db
+    }
+
     /// Create a new [`Database`] from the given slice of [`Note`]s.
     ///
     /// ## Errors
@@ -83,12 +193,15 @@ impl Database {
     pub fn from_notes(notes: &[Note]) -> Result<Self> {
         // crate::profile_guard!("from_notes", "db::file::Database");
 
-        let (sender, receiver) = crossbeam_channel::unbounded();
-
         let mut db = Database {
             notes: notes.to_vec(),
+            tag_styles: HashMap::new(),
             ids: notes.iter().map(Note::id).collect(),
-            sender, receiver,
+            channel: Self::create_channel(),
+            source_path: None,
+            method: Persistence::DEFAULT_METHOD,
+            tag_normalizer: TagNormalizer::default(),
+            modified: ModifiedFlag::default(),
         };
         if let Err(error) = db.init() {
             #[cfg(feature = "trace")] {
@@ -110,8 +223,16 @@ impl Database {
         // crate::profile_guard!("from_notes_vec", "db::file::Database");
 
         let ids = notes.iter().map(Note::id).collect();
-        let (sender, receiver) = crossbeam_channel::unbounded();
-        let mut db = Database { notes, ids, sender, receiver };
+        let mut db = Database {
+            notes,
+            tag_styles: HashMap::new(),
+            ids,
+            channel: Self::create_channel(),
+            source_path: None,
+            method: Persistence::DEFAULT_METHOD,
+            tag_normalizer: TagNormalizer::default(),
+            modified: ModifiedFlag::default(),
+        };
         if let Err(error) = db.init() {
             #[cfg(feature = "trace")] {
                 tracing::error!(?error, "database initialization failed");
@@ -135,17 +256,23 @@ impl Database {
         Persistence::load_from_bytes_default(bytes)
     }
 
-    /// Attempts to load a [`Database`] from the given filepath.
+    /// Attempts to load a [`Database`] from the given filepath. The serialization [`Method`] is
+    /// detected from the file contents (falling back to [`Persistence::DEFAULT_METHOD`]) and is
+    /// remembered so that [`Database::save`] writes the file back in the same format.
     ///
     /// ## Errors
     /// - [`DatabaseError::InvalidId`] if the given notes contains an invalid ID.
     /// - [`DatabaseError::InvalidState`] if a list of IDs cannot be built from the list of notes, usually indicating that the notes contain duplicate or invalid ids.
-    /// - Forwards any errors from [`Persistence::load_from_file_default`].
+    /// - Forwards any errors from [`Persistence::detect_method_for_file`] and [`Persistence::load_from_file`].
     #[tracing::instrument(level = "trace", skip(path), fields(path = path.as_ref().display().to_string().as_str()))]
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
         // crate::profile_guard!("load", "db::file::Database");
 
-        let mut db: Self = Persistence::load_from_file_default(path)?;
+        let path = path.as_ref();
+        let method = Persistence::detect_method_for_file(path)?.unwrap_or(Persistence::DEFAULT_METHOD);
+        let mut db: Self = Persistence::load_from_file(path, method)?;
+        db.source_path = Some(path.to_path_buf());
+        db.method = method;
         if let Err(error) = db.init() {
             #[cfg(feature = "trace")] {
                 tracing::error!(?error, "database initialization failed");
@@ -160,34 +287,147 @@ impl Database {
 /// Public Methods
 impl Database {
     pub const DEFAULT_UPDATE_POLICY: UpdateFailurePolicy = UpdateFailurePolicy::AllOrNothing;
+    /// Weight applied to each title match by [`Database::text_search_ranked`].
+    pub const TITLE_WEIGHT: f32 = 3.0;
+    /// Weight applied to each tag match by [`Database::text_search_ranked`].
+    pub const TAG_WEIGHT: f32 = 2.0;
+    /// Weight applied to each content match by [`Database::text_search_ranked`].
+    pub const CONTENT_WEIGHT: f32 = 1.0;
 
     /// Attempts to serialize this [`Database`] into bytes and writes them to a file at the given path.
     /// If the file exists it will be overwritten, and if it does not exist it will be created.
+    /// The file is replaced atomically (see [`Persistence::save_to_file_atomic`]), so a failed or
+    /// interrupted save leaves the original file intact.
+    ///
+    /// The data is written using the [`Method`] this [`Database`] was loaded with (see [`Database::method`]),
+    /// use [`Database::save_as`] to intentionally convert to a different format.
     ///
     /// ## Errors
-    /// - See [`Persistence::save_to_file_default`].
+    /// - See [`Persistence::save_to_file_atomic`].
     #[tracing::instrument(level = "trace", skip(self, path), fields(path = path.as_ref().display().to_string().as_str()))]
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result {
         // crate::profile_guard!("save", "db::file::Database");
 
-        match Persistence::save_to_file_default(self, path.as_ref()) {
-            Ok(_) => {
-                #[cfg(feature = "trace")] {
-                    tracing::trace!(path = %path.as_ref().display().to_string(), "database saved to path");
-                }
-                Self::send_msg(&self.sender, DatabaseMessage::DataSaved { path: path.as_ref().display().to_string() });
-                Ok(())
-            },
-            Err(err) => {
+        self.save_with_method(path.as_ref(), self.method)
+    }
+
+    /// Same as [`Database::save`], but first rotates up to `keep` backup generations of the existing file
+    /// at `path`, named `path.1` (newest) through `path.{keep}` (oldest). Older generations are pruned. The
+    /// existing file is copied (rather than moved) to `path.1` so that the original stays in place until the
+    /// new data has been written successfully. A `keep` of zero makes this identical to [`Database::save`].
+    ///
+    /// ## Errors
+    /// - `Error::Io` if rotating the backup files fails.
+    /// - See [`Database::save`].
+    #[tracing::instrument(level = "trace", skip(self, path), fields(path = path.as_ref().display().to_string().as_str()))]
+    pub fn save_with_backup<P: AsRef<Path>>(&self, path: P, keep: usize) -> Result {
+        // crate::profile_guard!("save_with_backup", "db::file::Database");
+
+        let path = path.as_ref();
+        if keep > 0 && path.is_file() {
+            if let Err(error) = Self::rotate_backups(path, keep) {
                 #[cfg(feature = "trace")] {
-                    tracing::error!(error = ?err, "database initialization failed");
+                    tracing::error!(?error, "unable to rotate database backups");
                 }
+                Self::send_error(&self.channel, || error.to_string());
+                return Err(error);
+            }
+        }
+
+        self.save(path)
+    }
 
-                Self::send_error(&self.sender, || err.to_string());
+    /// The path of the `generation`-th backup of `path`, as used by [`Database::save_with_backup`].
+    #[must_use]
+    pub fn backup_path(path: impl AsRef<Path>, generation: usize) -> PathBuf {
+        let path = path.as_ref();
+        let mut name = path.file_name().map(std::ffi::OsStr::to_os_string).unwrap_or_default();
+        name.push(format!(".{}", generation));
+        path.with_file_name(name)
+    }
 
-                Err(err)
-            },
+    /// Saves this [`Database`] to the given path using the given [`Method`], and remembers both
+    /// so that subsequent calls to [`Database::save`] will continue to use them.
+    ///
+    /// ## Errors
+    /// - See [`Persistence::save_to_file_atomic`].
+    #[tracing::instrument(level = "trace", skip(self, path), fields(path = path.as_ref().display().to_string().as_str()))]
+    pub fn save_as<P: AsRef<Path>>(&mut self, path: P, method: Method) -> Result {
+        // crate::profile_guard!("save_as", "db::file::Database");
+
+        self.save_with_method(path.as_ref(), method)?;
+        self.source_path = Some(path.as_ref().to_path_buf());
+        self.method = method;
+        Ok(())
+    }
+
+    /// Writes every [`Note`] to its own `<id>.md` file in the directory `dir` (which is created if needed),
+    /// with its metadata written as the given [`FrontMatter`]. Returns the number of files written.
+    ///
+    /// ## Errors
+    /// - If the directory cannot be created or a file cannot be written.
+    /// - If a note cannot be rendered, see [`markdown::to_markdown`].
+    #[tracing::instrument(level = "trace", skip(self, dir))]
+    pub fn export_markdown(&self, dir: impl AsRef<Path>, front_matter: FrontMatter) -> Result<usize> {
+        // crate::profile_guard!("export_markdown", "db::file::Database");
+
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        for note in &self.notes {
+            let text = markdown::to_markdown(note, front_matter)?;
+            std::fs::write(dir.join(format!("{}.md", note.id())), text)?;
         }
+        Ok(self.notes.len())
+    }
+
+    /// Reads every `.md` file in the directory `dir` as a [`Note`] (with any [`FrontMatter`] format, see
+    /// [`markdown::from_markdown`]) and adds them using [`Database::import`], so either every file is
+    /// imported or none are. Returns the number of notes imported.
+    ///
+    /// ## Errors
+    /// - If the directory or a file cannot be read.
+    /// - If a file cannot be parsed, see [`markdown::from_markdown`].
+    /// - See [`Database::import`].
+    #[tracing::instrument(level = "trace", skip(self, dir))]
+    pub fn import_markdown(&mut self, dir: impl AsRef<Path>) -> Result<usize> {
+        // crate::profile_guard!("import_markdown", "db::file::Database");
+
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.is_file() && path.extension().map_or(false, |ext| ext == "md"));
+        paths.sort();
+
+        let notes = paths
+            .iter()
+            .map(|path| markdown::from_markdown(&std::fs::read_to_string(path)?))
+            .collect::<Result<Vec<_>>>()?;
+        self.import(notes, None)
+    }
+
+    /// The path this [`Database`] was loaded from (or last saved to using [`Database::save_as`]), if any.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source_path.as_deref()
+    }
+
+    /// The serialization [`Method`] that will be used by [`Database::save`].
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// Whether any [`Note`] or [`TagStyle`] in this [`Database`] has changed since it was last saved, or since
+    /// it was created or loaded if it has never been saved. Frontends use this to decide whether to prompt
+    /// before closing.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn is_modified(&self) -> bool {
+        // crate::profile_guard!("is_modified", "db::file::Database");
+
+        self.modified.get()
     }
 
     /// Attempts to apply the given data transfer object to this [`Database`].
@@ -203,16 +443,155 @@ impl Database {
                 self.apply_create(create_note).map(DtoResponse::Created)
             }
             NoteDto::Update(update_note) => {
-                self.apply_update(update_note).map(DtoResponse::Updated)
+                self.apply_update(update_note).map(|(note, changed)| DtoResponse::Updated { note, changed })
             }
             NoteDto::Delete(delete_note) => {
-                self.apply_delete(delete_note).map(DtoResponse::Deleted)
+                self.apply_delete(delete_note).map(|note| DtoResponse::Deleted { note })
+            }
+            NoteDto::Flag(flag_note) => {
+                self.apply_flag(flag_note).map(DtoResponse::Flagged)
+            }
+        }
+    }
+
+    /// Sets a flag on an existing [`Note`] using the information from the [`FlagNote`] dto, see [`Note::flag`].
+    /// A [`DatabaseMessage::NoteUpdated`] is sent if the flag changed. Returns whether the flag changed.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::IdNotFound`] if the given ID is not found in this [`Database`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn apply_flag(&mut self, flag: impl Into<FlagNote>) -> Result<bool> {
+        // crate::profile_guard!("apply_flag", "db::file::Database");
+
+        let flag = flag.into();
+        if let Some(idx) = self.notes.iter().position(|n| n.id() == *flag.id()) {
+            let before = self.notes[idx].clone();
+            if !self.notes[idx].flag(flag) {
+                return Ok(false);
+            }
+            self.notes[idx].clear_flags();
+            self.mark_modified();
+            let after = self.notes[idx].clone();
+            #[cfg(feature = "trace")] {
+                tracing::trace!(?before, ?after, "note flagged");
+            }
+            // Flags never change the tags of a note.
+            Self::send_msg(&self.channel, DatabaseMessage::NoteUpdated { before, after, new_tags: Vec::new() });
+            Ok(true)
+        } else {
+            let error = DatabaseError::IdNotFound(*flag.id());
+            Self::send_error(&self.channel, || error.to_string());
+            Err(error.into())
+        }
+    }
+
+    /// Archives the [`Note`] with the given ID, see [`Database::apply_flag`]. Archived notes are kept (and
+    /// still found by [`Database::get`], [`Database::find`], and [`Database::query`]) but are left out of
+    /// [`Database::get_active`]. Returns whether the note was not already archived.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::IdNotFound`] if the given ID is not found in this [`Database`].
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn archive(&mut self, id: TinyId) -> Result<bool> {
+        // crate::profile_guard!("archive", "db::file::Database");
+
+        self.apply_flag(FlagNote::new(id, NoteFlag::Archived, true))
+    }
+
+    /// Restores an archived [`Note`], see [`Database::archive`]. Returns whether the note was archived.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::IdNotFound`] if the given ID is not found in this [`Database`].
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn unarchive(&mut self, id: TinyId) -> Result<bool> {
+        // crate::profile_guard!("unarchive", "db::file::Database");
+
+        self.apply_flag(FlagNote::new(id, NoteFlag::Archived, false))
+    }
+
+    /// Applies each of the given dtos in order, see [`Database::apply_dto`], handling failures according to
+    /// `policy`:
+    /// - [`UpdateFailurePolicy::AllOrNothing`] rolls back every change made by the batch on the first failure.
+    /// - [`UpdateFailurePolicy::AbortOnError`] stops at the first failure, keeping the changes made before it.
+    /// - [`UpdateFailurePolicy::Partial`] skips failing dtos and applies the rest.
+    ///
+    /// If `buffer_messages` is set, the [`DatabaseMessage`]s produced by the batch are held back and only sent
+    /// once the batch has finished, so observers never see an intermediate state. An
+    /// [`UpdateFailurePolicy::AllOrNothing`] batch is always buffered, and if it is rolled back (notes, tag
+    /// styles, and all) it sends nothing at all. Returns the responses of the dtos that were applied.
+    ///
+    /// ## Errors
+    /// - The first error encountered, unless `policy` is [`UpdateFailurePolicy::Partial`].
+    #[tracing::instrument(level = "trace", skip(self, dtos))]
+    pub fn apply_dtos<D: Into<NoteDto>>(
+        &mut self,
+        dtos: impl IntoIterator<Item = D>,
+        policy: UpdateFailurePolicy,
+        buffer_messages: bool,
+    ) -> Result<Vec<DtoResponse>> {
+        // crate::profile_guard!("apply_dtos", "db::file::Database");
+
+        // While buffering, the batch sends to a local channel and the real one is parked here.
+        // The local channel shares the sequence counter, so buffered messages keep the numbers assigned when
+        // each change was made. A batch that may be rolled back has to buffer, or its messages would describe
+        // changes that never happened.
+        let all_or_nothing = policy == UpdateFailurePolicy::AllOrNothing;
+        let buffer = self
+            .channel
+            .as_ref()
+            .filter(|_| buffer_messages || all_or_nothing)
+            .map(MessageChannel::buffer);
+        let parked = buffer.map(|buffer| std::mem::replace(&mut self.channel, Some(buffer)));
+        let snapshot = all_or_nothing.then(|| {
+            (
+                self.notes.clone(),
+                self.ids.clone(),
+                self.tag_styles.clone(),
+                self.modified.get(),
+            )
+        });
+
+        let mut responses = Vec::new();
+        let mut outcome = Ok(());
+        for dto in dtos {
+            match self.apply_dto(dto) {
+                Ok(response) => responses.push(response),
+                Err(error) if policy == UpdateFailurePolicy::Partial => {
+                    #[cfg(feature = "trace")] {
+                        tracing::trace!(?error, "skipping failed dto");
+                    }
+                }
+                Err(error) => {
+                    outcome = Err(error);
+                    break;
+                }
+            }
+        }
+
+        let rolled_back = outcome.is_err() && snapshot.is_some();
+        if rolled_back && let Some((notes, ids, tag_styles, modified)) = snapshot {
+            self.notes = notes;
+            self.ids = ids;
+            self.tag_styles = tag_styles;
+            self.modified.set(modified);
+        }
+
+        if let Some(parked) = parked {
+            let local = std::mem::replace(&mut self.channel, parked);
+            if !rolled_back && let Some(local) = local && let Some(channel) = &self.channel {
+                for msg in local.receiver().try_iter() {
+                    channel.forward(msg);
+                }
             }
         }
+
+        outcome.map(|()| responses)
     }
 
     /// Creates a new [`Note`] using the information from the [`CreateNote`] dto. The returned
     /// result contains the newly created [`Note`] upon success (for getting the `id`, for example).
+    /// The [`DatabaseMessage::NoteCreated`] that is sent lists which of the note's tags are new to
+    /// this [`Database`].
     ///
     /// ## Errors
     /// - [`DatabaseError::DuplicateId`] if the given ID is already contained in this [`Database`].
@@ -220,61 +599,67 @@ impl Database {
     pub fn apply_create(&mut self, create: impl Into<CreateNote>) -> Result<Note> {
         // crate::profile_guard!("apply_create", "db::file::Database");
 
-        let create: CreateNote = create.into();
+        let mut create: CreateNote = create.into();
+        create.tags = self.tag_normalizer.normalize_all(create.tags);
         let note = Note::create_for(self, create.clone());
         if !self.ids.insert(note.id()) {
             #[cfg(feature = "trace")] {
                 tracing::error!(?create, ?note, "duplicate id created for note");
             }
             let error = DatabaseError::DuplicateId(note.id());
-            Self::send_error(&self.sender, || error.to_string());
+            Self::send_error(&self.channel, || error.to_string());
             return Err(error.into());
         }
-        Self::send_msg(&self.sender, DatabaseMessage::NoteCreated { dto: create, created: note.clone() });
+        let new_tags = self.introduced_tags(None, &note);
+        Self::send_msg(&self.channel, DatabaseMessage::NoteCreated { dto: create, created: note.clone(), new_tags });
         self.notes.push(note.clone());
+        self.mark_modified();
         Ok(note)
     }
 
     /// Updates an existing [`Note`] using the information from the [`UpdateNote`] dto. The returned
-    /// result contains `true` if the [`Note`] was found and changed, `false` if it was found but there
-    /// were no changes detected, or an error if the [`Note`] could not be found, or another problem
-    /// was encountered..
+    /// result contains the [`Note`] as it is after the update along with the [`ChangedFields`], which
+    /// are empty if there were no changes detected. The [`DatabaseMessage::NoteUpdated`] that is sent
+    /// lists which of the note's tags are new to this [`Database`].
     ///
     /// ## Errors
     /// - [`DatabaseError::IdNotFound`] if the given ID is not found in this [`Database`].
     #[tracing::instrument(level = "trace", skip_all)]
-    pub fn apply_update(&mut self, update: impl Into<UpdateNote>) -> Result<bool> {
+    pub fn apply_update(&mut self, update: impl Into<UpdateNote>) -> Result<(Note, ChangedFields)> {
         // crate::profile_guard!("apply_update", "db::file::Database");
 
-        let update = update.into();
+        let mut update: UpdateNote = update.into();
+        update.tags = update.tags.map(|tags| self.tag_normalizer.normalize_all(tags));
 
         if let Some(idx) = self.notes.iter().position(|n| n.id() == update.id()) {
             let before = self.notes[idx].clone();
             if !self.notes[idx].update(update.clone()) {
-                return Ok(false);
+                return Ok((before, ChangedFields::none()));
             }
             self.notes[idx].clear_flags();
+            self.mark_modified();
             let after = self.notes[idx].clone();
+            let changed = ChangedFields::between(&before, &after);
             #[cfg(feature = "trace")] {
                 tracing::trace!(?before, ?after, "note updated");
             }
-            Self::send_msg(&self.sender, DatabaseMessage::NoteUpdated { before, after });
-            Ok(true)
+            let new_tags = self.introduced_tags(Some(&before), &after);
+            Self::send_msg(&self.channel, DatabaseMessage::NoteUpdated { before, after: after.clone(), new_tags });
+            Ok((after, changed))
         } else {
             let error = DatabaseError::IdNotFound(*update.id());
-            Self::send_error(&self.sender, || error.to_string());
+            Self::send_error(&self.channel, || error.to_string());
             Err(error.into())
         }
     }
 
-    /// Deletes an existing [`Note`] using the information from the [`DeleteNote`] dto.
-    ///
-    /// TODO: This should probably be a unit return instead of bool.
+    /// Deletes an existing [`Note`] using the information from the [`DeleteNote`] dto, returning the
+    /// removed [`Note`].
     ///
     /// ## Errors
     /// - [`DatabaseError::IdNotFound`] if the given ID is not found in this [`Database`].
     #[tracing::instrument(level = "trace", skip_all)]
-    pub fn apply_delete(&mut self, delete: impl Into<DeleteNote>) -> Result<bool> {
+    pub fn apply_delete(&mut self, delete: impl Into<DeleteNote>) -> Result<Note> {
         // crate::profile_guard!("apply_delete", "db::file::Database");
 
         let id = *delete.into().id();
@@ -283,21 +668,51 @@ impl Database {
             Some(index) => {
                 let removed = self.notes.remove(index);
                 self.ids.remove(&id);
+                self.mark_modified();
                 #[cfg(feature = "trace")] {
                     tracing::trace!(?removed, "note deleted");
                 }
-                Self::send_msg(&self.sender, DatabaseMessage::NoteDeleted { deleted: removed});
+                Self::send_msg(&self.channel, DatabaseMessage::NoteDeleted { deleted: removed.clone() });
 
-                Ok(true)
+                Ok(removed)
             }
             None => {
                 let error = DatabaseError::IdNotFound(id);
-                Self::send_error(&self.sender, || error.to_string());
+                Self::send_error(&self.channel, || error.to_string());
                 Err(error.into())
             },
         }
     }
 
+    /// Deletes every [`Note`] matching `pred`, sending a [`DatabaseMessage::NoteDeleted`] for each one.
+    /// The deleted notes are returned (in their original order) so that the caller can offer an undo.
+    ///
+    /// ## Errors
+    /// - Currently infallible, the `Result` is kept for parity with [`Database::apply_delete`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn delete_where(&mut self, pred: impl Fn(&Note) -> bool) -> Result<Vec<Note>> {
+        // crate::profile_guard!("delete_where", "db::file::Database");
+
+        // The notes are moved out and partitioned so the list is never mutated while it is being iterated.
+        let (deleted, kept): (Vec<Note>, Vec<Note>) =
+            std::mem::take(&mut self.notes).into_iter().partition(|note| pred(note));
+        self.notes = kept;
+
+        for note in &deleted {
+            self.ids.remove(&note.id());
+            Self::send_msg(&self.channel, DatabaseMessage::NoteDeleted { deleted: note.clone() });
+        }
+        if !deleted.is_empty() {
+            self.mark_modified();
+        }
+
+        #[cfg(feature = "trace")] {
+            tracing::trace!(count = deleted.len(), "notes deleted");
+        }
+
+        Ok(deleted)
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     pub fn ensure_sync<'n>(&mut self, input: impl Into<OneOrMore<&'n mut Note>>) {
         // crate::profile_guard!("ensure_sync", "db::file::Database");
@@ -349,6 +764,39 @@ impl Database {
             .ok_or_else(|| DatabaseError::IdNotFound(id).into())
     }
 
+    /// Gets every [`Note`] whose ID is in `ids` using a single pass over the notes. The notes are returned in
+    /// the order they are stored (not the order of `ids`), each at most once, and IDs that are not found are
+    /// silently skipped, the same as [`Database::find`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    #[must_use]
+    pub fn get_many<'a>(&self, ids: impl IntoIterator<Item = &'a TinyId>) -> Vec<&Note> {
+        // crate::profile_guard!("get_many", "db::file::Database");
+
+        let wanted = ids.into_iter().collect::<HashSet<_>>();
+        self.notes
+            .iter()
+            .filter(|n| wanted.contains(&n.id()))
+            .collect()
+    }
+
+    /// Same as [`Database::get_many`], but fails if any of the given IDs is not in this [`Database`].
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::IdNotFound`] for the first of the given IDs that is not found in this [`Database`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn get_many_strict<'a>(&self, ids: impl IntoIterator<Item = &'a TinyId>) -> Result<Vec<&Note>> {
+        // crate::profile_guard!("get_many_strict", "db::file::Database");
+
+        let mut wanted = HashSet::new();
+        for id in ids {
+            if !self.ids.contains(id) {
+                return Err(DatabaseError::IdNotFound(*id).into());
+            }
+            wanted.insert(id);
+        }
+        Ok(self.get_many(wanted))
+    }
+
     /// Uses the given function `f` to modify the [`Note`] that has the given [`TinyId`].
     ///
     /// ## Errors
@@ -366,17 +814,20 @@ impl Database {
                     // TODO: Delete this note
                     let deleted = self.notes.remove(idx);
                     self.ids.remove(&id);
+                    self.mark_modified();
                     #[cfg(feature = "trace")] {
                         tracing::trace!(?deleted, "note deleted by get_and_modify");
                     }
-                    Self::send_msg(&self.sender, DatabaseMessage::NoteDeleted { deleted });
+                    Self::send_msg(&self.channel, DatabaseMessage::NoteDeleted { deleted });
                 } else if self.notes[idx].dirty() {
                     self.notes[idx].clear_flags();
+                    self.mark_modified();
                     let updated = self.notes[idx].clone();
                     #[cfg(feature = "trace")] {
                         tracing::trace!(?original, ?updated, "note updated by get_and_modify");
                     }
-                    Self::send_msg(&self.sender, DatabaseMessage::NoteUpdated { before: original, after: updated });
+                    let new_tags = self.introduced_tags(Some(&original), &updated);
+                    Self::send_msg(&self.channel, DatabaseMessage::NoteUpdated { before: original, after: updated, new_tags });
                 }
                 
                 Ok(())
@@ -385,7 +836,7 @@ impl Database {
                 #[cfg(feature = "trace")] {
                     tracing::error!(?error, %id, "note with id not found");
                 }
-                Self::send_error(&self.sender, || error.to_string());
+                Self::send_error(&self.channel, || error.to_string());
                 Err(error.into())
             }
     }
@@ -399,6 +850,131 @@ impl Database {
         &self.notes
     }
 
+    /// Returns all [`Note`]s in this [`Database`] that are not archived, see [`Database::archive`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    #[must_use]
+    pub fn get_active(&self) -> Vec<&Note> {
+        // crate::profile_guard!("get_active", "db::file::Database");
+
+        self.notes.iter().filter(|n| !n.is_archived()).collect()
+    }
+
+    /// Checks every invariant of this [`Database`] and reports all problems found, unlike the internal
+    /// validation done while loading which stops at the first. Nothing is repaired, see
+    /// [`Note::validate_and_fix`] for the per-note repairs that are reported.
+    ///
+    /// The following are checked:
+    /// - The id-list and the note-list are the same size, and contain the same ids.
+    /// - Every note has a valid id, and no id is used by more than one note.
+    /// - Every note passes [`Note::validate_and_fix`] without needing any repairs.
+    /// - Every tag with an explicit [`TagStyle`] is used by at least one note.
+    #[tracing::instrument(level = "trace", skip(self), fields(issues))]
+    #[must_use]
+    pub fn verify_integrity(&self) -> IntegrityReport {
+        // crate::profile_guard!("verify_integrity", "db::file::Database");
+
+        let mut issues = Vec::new();
+        if self.ids.len() != self.notes.len() {
+            issues.push(IntegrityIssue::IdCountMismatch {
+                ids: self.ids.len(),
+                notes: self.notes.len(),
+            });
+        }
+
+        let mut seen = HashSet::with_capacity(self.notes.len());
+        for (index, note) in self.notes.iter().enumerate() {
+            let id = note.id();
+            if !id.is_valid() {
+                issues.push(IntegrityIssue::InvalidNoteId { index });
+            } else if !seen.insert(id) {
+                issues.push(IntegrityIssue::DuplicateNoteId(id));
+            } else if !self.ids.contains(&id) {
+                issues.push(IntegrityIssue::UnregisteredId(id));
+            }
+
+            let fixes = note.clone().validate_and_fix();
+            if !fixes.is_empty() {
+                issues.push(IntegrityIssue::NoteNeedsRepair { index, fixes });
+            }
+        }
+
+        let mut orphaned = self.ids.difference(&seen).copied().collect::<Vec<_>>();
+        orphaned.sort_by_cached_key(TinyId::to_string);
+        issues.extend(orphaned.into_iter().map(IntegrityIssue::OrphanedId));
+
+        let mut unused_styles = self
+            .tag_styles
+            .keys()
+            .filter(|tag| !self.notes.iter().any(|note| note.tags().contains(tag)))
+            .cloned()
+            .collect::<Vec<_>>();
+        unused_styles.sort_unstable();
+        issues.extend(unused_styles.into_iter().map(IntegrityIssue::UnusedTagStyle));
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("issues", &issues.len());
+        }
+
+        IntegrityReport { issues }
+    }
+
+    /// Summarizes the contents of this [`Database`]. Reminders are pending if they have not fired yet.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn stats(&self) -> DatabaseStats {
+        // crate::profile_guard!("stats", "db::file::Database");
+
+        let reminders = self.notes.iter().flat_map(Note::reminders);
+        DatabaseStats {
+            notes: self.notes.len(),
+            pinned: self.notes.iter().filter(|n| n.is_pinned()).count(),
+            archived: self.notes.iter().filter(|n| n.is_archived()).count(),
+            tags: self.get_all_tags().len(),
+            reminders: reminders.clone().count(),
+            pending_reminders: reminders.filter(|r| !r.has_fired()).count(),
+            words: self.notes.iter().map(Note::word_count).sum(),
+            oldest_created: self.notes.iter().map(|n| *n.created()).min(),
+            last_updated: self.notes.iter().map(|n| *n.updated()).max(),
+        }
+    }
+
+    /// Returns every [`Reminder`] (along with the [`Note`] it belongs to) whose current occurrence is at or
+    /// before `as_of`, longest overdue first. One-off reminders are only due if they have not fired yet,
+    /// recurring reminders are due again once their next occurrence after they last fired has passed, see
+    /// [`Reminder::current_occurrence`].
+    #[tracing::instrument(level = "trace", skip(self), fields(len))]
+    #[must_use]
+    pub fn due_reminders(&self, as_of: time::OffsetDateTime) -> Vec<(&Note, &Reminder)> {
+        // crate::profile_guard!("due_reminders", "db::file::Database");
+
+        let mut due = self
+            .notes
+            .iter()
+            .flat_map(|note| note.reminders().iter().map(move |reminder| (note, reminder)))
+            .filter(|(_, reminder)| reminder.is_due_at(as_of))
+            .collect::<Vec<_>>();
+        due.sort_by_key(|(_, reminder)| reminder.current_occurrence());
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &due.len());
+        }
+
+        due
+    }
+
+    /// Returns the [`Note`]s in the given index range, for rendering a window of a (virtualized) list.
+    /// The range is clamped to the valid bounds, so a range that is partially out of bounds returns the
+    /// part that is in bounds, and one that is fully out of bounds (or reversed) returns an empty slice.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn get_range(&self, range: Range<usize>) -> &[Note] {
+        // crate::profile_guard!("get_range", "db::file::Database");
+
+        let end = range.end.min(self.notes.len());
+        let start = range.start.min(end);
+        &self.notes[start..end]
+    }
+
     /// TODO: This seems like it's going to be an expensive operation, should we consider keeping a
     ///       tag-list similar to the ID-list we are already storing?
     #[tracing::instrument(level = "trace", skip_all)]
@@ -426,6 +1002,21 @@ impl Database {
         tags.into_iter().collect()
     }
 
+    /// Iterates over every unique tag in this [`Database`] along with the number of [`Note`]s that have it,
+    /// sorted by tag. The counts are gathered into a map of borrowed tags when this is called, so unlike
+    /// [`Database::get_all_tags_and_counts`] no tags are cloned and no intermediate `Vec` is built.
+    #[tracing::instrument(level = "trace", skip_all)]
+    #[must_use]
+    pub fn iter_tags(&self) -> impl Iterator<Item = (&str, usize)> {
+        // crate::profile_guard!("iter_tags", "db::file::Database");
+
+        let mut counts = std::collections::BTreeMap::new();
+        for tag in self.notes.iter().flat_map(Note::tags) {
+            *counts.entry(tag.as_str()).or_insert(0usize) += 1;
+        }
+        counts.into_iter()
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     #[must_use]
     pub fn get_all_tags_and_counts(&self) -> Vec<(String, usize)> {
@@ -440,41 +1031,490 @@ impl Database {
         map.into_iter().map(|(s, i)| (s.clone(), i)).collect()
     }
 
-    /// Returns a [`Vec`] containing all [`Note`]s in this [`Database`] that match
-    /// the given predicate `pred`.
-    #[tracing::instrument(level = "trace", skip_all, fields(len))]
-    #[must_use]
-    pub fn find(&self, pred: impl Fn(&&Note) -> bool) -> Vec<&Note> {
-        // crate::profile_guard!("find", "db::file::Database");
-        
-        let results = self.notes.iter().filter(pred).collect::<Vec<_>>();
-        
-        #[cfg(feature = "trace")] {
-            tracing::Span::current().record("len", &results.len());
-        }
+    /// Reserves capacity for at least `additional` more [`Note`]s.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn reserve(&mut self, additional: usize) {
+        // crate::profile_guard!("reserve", "db::file::Database");
 
-        results
+        self.notes.reserve(additional);
+        self.ids.reserve(additional);
     }
 
-    /// Performs a full text search using `query` against all [`Note`]s in this [`Database`].
-    #[tracing::instrument(level = "trace", skip(self), fields(len))]
+    /// The number of [`Note`]s this [`Database`] can hold without reallocating.
+    #[tracing::instrument(level = "trace", skip(self))]
     #[must_use]
-    pub fn text_search(&self, query: &str) -> Vec<&Note> {
-        // crate::profile_guard!("text_search", "db::file::Database");
-        
-        let results = self.notes
-            .iter()
-            .filter(|n| n.full_text_search(query))
-            .collect::<Vec<_>>();
+    pub fn capacity(&self) -> usize {
+        self.notes.capacity().min(self.ids.capacity())
+    }
 
-        #[cfg(feature = "trace")] {
-            tracing::Span::current().record("len", &results.len());
+    /// Creates a [`Note`] for each of the given dtos, see [`Database::apply_create`]. Stops at
+    /// the first error, keeping any notes created before it.
+    ///
+    /// ## Errors
+    /// - See [`Database::apply_create`].
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn create_many<C: Into<CreateNote>>(&mut self, dtos: impl IntoIterator<Item = C>) -> Result<Vec<Note>> {
+        // crate::profile_guard!("create_many", "db::file::Database");
+
+        let dtos = dtos.into_iter();
+        let (lower, _) = dtos.size_hint();
+        self.reserve(lower);
+        let mut created = Vec::with_capacity(lower);
+        for dto in dtos {
+            created.push(self.apply_create(dto)?);
         }
-
-        results
+        Ok(created)
     }
 
-    /// The number of [`Note`]s in this [`Database`].
+    /// Imports the given notes into this [`Database`]. Each note is repaired with [`Note::validate_and_fix`],
+    /// then staged and only added once all of them have been validated, so if the operation fails or is
+    /// cancelled through `cancel` the [`Database`] is left unchanged. Returns the number of notes imported.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::Cancelled`] if `cancel` is set before the import completes.
+    /// - [`DatabaseError::DuplicateId`] if any imported note has an ID already in use (or used twice by the input).
+    /// - [`DatabaseError::InvalidId`] if any imported note has an invalid ID.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn import(&mut self, notes: impl IntoIterator<Item = Note>, cancel: Option<&AtomicBool>) -> Result<usize> {
+        // crate::profile_guard!("import", "db::file::Database");
+
+        let notes = notes.into_iter();
+        let (lower, _) = notes.size_hint();
+        let mut staged = Vec::with_capacity(lower);
+        let mut staged_ids = HashSet::with_capacity(lower);
+        for mut note in notes {
+            Self::check_cancelled(cancel)?;
+            if !note.id().is_valid() {
+                return Err(DatabaseError::InvalidId.into());
+            }
+            if self.ids.contains(&note.id()) || !staged_ids.insert(note.id()) {
+                return Err(DatabaseError::DuplicateId(note.id()).into());
+            }
+            note.validate_and_fix();
+            note.clear_flags();
+            staged.push(note);
+        }
+        Self::check_cancelled(cancel)?;
+
+        let count = staged.len();
+        self.reserve(count);
+        self.ids.extend(staged_ids);
+        self.notes.extend(staged);
+        if count > 0 {
+            self.mark_modified();
+        }
+        Ok(count)
+    }
+
+    /// Replaces every [`Note`] in this [`Database`] with the given notes. The replacement list is fully
+    /// built and validated before anything is swapped in, so if the operation fails or is cancelled through
+    /// `cancel` the [`Database`] is left unchanged.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::Cancelled`] if `cancel` is set before the replacement completes.
+    /// - [`DatabaseError::DuplicateId`] if the given notes contain a duplicate ID.
+    /// - [`DatabaseError::InvalidId`] if any given note has an invalid ID.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub fn replace_all(&mut self, notes: impl IntoIterator<Item = Note>, cancel: Option<&AtomicBool>) -> Result {
+        // crate::profile_guard!("replace_all", "db::file::Database");
+
+        let notes = notes.into_iter();
+        let (lower, _) = notes.size_hint();
+        let mut new_notes = Vec::with_capacity(lower);
+        let mut new_ids = HashSet::with_capacity(lower);
+        for note in notes {
+            Self::check_cancelled(cancel)?;
+            if !note.id().is_valid() {
+                return Err(DatabaseError::InvalidId.into());
+            }
+            if !new_ids.insert(note.id()) {
+                return Err(DatabaseError::DuplicateId(note.id()).into());
+            }
+            new_notes.push(note);
+        }
+        Self::check_cancelled(cancel)?;
+
+        self.notes = new_notes;
+        self.ids = new_ids;
+        self.mark_modified();
+        Ok(())
+    }
+
+    /// Renames the tag `old` to `new` on every [`Note`] that has it, see [`Note::rename_tag`]. Notes that
+    /// already had `new` end up with a single `new` tag and are counted as merged in the returned
+    /// [`TagRenameReport`], all other changed notes are counted as renamed. A [`DatabaseMessage::NoteUpdated`]
+    /// is sent for each changed note, and any [`TagStyle`] set for `old` is moved to `new` (unless `new`
+    /// already has one).
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::PolicyFailure`] if `new` is empty.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> Result<TagRenameReport> {
+        // crate::profile_guard!("rename_tag", "db::file::Database");
+
+        if new.is_empty() {
+            let error = DatabaseError::PolicyFailure("cannot rename a tag to an empty string".to_string());
+            Self::send_error(&self.channel, || error.to_string());
+            return Err(error.into());
+        }
+
+        let mut report = TagRenameReport::default();
+        for index in 0..self.notes.len() {
+            if !self.notes[index].tag_matches(old) {
+                continue;
+            }
+            let before = self.notes[index].clone();
+            if self.notes[index].rename_tag(old, new) {
+                self.notes[index].clear_flags();
+                self.mark_modified();
+                if before.tag_matches(new) {
+                    report.merged += 1;
+                } else {
+                    report.renamed += 1;
+                }
+                let new_tags = self.introduced_tags(Some(&before), &self.notes[index]);
+                Self::send_msg(&self.channel, DatabaseMessage::NoteUpdated { before, after: self.notes[index].clone(), new_tags });
+            }
+        }
+
+        if let Some(style) = self.tag_styles.remove(old) {
+            self.tag_styles.entry(new.to_string()).or_insert(style);
+            self.mark_modified();
+        }
+
+        #[cfg(feature = "trace")] {
+            tracing::trace!(old, new, renamed = report.renamed, merged = report.merged, "tag renamed");
+        }
+
+        Ok(report)
+    }
+
+    /// Merges the notes of `other` into this [`Database`]. Notes with an ID that is not yet in use are added,
+    /// notes whose ID is already in use are resolved according to `on_conflict`. Any [`TagStyle`] from `other`
+    /// is kept unless this [`Database`] already has a style for that tag. A [`DatabaseMessage::NoteCreated`]
+    /// is sent for each added (or reassigned) note, and a [`DatabaseMessage::NoteUpdated`] for each overwritten note.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::InvalidId`] if any note in `other` has an invalid ID, in which case nothing is merged.
+    #[tracing::instrument(level = "trace", skip_all, fields(on_conflict = ?on_conflict))]
+    pub fn merge(&mut self, other: Self, on_conflict: MergeStrategy) -> Result<MergeReport> {
+        // crate::profile_guard!("merge", "db::file::Database");
+
+        if other.notes.iter().any(|note| !note.id().is_valid()) {
+            let error = DatabaseError::InvalidId;
+            Self::send_error(&self.channel, || error.to_string());
+            return Err(error.into());
+        }
+
+        // Reassigned IDs must not collide with incoming notes that have not been merged yet either.
+        let incoming_ids = other.ids;
+        let mut report = MergeReport::default();
+        self.reserve(other.notes.len());
+        for incoming in other.notes {
+            if !self.ids.contains(&incoming.id()) {
+                self.add_merged_note(incoming);
+                report.added += 1;
+                continue;
+            }
+
+            let index = self
+                .notes
+                .iter()
+                .position(|note| note.id() == incoming.id())
+                .expect("id set and note list are out of sync");
+            let replace = match on_conflict {
+                MergeStrategy::Skip => false,
+                MergeStrategy::Overwrite => true,
+                MergeStrategy::Newer => incoming.updated() > self.notes[index].updated(),
+                MergeStrategy::Reassign => {
+                    let mut id = self.create_id();
+                    while incoming_ids.contains(&id) {
+                        id = self.create_id();
+                    }
+                    let mut reassigned = incoming;
+                    reassigned.reassign_id(id);
+                    self.add_merged_note(reassigned);
+                    report.reassigned += 1;
+                    continue;
+                }
+            };
+            if replace {
+                let before = std::mem::replace(&mut self.notes[index], incoming);
+                self.mark_modified();
+                let new_tags = self.introduced_tags(Some(&before), &self.notes[index]);
+                Self::send_msg(&self.channel, DatabaseMessage::NoteUpdated { before, after: self.notes[index].clone(), new_tags });
+                report.overwritten += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        for (tag, style) in other.tag_styles {
+            if let std::collections::hash_map::Entry::Vacant(entry) = self.tag_styles.entry(tag) {
+                entry.insert(style);
+                self.mark_modified();
+            }
+        }
+
+        #[cfg(feature = "trace")] {
+            tracing::trace!(?report, "databases merged");
+        }
+
+        Ok(report)
+    }
+
+    /// Compares this [`Database`] to `other`, matching notes by ID. Notes only in `other` are `added`, notes only
+    /// in `self` are `removed`, and notes in both whose title, content, or tags differ are `changed` (as
+    /// `(self, other)` pairs). Nothing is cloned, the [`DatabaseDiff`] borrows from both databases.
+    #[tracing::instrument(level = "trace", skip_all)]
+    #[must_use]
+    pub fn diff<'a>(&'a self, other: &'a Self) -> DatabaseDiff<'a> {
+        // crate::profile_guard!("diff", "db::file::Database");
+
+        let theirs: HashMap<TinyId, &Note> = other.notes.iter().map(|note| (note.id(), note)).collect();
+        let mut diff = DatabaseDiff::default();
+        for mine in &self.notes {
+            match theirs.get(&mine.id()) {
+                None => diff.removed.push(mine),
+                Some(&their) => {
+                    if mine.title() != their.title()
+                        || mine.content() != their.content()
+                        || mine.tags() != their.tags()
+                    {
+                        diff.changed.push((mine, their));
+                    }
+                }
+            }
+        }
+        diff.added = other.notes.iter().filter(|note| !self.ids.contains(&note.id())).collect();
+
+        diff
+    }
+
+    /// Sorts the notes of this [`Database`] into canonical order, see [`Database::sort_canonical`]. Notes are
+    /// otherwise kept in the order they were added, so two databases holding the same notes can save or export
+    /// to different bytes; canonicalizing first makes the output reproducible, i.e. for keeping notes in git.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn canonicalize(&mut self) {
+        // crate::profile_guard!("canonicalize", "db::file::Database");
+
+        let sorted = self
+            .notes
+            .windows(2)
+            .all(|pair| Self::canonical_key(&pair[0]) <= Self::canonical_key(&pair[1]));
+        if !sorted {
+            Self::sort_canonical(&mut self.notes);
+            self.mark_modified();
+        }
+    }
+
+    /// Sorts `notes` into the canonical order used by [`Database::canonicalize`] and the exporters: oldest
+    /// created first, with ties broken by ID. The order only depends on the notes themselves, never on the
+    /// order they were added in.
+    pub fn sort_canonical(notes: &mut [Note]) {
+        crate::flame_guard!("db", "file", "Database", "sort_canonical");
+        notes.sort_by_cached_key(Self::canonical_key);
+    }
+
+    /// Gets the explicitly set [`TagStyle`] for the given tag, if there is one.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn tag_style(&self, tag: &str) -> Option<&TagStyle> {
+        // crate::profile_guard!("tag_style", "db::file::Database");
+
+        self.tag_styles.get(tag)
+    }
+
+    /// Gets the [`TagStyle`] for the given tag, falling back to [`TagStyle::default_for`]
+    /// if no style has been explicitly set.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn tag_style_or_default(&self, tag: &str) -> TagStyle {
+        // crate::profile_guard!("tag_style_or_default", "db::file::Database");
+
+        self.tag_style(tag)
+            .cloned()
+            .unwrap_or_else(|| TagStyle::default_for(tag))
+    }
+
+    /// Sets the [`TagStyle`] for the given tag, returning the previous style if there was one.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_tag_style(&mut self, tag: impl Into<String> + std::fmt::Debug, style: TagStyle) -> Option<TagStyle> {
+        // crate::profile_guard!("set_tag_style", "db::file::Database");
+
+        self.mark_modified();
+        self.tag_styles.insert(tag.into(), style)
+    }
+
+    /// Removes the explicit [`TagStyle`] for the given tag, returning it if there was one.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn remove_tag_style(&mut self, tag: &str) -> Option<TagStyle> {
+        // crate::profile_guard!("remove_tag_style", "db::file::Database");
+
+        let removed = self.tag_styles.remove(tag);
+        if removed.is_some() {
+            self.mark_modified();
+        }
+        removed
+    }
+
+    /// Gets the [`TagNormalizer`] applied to the tags of created and updated notes.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn tag_normalizer(&self) -> TagNormalizer {
+        // crate::profile_guard!("tag_normalizer", "db::file::Database");
+
+        self.tag_normalizer
+    }
+
+    /// Sets the [`TagNormalizer`] applied to the tags of every [`CreateNote`] and [`UpdateNote`] from now on.
+    /// Tags already in the database are not changed. This is runtime configuration and is not saved with the
+    /// database, frontends should set it from their own settings after loading.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_tag_normalizer(&mut self, normalizer: TagNormalizer) {
+        // crate::profile_guard!("set_tag_normalizer", "db::file::Database");
+
+        self.tag_normalizer = normalizer;
+    }
+
+    /// Gets a single page of the [`Note`]s matching `filter`, sorted by `order`. Filtering happens before
+    /// ordering and pagination, so `total_matched` and `total_pages` describe the filtered results. `page` is
+    /// zero-based, and a page past the end returns no notes. A `per_page` of zero is treated as one.
+    #[tracing::instrument(level = "trace", skip(self, filter, order))]
+    #[must_use]
+    pub fn query(&self, filter: &Filter, order: Ordering, page: usize, per_page: usize) -> QueryPage<'_> {
+        // crate::profile_guard!("query", "db::file::Database");
+
+        let per_page = per_page.max(1);
+        let mut matched = self.notes.iter().filter(filter.predicate()).collect::<Vec<_>>();
+        let comparison = order.comparison();
+        matched.sort_by(|a, b| comparison(a, b));
+
+        let total_matched = matched.len();
+        let start = page.saturating_mul(per_page).min(total_matched);
+        let end = start.saturating_add(per_page).min(total_matched);
+        QueryPage {
+            notes: matched.drain(start..end).collect(),
+            page,
+            per_page,
+            total_matched,
+            total_pages: (total_matched + per_page - 1) / per_page,
+        }
+    }
+
+    /// Gets up to `count` of the [`Note`]s matching `filter`, sorted by `order`, as owned clones. Only the
+    /// selected notes are cloned, which makes this suitable for sending results across a thread boundary.
+    #[tracing::instrument(level = "trace", skip(self, filter, order), fields(len))]
+    #[must_use]
+    pub fn query_owned(&self, filter: &Filter, order: Ordering, count: Count) -> Vec<Note> {
+        // crate::profile_guard!("query_owned", "db::file::Database");
+
+        let mut matched = self.notes.iter().filter(filter.predicate()).collect::<Vec<_>>();
+        let comparison = order.comparison();
+        matched.sort_by(|a, b| comparison(a, b));
+        let results = count.apply(matched.into_iter()).cloned().collect::<Vec<_>>();
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &results.len());
+        }
+
+        results
+    }
+
+    /// Returns a [`Vec`] containing all [`Note`]s in this [`Database`] that match
+    /// the given predicate `pred`.
+    #[tracing::instrument(level = "trace", skip_all, fields(len))]
+    #[must_use]
+    pub fn find(&self, pred: impl Fn(&&Note) -> bool) -> Vec<&Note> {
+        // crate::profile_guard!("find", "db::file::Database");
+        
+        let results = self.notes.iter().filter(pred).collect::<Vec<_>>();
+        
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &results.len());
+        }
+
+        results
+    }
+
+    /// Performs a full text search using `query` against all [`Note`]s in this [`Database`].
+    #[tracing::instrument(level = "trace", skip(self), fields(len))]
+    #[must_use]
+    pub fn text_search(&self, query: &str) -> Vec<&Note> {
+        // crate::profile_guard!("text_search", "db::file::Database");
+        
+        let results = self.notes
+            .iter()
+            .filter(|n| n.full_text_search(query))
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &results.len());
+        }
+
+        results
+    }
+
+    /// Performs a case-insensitive full text search using `query` against all [`Note`]s in this [`Database`].
+    /// The query is lowercased once, and each note's lowercased content is cached (see
+    /// [`Note::content_lowercase`]), so repeated searches don't re-lowercase large notes.
+    #[tracing::instrument(level = "trace", skip(self), fields(len))]
+    #[must_use]
+    pub fn text_search_ignore_case(&self, query: &str) -> Vec<&Note> {
+        // crate::profile_guard!("text_search_ignore_case", "db::file::Database");
+
+        let query = query.to_lowercase();
+        let results = self
+            .notes
+            .iter()
+            .filter(|n| n.full_text_search_lowercase(&query))
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &results.len());
+        }
+
+        results
+    }
+
+    /// Performs a ranked full text search using `query` against all [`Note`]s in this [`Database`].
+    ///
+    /// The query is split on whitespace and each term is scored by how often it occurs (case-insensitively)
+    /// in the title (weighted [`TITLE_WEIGHT`](Self::TITLE_WEIGHT)), tags ([`TAG_WEIGHT`](Self::TAG_WEIGHT)) and
+    /// content ([`CONTENT_WEIGHT`](Self::CONTENT_WEIGHT)). The per-term scores are summed and multiplied by the
+    /// number of distinct terms matched, so notes matching more of the query rank higher. Notes that match
+    /// nothing are excluded, and the results are sorted by descending score.
+    #[tracing::instrument(level = "trace", skip(self), fields(len))]
+    #[must_use]
+    pub fn text_search_ranked(&self, query: &str) -> Vec<(&Note, f32)> {
+        // crate::profile_guard!("text_search_ranked", "db::file::Database");
+
+        let terms = query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results = self
+            .notes
+            .iter()
+            .filter_map(|note| {
+                let score = Self::rank_note(note, &terms);
+                (score > 0.0).then_some((note, score))
+            })
+            .collect::<Vec<_>>();
+        results.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+        #[cfg(feature = "trace")] {
+            tracing::Span::current().record("len", &results.len());
+        }
+
+        results
+    }
+
+    /// The number of [`Note`]s in this [`Database`].
     #[tracing::instrument(level = "trace", skip(self), fields(len))]
     #[must_use]
     pub fn len(&self) -> usize {
@@ -496,6 +1536,40 @@ impl Database {
         self.notes.is_empty()
     }
 
+    /// Creates the standard "getting started" [`Note`] with a few usage tips, so that frontends have
+    /// something to show new users instead of an empty database. The note is created like any other, so it
+    /// can be edited or deleted freely.
+    ///
+    /// ## Errors
+    /// - Any error from [`Database::apply_create`].
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn seed_welcome(&mut self) -> Result<Note> {
+        // crate::profile_guard!("seed_welcome", "db::file::Database");
+
+        self.apply_create(CreateNote::new(
+            Some(WELCOME_TITLE.to_string()),
+            Some(WELCOME_CONTENT.to_string()),
+            vec!["welcome".to_string()],
+            Vec::new(),
+        ))
+    }
+
+    /// Calls [`Database::seed_welcome`] only if this [`Database`] [is empty](`Database::is_empty`), which
+    /// is the first-run case for frontends. Returns the welcome [`Note`] if one was created.
+    ///
+    /// ## Errors
+    /// - Any error from [`Database::seed_welcome`].
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn seed_welcome_if_empty(&mut self) -> Result<Option<Note>> {
+        // crate::profile_guard!("seed_welcome_if_empty", "db::file::Database");
+
+        if self.is_empty() {
+            self.seed_welcome().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Checks whether the given `id` is currently being used in this [`Database`].
     #[tracing::instrument(level = "trace", skip(self), fields(result))]
     #[must_use]
@@ -539,11 +1613,12 @@ impl Database {
             #[cfg(feature = "trace")] {
                 tracing::error!(insertion = ?note, "duplicate ID attempted to be inserted into db");
             }
-            Self::send_error(&self.sender, || error.to_string());
+            Self::send_error(&self.channel, || error.to_string());
             return Err(error.into());
         }
         self.notes.push(note.clone());
         self.ids.insert(note.id());
+        self.mark_modified();
         Ok(())
     }
 
@@ -558,29 +1633,81 @@ impl Database {
         }
     }
 
-    /// Currently this is guaranteed to be `Some`
+    /// Gets a receiver for the [`DatabaseMessage`]s sent by this [`Database`], or `None` if messaging is disabled.
+    /// Every message is wrapped in a [`SequencedMessage`], whose `seq` is strictly increasing in the order the
+    /// changes were made. Re-enabling messaging (see [`Database::set_messaging`]) starts a new sequence.
     #[tracing::instrument(level = "trace", skip(self))]
     #[must_use] 
-    pub fn get_receiver(&self) -> Option<Receiver<DatabaseMessage>> {
-        Some(self.receiver.clone())
+    pub fn get_receiver(&self) -> Option<Receiver<SequencedMessage>> {
+        self.channel.as_ref().map(|channel| channel.receiver().clone())
+    }
+
+    /// Enables or disables [`DatabaseMessage`] sending. Disabling drops the channel, along with any
+    /// messages that have not been received, and disconnects any receivers that are still held.
+    /// Enabling creates a fresh channel if there is not one already.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn set_messaging(&mut self, enabled: bool) {
+        // crate::profile_guard!("set_messaging", "db::file::Database");
+
+        match (enabled, self.channel.is_some()) {
+            (true, false) => self.channel = Self::create_channel(),
+            (false, true) => self.channel = None,
+            _ => {}
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn messaging_enabled(&self) -> bool {
+        self.channel.is_some()
     }
 }
 
 /// Private / Crate Methods
 impl Database {
+    #[tracing::instrument(skip(self, path), fields(path = path.display().to_string().as_str()))]
+    fn save_with_method(&self, path: &Path, method: Method) -> Result {
+        match Persistence::save_to_file_atomic(self, path, method) {
+            Ok(_) => {
+                #[cfg(feature = "trace")] {
+                    tracing::trace!(path = %path.display().to_string(), %method, "database saved to path");
+                }
+                self.modified.set(false);
+                Self::send_msg(&self.channel, DatabaseMessage::DataSaved { path: path.display().to_string() });
+                Ok(())
+            },
+            Err(err) => {
+                #[cfg(feature = "trace")] {
+                    tracing::error!(error = ?err, "database save failed");
+                }
+
+                Self::send_error(&self.channel, || err.to_string());
+
+                Err(err)
+            },
+        }
+    }
+
     pub(crate) fn save_dev(&self) -> Result {
         crate::profile_guard!("save_dev", "db::file::Database");
         
+        self.save(Self::dev_path()?)
+    }
+
+    /// The path of the development database used by [`Database::load_dev`] and [`Database::save_dev`].
+    ///
+    /// ## Errors
+    /// - If `CARGO_MANIFEST_DIR` is not set.
+    pub(crate) fn dev_path() -> Result<PathBuf> {
         let project_dir = std::env::var("CARGO_MANIFEST_DIR")?;
-        let path = Path::new(&project_dir).join("data").join("dev.fdb");
-        self.save(path)
+        Ok(Path::new(&project_dir).join("data").join("dev.fdb"))
     }
 
     pub(crate) fn create_random() -> Self {
         crate::profile_guard!("create_random", "db::file::Database");
         
         let entries = fastrand::usize(500..=1000);
-        let mut notes = Vec::new();
+        let mut notes = Vec::with_capacity(entries);
         for i in 0..entries {
             let mut note = Note::create((
                 format!("Title {}", i),
@@ -598,37 +1725,116 @@ impl Database {
         Database::from_notes_vec(notes).expect("Failed to create random database!")
     }
 
-    #[tracing::instrument(skip(self, writer))]
-    pub(crate) fn save_dev_with(
-        &self,
-        filename: &str,
-        writer: impl FnOnce(std::io::BufWriter<std::fs::File>, &Self) -> Result,
-    ) -> Result {
-        use std::fs::File;
-        use std::io::Write;
-        // crate::profile_guard!("save_dev_with", "db::file::Database");
-        
-        let project_dir = std::env::var("CARGO_MANIFEST_DIR")?;
-        let path = Path::new(&project_dir).join("data").join(filename);
-        let mut file = File::create(path)?;
-        let mut buf_writer = std::io::BufWriter::new(file);
-        writer(buf_writer, self)?;
-        Ok(())
-    }
+    /// Scores a single [`Note`] against the given (already lowercased) `terms`, see [`Database::text_search_ranked`].
+    #[allow(clippy::cast_precision_loss)]
+    fn rank_note(note: &Note, terms: &[String]) -> f32 {
+        let title = note.title().to_lowercase();
+        let content = note.content_lowercase();
+        let tags = note.tags().iter().map(|t| t.to_lowercase()).collect::<Vec<_>>();
+
+        let mut total = 0.0;
+        let mut matched = 0usize;
+        for term in terms {
+            let score = title.matches(term.as_str()).count() as f32 * Self::TITLE_WEIGHT
+                + tags.iter().filter(|t| t.contains(term.as_str())).count() as f32 * Self::TAG_WEIGHT
+                + content.matches(term.as_str()).count() as f32 * Self::CONTENT_WEIGHT;
+            if score > 0.0 {
+                matched += 1;
+                total += score;
+            }
+        }
 
-    #[tracing::instrument]
-    pub(crate) fn load_dev() -> Result<Self> {
-        crate::profile_guard!("load_dev", "db::file::Database");
-        
-        let project_dir = std::env::var("CARGO_MANIFEST_DIR")?;
-        let path = Path::new(&project_dir).join("data").join("dev.fdb");
-        Self::load(path)
+        total * matched as f32
     }
 
-    #[tracing::instrument(skip(f))]
-    pub(crate) fn load_dev_with(
-        filename: &str,
-        f: impl FnOnce(std::io::BufReader<std::fs::File>) -> Result<Self>,
+    /// Same as [`Database::create_random`], but reports progress as `(created, total)` through `progress`
+    /// and can be stopped early by setting `cancel`.
+    ///
+    /// ## Errors
+    /// - [`DatabaseError::Cancelled`] if `cancel` is set before all notes have been created.
+    pub(crate) fn create_random_with_progress(
+        cancel: Option<&AtomicBool>,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<Self> {
+        crate::profile_guard!("create_random_with_progress", "db::file::Database");
+
+        let entries = fastrand::usize(500..=1000);
+        let mut notes = Vec::with_capacity(entries);
+        for i in 0..entries {
+            Self::check_cancelled(cancel)?;
+            let mut note = Note::create((
+                format!("Title {}", i),
+                format!("Here is the content for note number {}.", i),
+            ));
+            for _ in 0..fastrand::usize(0..20) {
+                let tag = format!("tag{}", fastrand::usize(1..=20));
+                if !note.tag_matches(&tag) {
+                    note.add_tag(tag);
+                }
+            }
+            notes.push(note);
+            progress(i + 1, entries);
+        }
+
+        Database::from_notes_vec(notes)
+    }
+
+    /// Shifts every existing backup of `path` up one generation (dropping any that end up past `keep`),
+    /// then copies `path` into generation one.
+    fn rotate_backups(path: &Path, keep: usize) -> Result {
+        let mut generation = keep;
+        while Self::backup_path(path, generation).exists() {
+            std::fs::remove_file(Self::backup_path(path, generation))?;
+            generation += 1;
+        }
+
+        for generation in (1..keep).rev() {
+            let from = Self::backup_path(path, generation);
+            if from.exists() {
+                std::fs::rename(&from, Self::backup_path(path, generation + 1))?;
+            }
+        }
+
+        std::fs::copy(path, Self::backup_path(path, 1))?;
+        Ok(())
+    }
+
+    fn check_cancelled(cancel: Option<&AtomicBool>) -> Result {
+        match cancel {
+            Some(token) if token.load(AtomicOrdering::Relaxed) => Err(DatabaseError::Cancelled.into()),
+            _ => Ok(()),
+        }
+    }
+
+    #[tracing::instrument(skip(self, writer))]
+    pub(crate) fn save_dev_with(
+        &self,
+        filename: &str,
+        writer: impl FnOnce(std::io::BufWriter<std::fs::File>, &Self) -> Result,
+    ) -> Result {
+        use std::fs::File;
+        use std::io::Write;
+        // crate::profile_guard!("save_dev_with", "db::file::Database");
+        
+        let project_dir = std::env::var("CARGO_MANIFEST_DIR")?;
+        let path = Path::new(&project_dir).join("data").join(filename);
+        let mut file = File::create(path)?;
+        let mut buf_writer = std::io::BufWriter::new(file);
+        writer(buf_writer, self)?;
+        Ok(())
+    }
+
+    #[tracing::instrument]
+    pub(crate) fn load_dev() -> Result<Self> {
+        crate::profile_guard!("load_dev", "db::file::Database");
+        
+        Self::load(Self::dev_path()?)
+    }
+
+    #[tracing::instrument(skip(f))]
+    pub(crate) fn load_dev_with(
+        filename: &str,
+        f: impl FnOnce(std::io::BufReader<std::fs::File>) -> Result<Self>,
     ) -> Result<Self> {
         use std::fs::File;
         use std::io::Read;
@@ -657,7 +1863,7 @@ impl Database {
             }
             
             let msg = ID_NOTE_MISMATCH_MSG.to_string();
-            Self::send_error(&self.sender, || msg.clone());
+            Self::send_error(&self.channel, || msg.clone());
             
             return Err(DatabaseError::InvalidState(msg).into());
         }
@@ -687,16 +1893,72 @@ impl Database {
         Ok(())
     }
 
-    fn send_msg(sender: &Sender<DatabaseMessage>, msg: DatabaseMessage) {
-        if let Err(err) = sender.send(msg) {
-            tracing::error!(error = ?err, "Failed to send database message");
+    /// Adds a note whose ID is known to be free during a [`Database::merge`].
+    fn add_merged_note(&mut self, note: Note) {
+        self.ids.insert(note.id());
+        let dto = CreateNote::new(
+            Some(note.title().to_string()),
+            Some(note.content().to_string()),
+            note.tags().to_vec(),
+            note.reminders().to_vec(),
+        );
+        let new_tags = self.introduced_tags(None, &note);
+        Self::send_msg(&self.channel, DatabaseMessage::NoteCreated { dto, created: note.clone(), new_tags });
+        self.notes.push(note);
+        self.mark_modified();
+    }
+
+    /// The key notes are sorted by in [`Database::sort_canonical`].
+    fn canonical_key(note: &Note) -> (time::OffsetDateTime, String) {
+        (*note.created(), note.id().to_string())
+    }
+
+    /// Marks this [`Database`] as changed since it was last saved, see [`Database::is_modified`].
+    fn mark_modified(&mut self) {
+        *self.modified.0.get_mut() = true;
+    }
+
+    /// The tags of `after` that are new to this [`Database`], meaning `before` (the same note before it was
+    /// changed, if any) did not have them and no other note uses them. The note with the id of `after` is
+    /// ignored whether or not it has been added to (or replaced in) `self.notes` yet. This is a scan over
+    /// every note, so it is only worth doing for tags that actually changed.
+    fn introduced_tags(&self, before: Option<&Note>, after: &Note) -> Vec<String> {
+        let before_tags = before.map_or(&[][..], Note::tags);
+        let mut candidates = after
+            .tags()
+            .iter()
+            .filter(|tag| !before_tags.contains(tag))
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        for note in self.notes.iter().filter(|note| note.id() != after.id()) {
+            candidates.retain(|tag| !note.tags().contains(tag));
+            if candidates.is_empty() {
+                break;
+            }
+        }
+        candidates.into_iter().cloned().collect()
+    }
+
+    fn create_channel() -> Option<MessageChannel> {
+        Some(MessageChannel::new())
+    }
+
+    /// These take the channel rather than `&self` so they can be called while `self.notes` is borrowed.
+    fn send_msg(channel: &Option<MessageChannel>, msg: DatabaseMessage) {
+        if let Some(channel) = channel {
+            channel.send(msg);
         }
     }
 
-    /// Making this take a lambda instead of a string should ensure that it is lazily evaluated which
-    /// would give flexibility when I made message sending optional.
-    fn send_error(sender: &Sender<DatabaseMessage>, err: impl FnOnce() -> String) {
-        Self::send_msg(sender, DatabaseMessage::Error { msg: err() });
+    /// Making this take a lambda instead of a string ensures that it is lazily evaluated, so nothing
+    /// is formatted when messaging is disabled.
+    fn send_error(channel: &Option<MessageChannel>, err: impl FnOnce() -> String) {
+        if channel.is_some() {
+            Self::send_msg(channel, DatabaseMessage::Error { msg: err() });
+        }
     }
 }
 
@@ -719,8 +1981,11 @@ impl<'input> TryFrom<&'input [Note]> for Database {
 #[derive(Clone, Debug, PartialEq)]
 pub enum DtoResponse {
     Created(Note),
-    Updated(bool),
-    Deleted(bool),
+    /// The note as it is after the update, and which of its fields changed.
+    Updated { note: Note, changed: ChangedFields },
+    /// The note that was removed.
+    Deleted { note: Note },
+    Flagged(bool),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -733,6 +1998,244 @@ pub enum UpdateFailurePolicy {
     AbortOnError,
 }
 
+/// How [`Database::merge`] resolves an incoming note whose ID is already in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MergeStrategy {
+    /// The existing note is kept and the incoming note is dropped.
+    Skip,
+    /// The existing note is replaced by the incoming note.
+    Overwrite,
+    /// The incoming note is added under a freshly generated ID, keeping both notes.
+    Reassign,
+    /// Whichever note was updated most recently is kept, ties keep the existing note.
+    Newer,
+}
+
+impl Default for MergeStrategy {
+    fn default() -> Self {
+        Self::Newer
+    }
+}
+
+/// The outcome of a [`Database::merge`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MergeReport {
+    /// Incoming notes whose ID was not yet in use.
+    pub added: usize,
+    /// Incoming notes that were dropped in favor of the existing note.
+    pub skipped: usize,
+    /// Existing notes that were replaced by the incoming note.
+    pub overwritten: usize,
+    /// Incoming notes that were added under a new ID.
+    pub reassigned: usize,
+}
+
+impl MergeReport {
+    /// The total number of incoming notes that were processed.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.added + self.skipped + self.overwritten + self.reassigned
+    }
+}
+
+impl std::fmt::Display for MergeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} added, {} skipped, {} overwritten, {} reassigned ({} total)",
+            self.added,
+            self.skipped,
+            self.overwritten,
+            self.reassigned,
+            self.total()
+        )
+    }
+}
+
+/// A single problem found by [`Database::verify_integrity`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IntegrityIssue {
+    /// The id-list and the note-list are not the same size.
+    IdCountMismatch { ids: usize, notes: usize },
+    /// The note at `index` has an invalid id.
+    InvalidNoteId { index: usize },
+    /// More than one note uses the given id.
+    DuplicateNoteId(TinyId),
+    /// A note uses the given id but it is missing from the id-list.
+    UnregisteredId(TinyId),
+    /// The given id is in the id-list but no note uses it.
+    OrphanedId(TinyId),
+    /// The note at `index` needs the given repairs, see [`Note::validate_and_fix`].
+    NoteNeedsRepair { index: usize, fixes: Vec<FixApplied> },
+    /// The given tag has an explicit [`TagStyle`] but no note uses it.
+    UnusedTagStyle(String),
+}
+
+impl std::fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegrityIssue::IdCountMismatch { ids, notes } => {
+                write!(f, "{} registered ids for {} notes", ids, notes)
+            }
+            IntegrityIssue::InvalidNoteId { index } => write!(f, "note #{} has an invalid id", index),
+            IntegrityIssue::DuplicateNoteId(id) => write!(f, "id {} is used by more than one note", id),
+            IntegrityIssue::UnregisteredId(id) => write!(f, "id {} is not registered", id),
+            IntegrityIssue::OrphanedId(id) => write!(f, "id {} is registered but not used by any note", id),
+            IntegrityIssue::NoteNeedsRepair { index, fixes } => {
+                write!(f, "note #{} needs repairs: {:?}", index, fixes)
+            }
+            IntegrityIssue::UnusedTagStyle(tag) => write!(f, "tag '{}' has a style but is not used", tag),
+        }
+    }
+}
+
+/// The outcome of a [`Database::verify_integrity`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntegrityReport {
+    issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no issues were found.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Every issue found, in the order they were checked.
+    #[must_use]
+    pub fn issues(&self) -> &[IntegrityIssue] {
+        &self.issues
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl std::fmt::Display for IntegrityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return write!(f, "No issues found");
+        }
+        writeln!(f, "{} issue(s) found:", self.issues.len())?;
+        for issue in &self.issues {
+            writeln!(f, "- {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a [`Database::rename_tag`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TagRenameReport {
+    /// Notes that had the old tag, which was replaced by the new tag.
+    pub renamed: usize,
+    /// Notes that had both tags, from which the old tag was removed.
+    pub merged: usize,
+}
+
+impl TagRenameReport {
+    /// The total number of notes that changed.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.renamed + self.merged
+    }
+}
+
+impl std::fmt::Display for TagRenameReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} renamed, {} merged ({} total)",
+            self.renamed,
+            self.merged,
+            self.total()
+        )
+    }
+}
+
+/// A summary of the contents of a [`Database`], see [`Database::stats`]. Timestamps are serialized as
+/// RFC 3339 strings so the serialized form is stable for scripts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatabaseStats {
+    /// The total number of notes, including pinned and archived notes.
+    pub notes: usize,
+    pub pinned: usize,
+    pub archived: usize,
+    /// The number of distinct tags.
+    pub tags: usize,
+    pub reminders: usize,
+    /// Reminders that have not fired yet.
+    pub pending_reminders: usize,
+    /// The total word count of all note contents.
+    pub words: usize,
+    /// When the oldest note was created, `None` if there are no notes.
+    #[serde(with = "crate::util::dtf::rfc3339_option")]
+    pub oldest_created: Option<time::OffsetDateTime>,
+    /// When any note was last updated, `None` if there are no notes.
+    #[serde(with = "crate::util::dtf::rfc3339_option")]
+    pub last_updated: Option<time::OffsetDateTime>,
+}
+
+impl std::fmt::Display for DatabaseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let timestamp = |dt: Option<time::OffsetDateTime>| {
+            dt.as_ref()
+                .map_or_else(|| String::from("never"), crate::util::dtf::timestamp_to_string)
+        };
+        writeln!(f, "Notes: {}", self.notes)?;
+        writeln!(f, "Pinned: {}", self.pinned)?;
+        writeln!(f, "Archived: {}", self.archived)?;
+        writeln!(f, "Tags: {}", self.tags)?;
+        writeln!(f, "Reminders: {}", self.reminders)?;
+        writeln!(f, "Pending Reminders: {}", self.pending_reminders)?;
+        writeln!(f, "Words: {}", self.words)?;
+        writeln!(f, "Oldest Note: {}", timestamp(self.oldest_created))?;
+        write!(f, "Last Updated: {}", timestamp(self.last_updated))
+    }
+}
+
+/// A single page of results from [`Database::query`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueryPage<'a> {
+    /// The notes on this page, in order.
+    pub notes: Vec<&'a Note>,
+    /// The zero-based index of this page.
+    pub page: usize,
+    /// The maximum number of notes on each page.
+    pub per_page: usize,
+    /// The number of notes that matched the filter, across all pages.
+    pub total_matched: usize,
+    /// The number of pages needed to hold every matched note.
+    pub total_pages: usize,
+}
+
+/// The differences between two [`Database`]s, see [`Database::diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DatabaseDiff<'a> {
+    /// Notes that are only in the other database.
+    pub added: Vec<&'a Note>,
+    /// Notes that are only in this database.
+    pub removed: Vec<&'a Note>,
+    /// Notes that are in both databases but differ, as `(this, other)` pairs.
+    pub changed: Vec<(&'a Note, &'a Note)>,
+}
+
+impl DatabaseDiff<'_> {
+    /// Whether the two databases contain the same notes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Method;
@@ -777,6 +2280,1033 @@ mod tests {
         result
     }
 
+    #[test]
+    #[no_coverage]
+    fn save_keeps_loaded_method() {
+        let db = create_dev_db(10);
+        let tempfile = std::env::temp_dir().join(format!(
+            "db-file-tests-save_keeps_loaded_method-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        assert!(!tempfile.exists(), "tempfile should not already exist!");
+
+        let other = Method::working_methods()
+            .find(|&m| m != Persistence::DEFAULT_METHOD)
+            .expect("there should be a non-default working method");
+        Persistence::save_to_file(&db, &tempfile, other).expect("Unable to save database");
+
+        let mut loaded = Database::load(&tempfile).expect("Unable to load database");
+        assert_eq!(loaded.method(), other);
+        assert_eq!(loaded.source_path(), Some(tempfile.as_path()));
+        assert_eq!(loaded.len(), db.len());
+
+        loaded.save(&tempfile).expect("Unable to re-save database");
+        assert_eq!(
+            Persistence::detect_method_for_file(&tempfile).unwrap(),
+            Some(other)
+        );
+
+        loaded
+            .save_as(&tempfile, Persistence::DEFAULT_METHOD)
+            .expect("Unable to convert database");
+        assert_eq!(loaded.method(), Persistence::DEFAULT_METHOD);
+        assert_eq!(
+            Persistence::detect_method_for_file(&tempfile).unwrap(),
+            Some(Persistence::DEFAULT_METHOD)
+        );
+
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn rename_tag() {
+        let mut db = Database::from_notes_vec(vec![
+            Note::create(("One", "", vec!["old", "other"])),
+            Note::create(("Two", "", vec!["old", "new"])),
+            Note::create(("Three", "", vec!["new"])),
+            Note::create(("Four", "", vec!["other"])),
+        ])
+        .expect("Unable to create database");
+        let receiver = db.get_receiver().unwrap();
+        db.set_tag_style("old", TagStyle::from_color([1, 2, 3]));
+
+        assert_eq!(db.rename_tag("old", "new").unwrap(), TagRenameReport { renamed: 1, merged: 1 });
+        assert!(db.get_all().iter().all(|n| !n.tag_matches("old")));
+        for note in db.get_all() {
+            let count = note.tags().iter().filter(|t| *t == "new").count();
+            assert!(count <= 1, "note {} has a duplicate tag", note.title());
+        }
+        assert_eq!(db.get_all()[0].tags(), &["new".to_string(), "other".to_string()]);
+        assert_eq!(db.get_all()[1].tags(), &["new".to_string()]);
+        assert_eq!(db.tag_style("new"), Some(&TagStyle::from_color([1, 2, 3])));
+        assert!(db.tag_style("old").is_none());
+
+        let messages = receiver.try_iter().map(|m| m.msg).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        for msg in messages {
+            match msg {
+                DatabaseMessage::NoteUpdated { before, after, .. } => {
+                    assert!(before.tag_matches("old"));
+                    assert!(!after.tag_matches("old"));
+                    assert!(after.tag_matches("new"));
+                }
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+
+        assert_eq!(db.rename_tag("old", "new").unwrap().total(), 0);
+        assert!(db.rename_tag("new", "").is_err());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn rename_tag_merges() {
+        let mut db = Database::from_notes_vec(vec![
+            Note::create(("Only Old", "", vec!["a", "old", "b"])),
+            Note::create(("Only New", "", vec!["new", "a"])),
+            Note::create(("Both", "", vec!["old", "a", "new"])),
+            Note::create(("Both Reversed", "", vec!["new", "old"])),
+        ])
+        .expect("Unable to create database");
+        let tags = |db: &Database, i: usize| db.get_all()[i].tags().to_vec();
+
+        let report = db.rename_tag("old", "new").unwrap();
+        assert_eq!(report, TagRenameReport { renamed: 1, merged: 2 });
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.to_string(), "1 renamed, 2 merged (3 total)");
+
+        assert_eq!(tags(&db, 0), vec!["a", "new", "b"]);
+        assert_eq!(tags(&db, 1), vec!["new", "a"]);
+        assert_eq!(tags(&db, 2), vec!["a", "new"]);
+        assert_eq!(tags(&db, 3), vec!["new"]);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn canonical_order() {
+        let notes = vec![
+            Note::create(("First", "a", vec!["x"])),
+            Note::create(("Second", "b", vec!["y"])),
+            Note::create(("Third", "c", vec!["z"])),
+            Note::create(("Fourth", "d", vec![])),
+        ];
+        let mut reversed = notes.clone();
+        reversed.reverse();
+
+        let mut forward = Database::from_notes(&notes).expect("Unable to create database");
+        let mut backward = Database::from_notes(&reversed).expect("Unable to create database");
+        for tag in ["x", "y", "z"] {
+            forward.set_tag_style(tag, TagStyle::default_for(tag));
+        }
+        for tag in ["z", "y", "x"] {
+            backward.set_tag_style(tag, TagStyle::default_for(tag));
+        }
+        let json = |db: &Database| serde_json::to_string(db).expect("Unable to serialize database");
+        assert_ne!(json(&forward), json(&backward));
+
+        forward.canonicalize();
+        backward.canonicalize();
+        assert_eq!(json(&forward), json(&backward), "canonical json should be byte-identical");
+        let ids = |db: &Database| db.get_all().iter().map(Note::id).collect::<Vec<_>>();
+        assert_eq!(ids(&forward), ids(&backward));
+        assert!(forward
+            .get_all()
+            .windows(2)
+            .all(|pair| pair[0].created() <= pair[1].created()));
+
+        // Canonicalizing an already canonical database changes nothing.
+        let mut saved = Database::from_notes(forward.get_all()).unwrap();
+        saved.canonicalize();
+        assert!(!saved.is_modified());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn modified_since_save() {
+        let mut db = Database::without_messaging();
+        assert!(!db.is_modified(), "a new database should not need saving");
+
+        let note = db.apply_create(("Title", "Content")).expect("Unable to create note");
+        assert!(db.is_modified(), "creating a note should mark the database as modified");
+
+        let tempfile = std::env::temp_dir().join(format!(
+            "db-file-tests-modified-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        db.save(&tempfile).expect("Unable to save database");
+        assert!(!db.is_modified(), "saving should clear the modified flag");
+
+        let loaded = Database::load(&tempfile).expect("Unable to load database");
+        assert!(!loaded.is_modified(), "a freshly loaded database should not need saving");
+
+        // Operations that change nothing leave the flag alone.
+        assert!(!db.apply_flag(FlagNote::new(note.id(), NoteFlag::Archived, false)).unwrap());
+        assert!(db.delete_where(|_| false).unwrap().is_empty());
+        assert!(db.remove_tag_style("missing").is_none());
+        assert!(!db.is_modified());
+
+        db.apply_delete(note.id()).expect("Unable to delete note");
+        assert!(db.is_modified(), "deleting a note should mark the database as modified");
+
+        db.save(&tempfile).expect("Unable to save database");
+        assert!(!db.is_modified());
+        db.set_tag_style("tag", TagStyle::default_for("tag"));
+        assert!(db.is_modified(), "setting a tag style should mark the database as modified");
+        assert!(db.clone().is_modified(), "clones should keep the modified flag");
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+
+        // The flag must not stop the database from being shared between threads, e.g. by the server.
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Database>();
+    }
+
+    #[test]
+    #[no_coverage]
+    fn verify_integrity() {
+        let mut db = Database::from_notes_vec(vec![
+            Note::create(("One", "", vec!["a", "b"])),
+            Note::create(("Two", "", vec!["b"])),
+            Note::create(("Three", "", vec!["c"])),
+            Note::create(("Four", "", vec!["d"])),
+        ])
+        .expect("Unable to create database");
+        db.set_tag_style("a", TagStyle::from_color([1, 2, 3]));
+        assert!(db.verify_integrity().is_ok());
+        assert_eq!(db.verify_integrity().to_string(), "No issues found");
+
+        // Corrupt the database in as many distinct ways as possible.
+        let duplicate = db.notes[0].clone();
+        db.notes.push(duplicate.clone());
+        let orphaned = db.notes[1].id();
+        db.notes[1].make_invalid();
+        let unregistered = db.notes[2].id();
+        db.ids.remove(&unregistered);
+        db.notes[3].set_title("  Untrimmed ");
+        db.set_tag_style("unused", TagStyle::from_color([4, 5, 6]));
+
+        let report = db.verify_integrity();
+        assert_eq!(
+            report.issues(),
+            &[
+                IntegrityIssue::IdCountMismatch { ids: 3, notes: 5 },
+                IntegrityIssue::InvalidNoteId { index: 1 },
+                IntegrityIssue::UnregisteredId(unregistered),
+                IntegrityIssue::NoteNeedsRepair {
+                    index: 3,
+                    fixes: vec![FixApplied::TitleTrimmed]
+                },
+                IntegrityIssue::DuplicateNoteId(duplicate.id()),
+                IntegrityIssue::OrphanedId(orphaned),
+                IntegrityIssue::UnusedTagStyle(String::from("unused")),
+            ]
+        );
+        assert!(!report.is_ok());
+        assert!(report.to_string().starts_with("7 issue(s) found:"));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn new_tags_reported() {
+        let mut db = Database::from_notes_vec(vec![
+            Note::create(("One", "", vec!["existing"])),
+            Note::create(("Two", "", vec!["shared", "existing"])),
+        ])
+        .expect("Unable to create database");
+        let receiver = db.get_receiver().unwrap();
+        let next_new_tags = || match receiver.try_recv().map(|m| m.msg) {
+            Ok(DatabaseMessage::NoteCreated { new_tags, .. } | DatabaseMessage::NoteUpdated { new_tags, .. }) => new_tags,
+            other => panic!("expected NoteCreated or NoteUpdated, got {:?}", other),
+        };
+
+        let created = db.apply_create(("Three", "", vec!["existing", "brand-new"])).unwrap();
+        assert_eq!(next_new_tags(), vec!["brand-new".to_string()]);
+        db.apply_create(("Four", "", vec!["shared"])).unwrap();
+        assert!(next_new_tags().is_empty());
+
+        // Tags the note already had are not new, even if no other note uses them.
+        let update = UpdateNote::empty(created.id()).with_tags(Some(vec![
+            "brand-new".to_string(),
+            "another".to_string(),
+            "existing".to_string(),
+        ]));
+        db.apply_update(update).unwrap();
+        assert_eq!(next_new_tags(), vec!["another".to_string()]);
+
+        // Dropping a tag and adding it back makes it new again once nothing else uses it.
+        db.apply_update(UpdateNote::empty(created.id()).with_tags(Some(vec!["existing".to_string()])))
+            .unwrap();
+        assert!(next_new_tags().is_empty());
+        db.apply_update(UpdateNote::empty(created.id()).with_tags(Some(vec!["brand-new".to_string()])))
+            .unwrap();
+        assert_eq!(next_new_tags(), vec!["brand-new".to_string()]);
+
+        db.rename_tag("shared", "renamed").unwrap();
+        assert_eq!(next_new_tags(), vec!["renamed".to_string()]);
+        assert!(next_new_tags().is_empty(), "only the first renamed note introduces the tag");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn iter_tags() {
+        let db = Database::from_notes_vec(vec![
+            Note::create(("One", "", vec!["work", "b"])),
+            Note::create(("Two", "", vec!["a", "work"])),
+            Note::create(("Three", "", vec!["Work"])),
+            Note::create(("Four", "", Vec::<&str>::new())),
+        ])
+        .expect("Unable to create database");
+
+        let tags = db.iter_tags().collect::<Vec<_>>();
+        assert_eq!(tags, vec![("Work", 1), ("a", 1), ("b", 1), ("work", 2)]);
+        assert_eq!(tags.len(), db.get_all_tags().len());
+        assert_eq!(Database::empty().iter_tags().count(), 0);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn seed_welcome() {
+        let mut db = Database::empty();
+        let note = db.seed_welcome_if_empty().unwrap().expect("Empty database was not seeded");
+        assert_eq!(db.len(), 1);
+        assert_eq!(db.get_all()[0].id(), note.id());
+        assert!(!note.title().trim().is_empty());
+        assert!(!note.content().trim().is_empty());
+        assert_eq!(note.title(), WELCOME_TITLE);
+
+        assert!(db.seed_welcome_if_empty().unwrap().is_none());
+        assert_eq!(db.len(), 1);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn delete_where() {
+        let mut db = Database::from_notes_vec(vec![
+            Note::create(("One", "", vec!["doomed"])),
+            Note::create(("Two", "", vec!["keep"])),
+            Note::create(("Three", "", vec!["doomed", "keep"])),
+            Note::create(("Four", "", vec![])),
+        ])
+        .expect("Unable to create database");
+        let receiver = db.get_receiver().unwrap();
+
+        let deleted = db.delete_where(|n| n.tag_matches("doomed")).unwrap();
+        assert_eq!(
+            deleted.iter().map(Note::title).collect::<Vec<_>>(),
+            vec!["One", "Three"]
+        );
+        assert_eq!(db.len(), 2);
+        assert!(deleted.iter().all(|n| !db.id_in_use(n.id())));
+        assert!(db.get_all().iter().all(|n| db.id_in_use(n.id())));
+
+        let messages = receiver.try_iter().map(|m| m.msg).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        assert!(messages
+            .iter()
+            .all(|msg| matches!(msg, DatabaseMessage::NoteDeleted { deleted } if deleted.tag_matches("doomed"))));
+
+        assert!(db.delete_where(|n| n.tag_matches("doomed")).unwrap().is_empty());
+        assert_eq!(db.len(), 2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn due_reminders() {
+        use time::{macros::datetime, Duration};
+
+        use crate::types::Recurrence;
+
+        let as_of = datetime!(2022-06-15 12:00 UTC);
+        let reminder = |text: &str, offset: Duration, fired: bool| {
+            Reminder::existing(TinyId::random(), text.to_string(), as_of + offset, fired)
+        };
+
+        let mut db = Database::without_messaging();
+        db.apply_create(CreateNote::from((
+            "First",
+            "",
+            vec![],
+            vec![
+                reminder("future", Duration::hours(1), false),
+                reminder("past", -Duration::hours(1), false),
+                reminder("fired", -Duration::hours(3), true),
+            ],
+        )))
+        .unwrap();
+        db.apply_create(CreateNote::from((
+            "Second",
+            "",
+            vec![],
+            vec![
+                reminder("present", Duration::ZERO, false),
+                reminder("long ago", -Duration::days(30), false),
+                reminder("recurring", -Duration::days(2), true).with_recurrence(Recurrence::Daily),
+            ],
+        )))
+        .unwrap();
+
+        let due = db.due_reminders(as_of);
+        let texts = due.iter().map(|(_, r)| r.text()).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["long ago", "recurring", "past", "present"]);
+        let titles = due.iter().map(|(n, _)| n.title()).collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Second", "Second", "First", "Second"]);
+
+        assert_eq!(db.due_reminders(as_of - Duration::days(365)).len(), 0);
+        assert_eq!(db.due_reminders(as_of + Duration::days(1)).len(), 5);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn recurring_reminders_move_to_their_next_occurrence() {
+        use time::{macros::datetime, Duration};
+
+        use crate::types::Recurrence;
+
+        let as_of = datetime!(2022-06-15 12:00 UTC);
+        // Due daily at 13:00, starting two days ago, and last fired yesterday.
+        let mut daily = Reminder::existing(TinyId::random(), "daily".to_string(), datetime!(2022-06-13 13:00 UTC), false)
+            .with_recurrence(Recurrence::Daily);
+        daily.fire_at(datetime!(2022-06-14 13:05 UTC));
+        assert_eq!(daily.current_occurrence(), Some(datetime!(2022-06-15 13:00 UTC)));
+
+        let mut once = Reminder::existing(TinyId::random(), "once".to_string(), datetime!(2022-06-13 13:00 UTC), false);
+        once.fire_at(datetime!(2022-06-13 13:00 UTC));
+        assert_eq!(once.current_occurrence(), None);
+
+        let mut db = Database::without_messaging();
+        db.apply_create(CreateNote::from(("Note", "", vec![], vec![daily, once]))).unwrap();
+        let texts = |db: &Database, as_of| {
+            db.due_reminders(as_of)
+                .iter()
+                .map(|(_, reminder)| reminder.text().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // Not due again until today's occurrence has passed, and never again after that until it fires.
+        assert!(texts(&db, as_of).is_empty());
+        assert_eq!(texts(&db, as_of + Duration::hours(1)), ["daily"]);
+        assert_eq!(texts(&db, as_of + Duration::days(7)), ["daily"]);
+
+        // Firing it again moves it on to tomorrow.
+        let id = db.get_all()[0].id();
+        db.get_and_modify(id, |note| {
+            let mut reminders = note.reminders().to_vec();
+            reminders[0].fire_at(as_of + Duration::hours(1));
+            note.set_reminders(reminders);
+        })
+        .unwrap();
+        assert!(texts(&db, as_of + Duration::hours(2)).is_empty());
+        assert_eq!(texts(&db, as_of + Duration::days(1) + Duration::hours(1)), ["daily"]);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn dto_responses() {
+        let mut db = Database::without_messaging();
+        let created = match db.apply_dto(CreateNote::from(("Title", "Content", vec!["tag"]))).unwrap() {
+            DtoResponse::Created(note) => note,
+            other => panic!("expected Created, got {:?}", other),
+        };
+
+        let update = UpdateNote::from((created.id(), "New Title".to_string(), "Content".to_string()));
+        match db.apply_dto(update.clone()).unwrap() {
+            DtoResponse::Updated { note, changed } => {
+                assert_eq!(note.id(), created.id());
+                assert_eq!(note.title(), "New Title");
+                assert_eq!(changed, ChangedFields::new(true, false, false, false));
+                assert_eq!(changed.names(), vec!["title"]);
+            }
+            other => panic!("expected Updated, got {:?}", other),
+        }
+        let (note, changed) = db.apply_update(update).unwrap();
+        assert_eq!(note.title(), "New Title");
+        assert!(changed.is_empty());
+
+        match db.apply_dto(DeleteNote::from(created.id())).unwrap() {
+            DtoResponse::Deleted { note } => {
+                assert_eq!(note.id(), created.id());
+                assert_eq!(note.title(), "New Title");
+                assert_eq!(note.tags(), &["tag".to_string()]);
+            }
+            other => panic!("expected Deleted, got {:?}", other),
+        }
+        assert!(db.is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn messaging_disabled() {
+        let mut db = Database::without_messaging();
+        assert!(!db.messaging_enabled());
+        assert!(db.get_receiver().is_none());
+
+        for i in 0..1000 {
+            db.apply_dto(CreateNote::from((format!("Title {}", i), format!("Content {}", i))))
+                .expect("apply_dto failed");
+        }
+        let id = db.get_all()[0].id();
+        db.apply_dto(UpdateNote::from((id, "Updated".to_string(), "Updated".to_string())))
+            .expect("apply_dto failed");
+        db.apply_dto(DeleteNote::from(id)).expect("apply_dto failed");
+        assert!(db.apply_delete(id).is_err());
+        assert_eq!(db.len(), 999);
+        assert!(db.get_receiver().is_none());
+
+        db.set_messaging(true);
+        let receiver = db.get_receiver().expect("messaging was enabled");
+        assert_eq!(receiver.len(), 0, "no backlog should exist from while messaging was disabled");
+        db.apply_create(("Title", "Content")).unwrap();
+        assert_eq!(receiver.len(), 1);
+
+        db.set_messaging(false);
+        assert!(db.get_receiver().is_none());
+        db.apply_create(("Title", "Content")).unwrap();
+        assert!(receiver.try_recv().is_ok());
+        assert!(receiver.try_recv().is_err(), "nothing should be sent once disabled");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn merge_strategies() {
+        let shared = Note::create(("Shared", "Base content"));
+        let mut changed = shared.clone();
+        changed.set_content("Incoming content");
+        let create = || {
+            let base = Database::from_notes_vec(vec![shared.clone(), Note::create(("Base", ""))])
+                .expect("Unable to create base database");
+            let incoming = Database::from_notes_vec(vec![changed.clone(), Note::create(("Incoming", ""))])
+                .expect("Unable to create incoming database");
+            (base, incoming)
+        };
+        let content_of = |db: &Database| db.get(shared.id()).unwrap().content().to_string();
+
+        let (mut base, incoming) = create();
+        let report = base.merge(incoming, MergeStrategy::Skip).unwrap();
+        assert_eq!(report, MergeReport { added: 1, skipped: 1, overwritten: 0, reassigned: 0 });
+        assert_eq!(base.len(), 3);
+        assert_eq!(content_of(&base), "Base content");
+
+        let (mut base, incoming) = create();
+        let receiver = base.get_receiver().unwrap();
+        let report = base.merge(incoming, MergeStrategy::Overwrite).unwrap();
+        assert_eq!(report, MergeReport { added: 1, skipped: 0, overwritten: 1, reassigned: 0 });
+        assert_eq!(base.len(), 3);
+        assert_eq!(content_of(&base), "Incoming content");
+        let messages = receiver.try_iter().map(|m| m.msg).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().any(|m| matches!(m, DatabaseMessage::NoteUpdated { .. })));
+        assert!(messages.iter().any(|m| matches!(m, DatabaseMessage::NoteCreated { .. })));
+
+        let (mut base, incoming) = create();
+        let report = base.merge(incoming, MergeStrategy::Reassign).unwrap();
+        assert_eq!(report, MergeReport { added: 1, skipped: 0, overwritten: 0, reassigned: 1 });
+        assert_eq!(base.len(), 4);
+        assert_eq!(content_of(&base), "Base content");
+        let copy = base
+            .get_all()
+            .iter()
+            .find(|n| n.content() == "Incoming content")
+            .expect("reassigned note should exist");
+        assert_ne!(copy.id(), shared.id());
+        assert!(base.id_in_use(copy.id()));
+        assert_eq!(base.get_all().iter().map(Note::id).collect::<HashSet<_>>().len(), 4);
+
+        // Everything but the ID survives being reassigned.
+        let mut flagged = changed.clone();
+        flagged.pin();
+        flagged.archive();
+        flagged.add_attachment(crate::types::Attachment::new("diagram", "/tmp/diagram.png", None));
+        let (mut base, _) = create();
+        let incoming = Database::from_notes_vec(vec![flagged.clone()]).expect("Unable to create incoming database");
+        base.merge(incoming, MergeStrategy::Reassign).unwrap();
+        let copy = base
+            .get_all()
+            .iter()
+            .find(|n| n.content() == "Incoming content")
+            .expect("reassigned note should exist");
+        assert_ne!(copy.id(), flagged.id());
+        assert!(copy.is_pinned());
+        assert!(copy.is_archived());
+        assert_eq!(copy.attachments(), flagged.attachments());
+        assert_eq!(copy.created(), flagged.created());
+        assert_eq!(copy.updated(), flagged.updated());
+
+        let (mut base, incoming) = create();
+        let report = base.merge(incoming, MergeStrategy::Newer).unwrap();
+        assert_eq!(report, MergeReport { added: 1, skipped: 0, overwritten: 1, reassigned: 0 });
+        assert_eq!(content_of(&base), "Incoming content");
+        assert_eq!(report.total(), 2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn tag_normalizer() {
+        let mut db = Database::without_messaging();
+        let distinct = db.apply_create(("Distinct", "", vec![" Work", "work ", "", "work"])).unwrap();
+        assert_eq!(distinct.tags(), &["Work".to_string(), "work".to_string()]);
+
+        db.set_tag_normalizer(TagNormalizer::default().with_lowercase(true));
+        assert!(db.tag_normalizer().lowercase());
+        let collapsed = db.apply_create(("Collapsed", "", vec!["Work", "work"])).unwrap();
+        assert_eq!(collapsed.tags(), &["work".to_string()]);
+        // Existing notes are left alone.
+        assert_eq!(db.get(distinct.id()).unwrap().tags().len(), 2);
+
+        db.set_tag_normalizer(TagNormalizer::new(true, true));
+        let tags = vec!["To Do".to_string(), "to  do".to_string(), "WORK".to_string()];
+        assert!(db.apply_update((distinct.id(), None, None, Some(tags))).unwrap().1.tags());
+        assert_eq!(
+            db.get(distinct.id()).unwrap().tags(),
+            &["to-do".to_string(), "work".to_string()]
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn get_range() {
+        let db = create_dev_db(10);
+        let all = db.get_all();
+
+        assert_eq!(db.get_range(2..5), &all[2..5]);
+        assert_eq!(db.get_range(0..10), all);
+        assert_eq!(db.get_range(8..15), &all[8..]);
+        assert_eq!(db.get_range(0..usize::MAX), all);
+        assert!(db.get_range(10..12).is_empty());
+        assert!(db.get_range(20..30).is_empty());
+        assert!(db.get_range(5..3).is_empty());
+        assert!(Database::empty().get_range(0..5).is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn diff() {
+        let base = create_dev_db(5);
+        assert!(base.diff(&base.clone()).is_empty());
+
+        let mut other = base.clone();
+        let edited = other.get_all()[1].id();
+        let deleted = other.get_all()[3].id();
+        other.get_and_modify(edited, |n| n.set_title("Edited")).unwrap();
+        other.apply_delete(deleted).unwrap();
+        let added = other.apply_create(("Added", "")).unwrap();
+
+        let diff = base.diff(&other);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id(), added.id());
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].id(), deleted);
+        assert_eq!(diff.changed.len(), 1);
+        let (before, after) = diff.changed[0];
+        assert_eq!(before.id(), edited);
+        assert_eq!(after.id(), edited);
+        assert_eq!(after.title(), "Edited");
+        assert_ne!(before.title(), "Edited");
+
+        let reversed = other.diff(&base);
+        assert_eq!(reversed.added[0].id(), deleted);
+        assert_eq!(reversed.removed[0].id(), added.id());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn get_many() {
+        let db = create_dev_db(6);
+        let all = db.get_all();
+        let missing = db.create_id();
+        let ids = [all[4].id(), all[1].id(), missing, all[4].id()];
+
+        let found = db.get_many(&ids);
+        assert_eq!(found, vec![&all[1], &all[4]]);
+        assert!(db.get_many(&[missing]).is_empty());
+        assert!(db.get_many(&[]).is_empty());
+
+        let strict = db.get_many_strict(&[all[4].id(), all[1].id(), all[1].id()]).unwrap();
+        assert_eq!(strict, vec![&all[1], &all[4]]);
+        match db.get_many_strict(&ids) {
+            Err(Error::Database(DatabaseError::IdNotFound(id))) => assert_eq!(id, missing),
+            other => panic!("expected IdNotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn apply_dtos_buffered() {
+        let mut db = create_dev_db(3);
+        let receiver = db.get_receiver().unwrap();
+        let existing = db.get_all()[0].id();
+        let missing = db.create_id();
+
+        let failing: Vec<NoteDto> = vec![
+            CreateNote::from(("One", "")).into(),
+            UpdateNote::from((existing, "Updated".to_string(), "Updated".to_string())).into(),
+            DeleteNote::from(missing).into(),
+        ];
+        assert!(db
+            .apply_dtos(failing.clone(), UpdateFailurePolicy::AllOrNothing, true)
+            .is_err());
+        assert_eq!(db.len(), 3);
+        assert_ne!(db.get(existing).unwrap().title(), "Updated");
+        assert_eq!(receiver.try_iter().count(), 0, "a rolled back batch should emit nothing");
+
+        // All-or-nothing batches are buffered even when not asked to be, and keep the tag styles as they were.
+        db.set_tag_style("styled", TagStyle::default_for("styled"));
+        let styles = db.tag_styles.clone();
+        assert!(db
+            .apply_dtos(failing.clone(), UpdateFailurePolicy::AllOrNothing, false)
+            .is_err());
+        assert_eq!(db.len(), 3);
+        assert_eq!(db.tag_styles, styles);
+        assert_eq!(receiver.try_iter().count(), 0, "a rolled back batch should emit nothing");
+
+        let responses = db
+            .apply_dtos(failing.clone(), UpdateFailurePolicy::Partial, true)
+            .expect("partial batch should not fail");
+        assert_eq!(responses.len(), 2);
+        assert_eq!(db.len(), 4);
+        let messages = receiver.try_iter().map(|m| m.msg).collect::<Vec<_>>();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], DatabaseMessage::NoteCreated { .. }));
+        assert!(matches!(messages[1], DatabaseMessage::NoteUpdated { .. }));
+        assert!(matches!(messages[2], DatabaseMessage::Error { .. }));
+
+        let succeeding = vec![
+            CreateNote::from(("Two", "")),
+            CreateNote::from(("Three", "")),
+        ];
+        let responses = db
+            .apply_dtos(succeeding, UpdateFailurePolicy::AllOrNothing, true)
+            .expect("batch should succeed");
+        assert_eq!(responses.len(), 2);
+        assert_eq!(db.len(), 6);
+        assert_eq!(receiver.try_iter().count(), 2);
+
+        assert!(db
+            .apply_dtos(failing, UpdateFailurePolicy::AbortOnError, false)
+            .is_err());
+        assert_eq!(db.len(), 7);
+        // The update is a no-op by now, so only the create and the error are sent.
+        assert_eq!(receiver.try_iter().count(), 2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn message_sequence() {
+        let mut db = create_dev_db(3);
+        let receiver = db.get_receiver().unwrap();
+        let existing = db.get_all()[0].id();
+
+        let created = db.apply_create(("New", "")).unwrap();
+        db.apply_update((existing, "Updated".to_string(), "Updated".to_string())).unwrap();
+        db.apply_flag(FlagNote::new(existing, NoteFlag::Pinned, true)).unwrap();
+        assert!(db.apply_delete(db.create_id()).is_err());
+        db.apply_delete(created.id()).unwrap();
+        // Buffered batches keep the numbers assigned when each change was made, even across a rollback.
+        let batch: Vec<NoteDto> = vec![
+            CreateNote::from(("Rolled Back", "")).into(),
+            DeleteNote::from(db.create_id()).into(),
+        ];
+        assert!(db.apply_dtos(batch, UpdateFailurePolicy::AllOrNothing, true).is_err());
+        let batch = vec![CreateNote::from(("One", "")), CreateNote::from(("Two", ""))];
+        db.apply_dtos(batch, UpdateFailurePolicy::AllOrNothing, true).unwrap();
+        // Clones share the sequence along with the channel.
+        let mut clone = db.clone();
+        clone.apply_create(("From Clone", "")).unwrap();
+        db.apply_create(("From Original", "")).unwrap();
+
+        let messages = receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(messages.len(), 9);
+        assert!(
+            messages.windows(2).all(|pair| pair[0].seq < pair[1].seq),
+            "sequence numbers should be strictly increasing: {:?}",
+            messages.iter().map(|m| m.seq).collect::<Vec<_>>()
+        );
+        assert_eq!(messages[0].seq, 0);
+        assert!(matches!(messages[0].msg, DatabaseMessage::NoteCreated { .. }));
+        assert!(matches!(messages[3].msg, DatabaseMessage::Error { .. }));
+        assert!(matches!(messages[4].msg, DatabaseMessage::NoteDeleted { .. }));
+        // The rolled back batch used up two numbers that were never sent.
+        assert_eq!(messages[5].seq, messages[4].seq + 3);
+
+        let mut sorted = messages.clone();
+        sorted.reverse();
+        sorted.sort_by_key(|m| m.seq);
+        assert_eq!(sorted, messages);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn archive() {
+        let mut db = create_dev_db(5);
+        let receiver = db.get_receiver().unwrap();
+        let id = db.get_all()[2].id();
+        let updated = *db.get(id).unwrap().updated();
+
+        assert!(db.archive(id).unwrap());
+        assert!(!db.archive(id).unwrap());
+        assert!(db.archive(db.create_id()).is_err());
+        match receiver.try_recv().map(|m| m.msg) {
+            Ok(DatabaseMessage::NoteUpdated { before, after, .. }) => {
+                assert!(!before.is_archived());
+                assert!(after.is_archived());
+                assert_eq!(*after.updated(), updated);
+            }
+            other => panic!("expected NoteUpdated, got {:?}", other),
+        }
+
+        assert_eq!(db.len(), 5);
+        assert_eq!(db.get_active().len(), 4);
+        assert!(db.get_active().iter().all(|n| n.id() != id));
+        assert!(db.get(id).unwrap().is_archived());
+        assert_eq!(db.find(|n| n.id() == id).len(), 1);
+
+        let tempfile = std::env::temp_dir().join(format!(
+            "db-file-tests-archive-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        db.save(&tempfile).expect("Unable to save database");
+        let mut loaded = Database::load(&tempfile).expect("Unable to load database");
+        assert!(loaded.get(id).unwrap().is_archived());
+        assert_eq!(loaded.get_active().len(), 4);
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+
+        assert!(loaded.unarchive(id).unwrap());
+        assert_eq!(loaded.get_active().len(), 5);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn markdown_round_trip() {
+        let db = Database::from_notes_vec(vec![
+            Note::create(("First", "Some *markdown* content.", vec!["a", "b"])),
+            Note::create(("Second", "", vec![])),
+            Note::create(("Third", "More content", vec!["c"])),
+        ])
+        .expect("Unable to create database");
+
+        for front_matter in [FrontMatter::Yaml, FrontMatter::Toml] {
+            let dir = std::env::temp_dir().join(format!(
+                "db-file-tests-markdown_round_trip-{:010}",
+                fastrand::u32(..)
+            ));
+            assert_eq!(db.export_markdown(&dir, front_matter).unwrap(), 3);
+
+            let mut imported = Database::empty();
+            assert_eq!(imported.import_markdown(&dir).unwrap(), 3);
+            for note in db.get_all() {
+                let other = imported.get(note.id()).expect("id should be preserved");
+                assert_eq!(other.title(), note.title());
+                assert_eq!(other.content(), note.content());
+                assert_eq!(other.tags(), note.tags());
+            }
+
+            std::fs::remove_dir_all(dir).expect("Unable to delete temp dir");
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn with_capacity() {
+        let mut db = Database::with_capacity(100);
+        assert!(db.is_empty());
+        assert!(db.capacity() >= 100);
+
+        let created = db
+            .create_many((0..250).map(|i| (format!("Title {}", i), format!("Content {}", i))))
+            .expect("create_many failed");
+        assert_eq!(created.len(), 250);
+        assert_eq!(db.len(), 250);
+        assert!(created.iter().all(|n| db.id_in_use(n.id())));
+        assert_eq!(db.get(created[0].id()).unwrap().title(), "Title 0");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn rotating_backups() {
+        let mut db = create_dev_db(5);
+        let tempfile = std::env::temp_dir().join(format!(
+            "db-file-tests-rotating_backups-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        assert!(!tempfile.exists(), "tempfile should not already exist!");
+
+        db.save_with_backup(&tempfile, 2).expect("first save failed");
+        assert!(!Database::backup_path(&tempfile, 1).exists());
+
+        db.apply_create(("Second", "State")).unwrap();
+        db.save_with_backup(&tempfile, 2).expect("second save failed");
+        let previous = db.get_all().to_vec();
+
+        db.apply_create(("Third", "State")).unwrap();
+        db.save_with_backup(&tempfile, 2).expect("third save failed");
+
+        let backups = (1..=3)
+            .map(|n| Database::backup_path(&tempfile, n))
+            .filter(|p| p.exists())
+            .collect::<Vec<_>>();
+        assert_eq!(backups.len(), 2);
+
+        let newest = Database::load(Database::backup_path(&tempfile, 1)).expect("unable to load newest backup");
+        assert_eq!(newest.get_all(), previous.as_slice());
+        let oldest = Database::load(Database::backup_path(&tempfile, 2)).expect("unable to load oldest backup");
+        assert_eq!(oldest.len(), 5);
+        assert_eq!(Database::load(&tempfile).unwrap().len(), 7);
+
+        for backup in backups {
+            std::fs::remove_file(backup).expect("Unable to delete backup");
+        }
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn atomic_save() {
+        let db = create_dev_db(10);
+        let receiver = db.get_receiver().expect("receiver should always be present");
+        let tempfile = std::env::temp_dir().join(format!(
+            "db-file-tests-atomic_save-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        let partial = Persistence::temp_path_for(&tempfile);
+        assert!(!tempfile.exists(), "tempfile should not already exist!");
+
+        db.save(&tempfile).expect("Unable to save database");
+        assert!(!partial.exists(), "temp file should be renamed away");
+        assert!(matches!(receiver.try_recv().map(|m| m.msg), Ok(DatabaseMessage::DataSaved { .. })));
+
+        // Simulate a save that was interrupted mid-write, the original must be untouched.
+        std::fs::write(&partial, b"garbage from an interrupted save").unwrap();
+        let loaded = Database::load(&tempfile).expect("original should still be loadable");
+        assert_eq!(loaded.get_all(), db.get_all());
+
+        // A failed save (the temp path is blocked by a directory) must leave the original intact and not report success.
+        std::fs::remove_file(&partial).unwrap();
+        std::fs::create_dir(&partial).unwrap();
+        let bigger = create_dev_db(20);
+        let bigger_rx = bigger.get_receiver().unwrap();
+        assert!(bigger.save(&tempfile).is_err());
+        assert!(matches!(bigger_rx.try_recv().map(|m| m.msg), Ok(DatabaseMessage::Error { .. })));
+        let loaded = Database::load(&tempfile).expect("original should still be loadable");
+        assert_eq!(loaded.len(), 10);
+        std::fs::remove_dir(&partial).unwrap();
+
+        bigger.save(&tempfile).expect("Unable to save database");
+        assert!(matches!(bigger_rx.try_recv().map(|m| m.msg), Ok(DatabaseMessage::DataSaved { .. })));
+        assert_eq!(Database::load(&tempfile).unwrap().len(), 20);
+        assert!(!partial.exists());
+
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn cancellation() {
+        let cancel = AtomicBool::new(false);
+        let result = Database::create_random_with_progress(Some(&cancel), |done, _| {
+            if done == 100 {
+                cancel.store(true, AtomicOrdering::Relaxed);
+            }
+        });
+        assert!(matches!(result, Err(Error::Database(DatabaseError::Cancelled))));
+
+        let mut db = create_dev_db(10);
+        let before = db.get_all().to_vec();
+        let cancel = AtomicBool::new(false);
+        let incoming = create_dev_db(100).get_all().to_vec();
+        let result = db.import(
+            incoming.iter().cloned().enumerate().inspect(|(i, _)| {
+                if *i == 50 {
+                    cancel.store(true, AtomicOrdering::Relaxed);
+                }
+            }).map(|(_, n)| n),
+            Some(&cancel),
+        );
+        assert!(matches!(result, Err(Error::Database(DatabaseError::Cancelled))));
+        assert_eq!(db.get_all(), before.as_slice());
+
+        cancel.store(false, AtomicOrdering::Relaxed);
+        let result = db.replace_all(
+            incoming.iter().cloned().enumerate().inspect(|(i, _)| {
+                if *i == 50 {
+                    cancel.store(true, AtomicOrdering::Relaxed);
+                }
+            }).map(|(_, n)| n),
+            Some(&cancel),
+        );
+        assert!(matches!(result, Err(Error::Database(DatabaseError::Cancelled))));
+        assert_eq!(db.get_all(), before.as_slice());
+
+        assert_eq!(db.import(incoming.clone(), None).expect("import failed"), 100);
+        assert_eq!(db.len(), 110);
+        assert!(db.id_in_use(incoming[0].id()));
+
+        db.replace_all(incoming.clone(), None).expect("replace_all failed");
+        assert_eq!(db.len(), 100);
+        assert!(!db.id_in_use(before[0].id()));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn msgpack_round_trip() {
+        let db = create_dev_db(100);
+        let bytes = Persistence::save_to_bytes(&db, Method::MsgPack).expect("Unable to serialize database");
+        assert_eq!(Persistence::detect_method(&bytes), Some(Method::MsgPack));
+        let back: Database = Persistence::load_from_bytes(&bytes, Method::MsgPack).expect("Unable to deserialize database");
+        assert_eq!(back.len(), 100);
+        assert_eq!(back.get_all(), db.get_all());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn text_search_ranked() {
+        let db = Database::from_notes_vec(vec![
+            Note::create(("Groceries", "Buy milk and eggs.", vec!["errands"])),
+            Note::create(("Milk", "Remember the milk.", vec!["dairy"])),
+            Note::create(("Unrelated", "Nothing to see here.", vec!["misc"])),
+            Note::create(("Breakfast", "Eggs, toast, and some milk.", vec!["food"])),
+            Note::create(("Shopping", "Milk.", vec!["errands"])),
+        ])
+        .expect("Unable to create database");
+
+        let results = db.text_search_ranked("milk");
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|(n, _)| n.title() != "Unrelated"));
+        // Title matches are weighted higher than content matches.
+        assert_eq!(results[0].0.title(), "Milk");
+        assert!(results.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let results = db.text_search_ranked("milk eggs");
+        assert_eq!(results.len(), 4);
+        let shopping = results.iter().find(|(n, _)| n.title() == "Shopping").unwrap().1;
+        let breakfast = results.iter().find(|(n, _)| n.title() == "Breakfast").unwrap().1;
+        assert!(breakfast > shopping, "matching two terms should outrank matching one");
+
+        assert!(db.text_search_ranked("nonexistent").is_empty());
+        assert!(db.text_search_ranked("   ").is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn tag_styles() {
+        let mut db = create_dev_db(5);
+        assert!(db.tag_style("tag1").is_none());
+        assert_eq!(db.tag_style_or_default("tag1"), TagStyle::default_for("tag1"));
+
+        let style = TagStyle::new([255, 0, 0], Some("!".to_string()));
+        assert!(db.set_tag_style("tag1", style.clone()).is_none());
+        assert_eq!(db.tag_style("tag1"), Some(&style));
+        assert_eq!(db.tag_style_or_default("tag1"), style);
+        assert_eq!(db.tag_style_or_default("tag2"), TagStyle::default_for("tag2"));
+
+        for method in Method::working_methods() {
+            let bytes = Persistence::save_to_bytes(&db, method).expect("Unable to serialize database");
+            let back: Database = Persistence::load_from_bytes(&bytes, method).expect("Unable to deserialize database");
+            assert_eq!(back.tag_style("tag1"), Some(&style));
+        }
+
+        assert_eq!(db.remove_tag_style("tag1"), Some(style));
+        assert!(db.tag_style("tag1").is_none());
+    }
+
     #[test]
     #[ignore]
     #[no_coverage]
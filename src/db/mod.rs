@@ -8,6 +8,9 @@ mod file;
 mod msg;
 mod traits;
 
-pub use file::{Database, DtoResponse, UpdateFailurePolicy};
-pub use msg::DatabaseMessage;
+pub use file::{
+    Database, DatabaseDiff, DatabaseStats, DtoResponse, IntegrityIssue, IntegrityReport, MergeReport,
+    MergeStrategy, QueryPage, TagRenameReport, UpdateFailurePolicy, WELCOME_CONTENT, WELCOME_TITLE,
+};
+pub use msg::{DatabaseMessage, SequencedMessage};
 pub use traits::*;
@@ -10,6 +10,12 @@
 //! should be representable as a `DatabaseMessage`. From there we can code implementations that are able to perform and reverse
 //! each change.
 
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use crossbeam_channel::{Receiver, Sender};
 use serde::{Deserialize, Serialize};
 
 use crate::types::{CreateNote, DeleteNote, Note, UpdateNote};
@@ -18,8 +24,139 @@ use crate::types::{CreateNote, DeleteNote, Note, UpdateNote};
 pub enum DatabaseMessage {
     DataSaved { path: String },
     DataLoaded { path: String },
-    NoteCreated { dto: CreateNote, created: Note },
-    NoteUpdated { before: Note, after: Note },
+    /// A note was created. `new_tags` holds the tags of the note that no other note was using.
+    ///
+    /// `new_tags` only reports tags being added. Tags also go away, when the last note using one drops it in a
+    /// [`DatabaseMessage::NoteUpdated`] or is removed in a [`DatabaseMessage::NoteDeleted`], so consumers keeping
+    /// a list of tags have to check those messages as well rather than relying on `new_tags` alone.
+    NoteCreated { dto: CreateNote, created: Note, new_tags: Vec<String> },
+    /// A note was changed. `new_tags` holds the tags of `after` that neither `before` nor any other note was
+    /// using. Tags of `before` missing from `after` may no longer be used by any note, see
+    /// [`DatabaseMessage::NoteCreated`].
+    NoteUpdated { before: Note, after: Note, new_tags: Vec<String> },
+    /// A note was deleted. Its tags may no longer be used by any note, see [`DatabaseMessage::NoteCreated`].
     NoteDeleted { deleted: Note },
     Error { msg: String },
 }
+
+impl DatabaseMessage {
+    /// The name of this message's variant, e.g. `"NoteCreated"`, which is also the key it is serialized under.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::DataSaved { .. } => "DataSaved",
+            Self::DataLoaded { .. } => "DataLoaded",
+            Self::NoteCreated { .. } => "NoteCreated",
+            Self::NoteUpdated { .. } => "NoteUpdated",
+            Self::NoteDeleted { .. } => "NoteDeleted",
+            Self::Error { .. } => "Error",
+        }
+    }
+}
+
+/// A [`DatabaseMessage`] along with its position in the stream of messages sent by a
+/// [`Database`](super::Database). The sequence number is assigned when the change is made and is strictly
+/// increasing for as long as messaging stays enabled, so consumers replaying messages can sort and dedupe by it.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct SequencedMessage {
+    pub seq: u64,
+    pub msg: DatabaseMessage,
+}
+
+/// The channel used by a [`Database`](super::Database) to send [`SequencedMessage`]s, along with the sequence
+/// counter shared by every sender of the channel (including clones of the database).
+#[derive(Clone, Debug)]
+pub(super) struct MessageChannel {
+    sender: Sender<SequencedMessage>,
+    receiver: Receiver<SequencedMessage>,
+    next_seq: Arc<AtomicU64>,
+}
+
+impl MessageChannel {
+    pub(super) fn new() -> Self {
+        Self::continuing(Arc::new(AtomicU64::new(0)))
+    }
+
+    /// Creates a new channel that shares the sequence counter of `self`, used to buffer messages that are
+    /// later forwarded to `self` with [`MessageChannel::forward`].
+    pub(super) fn buffer(&self) -> Self {
+        Self::continuing(Arc::clone(&self.next_seq))
+    }
+
+    fn continuing(next_seq: Arc<AtomicU64>) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            sender,
+            receiver,
+            next_seq,
+        }
+    }
+
+    pub(super) fn receiver(&self) -> &Receiver<SequencedMessage> {
+        &self.receiver
+    }
+
+    /// Assigns the next sequence number to `msg` and sends it.
+    pub(super) fn send(&self, msg: DatabaseMessage) {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        self.forward(SequencedMessage { seq, msg });
+    }
+
+    /// Sends an already sequenced message as-is.
+    pub(super) fn forward(&self, msg: SequencedMessage) {
+        if let Err(err) = self.sender.send(msg) {
+            tracing::error!(error = ?err, "Failed to send database message");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn serialize_messages() {
+        let note = Note::create(("Title", "Content", vec!["tag"]));
+        let mut after = note.clone();
+        after.set_title("Changed");
+        let messages = vec![
+            DatabaseMessage::DataSaved {
+                path: "notes.fdb".to_string(),
+            },
+            DatabaseMessage::DataLoaded {
+                path: "notes.fdb".to_string(),
+            },
+            DatabaseMessage::NoteCreated {
+                dto: ("Title", "Content", vec!["tag"]).into(),
+                created: note.clone(),
+                new_tags: vec!["tag".to_string()],
+            },
+            DatabaseMessage::NoteUpdated {
+                before: note.clone(),
+                after,
+                new_tags: vec![],
+            },
+            DatabaseMessage::NoteDeleted { deleted: note.clone() },
+            DatabaseMessage::Error {
+                msg: "Something broke".to_string(),
+            },
+        ];
+
+        for (seq, msg) in messages.into_iter().enumerate() {
+            let sequenced = SequencedMessage { seq: seq as u64, msg };
+            let json = serde_json::to_value(&sequenced).expect("Unable to serialize message");
+            assert_eq!(json["seq"], seq);
+            let body = &json["msg"][sequenced.msg.kind()];
+            assert!(body.is_object(), "{} should be serialized under its kind: {}", sequenced.msg.kind(), json);
+            assert_eq!(serde_json::from_value::<SequencedMessage>(json).unwrap(), sequenced);
+        }
+
+        let json = serde_json::to_value(DatabaseMessage::NoteDeleted { deleted: note.clone() }).unwrap();
+        assert_eq!(json["NoteDeleted"]["deleted"]["id"], note.id().to_string());
+        assert_eq!(json["NoteDeleted"]["deleted"]["title"], "Title");
+        let json = serde_json::to_value(DatabaseMessage::Error { msg: "oops".to_string() }).unwrap();
+        assert_eq!(json, serde_json::json!({ "Error": { "msg": "oops" } }));
+    }
+}
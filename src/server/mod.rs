@@ -3,3 +3,412 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! `server` Module
+//!
+//! A minimal REST API over a single database:
+//! - `GET /notes` lists every note.
+//! - `GET /notes/:id` gets a single note.
+//! - `POST /notes` creates a note from a [`CreateNote`] and responds with `201 Created` and the new note.
+//! - `PUT /notes/:id` applies the fields of an [`UpdateNote`] to a note and responds with the updated note. The
+//!   id in the path is the note that is updated, an `id` in the body is ignored.
+//! - `DELETE /notes/:id` deletes a note and responds with it.
+//! - `GET /events` is a stream of server-sent events, one for each
+//!   [`DatabaseMessage`](crate::db::DatabaseMessage) caused by the requests above. The event name is the
+//!   message's [`kind`](crate::db::DatabaseMessage::kind) and the data is the [`SequencedMessage`] as JSON.
+//!
+//! Notes are sent and received as JSON. Errors are sent as `{"error": "..."}`, with `404 Not Found` for ids
+//! that don't exist and `400 Bad Request` for malformed ids and notes that fail validation. Bodies that can't be
+//! read as JSON get the status axum picks for them, e.g. `415 Unsupported Media Type` without a JSON
+//! `content-type` or `422 Unprocessable Entity` for JSON of the wrong shape.
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{rejection::JsonRejection, Extension, Path},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use tinyid::TinyId;
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+
+use crate::{
+    db::SequencedMessage,
+    services::NoteService,
+    types::{CreateNote, Note, Reminder, UpdateNote},
+    util::id::parse_url_segment,
+    DatabaseError, Error,
+};
+
+/// The number of messages kept for `/events` clients that are falling behind. A client that falls further
+/// behind skips the messages it missed.
+const EVENT_CAPACITY: usize = 256;
+
+/// The state shared by every request handler.
+#[derive(Clone)]
+struct ServerState {
+    service: Arc<Mutex<NoteService>>,
+    /// The messages sent by the database, forwarded to every `/events` client.
+    events: broadcast::Sender<SequencedMessage>,
+}
+
+impl ServerState {
+    /// The state for serving `service`, with its messaging enabled so that changes can be sent to `/events`.
+    fn new(mut service: NoteService) -> Self {
+        service.set_messaging(true);
+        Self {
+            service: Arc::new(Mutex::new(service)),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Runs `f` with the service, then forwards the messages sent by the database while it ran to the event
+    /// stream. The messages are drained even if no client is listening, so they don't pile up.
+    fn with_service<T>(&self, f: impl FnOnce(&mut NoteService) -> T) -> T {
+        let mut service = self.service.lock();
+        let result = f(&mut service);
+        if let Some(receiver) = service.database().get_receiver() {
+            for msg in receiver.try_iter() {
+                // This only fails when no client is listening.
+                let _ = self.events.send(msg);
+            }
+        }
+        result
+    }
+}
+
+/// Builds the [`Router`] for the API, serving the notes of `service`.
+pub fn router(service: NoteService) -> Router {
+    routes(ServerState::new(service))
+}
+
+fn routes(state: ServerState) -> Router {
+    Router::new()
+        .route("/notes", get(list_notes).post(create_note))
+        .route("/notes/:id", get(get_note).put(update_note).delete(delete_note))
+        .route("/events", get(events))
+        .layer(Extension(state))
+}
+
+/// Serves the API for `service` on `addr` until the server fails.
+///
+/// ## Errors
+/// - If the server cannot bind to `addr` or fails while running.
+pub async fn serve(service: NoteService, addr: SocketAddr) -> crate::Result {
+    tracing::info!("Serving notes on http://{}", addr);
+    axum::Server::bind(&addr)
+        .serve(router(service).into_make_service())
+        .await
+        .map_err(|err| Error::io(std::io::Error::other(err)))
+}
+
+/// An error response, sent as `{"error": "..."}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(err: Error) -> Self {
+        let status = match err {
+            Error::Database(DatabaseError::IdNotFound(_)) => StatusCode::NOT_FOUND,
+            Error::Validation(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let message = rejection.to_string();
+        Self {
+            status: rejection.into_response().status(),
+            message,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(serde_json::json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// The body of `PUT /notes/:id`, an [`UpdateNote`] without the id.
+#[derive(Debug, Default, Deserialize)]
+struct UpdateBody {
+    title: Option<String>,
+    content: Option<String>,
+    tags: Option<Vec<String>>,
+    reminders: Option<Vec<Reminder>>,
+}
+
+fn parse_id(id: &str) -> Result<TinyId, ApiError> {
+    parse_url_segment(id).ok_or_else(|| ApiError::bad_request(format!("'{}' is not a valid note id", id)))
+}
+
+async fn list_notes(Extension(state): Extension<ServerState>) -> Json<Vec<Note>> {
+    Json(state.service.lock().database().get_all().to_vec())
+}
+
+async fn get_note(Extension(state): Extension<ServerState>, Path(id): Path<String>) -> Result<Json<Note>, ApiError> {
+    let id = parse_id(&id)?;
+    Ok(Json(state.service.lock().database().get_clone(id)?))
+}
+
+async fn create_note(
+    Extension(state): Extension<ServerState>,
+    body: Result<Json<CreateNote>, JsonRejection>,
+) -> Result<(StatusCode, Json<Note>), ApiError> {
+    let Json(dto) = body?;
+    let note = state.with_service(|service| service.create(dto))?;
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+async fn update_note(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<String>,
+    body: Result<Json<UpdateBody>, JsonRejection>,
+) -> Result<Json<Note>, ApiError> {
+    let id = parse_id(&id)?;
+    let Json(body) = body?;
+    let dto = UpdateNote::new(id, body.title, body.content, body.tags, body.reminders);
+    let (note, _) = state.with_service(|service| service.update(dto))?;
+    Ok(Json(note))
+}
+
+async fn delete_note(
+    Extension(state): Extension<ServerState>,
+    Path(id): Path<String>,
+) -> Result<Json<Note>, ApiError> {
+    let id = parse_id(&id)?;
+    Ok(Json(state.with_service(|service| service.delete(id))?))
+}
+
+async fn events(Extension(state): Extension<ServerState>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(state.events.subscribe()).filter_map(|msg| msg.ok().and_then(|msg| event(&msg)));
+    Sse::new(stream.map(Ok)).keep_alive(KeepAlive::default())
+}
+
+/// The server-sent event for `msg`, or `None` if it can't be serialized.
+fn event(msg: &SequencedMessage) -> Option<Event> {
+    match Event::default().event(msg.msg.kind()).id(msg.seq.to_string()).json_data(msg) {
+        Ok(event) => Some(event),
+        Err(err) => {
+            tracing::error!(error = ?err, seq = msg.seq, "Failed to serialize database message");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use crate::db::{Database, DatabaseMessage};
+
+    /// Sends a request to `router` and returns the response status and JSON body.
+    async fn send(router: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .expect("Unable to build request");
+
+        let response = router.clone().oneshot(request).await.expect("Unable to send request");
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.expect("Unable to read body");
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    #[test]
+    #[no_coverage]
+    fn crud_routes() {
+        let runtime = tokio::runtime::Runtime::new().expect("Unable to create runtime");
+        runtime.block_on(async {
+            let existing = Note::create(("Existing", "Already here"));
+            let db = Database::from_notes_vec(vec![existing.clone()]).expect("Unable to create database");
+            let router = router(NoteService::new(db));
+
+            let (status, notes) = send(&router, "GET", "/notes", None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(notes.as_array().map(Vec::len), Some(1));
+            assert_eq!(notes[0]["title"], "Existing");
+
+            let uri = format!("/notes/{}", existing.id());
+            let (status, note) = send(&router, "GET", &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(note["id"], existing.id().to_string());
+            assert_eq!(note["content"], "Already here");
+
+            let (status, created) = send(
+                &router,
+                "POST",
+                "/notes",
+                Some(json!({ "title": "Created", "content": "Over HTTP", "tags": ["api"] })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::CREATED);
+            assert_eq!(created["title"], "Created");
+            assert_eq!(created["tags"], json!(["api"]));
+            let created_uri = format!("/notes/{}", created["id"].as_str().expect("id should be a string"));
+
+            let (status, updated) = send(&router, "PUT", &created_uri, Some(json!({ "title": "Renamed" }))).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(updated["title"], "Renamed");
+            assert_eq!(updated["content"], "Over HTTP");
+            assert_eq!(send(&router, "GET", &created_uri, None).await.1["title"], "Renamed");
+
+            let (status, deleted) = send(&router, "DELETE", &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(deleted["title"], "Existing");
+            let (_, notes) = send(&router, "GET", "/notes", None).await;
+            assert_eq!(notes.as_array().map(Vec::len), Some(1));
+            assert_eq!(notes[0]["title"], "Renamed");
+        });
+    }
+
+    #[test]
+    #[no_coverage]
+    fn error_responses() {
+        let runtime = tokio::runtime::Runtime::new().expect("Unable to create runtime");
+        runtime.block_on(async {
+            let router = router(NoteService::new(Database::without_messaging()));
+            let missing = format!("/notes/{}", TinyId::random());
+
+            for method in ["GET", "DELETE"] {
+                let (status, body) = send(&router, method, &missing, None).await;
+                assert_eq!(status, StatusCode::NOT_FOUND, "{}", method);
+                assert!(body["error"].as_str().unwrap().contains("not found"), "{}", body);
+            }
+            let (status, _) = send(&router, "PUT", &missing, Some(json!({ "title": "Nope" }))).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            let (status, body) = send(&router, "GET", "/notes/not-an-id", None).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "'not-an-id' is not a valid note id");
+
+            let (status, body) = send(&router, "POST", "/notes", Some(json!({ "title": " ", "content": "" }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["error"], "Invalid note, note title is empty");
+
+            // Bodies axum can't read are still sent as `{"error": "..."}`.
+            let (status, body) = send(&router, "POST", "/notes", Some(json!({ "title": 5 }))).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert!(body["error"].is_string(), "{}", body);
+            let (status, body) = send(&router, "POST", "/notes", None).await;
+            assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            assert!(body["error"].is_string(), "{}", body);
+            let (status, body) = send(&router, "PUT", &missing, Some(json!({ "tags": "not a list" }))).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert!(body["error"].is_string(), "{}", body);
+
+            let (_, created) = send(&router, "POST", "/notes", Some(json!({ "title": "Valid" }))).await;
+            let uri = format!("/notes/{}", created["id"].as_str().unwrap());
+            let (status, _) = send(&router, "PUT", &uri, Some(json!({ "tags": ["a", "a"] }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            let (_, notes) = send(&router, "GET", "/notes", None).await;
+            assert_eq!(notes.as_array().map(Vec::len), Some(1));
+        });
+    }
+
+    #[test]
+    #[no_coverage]
+    fn mutations_are_sent_as_events() {
+        let runtime = tokio::runtime::Runtime::new().expect("Unable to create runtime");
+        runtime.block_on(async {
+            let state = ServerState::new(NoteService::new(Database::without_messaging()));
+            let mut events = state.events.subscribe();
+            let router = routes(state);
+
+            let (_, created) = send(&router, "POST", "/notes", Some(json!({ "title": "Watched" }))).await;
+            let uri = format!("/notes/{}", created["id"].as_str().unwrap());
+            send(&router, "PUT", &uri, Some(json!({ "content": "Changed" }))).await;
+            send(&router, "DELETE", &uri, None).await;
+            // Failed requests don't change anything, so they don't send anything.
+            send(&router, "POST", "/notes", Some(json!({ "title": "" }))).await;
+
+            let mut received = Vec::new();
+            while let Ok(msg) = events.try_recv() {
+                received.push(msg);
+            }
+            assert_eq!(
+                received.iter().map(|msg| msg.msg.kind()).collect::<Vec<_>>(),
+                ["NoteCreated", "NoteUpdated", "NoteDeleted"]
+            );
+            assert!(received.windows(2).all(|pair| pair[0].seq < pair[1].seq));
+            match &received[1].msg {
+                DatabaseMessage::NoteUpdated { before, after, .. } => {
+                    assert_eq!(before.content(), "");
+                    assert_eq!(after.content(), "Changed");
+                }
+                msg => panic!("Expected NoteUpdated, got {:?}", msg),
+            }
+        });
+    }
+
+    #[test]
+    #[no_coverage]
+    fn events_endpoint_streams_messages() {
+        use hyper::body::HttpBody;
+
+        let runtime = tokio::runtime::Runtime::new().expect("Unable to create runtime");
+        runtime.block_on(async {
+            let router = router(NoteService::new(Database::without_messaging()));
+            let request = Request::builder().uri("/events").body(Body::empty()).unwrap();
+            let response = router.clone().oneshot(request).await.expect("Unable to send request");
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+            let (_, created) = send(&router, "POST", "/notes", Some(json!({ "title": "Streamed" }))).await;
+
+            let mut body = response.into_body();
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.data())
+                .await
+                .expect("No event was sent")
+                .expect("The stream ended")
+                .expect("Unable to read event");
+            let text = String::from_utf8(chunk.to_vec()).unwrap();
+            assert!(text.contains("event: NoteCreated\n"), "{}", text);
+            assert!(text.contains("id: 0\n"), "{}", text);
+
+            let data = text
+                .lines()
+                .find_map(|line| line.strip_prefix("data: "))
+                .expect("The event has no data");
+            let msg = serde_json::from_str::<Value>(data).expect("The event data is not JSON");
+            assert_eq!(msg["seq"], 0);
+            assert_eq!(msg["msg"]["NoteCreated"]["created"]["id"], created["id"]);
+        });
+    }
+}
@@ -5,5 +5,8 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 mod input;
+mod note;
 mod output;
 mod repo;
+
+pub use note::NoteService;
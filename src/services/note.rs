@@ -0,0 +1,335 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::path::{Path, PathBuf};
+
+use tinyid::TinyId;
+
+use crate::{
+    db::{Database, QueryPage},
+    types::{
+        api::{Filter, Ordering},
+        ChangedFields, CreateNote, DeleteNote, FlagNote, Note, TagNormalizer, UpdateNote,
+    },
+    util::validation::NoteValidator,
+    Result,
+};
+
+/// The layer between the frontends and the [`Database`]. Every change is validated with a [`NoteValidator`]
+/// before it is applied, and when the service was opened from a file, every change is saved to that file as
+/// soon as it is applied, so the frontends don't each have to remember to. Changes that leave the database
+/// as it was are not saved.
+#[derive(Debug)]
+pub struct NoteService {
+    db: Database,
+    /// The file every change is saved to, if any.
+    path: Option<PathBuf>,
+    validator: NoteValidator,
+}
+
+impl NoteService {
+    /// A service for `db` that never saves it, the owner of the database is responsible for that.
+    #[must_use]
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            path: None,
+            validator: NoteValidator::default(),
+        }
+    }
+
+    /// A service for the database at `path` that saves every change back to it. If there is no file at `path`
+    /// yet, the service starts with an empty database and creates the file (and its parent directory) on the
+    /// first change.
+    ///
+    /// ## Errors
+    /// - If the file exists but fails to load, see [`Database::load`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        crate::flame_guard!("services", "note", "open");
+        let path = path.as_ref();
+        let db = if path.exists() {
+            Database::load(path)?
+        } else {
+            Database::without_messaging()
+        };
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            ..Self::new(db)
+        })
+    }
+
+    /// Replaces the [`NoteValidator`] changes are checked with.
+    #[must_use]
+    pub fn with_validator(mut self, validator: NoteValidator) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    #[must_use]
+    pub fn database(&self) -> &Database {
+        &self.db
+    }
+
+    #[must_use]
+    pub fn into_database(self) -> Database {
+        self.db
+    }
+
+    /// The file every change is saved to, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Sets the [`TagNormalizer`] applied to the tags of every change, see [`Database::set_tag_normalizer`].
+    pub fn set_tag_normalizer(&mut self, normalizer: TagNormalizer) {
+        self.db.set_tag_normalizer(normalizer);
+    }
+
+    /// Enables or disables the [`DatabaseMessage`](crate::db::DatabaseMessage)s sent by the database, see
+    /// [`Database::set_messaging`].
+    pub fn set_messaging(&mut self, enabled: bool) {
+        self.db.set_messaging(enabled);
+    }
+
+    /// Validates and creates a new [`Note`], returning it.
+    ///
+    /// ## Errors
+    /// - [`Error::Validation`](crate::Error::Validation) if the note is invalid.
+    /// - See [`Database::apply_create`].
+    /// - If the database fails to save.
+    pub fn create(&mut self, dto: impl Into<CreateNote>) -> Result<Note> {
+        crate::flame_guard!("services", "note", "create");
+        let dto = dto.into();
+        self.validator.validate_create(&dto)?;
+        let note = self.db.apply_create(dto)?;
+        self.save()?;
+        Ok(note)
+    }
+
+    /// Validates and applies an update to an existing [`Note`], returning the note as it is afterwards along
+    /// with what changed. Nothing is saved if nothing changed.
+    ///
+    /// ## Errors
+    /// - [`Error::Validation`](crate::Error::Validation) if the changed fields are invalid.
+    /// - See [`Database::apply_update`].
+    /// - If the database fails to save.
+    pub fn update(&mut self, dto: impl Into<UpdateNote>) -> Result<(Note, ChangedFields)> {
+        crate::flame_guard!("services", "note", "update");
+        let dto = dto.into();
+        self.validator.validate_update(&dto)?;
+        let (note, changes) = self.db.apply_update(dto)?;
+        self.save()?;
+        Ok((note, changes))
+    }
+
+    /// Sets or clears a flag on an existing [`Note`], returning the note as it is afterwards. Nothing is saved
+    /// if the flag was already set that way.
+    ///
+    /// ## Errors
+    /// - See [`Database::apply_flag`].
+    /// - If the database fails to save.
+    pub fn flag(&mut self, dto: impl Into<FlagNote>) -> Result<Note> {
+        crate::flame_guard!("services", "note", "flag");
+        let dto = dto.into();
+        let id = *dto.id();
+        self.db.apply_flag(dto)?;
+        self.save()?;
+        self.db.get_clone(id)
+    }
+
+    /// Validates and creates a [`Note`] for each of `dtos`, returning them in order. Every dto is validated
+    /// before any note is created, and the database is only saved once.
+    ///
+    /// ## Errors
+    /// - [`Error::Validation`](crate::Error::Validation) for the first invalid dto, nothing is created.
+    /// - See [`Database::apply_create`].
+    /// - If the database fails to save.
+    pub fn import(&mut self, dtos: impl IntoIterator<Item = CreateNote>) -> Result<Vec<Note>> {
+        crate::flame_guard!("services", "note", "import");
+        let dtos = dtos.into_iter().collect::<Vec<_>>();
+        for dto in &dtos {
+            self.validator.validate_create(dto)?;
+        }
+        let notes = dtos
+            .into_iter()
+            .map(|dto| self.db.apply_create(dto))
+            .collect::<Result<Vec<_>>>()?;
+        self.save()?;
+        Ok(notes)
+    }
+
+    /// Writes the edits made directly to `note`, e.g. by an editor, back to the database, see
+    /// [`Database::ensure_sync`]. A dirty note is validated first, and a note marked for deletion is deleted.
+    ///
+    /// ## Errors
+    /// - [`Error::Validation`](crate::Error::Validation) if the edited note is invalid, nothing is changed.
+    /// - If the database fails to save.
+    pub fn sync(&mut self, note: &mut Note) -> Result {
+        crate::flame_guard!("services", "note", "sync");
+        if note.dirty() && !note.pending_delete() {
+            let edited = UpdateNote::from((
+                note.id(),
+                note.title().to_string(),
+                note.content().to_string(),
+                note.tags().to_vec(),
+            ));
+            self.validator.validate_update(&edited)?;
+        }
+        self.db.ensure_sync(note);
+        self.save()
+    }
+
+    /// Deletes the [`Note`] with the given `id`, returning it.
+    ///
+    /// ## Errors
+    /// - See [`Database::apply_delete`].
+    /// - If the database fails to save.
+    pub fn delete(&mut self, id: TinyId) -> Result<Note> {
+        crate::flame_guard!("services", "note", "delete");
+        let note = self.db.apply_delete(DeleteNote::new(id))?;
+        self.save()?;
+        Ok(note)
+    }
+
+    /// The notes matching `query`, best match first, see [`Database::text_search_ranked`].
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<&Note> {
+        self.db
+            .text_search_ranked(query)
+            .into_iter()
+            .map(|(note, _)| note)
+            .collect()
+    }
+
+    /// A single page of the notes matching `filter`, sorted by `order`, see [`Database::query`].
+    #[must_use]
+    pub fn list_paged(&self, filter: &Filter, order: Ordering, page: usize, per_page: usize) -> QueryPage<'_> {
+        self.db.query(filter, order, page, per_page)
+    }
+
+    /// Every tag in use along with the number of notes using it, sorted by tag.
+    #[must_use]
+    pub fn tags(&self) -> Vec<(String, usize)> {
+        let mut tags = self.db.get_all_tags_and_counts();
+        tags.sort_unstable();
+        tags
+    }
+
+    /// Saves the database to [`NoteService::path`] if there is one and the database has unsaved changes,
+    /// creating the parent directory if needed.
+    fn save(&self) -> Result {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !self.db.is_modified() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() && !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+        self.db.save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::NoteFlag, util::validation::NoteValidationError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn create_search_delete() {
+        let dir = std::env::temp_dir().join(format!("service-tests-{:010}", fastrand::u32(..)));
+        let path = dir.join("notes.fdb");
+        let mut service = NoteService::open(&path).expect("Unable to open service");
+        assert_eq!(service.path(), Some(path.as_path()));
+        assert!(!path.exists());
+
+        let groceries = service
+            .create(("Groceries", "Milk and eggs", vec!["errands"]))
+            .expect("Unable to create note");
+        service
+            .create(("Recipes", "Needs eggs", vec!["cooking", "errands"]))
+            .expect("Unable to create note");
+        assert!(path.exists(), "creating a note should save the database");
+
+        let titles = |notes: Vec<&Note>| notes.iter().map(|note| note.title().to_string()).collect::<Vec<_>>();
+        assert_eq!(titles(service.search("eggs")), ["Groceries", "Recipes"]);
+        assert_eq!(titles(service.search("milk")), ["Groceries"]);
+        assert_eq!(service.tags(), [("cooking".to_string(), 1), ("errands".to_string(), 2)]);
+
+        let page = service.list_paged(&Filter::default(), Ordering::default(), 0, 1);
+        assert_eq!((page.notes.len(), page.total_matched, page.total_pages), (1, 2, 2));
+
+        let (updated, changes) = service
+            .update(UpdateNote::empty(groceries.id()).with_title(Some("Shopping".to_string())))
+            .expect("Unable to update note");
+        assert_eq!(updated.title(), "Shopping");
+        assert!(changes.title());
+        assert_eq!(Database::load(&path).unwrap().get(groceries.id()).unwrap().title(), "Shopping");
+
+        let deleted = service.delete(groceries.id()).expect("Unable to delete note");
+        assert_eq!(deleted.id(), groceries.id());
+        assert_eq!(titles(service.search("eggs")), ["Recipes"]);
+        assert!(service.delete(groceries.id()).is_err(), "the note is already deleted");
+
+        let reloaded = NoteService::open(&path).expect("Unable to reopen service");
+        assert_eq!(reloaded.database().len(), 1);
+        assert_eq!(titles(reloaded.search("milk")), Vec::<String>::new());
+
+        let recipes = service.search("eggs")[0].id();
+        assert!(service.flag(FlagNote::new(recipes, NoteFlag::Pinned, true)).unwrap().is_pinned());
+        assert!(NoteService::open(&path).unwrap().database().get(recipes).unwrap().is_pinned());
+
+        let imported = service
+            .import(vec![CreateNote::from(("Imported", "")), CreateNote::from(("Another", ""))])
+            .expect("Unable to import notes");
+        assert_eq!(titles(imported.iter().collect()), ["Imported", "Another"]);
+        assert!(service.import(vec![CreateNote::from(("Valid", "")), CreateNote::from(("", "No title"))]).is_err());
+        assert_eq!(NoteService::open(&path).unwrap().database().len(), 3, "invalid imports create nothing");
+
+        let mut edited = imported[0].clone();
+        edited.set_title("Edited");
+        service.sync(&mut edited).expect("Unable to sync note");
+        assert!(!edited.dirty());
+        assert_eq!(Database::load(&path).unwrap().get(edited.id()).unwrap().title(), "Edited");
+        edited.set_title(" ");
+        assert!(matches!(
+            service.sync(&mut edited),
+            Err(crate::Error::Validation(NoteValidationError::EmptyTitle))
+        ));
+        assert_eq!(service.database().get(edited.id()).unwrap().title(), "Edited");
+
+        std::fs::remove_dir_all(dir).expect("Unable to delete temp dir");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn rejects_invalid_notes() {
+        let mut service = NoteService::new(Database::without_messaging()).with_validator(NoteValidator::new(10));
+        assert_eq!(service.path(), None);
+
+        assert!(matches!(
+            service.create(("  ", "No title")),
+            Err(crate::Error::Validation(NoteValidationError::EmptyTitle))
+        ));
+        assert!(matches!(
+            service.create(("A title that is too long", "")),
+            Err(crate::Error::Validation(NoteValidationError::TitleTooLong { len: 24, max: 10 }))
+        ));
+        assert!(service.database().is_empty(), "invalid notes should not be created");
+
+        let note = service.create(("Valid", "", vec!["tag"])).expect("Unable to create note");
+        let update = UpdateNote::empty(note.id()).with_tags(Some(vec!["two words".to_string()]));
+        assert!(matches!(
+            service.update(update),
+            Err(crate::Error::Validation(NoteValidationError::TagContainsWhitespace(tag))) if tag == "two words"
+        ));
+        assert_eq!(service.database().get(note.id()).unwrap().tags(), &["tag"]);
+    }
+}
@@ -7,7 +7,7 @@
 use crate::{
     db::{Database, MultiValueArgs},
     types::api::{Count, Filter, Ordering},
-    types::{CreateNote, DeleteNote, Note, UpdateNote},
+    types::{ChangedFields, CreateNote, DeleteNote, Note, UpdateNote},
     Error, Result,
 };
 
@@ -30,12 +30,7 @@ impl Repository {
         let comp = args.order.comparison();
         let pred = args.filter.predicate();
         all.sort_unstable_by(comp);
-        Ok(all
-            .iter()
-            .filter(pred)
-            .take(args.count.to_usize())
-            .cloned()
-            .collect())
+        Ok(args.count.apply(all.iter().filter(pred)).cloned().collect())
     }
 
     /// TODO: Interior Mutability?
@@ -44,12 +39,12 @@ impl Repository {
     }
 
     /// TODO: Interior Mutability?
-    pub fn update(&mut self, dto: impl Into<UpdateNote>) -> Result<bool> {
+    pub fn update(&mut self, dto: impl Into<UpdateNote>) -> Result<(Note, ChangedFields)> {
         self.db.apply_update(dto)
     }
 
     /// TODO: Interior Mutability?
-    pub fn delete(&mut self, dto: impl Into<DeleteNote>) -> Result<bool> {
+    pub fn delete(&mut self, dto: impl Into<DeleteNote>) -> Result<Note> {
         self.db.apply_delete(dto)
     }
 }
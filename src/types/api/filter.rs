@@ -6,15 +6,17 @@
 
 //! TODO: Clean up this file, maybe separate or at least better organize the types.
 
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::types::Note;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NoteStringField {
     Title,
     Content,
     Tags,
+    ReminderLabels,
 }
 
 impl NoteStringField {
@@ -25,11 +27,17 @@ impl NoteStringField {
             NoteStringField::Title => note.title().to_string(),
             NoteStringField::Content => note.content().to_string(),
             NoteStringField::Tags => note.tags().join(","),
+            NoteStringField::ReminderLabels => note
+                .reminders()
+                .iter()
+                .filter_map(crate::types::Reminder::label)
+                .collect::<Vec<_>>()
+                .join(","),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NoteDateField {
     Created,
     Updated,
@@ -45,16 +53,31 @@ impl NoteDateField {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct StringSearchArgs {
     text: String,
     invert: bool,
+    #[serde(default)]
+    ignore_case: bool,
 }
 
 impl StringSearchArgs {
     #[must_use]
     pub fn new(text: String, invert: bool) -> StringSearchArgs {
-        StringSearchArgs { text, invert }
+        StringSearchArgs {
+            text,
+            invert,
+            ignore_case: false,
+        }
+    }
+
+    #[must_use]
+    pub fn new_ci(text: String, invert: bool) -> StringSearchArgs {
+        StringSearchArgs {
+            text,
+            invert,
+            ignore_case: true,
+        }
     }
 
     #[must_use]
@@ -67,6 +90,11 @@ impl StringSearchArgs {
         self.invert
     }
 
+    #[must_use]
+    pub fn ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+
     pub fn set_title(&mut self, title: String) {
         self.text = title;
     }
@@ -78,9 +106,22 @@ impl StringSearchArgs {
     pub fn toggle_invert(&mut self) {
         self.invert = !self.invert;
     }
+
+    pub fn set_ignore_case(&mut self, ignore_case: bool) {
+        self.ignore_case = ignore_case;
+    }
+
+    /// Compares `value` to the search text using `cmp`, lowercasing both first if the search ignores case.
+    fn compare(&self, value: &str, cmp: impl Fn(&str, &str) -> bool) -> bool {
+        if self.ignore_case {
+            cmp(&value.to_lowercase(), &self.text.to_lowercase())
+        } else {
+            cmp(value, &self.text)
+        }
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum StringSearch {
     Contains(StringSearchArgs),
     Matches(StringSearchArgs),
@@ -95,6 +136,12 @@ impl StringSearch {
         Self::Contains(StringSearchArgs::new(text, invert))
     }
 
+    /// Like [`StringSearch::contains`], but ignoring case.
+    #[must_use]
+    pub fn contains_ci(text: String, invert: bool) -> Self {
+        Self::Contains(StringSearchArgs::new_ci(text, invert))
+    }
+
     #[must_use]
     pub fn matches(text: String, invert: bool) -> Self {
         Self::Matches(StringSearchArgs::new(text, invert))
@@ -151,9 +198,31 @@ impl StringSearch {
             | StringSearch::Contains(args) => args.text(),
         }
     }
+
+    /// Whether `value` matches the search, without taking [`StringSearch::invert`] into account.
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            StringSearch::Contains(args) => args.compare(value, |value, text| value.contains(text)),
+            StringSearch::Matches(args) => args.compare(value, |value, text| value == text),
+            StringSearch::StartsWith(args) => args.compare(value, |value, text| value.starts_with(text)),
+            StringSearch::EndsWith(args) => args.compare(value, |value, text| value.ends_with(text)),
+        }
+    }
+
+    /// Whether `value` passes the search.
+    #[must_use]
+    pub fn test(&self, value: &str) -> bool {
+        self.is_match(value) != self.invert()
+    }
+
+    /// Whether any of `values` matches the search, or if the search is inverted, whether none of them do.
+    #[must_use]
+    pub fn test_any<S: AsRef<str>>(&self, values: &[S]) -> bool {
+        values.iter().any(|value| self.is_match(value.as_ref())) != self.invert()
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum DateSearch {
     Before(OffsetDateTime),
     After(OffsetDateTime),
@@ -190,10 +259,13 @@ impl DateSearch {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum NoteFilter {
     String(NoteStringField, StringSearch),
     Date(NoteDateField, DateSearch),
+    /// Searches each of a note's tags on its own rather than the comma-joined tag list searched by
+    /// `String(NoteStringField::Tags, _)`, so e.g. `Matches` finds the notes carrying exactly that tag.
+    AnyTag(StringSearch),
 }
 
 pub type Predicate = Box<dyn Fn(&&Note) -> bool + Send + Sync>;
@@ -232,6 +304,16 @@ impl NoteFilter {
         NoteFilter::String(NoteStringField::Tags, search)
     }
 
+    #[must_use]
+    pub fn any_tag(search: StringSearch) -> Self {
+        NoteFilter::AnyTag(search)
+    }
+
+    #[must_use]
+    pub fn reminder_label(search: StringSearch) -> Self {
+        NoteFilter::String(NoteStringField::ReminderLabels, search)
+    }
+
     #[must_use]
     pub fn created(search: DateSearch) -> Self {
         NoteFilter::Date(NoteDateField::Created, search)
@@ -250,40 +332,12 @@ impl NoteFilter {
         match self {
             NoteFilter::String(field, ss) => {
                 let field = *field;
-                match ss.clone() {
-                    StringSearch::Contains(args) => box move |&note| {
-                        let value = field.get_value(note);
-                        if args.invert() {
-                            !value.contains(args.text())
-                        } else {
-                            value.contains(args.text())
-                        }
-                    },
-                    StringSearch::Matches(args) => box move |&note| {
-                        let value = field.get_value(note);
-                        if args.invert() {
-                            value != args.text()
-                        } else {
-                            value == args.text()
-                        }
-                    },
-                    StringSearch::StartsWith(args) => box move |&note| {
-                        let value = field.get_value(note);
-                        if args.invert() {
-                            !value.starts_with(args.text())
-                        } else {
-                            value.starts_with(args.text())
-                        }
-                    },
-                    StringSearch::EndsWith(args) => box move |&note| {
-                        let value = field.get_value(note);
-                        if args.invert() {
-                            !value.ends_with(args.text())
-                        } else {
-                            value.ends_with(args.text())
-                        }
-                    },
-                }
+                let ss = ss.clone();
+                box move |&note| ss.test(&field.get_value(note))
+            }
+            NoteFilter::AnyTag(ss) => {
+                let ss = ss.clone();
+                box move |&note| ss.test_any(note.tags())
             }
             NoteFilter::Date(field, ds) => {
                 let field = *field;
@@ -313,6 +367,21 @@ impl NoteFilter {
         }
     }
 
+    /// Whether this filter matches every [`Note`] and can therefore be dropped from a [`Filter`],
+    /// e.g. a non-inverted `Contains("")`.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        match self {
+            NoteFilter::String(_, ss) | NoteFilter::AnyTag(ss) => match ss {
+                StringSearch::Contains(args)
+                | StringSearch::StartsWith(args)
+                | StringSearch::EndsWith(args) => args.text().is_empty() && !args.invert(),
+                StringSearch::Matches(_) => false,
+            },
+            NoteFilter::Date(_, _) => false,
+        }
+    }
+
     pub fn set_string_search(&mut self, field: NoteStringField, ss: StringSearch) {
         *self = NoteFilter::String(field, ss);
     }
@@ -324,7 +393,7 @@ impl NoteFilter {
     #[must_use]
     pub fn string_search(&self) -> Option<&StringSearch> {
         match self {
-            NoteFilter::String(_, ss) => Some(ss),
+            NoteFilter::String(_, ss) | NoteFilter::AnyTag(ss) => Some(ss),
             NoteFilter::Date(_, _) => None,
         }
     }
@@ -333,7 +402,7 @@ impl NoteFilter {
     pub fn date_search(&self) -> Option<&DateSearch> {
         match self {
             NoteFilter::Date(_, ds) => Some(ds),
-            NoteFilter::String(_, _) => None,
+            NoteFilter::String(_, _) | NoteFilter::AnyTag(_) => None,
         }
     }
 
@@ -341,6 +410,7 @@ impl NoteFilter {
     pub fn string_field(&self) -> Option<&NoteStringField> {
         match self {
             NoteFilter::String(f, _) => Some(f),
+            NoteFilter::AnyTag(_) => Some(&NoteStringField::Tags),
             NoteFilter::Date(_, _) => None,
         }
     }
@@ -349,7 +419,7 @@ impl NoteFilter {
     pub fn date_field(&self) -> Option<&NoteDateField> {
         match self {
             NoteFilter::Date(f, _) => Some(f),
-            NoteFilter::String(_, _) => None,
+            NoteFilter::String(_, _) | NoteFilter::AnyTag(_) => None,
         }
     }
     /////////////////////////////////////////
@@ -357,7 +427,7 @@ impl NoteFilter {
     #[must_use]
     pub fn string_search_mut(&mut self) -> Option<&mut StringSearch> {
         match self {
-            NoteFilter::String(_, ss) => Some(ss),
+            NoteFilter::String(_, ss) | NoteFilter::AnyTag(ss) => Some(ss),
             NoteFilter::Date(_, _) => None,
         }
     }
@@ -366,7 +436,7 @@ impl NoteFilter {
     pub fn date_search_mut(&mut self) -> Option<&mut DateSearch> {
         match self {
             NoteFilter::Date(_, ds) => Some(ds),
-            NoteFilter::String(_, _) => None,
+            NoteFilter::String(_, _) | NoteFilter::AnyTag(_) => None,
         }
     }
 
@@ -374,7 +444,7 @@ impl NoteFilter {
     pub fn string_field_mut(&mut self) -> Option<&mut NoteStringField> {
         match self {
             NoteFilter::String(f, _) => Some(f),
-            NoteFilter::Date(_, _) => None,
+            NoteFilter::Date(_, _) | NoteFilter::AnyTag(_) => None,
         }
     }
 
@@ -382,12 +452,12 @@ impl NoteFilter {
     pub fn date_field_mut(&mut self) -> Option<&mut NoteDateField> {
         match self {
             NoteFilter::Date(f, _) => Some(f),
-            NoteFilter::String(_, _) => None,
+            NoteFilter::String(_, _) | NoteFilter::AnyTag(_) => None,
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Filter {
     filters: Vec<NoteFilter>,
 }
@@ -451,28 +521,123 @@ impl Filter {
         &self.filters
     }
 
+    /// Removes duplicate [`NoteFilter`]s (keeping the first occurrence) and drops any filters
+    /// that match every note (see [`NoteFilter::is_noop`]), so the generated predicate is minimal.
+    pub fn normalize(&mut self) {
+        crate::flame_guard!("types", "api", "filter", "Filter", "normalize");
+        let mut seen = std::collections::HashSet::with_capacity(self.filters.len());
+        self.filters
+            .retain(|filter| !filter.is_noop() && seen.insert(filter.clone()));
+    }
+
+    /// Converts this [`Filter`] into the equivalent [`FilterExpr`], which is an
+    /// [`FilterExpr::And`] of all contained filters.
+    #[must_use]
+    pub fn to_expr(&self) -> FilterExpr {
+        crate::flame_guard!("types", "api", "filter", "Filter", "to_expr");
+        FilterExpr::And(self.filters.iter().cloned().map(FilterExpr::Leaf).collect())
+    }
+
     #[must_use]
     pub fn predicate(&self) -> Predicate {
         crate::flame_guard!("types", "api", "filter", "Filter", "predicate");
-        if self.filters.is_empty() {
-            return box |_| true;
-        }
+        self.to_expr().predicate()
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// A boolean expression tree of [`NoteFilter`]s.
+///
+/// An empty [`FilterExpr::And`] matches every note, while an empty [`FilterExpr::Or`]
+/// matches nothing.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum FilterExpr {
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Leaf(NoteFilter),
+}
+
+/// Constructors
+impl FilterExpr {
+    #[must_use]
+    pub fn and(exprs: Vec<FilterExpr>) -> Self {
+        Self::And(exprs)
+    }
 
-        let mut predicates = Vec::new();
-        for filter in &self.filters {
-            predicates.push(filter.predicate());
+    #[must_use]
+    pub fn or(exprs: Vec<FilterExpr>) -> Self {
+        Self::Or(exprs)
+    }
+
+    #[must_use]
+    pub fn not(expr: FilterExpr) -> Self {
+        Self::Not(box expr)
+    }
+
+    #[must_use]
+    pub fn leaf(filter: NoteFilter) -> Self {
+        Self::Leaf(filter)
+    }
+}
+
+/// Member Functions
+impl FilterExpr {
+    #[must_use]
+    pub fn predicate(&self) -> Predicate {
+        crate::flame_guard!("types", "api", "filter", "FilterExpr", "predicate");
+        match self {
+            FilterExpr::And(exprs) => {
+                let predicates = exprs.iter().map(FilterExpr::predicate).collect::<Vec<_>>();
+                box move |note| predicates.iter().all(|pred| pred(note))
+            }
+            FilterExpr::Or(exprs) => {
+                let predicates = exprs.iter().map(FilterExpr::predicate).collect::<Vec<_>>();
+                box move |note| predicates.iter().any(|pred| pred(note))
+            }
+            FilterExpr::Not(expr) => {
+                let predicate = expr.predicate();
+                box move |note| !predicate(note)
+            }
+            FilterExpr::Leaf(filter) => filter.predicate(),
         }
+    }
+}
 
-        box move |note| predicates.iter().all(|pred| pred(note))
+impl From<NoteFilter> for FilterExpr {
+    fn from(filter: NoteFilter) -> Self {
+        Self::Leaf(filter)
     }
 }
 
-impl Default for Filter {
+impl From<Filter> for FilterExpr {
+    fn from(filter: Filter) -> Self {
+        Self::And(filter.filters.into_iter().map(FilterExpr::Leaf).collect())
+    }
+}
+
+impl From<&Filter> for FilterExpr {
+    fn from(filter: &Filter) -> Self {
+        filter.to_expr()
+    }
+}
+
+impl Default for FilterExpr {
     fn default() -> Self {
-        Self::empty()
+        Self::And(Vec::new())
     }
 }
 
+/// How many results a query should return.
+///
+/// [`Count::All`] means there is no limit at all, it is not the same as a very large cap. Prefer
+/// [`Count::apply`] over `take(count.to_usize())` so that [`Count::All`] never goes through the
+/// [`usize::MAX`] sentinel.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Count {
     All,
@@ -481,6 +646,25 @@ pub enum Count {
 }
 
 impl Count {
+    /// Limits `iter` to the number of items represented by this [`Count`]. [`Count::All`] returns
+    /// `iter` untouched rather than taking [`usize::MAX`] items from it.
+    #[must_use]
+    pub fn apply<'a, I>(self, iter: I) -> Box<dyn Iterator<Item = I::Item> + 'a>
+    where
+        I: Iterator + 'a,
+    {
+        match self {
+            Count::All => Box::new(iter),
+            Count::Some(n) => Box::new(iter.take(n)),
+            Count::One => Box::new(iter.take(1)),
+        }
+    }
+
+    /// Converts this [`Count`] into the maximum number of items it represents, with
+    /// [`Count::All`] being [`usize::MAX`] and `Count::Some(0)` being zero.
+    ///
+    /// Note that [`usize::MAX`] converts back into [`Count::All`] (see the `From<usize>` impl), so
+    /// this is only lossless when used as an upper bound.
     #[must_use]
     pub fn to_usize(&self) -> usize {
         match self {
@@ -491,10 +675,11 @@ impl Count {
     }
 }
 
+/// `0` is a real zero-count (`Count::Some(0)`), only [`usize::MAX`] maps to [`Count::All`].
 impl From<usize> for Count {
     fn from(count: usize) -> Self {
         match count {
-            0 | usize::MAX => Count::All,
+            usize::MAX => Count::All,
             1 => Count::One,
             _ => Count::Some(count),
         }
@@ -562,6 +747,64 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    #[no_coverage]
+    fn database_query() {
+        let notes = create_notes();
+        let db = crate::db::Database::from_notes_vec(notes.clone()).expect("Unable to create database");
+        let order = Ordering::ascending(crate::types::api::OrderBy::Title);
+        let titles = |page: &crate::db::QueryPage<'_>| {
+            page.notes.iter().map(|n| n.title().to_string()).collect::<Vec<_>>()
+        };
+
+        let first = db.query(&Filter::empty(), order, 0, 2);
+        assert_eq!(first.total_matched, 6);
+        assert_eq!(first.total_pages, 3);
+        assert_eq!(titles(&first), vec!["A Title", "Gooooo Title"]);
+
+        let last = db.query(&Filter::empty(), order, 2, 2);
+        assert_eq!(last.total_pages, 3);
+        assert_eq!(titles(&last), vec!["This is Title", "Title Goes Here"]);
+
+        let past_end = db.query(&Filter::empty(), order, 3, 2);
+        assert!(past_end.notes.is_empty());
+        assert_eq!(past_end.total_matched, 6);
+        assert_eq!(past_end.total_pages, 3);
+
+        // Filtering happens before pagination, so the page counts describe the filtered notes.
+        let filter = Filter::single(NoteFilter::tag(StringSearch::contains("tag1".to_string(), false)));
+        let filtered = db.query(&filter, order, 1, 2);
+        assert_eq!(filtered.total_matched, 3);
+        assert_eq!(filtered.total_pages, 2);
+        assert_eq!(titles(&filtered), vec!["This is Title"]);
+        let all = fake_repo_get(&notes, &filter, order, Count::All);
+        assert_eq!(filtered.notes[0].title(), all[2].title());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn database_query_owned() {
+        let notes = create_notes();
+        let mut db = crate::db::Database::from_notes_vec(notes.clone()).expect("Unable to create database");
+        let order = Ordering::ascending(crate::types::api::OrderBy::Title);
+        let filter = Filter::single(NoteFilter::tag(StringSearch::contains("tag1".to_string(), false)));
+
+        let owned = db.query_owned(&filter, order, Count::Some(2));
+        assert_eq!(owned, fake_repo_get(&notes, &filter, order, Count::Some(2)));
+        assert_eq!(owned.len(), 2);
+        assert_eq!(&owned[0], db.get(owned[0].id()).unwrap());
+
+        // The returned notes are clones, so changes to the database don't affect them.
+        let id = owned[0].id();
+        db.get_and_modify(id, |n| n.set_title("Changed")).unwrap();
+        assert_eq!(owned[0].title(), "A Title");
+        assert_eq!(db.get(id).unwrap().title(), "Changed");
+
+        assert_eq!(db.query_owned(&Filter::empty(), order, Count::All).len(), 6);
+        assert_eq!(db.query_owned(&Filter::empty(), order, Count::One)[0].title(), "A Title");
+        assert!(db.query_owned(&Filter::empty(), order, Count::Some(0)).is_empty());
+    }
+
     #[no_coverage]
     fn apply_filter(notes: &[Note], filter: &NoteFilter) -> Vec<Note> {
         notes.iter().filter(filter.predicate()).cloned().collect()
@@ -584,6 +827,11 @@ mod tests {
         notes.iter().take(count.to_usize()).cloned().collect()
     }
 
+    #[no_coverage]
+    fn apply_count_lazily(notes: &[Note], count: Count) -> Vec<Note> {
+        count.apply(notes.iter()).cloned().collect()
+    }
+
     #[no_coverage]
     fn print_titles(notes: &[Note], linebreak: bool) {
         let titles = notes.iter().map(Note::title).collect::<Vec<_>>();
@@ -607,6 +855,74 @@ mod tests {
         let count = Count::All;
     }
 
+    #[test]
+    #[no_coverage]
+    fn filter_ignore_case() {
+        let notes = vec![
+            Note::create(("Shopping List", "Eggs and MILK", vec!["Errands"])),
+            Note::create(("shopping", "", vec![])),
+            Note::create(("Other", "", vec![])),
+        ];
+        let titles = |filter: NoteFilter| {
+            apply_filter(&notes, &filter)
+                .iter()
+                .map(|n| n.title().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(titles(NoteFilter::title(StringSearch::contains("shop".to_string(), false))), ["shopping"]);
+        assert_eq!(
+            titles(NoteFilter::title(StringSearch::contains_ci("SHOP".to_string(), false))),
+            ["Shopping List", "shopping"]
+        );
+        assert_eq!(titles(NoteFilter::title(StringSearch::contains_ci("shop".to_string(), true))), ["Other"]);
+        assert_eq!(titles(NoteFilter::content(StringSearch::contains_ci("milk".to_string(), false))), ["Shopping List"]);
+        assert_eq!(titles(NoteFilter::tag(StringSearch::contains_ci("errand".to_string(), false))), ["Shopping List"]);
+
+        let mut search = StringSearch::contains_ci("x".to_string(), false);
+        assert!(search.args().ignore_case());
+        search.args_mut().set_ignore_case(false);
+        assert!(!search.args().ignore_case());
+        assert!(!StringSearch::contains("x".to_string(), false).args().ignore_case());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn filter_tags() {
+        let notes = vec![
+            Note::create(("Both", "", vec!["work", "urgent"])),
+            Note::create(("Work", "", vec!["work"])),
+            Note::create(("Homework", "", vec!["homework"])),
+            Note::create(("None", "")),
+        ];
+        let titles = |filter: NoteFilter| {
+            apply_filter(&notes, &filter)
+                .iter()
+                .map(|n| n.title().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // `tag` searches the comma-joined tag list, as the CLI `--tags` filter and the icli search expect.
+        assert_eq!(
+            titles(NoteFilter::tag(StringSearch::contains("work".to_string(), false))),
+            ["Both", "Work", "Homework"]
+        );
+        assert_eq!(titles(NoteFilter::tag(StringSearch::contains("k,u".to_string(), false))), ["Both"]);
+        assert_eq!(titles(NoteFilter::tag(StringSearch::matches("work".to_string(), false))), ["Work"]);
+        assert_eq!(titles(NoteFilter::tag(StringSearch::ends_with("urgent".to_string(), false))), ["Both"]);
+        assert_eq!(titles(NoteFilter::tag(StringSearch::contains("work".to_string(), true))), ["None"]);
+
+        // `any_tag` searches each tag on its own.
+        assert_eq!(titles(NoteFilter::any_tag(StringSearch::matches("work".to_string(), false))), ["Both", "Work"]);
+        assert_eq!(titles(NoteFilter::any_tag(StringSearch::contains("k,u".to_string(), false))), Vec::<String>::new());
+        assert_eq!(titles(NoteFilter::any_tag(StringSearch::starts_with("home".to_string(), false))), ["Homework"]);
+        assert_eq!(titles(NoteFilter::any_tag(StringSearch::matches("work".to_string(), true))), ["Homework", "None"]);
+        assert_eq!(
+            NoteFilter::any_tag(StringSearch::matches("work".to_string(), false)).string_field(),
+            Some(&NoteStringField::Tags)
+        );
+    }
+
     #[test]
     #[no_coverage]
     fn filter_title() {
@@ -720,6 +1036,119 @@ mod tests {
         assert_eq!(filtered.len(), 5);
     }
 
+    #[test]
+    #[no_coverage]
+    fn filter_reminder_label() {
+        use crate::types::Reminder;
+
+        let mut notes = create_notes();
+        assert_eq!(notes.len(), 6, "create_notes should create 6 notes");
+
+        let mut label_contains =
+            NoteFilter::reminder_label(StringSearch::contains("dentist".to_string(), false));
+        let filtered = apply_filter(&notes, &label_contains);
+        assert!(filtered.is_empty());
+
+        notes[2].add_reminder(Reminder::default().with_label(&"call dentist"));
+        notes[4].add_reminder(Reminder::default());
+
+        let filtered = apply_filter(&notes, &label_contains);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].title(), "This is Title");
+
+        label_contains
+            .string_search_mut()
+            .unwrap()
+            .args_mut()
+            .toggle_invert();
+        let filtered = apply_filter(&notes, &label_contains);
+        assert_eq!(filtered.len(), 5);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn filter_normalize() {
+        let notes = create_notes();
+        assert_eq!(notes.len(), 6, "create_notes should create 6 notes");
+
+        let tag1 = NoteFilter::tag(StringSearch::contains("tag1".to_string(), false));
+        let mut filter = Filter::multiple(vec![tag1.clone(), tag1.clone()]);
+        let before = apply_filters(&notes, &filter).len();
+        filter.normalize();
+        assert_eq!(filter.filters(), &[tag1.clone()]);
+        assert_eq!(apply_filters(&notes, &filter).len(), before);
+
+        let empty_contains = NoteFilter::title(StringSearch::contains(String::new(), false));
+        let inverted_empty = NoteFilter::title(StringSearch::contains(String::new(), true));
+        assert!(empty_contains.is_noop());
+        assert!(!inverted_empty.is_noop());
+
+        let mut filter = Filter::multiple(vec![empty_contains, tag1.clone(), inverted_empty.clone()]);
+        filter.normalize();
+        assert_eq!(filter.filters(), &[tag1, inverted_empty]);
+
+        let mut only_noop = Filter::single(NoteFilter::content(StringSearch::starts_with(
+            String::new(),
+            false,
+        )));
+        only_noop.normalize();
+        assert!(only_noop.filters().is_empty());
+        assert_eq!(apply_filters(&notes, &only_noop).len(), 6);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn filter_expr_or() {
+        let notes = create_notes();
+        assert_eq!(notes.len(), 6, "create_notes should create 6 notes");
+
+        let title_some = NoteFilter::title(StringSearch::contains("Some".to_string(), false));
+        let tag_whoa = NoteFilter::tag(StringSearch::contains("whoa".to_string(), false));
+
+        let expr = FilterExpr::or(vec![title_some.into(), tag_whoa.into()]);
+        let filtered = notes.iter().filter(expr.predicate()).collect::<Vec<_>>();
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().any(|n| n.title() == "Some Title"));
+        assert!(filtered.iter().any(|n| n.title() == "Gooooo Title"));
+
+        let empty_or = FilterExpr::or(Vec::new());
+        assert_eq!(notes.iter().filter(empty_or.predicate()).count(), 0);
+        let empty_and = FilterExpr::default();
+        assert_eq!(notes.iter().filter(empty_and.predicate()).count(), 6);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn filter_expr_not_and() {
+        let notes = create_notes();
+        assert_eq!(notes.len(), 6, "create_notes should create 6 notes");
+
+        let tag1 = NoteFilter::tag(StringSearch::contains("tag1".to_string(), false));
+        let title_last = NoteFilter::title(StringSearch::starts_with("Last".to_string(), false));
+
+        let and = FilterExpr::and(vec![tag1.clone().into(), title_last.into()]);
+        assert_eq!(notes.iter().filter(and.predicate()).count(), 1);
+
+        let not = FilterExpr::not(and);
+        let filtered = notes.iter().filter(not.predicate()).collect::<Vec<_>>();
+        assert_eq!(filtered.len(), 5);
+        assert!(filtered.iter().all(|n| n.title() != "Last Title"));
+
+        let flat = Filter::single(tag1);
+        assert_eq!(
+            FilterExpr::from(&flat),
+            FilterExpr::And(vec![FilterExpr::Leaf(flat.filters()[0].clone())])
+        );
+        assert_eq!(
+            notes.iter().filter(flat.predicate()).count(),
+            notes.iter().filter(flat.to_expr().predicate()).count()
+        );
+
+        let json = serde_json::to_string(&not).expect("unable to serialize FilterExpr");
+        let back: FilterExpr = serde_json::from_str(&json).expect("unable to deserialize FilterExpr");
+        assert_eq!(back, not);
+    }
+
     #[test]
     #[no_coverage]
     fn filter_created() {
@@ -831,6 +1260,34 @@ mod tests {
 
         let counted = apply_count(&notes, 1.into());
         assert_eq!(counted.len(), 1);
+
+        assert_eq!(Count::from(0), Count::Some(0));
+        assert!(apply_count(&notes, 0.into()).is_empty());
+        assert_eq!(Count::All.to_usize(), usize::MAX);
+        assert_eq!(Count::from(usize::MAX), Count::All);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn count_apply() {
+        let collect = |count: Count| count.apply(1..=10).collect::<Vec<_>>();
+        assert_eq!(collect(Count::One), vec![1]);
+        assert_eq!(collect(Count::Some(3)), vec![1, 2, 3]);
+        assert_eq!(collect(Count::Some(0)), Vec::<i32>::new());
+        assert_eq!(collect(Count::Some(100)), (1..=10).collect::<Vec<_>>());
+        assert_eq!(collect(Count::All), (1..=10).collect::<Vec<_>>());
+
+        // `All` must not go through a `take`, so an iterator with more than `usize::MAX` items would
+        // still be unbounded. Checking the size hint is the closest we can get to that.
+        assert_eq!(Count::All.apply(std::iter::repeat(0)).size_hint(), (usize::MAX, None));
+        assert_eq!(Count::All.apply(std::iter::repeat(0)).skip(1_000).take(5).count(), 5);
+        assert_eq!(Count::Some(5).apply(std::iter::repeat(0)).size_hint(), (5, Some(5)));
+
+        // `apply` agrees with the `take(count.to_usize())` it replaces for every finite input.
+        let notes = create_notes();
+        for count in [Count::One, Count::Some(0), Count::Some(2), Count::Some(100), Count::All] {
+            assert_eq!(apply_count_lazily(&notes, count), apply_count(&notes, count));
+        }
     }
 
     #[test]
@@ -886,4 +1343,84 @@ mod tests {
             assert_eq!(ordered[i].content(), notes[i].content());
         }
     }
+
+    #[test]
+    #[no_coverage]
+    fn ordering_then_by() {
+        use super::super::order::{OrderBy, Ordering};
+
+        let notes = vec![
+            Note::create(("Same", "d", vec!["b"])),
+            Note::create(("Same", "b", vec!["a"])),
+            Note::create(("Another", "z", vec!["z"])),
+            Note::create(("Same", "c", vec!["a"])),
+            Note::create(("Same", "a", vec!["b"])),
+        ];
+
+        let order = Ordering::ascending(OrderBy::Title).then_by(OrderBy::Content);
+        let ordered = apply_order(&notes, order);
+        let contents = ordered.iter().map(Note::content).collect::<Vec<_>>();
+        assert_eq!(contents, vec!["z", "a", "b", "c", "d"]);
+        // Repeated sorts must give the same result.
+        assert_eq!(apply_order(&notes, order), ordered);
+
+        let order = Ordering::ascending(OrderBy::Title).then_by_descending(OrderBy::Content);
+        let ordered = apply_order(&notes, order);
+        let contents = ordered.iter().map(Note::content).collect::<Vec<_>>();
+        assert_eq!(contents, vec!["z", "d", "c", "b", "a"]);
+
+        let order = Ordering::ascending(OrderBy::Title)
+            .then_by(OrderBy::Tags)
+            .then_by(OrderBy::Content);
+        assert_eq!(order.tie_breakers().len(), 2);
+        // Keys that are already used can never break a tie, so they are not added again.
+        assert_eq!(order.then_by(OrderBy::Title).then_by_descending(OrderBy::Tags), order);
+        let ordered = apply_order(&notes, order);
+        let contents = ordered.iter().map(Note::content).collect::<Vec<_>>();
+        assert_eq!(contents, vec!["z", "b", "c", "a", "d"]);
+
+        let mut reversed = order;
+        reversed.reverse();
+        let ordered = apply_order(&notes, reversed);
+        let contents = ordered.iter().map(Note::content).collect::<Vec<_>>();
+        assert_eq!(contents, vec!["d", "a", "c", "b", "z"]);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn ordering_pinned_first() {
+        use super::super::order::{OrderBy, Ordering};
+
+        let mut notes = create_notes();
+        // "Some Title" and "Gooooo Title"
+        notes[1].pin();
+        notes[3].pin();
+
+        let order = Ordering::ascending(OrderBy::Title).with_pinned_first();
+        assert!(order.pinned_first());
+        let ordered = apply_order(&notes, order);
+        let titles = ordered.iter().map(Note::title).collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            vec![
+                "Gooooo Title",
+                "Some Title",
+                "A Title",
+                "Last Title",
+                "This is Title",
+                "Title Goes Here",
+            ]
+        );
+
+        // Reversing flips the order within each group, but pinned notes stay on top.
+        let mut reversed = order;
+        reversed.reverse();
+        let ordered = apply_order(&notes, reversed);
+        let titles = ordered.iter().map(Note::title).collect::<Vec<_>>();
+        assert_eq!(&titles[..2], &["Some Title", "Gooooo Title"]);
+        assert_eq!(titles[2], "Title Goes Here");
+
+        let ordered = apply_order(&notes, Ordering::ascending(OrderBy::Title));
+        assert_eq!(ordered[0].title(), "A Title");
+    }
 }
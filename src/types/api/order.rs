@@ -4,15 +4,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use serde::{Deserialize, Serialize};
+
 use crate::types::Note;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum OrderDirection {
     Ascending,
     Descending,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum OrderBy {
     Title,
     Content,
@@ -21,16 +23,34 @@ pub enum OrderBy {
     Updated,
 }
 
+/// The most tie-breaker keys an [`Ordering`] can have, one for every [`OrderBy`] besides the primary field.
+const MAX_TIE_BREAKERS: usize = 4;
+
+/// What the unused tie-breaker slots of an [`Ordering`] hold, so that they never affect equality or hashing.
+const UNUSED_TIE_BREAKER: (OrderBy, OrderDirection) = (OrderBy::Updated, OrderDirection::Ascending);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ordering {
     field: OrderBy,
     direction: OrderDirection,
+    /// Secondary (tertiary, etc.) keys used, in order, to break ties on the primary field. Only the first
+    /// `then_len` are used, see [`Ordering::tie_breakers`].
+    then: [(OrderBy, OrderDirection); MAX_TIE_BREAKERS],
+    then_len: usize,
+    /// Whether pinned notes are grouped before unpinned notes, regardless of the keys above.
+    pinned_first: bool,
 }
 
 impl Ordering {
     #[must_use]
     pub fn new(field: OrderBy, direction: OrderDirection) -> Self {
-        Self { field, direction }
+        Self {
+            field,
+            direction,
+            then: [UNUSED_TIE_BREAKER; MAX_TIE_BREAKERS],
+            then_len: 0,
+            pinned_first: false,
+        }
     }
 
     #[must_use]
@@ -53,8 +73,64 @@ impl Ordering {
         self.direction
     }
 
+    /// Adds an ascending tie-breaker key, used when all previous keys compare equal.
+    #[must_use]
+    pub fn then_by(self, field: OrderBy) -> Self {
+        self.then_by_direction(field, OrderDirection::Ascending)
+    }
+
+    /// Adds a descending tie-breaker key, used when all previous keys compare equal.
+    #[must_use]
+    pub fn then_by_descending(self, field: OrderBy) -> Self {
+        self.then_by_direction(field, OrderDirection::Descending)
+    }
+
+    /// Adds a tie-breaker key, used when all previous keys compare equal. A field that is already one of the
+    /// keys can never break a tie, so it is ignored.
+    #[must_use]
+    pub fn then_by_direction(mut self, field: OrderBy, direction: OrderDirection) -> Self {
+        if field != self.field && self.tie_breakers().iter().all(|&(existing, _)| existing != field) {
+            self.then[self.then_len] = (field, direction);
+            self.then_len += 1;
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn tie_breakers(&self) -> &[(OrderBy, OrderDirection)] {
+        &self.then[..self.then_len]
+    }
+
+    /// Groups pinned notes before unpinned notes, with the ordering keys applied within each group.
+    /// This is not affected by [`Ordering::reverse`].
+    #[must_use]
+    pub fn with_pinned_first(mut self) -> Self {
+        self.pinned_first = true;
+        self
+    }
+
+    #[must_use]
+    pub fn pinned_first(&self) -> bool {
+        self.pinned_first
+    }
+
+    pub fn set_pinned_first(&mut self, pinned_first: bool) {
+        self.pinned_first = pinned_first;
+    }
+
+    /// Reverses the direction of the primary key and all tie-breaker keys.
     pub fn reverse(&mut self) {
-        self.direction = match self.direction {
+        self.direction = self.direction.reversed();
+        for (_, direction) in &mut self.then[..self.then_len] {
+            *direction = direction.reversed();
+        }
+    }
+}
+
+impl OrderDirection {
+    #[must_use]
+    pub fn reversed(self) -> Self {
+        match self {
             OrderDirection::Ascending => OrderDirection::Descending,
             OrderDirection::Descending => OrderDirection::Ascending,
         }
@@ -63,10 +139,7 @@ impl Ordering {
 
 impl Default for Ordering {
     fn default() -> Self {
-        Self {
-            field: OrderBy::Updated,
-            direction: OrderDirection::Ascending,
-        }
+        Self::new(OrderBy::Updated, OrderDirection::Ascending)
     }
 }
 
@@ -76,24 +149,49 @@ impl Ordering {
     #[must_use]
     pub fn comparison(self) -> Comparison {
         crate::flame_guard!("types", "api", "order", "Ordering", "comparison");
-        match self.field {
-            OrderBy::Title => match self.direction {
+        let primary = Self::key_comparison(self.field, self.direction);
+        if self.then_len == 0 && !self.pinned_first {
+            return primary;
+        }
+
+        let mut comparisons: Vec<Comparison> = Vec::with_capacity(self.then_len + 2);
+        if self.pinned_first {
+            comparisons.push(box |a: &Note, b: &Note| b.is_pinned().cmp(&a.is_pinned()));
+        }
+        comparisons.push(primary);
+        comparisons.extend(
+            self.tie_breakers()
+                .iter()
+                .map(|&(field, direction)| Self::key_comparison(field, direction)),
+        );
+        box move |a: &Note, b: &Note| {
+            comparisons
+                .iter()
+                .fold(std::cmp::Ordering::Equal, |ord, cmp| {
+                    ord.then_with(|| cmp(a, b))
+                })
+        }
+    }
+
+    fn key_comparison(field: OrderBy, direction: OrderDirection) -> Comparison {
+        match field {
+            OrderBy::Title => match direction {
                 OrderDirection::Ascending => box |a: &Note, b: &Note| a.title().cmp(b.title()),
                 OrderDirection::Descending => box |a: &Note, b: &Note| b.title().cmp(a.title()),
             },
-            OrderBy::Content => match self.direction {
+            OrderBy::Content => match direction {
                 OrderDirection::Ascending => box |a: &Note, b: &Note| a.content().cmp(b.content()),
                 OrderDirection::Descending => box |a: &Note, b: &Note| b.content().cmp(a.content()),
             },
-            OrderBy::Tags => match self.direction {
+            OrderBy::Tags => match direction {
                 OrderDirection::Ascending => box |a: &Note, b: &Note| a.tags().cmp(b.tags()),
                 OrderDirection::Descending => box |a: &Note, b: &Note| b.tags().cmp(a.tags()),
             },
-            OrderBy::Created => match self.direction {
+            OrderBy::Created => match direction {
                 OrderDirection::Ascending => box |a: &Note, b: &Note| a.created().cmp(b.created()),
                 OrderDirection::Descending => box |a: &Note, b: &Note| b.created().cmp(a.created()),
             },
-            OrderBy::Updated => match self.direction {
+            OrderBy::Updated => match direction {
                 OrderDirection::Ascending => box |a: &Note, b: &Note| a.updated().cmp(b.updated()),
                 OrderDirection::Descending => box |a: &Note, b: &Note| b.updated().cmp(a.updated()),
             },
@@ -0,0 +1,50 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use serde::{Deserialize, Serialize};
+
+/// A reference to a file associated with a [`Note`](crate::types::Note). Only the reference is stored in
+/// the database, never the file contents, so `path_or_hash` is either a path to the file or the hash of a
+/// blob kept elsewhere.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Attachment {
+    name: String,
+    path_or_hash: String,
+    #[serde(default)]
+    mime: Option<String>,
+}
+
+/// Constructors
+impl Attachment {
+    #[must_use]
+    pub fn new(name: impl Into<String>, path_or_hash: impl Into<String>, mime: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            path_or_hash: path_or_hash.into(),
+            mime,
+        }
+    }
+}
+
+/// Member Functions
+impl Attachment {
+    /// The display name of the attachment, also used to remove it from a note.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn path_or_hash(&self) -> &str {
+        &self.path_or_hash
+    }
+
+    /// The MIME type of the attachment (e.g. `image/png`), if known.
+    #[must_use]
+    pub fn mime(&self) -> Option<&str> {
+        self.mime.as_deref()
+    }
+}
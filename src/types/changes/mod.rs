@@ -4,6 +4,100 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::types::Note;
+
 pub struct NoteChange;
 
 pub struct NoteChangeLog(Vec<NoteChange>);
+
+/// The user-editable fields of a [`Note`] that differ between two versions of it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChangedFields {
+    title: bool,
+    content: bool,
+    tags: bool,
+    reminders: bool,
+}
+
+/// Constructors
+impl ChangedFields {
+    /// No fields changed.
+    #[must_use]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn new(title: bool, content: bool, tags: bool, reminders: bool) -> Self {
+        Self {
+            title,
+            content,
+            tags,
+            reminders,
+        }
+    }
+
+    /// Compares the fields of `before` and `after`.
+    #[must_use]
+    pub fn between(before: &Note, after: &Note) -> Self {
+        Self {
+            title: before.title() != after.title(),
+            content: before.content() != after.content(),
+            tags: before.tags() != after.tags(),
+            reminders: before.reminders() != after.reminders(),
+        }
+    }
+}
+
+/// Member Functions
+impl ChangedFields {
+    #[must_use]
+    pub fn title(&self) -> bool {
+        self.title
+    }
+
+    #[must_use]
+    pub fn content(&self) -> bool {
+        self.content
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> bool {
+        self.tags
+    }
+
+    #[must_use]
+    pub fn reminders(&self) -> bool {
+        self.reminders
+    }
+
+    /// Returns `true` if no fields changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::none()
+    }
+
+    /// The names of the changed fields, in declaration order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        [
+            ("title", self.title),
+            ("content", self.content),
+            ("tags", self.tags),
+            ("reminders", self.reminders),
+        ]
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then(|| name))
+        .collect()
+    }
+}
+
+impl std::fmt::Display for ChangedFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "{}", self.names().join(", "))
+        }
+    }
+}
@@ -10,6 +10,8 @@ use uuid::Uuid;
 
 use tinyid::{TinyId, TinyIdError};
 
+use crate::util::validation::NoteValidationError;
+
 #[derive(Debug)]
 pub enum Error {
     EnvVar(std::env::VarError),
@@ -24,6 +26,8 @@ pub enum Error {
     TinyId(TinyIdError),
     Interface(String),
     Time(time::Error),
+    Reminder(ReminderError),
+    Validation(NoteValidationError),
 }
 
 impl Error {
@@ -92,6 +96,8 @@ impl std::fmt::Display for Error {
             Self::TinyId(e) => e.fmt(f),
             Self::Interface(s) => write!(f, "User interface error: {}", s),
             Self::Time(e) => e.fmt(f),
+            Self::Reminder(e) => e.fmt(f),
+            Self::Validation(e) => write!(f, "Invalid note, {}", e),
         }
     }
 }
@@ -157,6 +163,16 @@ impl From<DatabaseError> for Error {
         Self::Database(err)
     }
 }
+impl From<ReminderError> for Error {
+    fn from(err: ReminderError) -> Self {
+        Self::Reminder(err)
+    }
+}
+impl From<NoteValidationError> for Error {
+    fn from(err: NoteValidationError) -> Self {
+        Self::Validation(err)
+    }
+}
 impl From<String> for Error {
     fn from(err: String) -> Self {
         Self::Unknown(err)
@@ -191,6 +207,7 @@ pub enum DatabaseError {
     DuplicateId(TinyId),
     InvalidState(String),
     InvalidId,
+    Cancelled,
 }
 
 impl std::fmt::Display for DatabaseError {
@@ -204,12 +221,47 @@ impl std::fmt::Display for DatabaseError {
                 write!(f, "Data file not found at path '{}'", pb.display())
             }
             DatabaseError::InvalidState(s) => write!(f, "Invalid Database state: {}", s),
+            DatabaseError::Cancelled => write!(f, "Database operation was cancelled"),
         }
     }
 }
 
 impl std::error::Error for DatabaseError {}
 
+/// Errors produced when building a [`Reminder`](crate::types::Reminder) from its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReminderError {
+    /// A required part of the reminder was never set.
+    Missing(&'static str),
+    /// The year, month and day do not form a valid calendar date (e.g. February 30th).
+    InvalidDate { year: i32, month: u8, day: u8 },
+    /// The hour is not on a 12-hour clock face (1-12) or the minute is not in 0-59.
+    InvalidTime { hour: u8, minute: u8 },
+    /// A [`Recurrence::Monthly`](crate::types::Recurrence::Monthly) day that is not in 1-31.
+    InvalidMonthDay(u8),
+}
+
+impl std::fmt::Display for ReminderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReminderError::Missing(part) => write!(f, "Reminder is missing its {}", part),
+            ReminderError::InvalidDate { year, month, day } => {
+                write!(f, "Invalid reminder date: {:04}-{:02}-{:02}", year, month, day)
+            }
+            ReminderError::InvalidTime { hour, minute } => write!(
+                f,
+                "Invalid reminder time: {}:{:02} (hour must be 1-12, minute 0-59)",
+                hour, minute
+            ),
+            ReminderError::InvalidMonthDay(day) => {
+                write!(f, "Invalid day of the month for monthly reminder: {}", day)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReminderError {}
+
 #[must_use]
 pub type Result<T = ()> = std::result::Result<T, Error>;
 
@@ -5,19 +5,26 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub mod api;
+mod attachment;
 mod changes;
 mod error;
 mod note;
 mod note_dto;
 mod reminder;
+mod tag_normalizer;
+mod tag_style;
 mod taglist;
 pub mod time;
 mod traits;
 
+pub use attachment::Attachment;
+pub use changes::ChangedFields;
 pub use error::*;
-pub use note::Note;
-pub use note_dto::{CreateNote, DeleteNote, NoteDto, UpdateNote};
-pub use reminder::Reminder;
+pub use note::{FixApplied, Note};
+pub use note_dto::{CreateNote, DeleteNote, FlagNote, NoteDto, NoteFlag, UpdateNote};
+pub use reminder::{Recurrence, Reminder, ReminderBuilder};
+pub use tag_normalizer::TagNormalizer;
+pub use tag_style::TagStyle;
 pub use taglist::TagList;
 pub use traits::HasId;
 
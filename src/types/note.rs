@@ -4,6 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use tinyid::TinyId;
@@ -11,11 +12,13 @@ use uuid::Uuid;
 
 use crate::{
     flame_guard,
-    types::{CreateNote, DeleteNote, HasId, Reminder, UpdateNote},
+    types::{Attachment, CreateNote, DeleteNote, FlagNote, HasId, NoteFlag, Reminder, UpdateNote},
+    util::text,
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Note {
+    #[serde(with = "crate::util::id::as_string")]
     id: TinyId,
     title: String,
     content: String,
@@ -23,13 +26,27 @@ pub struct Note {
     reminders: Vec<Reminder>,
     created: OffsetDateTime,
     updated: OffsetDateTime,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default)]
+    archived: bool,
+    /// References to files associated with this note, the files themselves are not stored.
+    #[serde(default)]
+    attachments: Vec<Attachment>,
     #[serde(skip)]
     dirty: bool,
     #[serde(skip)]
     pending_delete: bool,
+    /// Lowercased copy of `content`, built on first use by case-insensitive searches and cleared whenever
+    /// `content` changes.
+    #[serde(skip)]
+    content_lowercase: OnceCell<String>,
 }
 
 impl Note {
+    /// The reading speed used by [`Note::reading_time_minutes`].
+    pub const WORDS_PER_MINUTE: usize = 200;
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn existing(
@@ -49,8 +66,12 @@ impl Note {
             reminders,
             created,
             updated,
+            pinned: false,
+            archived: false,
+            attachments: Vec::new(),
             dirty: false,
             pending_delete: false,
+            content_lowercase: OnceCell::new(),
         }
     }
 
@@ -73,8 +94,12 @@ impl Note {
             reminders,
             created: OffsetDateTime::now_utc(),
             updated: OffsetDateTime::now_utc(),
+            pinned: false,
+            archived: false,
+            attachments: Vec::new(),
             dirty: true,
             pending_delete: false,
+            content_lowercase: OnceCell::new(),
         }
     }
 
@@ -97,8 +122,12 @@ impl Note {
             reminders,
             created: OffsetDateTime::now_utc(),
             updated: OffsetDateTime::now_utc(),
+            pinned: false,
+            archived: false,
+            attachments: Vec::new(),
             dirty: true,
             pending_delete: false,
+            content_lowercase: OnceCell::new(),
         }
     }
 
@@ -128,6 +157,7 @@ impl Note {
         if let Some(content) = content {
             if self.content != content {
                 self.content = content;
+                self.content_lowercase.take();
                 self.dirty = true;
             }
         }
@@ -160,10 +190,14 @@ impl Note {
 
         self.title = other.title.clone();
         self.content = other.content.clone();
+        self.content_lowercase.take();
         self.tags = other.tags.clone();
         self.reminders = other.reminders.clone();
         self.created = other.created;
         self.updated = other.updated;
+        self.pinned = other.pinned;
+        self.archived = other.archived;
+        self.attachments = other.attachments.clone();
         self.dirty = false;
         self.pending_delete = false;
     }
@@ -184,6 +218,27 @@ impl Note {
         self.pending_delete
     }
 
+    /// Sets the flag described by the [`FlagNote`] dto, **if the IDs match**. Flags are metadata so the
+    /// `updated` timestamp is not changed, but the note is marked as dirty. Returns whether the flag changed.
+    #[tracing::instrument(level = "trace", skip(dto), fields(dto))]
+    pub fn flag(&mut self, dto: impl Into<FlagNote>) -> bool {
+        let dto = dto.into();
+        if self.id != *dto.id() {
+            return false;
+        }
+
+        let flag = match dto.flag() {
+            NoteFlag::Pinned => &mut self.pinned,
+            NoteFlag::Archived => &mut self.archived,
+        };
+        if *flag == dto.value() {
+            return false;
+        }
+        *flag = dto.value();
+        self.dirty = true;
+        true
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn id(&self) -> TinyId {
@@ -225,6 +280,7 @@ impl Note {
     pub fn set_content(&mut self, content: &str) {
         if self.content != content {
             self.content = content.to_string();
+            self.content_lowercase.take();
             self.set_updated_now();
             self.dirty = true;
         }
@@ -235,6 +291,7 @@ impl Note {
         let new = f(&self.content);
         if new != self.content {
             self.content = new;
+            self.content_lowercase.take();
             self.set_updated_now();
             self.dirty = true;
         }
@@ -247,6 +304,7 @@ impl Note {
                 self.content.push(' ');
             }
             self.content.push_str(content);
+            self.content_lowercase.take();
             self.set_updated_now();
             self.dirty = true;
         }
@@ -335,6 +393,27 @@ impl Note {
         }
     }
 
+    /// Replaces the tag `old` with `new`, keeping its position. If this note already has `new`
+    /// the `old` tag is simply removed so no duplicate is created, and any repeated `old` tags are
+    /// removed as well. Returns whether anything changed.
+    #[tracing::instrument(level = "trace")]
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> bool {
+        if old == new {
+            return false;
+        }
+        let Some(index) = self.tags.iter().position(|t| t == old) else {
+            return false;
+        };
+
+        if !self.tag_matches(new) {
+            self.tags[index] = new.to_string();
+        }
+        self.tags.retain(|t| t != old);
+        self.set_updated_now();
+        self.dirty = true;
+        true
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn tag_len(&self) -> usize {
@@ -387,6 +466,62 @@ impl Note {
         self.pending_delete = pending_delete;
     }
 
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn attachments(&self) -> &[Attachment] {
+        &self.attachments
+    }
+
+    /// Adds a reference to a file to this [`Note`], marking it as dirty.
+    #[tracing::instrument(level = "trace")]
+    pub fn add_attachment(&mut self, attachment: Attachment) {
+        self.attachments.push(attachment);
+        self.set_updated_now();
+        self.dirty = true;
+    }
+
+    /// Removes the first attachment with the given `name`, marking this [`Note`] as dirty if one was found.
+    #[tracing::instrument(level = "trace")]
+    pub fn remove_attachment(&mut self, name: &str) -> Option<Attachment> {
+        let idx = self.attachments.iter().position(|a| a.name() == name)?;
+        let removed = self.attachments.remove(idx);
+        self.set_updated_now();
+        self.dirty = true;
+        Some(removed)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn pin(&mut self) {
+        self.flag(FlagNote::new(self.id, NoteFlag::Pinned, true));
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn unpin(&mut self) {
+        self.flag(FlagNote::new(self.id, NoteFlag::Pinned, false));
+    }
+
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn is_archived(&self) -> bool {
+        self.archived
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn archive(&mut self) {
+        self.flag(FlagNote::new(self.id, NoteFlag::Archived, true));
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn unarchive(&mut self) {
+        self.flag(FlagNote::new(self.id, NoteFlag::Archived, false));
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn title_contains(&self, text: &str) -> bool {
@@ -405,6 +540,22 @@ impl Note {
         self.content.contains(text)
     }
 
+    /// This note's content in lowercase. The lowercased copy is cached until the content changes, so
+    /// repeated searches over large notes don't lowercase the whole content every time.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn content_lowercase(&self) -> &str {
+        self.content_lowercase.get_or_init(|| self.content.to_lowercase())
+    }
+
+    /// Case-insensitive [`Note::content_contains`], using the cached [`Note::content_lowercase`].
+    /// `lowercase_text` must already be lowercase.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn content_contains_lowercase(&self, lowercase_text: &str) -> bool {
+        self.content_lowercase().contains(lowercase_text)
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn content_matches(&self, text: &str) -> bool {
@@ -423,10 +574,152 @@ impl Note {
         self.tags.iter().any(|tag| tag == text)
     }
 
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn reminder_label_contains(&self, text: &str) -> bool {
+        self.reminders.iter().any(|r| r.label_contains(text))
+    }
+
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn reminder_label_matches(&self, text: &str) -> bool {
+        self.reminders.iter().any(|r| r.label() == Some(text))
+    }
+
+    /// Renders this note as a single line of at most `max_width` characters for list views, made up of
+    /// the title followed by a tag indicator. The indicator is the tag itself (`#work`) when there is exactly
+    /// one tag, or the number of tags (`#3`) when there are more. The title is truncated with an ellipsis
+    /// if it does not fit, and the indicator is dropped entirely if there is no room for any of the title.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn summary_line(&self, max_width: usize) -> String {
+        fn truncate(text: &str, width: usize) -> String {
+            if text.chars().count() <= width {
+                text.to_string()
+            } else if width == 0 {
+                String::new()
+            } else {
+                let mut truncated = text.chars().take(width - 1).collect::<String>();
+                truncated.push('…');
+                truncated
+            }
+        }
+
+        let count_indicator = format!("#{}", self.tags.len());
+        let indicator = match self.tags.as_slice() {
+            [] => None,
+            [tag] if tag.chars().count() + 1 < max_width / 2 => Some(format!("#{}", tag)),
+            _ => Some(count_indicator),
+        };
+
+        match indicator {
+            // One column for the separating space, and at least one for the title.
+            Some(indicator) if indicator.chars().count() + 2 <= max_width => {
+                let title_width = max_width - indicator.chars().count() - 1;
+                format!("{} {}", truncate(&self.title, title_width), indicator)
+            }
+            _ => truncate(&self.title, max_width),
+        }
+    }
+
+    /// Extracts the targets of all markdown `[text](url)` links and bare `http(s)://` URLs in this note's content,
+    /// in the order they appear. Anything inside a fenced code block is ignored.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn extract_links(&self) -> Vec<String> {
+        crate::util::markdown::links(&self.content)
+    }
+
+    /// Extracts all ATX (`#`) headings in this note's content as `(level, text)`, in the order they appear.
+    /// Anything inside a fenced code block is ignored.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn headings(&self) -> Vec<(u8, String)> {
+        crate::util::markdown::headings(&self.content)
+    }
+
+    /// The number of words in this note's content, where words are separated by any Unicode whitespace.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.content.split_whitespace().count()
+    }
+
+    /// The number of characters (Unicode scalar values, not bytes) in this note's content.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.content.chars().count()
+    }
+
+    /// The estimated time in minutes to read this note's content, at [`Note::WORDS_PER_MINUTE`], rounded up.
+    /// Empty content takes zero minutes, any other content takes at least one.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn reading_time_minutes(&self) -> u32 {
+        let words = self.word_count();
+        u32::try_from((words + Self::WORDS_PER_MINUTE - 1) / Self::WORDS_PER_MINUTE).unwrap_or(u32::MAX)
+    }
+
+    /// This note's content hard-wrapped so no line is wider than `width` columns, see [`text::wrap`].
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn wrap_content(&self, width: usize) -> Vec<String> {
+        text::wrap(&self.content, width)
+    }
+
+    /// The byte ranges in this note's content that match `query`, see [`Note::match_ranges`].
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn highlight_ranges(&self, query: &str) -> Vec<std::ops::Range<usize>> {
+        Self::match_ranges(&self.content, query)
+    }
+
+    /// Finds every case-insensitive, non-overlapping occurrence of `query` in `text`, returning the byte range
+    /// of each in order. The ranges always fall on `char` boundaries so they can be used to slice `text`. An
+    /// empty `query` matches nothing.
+    #[must_use]
+    pub fn match_ranges(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let query = query.to_lowercase();
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while let Some(c) = text[start..].chars().next() {
+            if let Some(len) = lowercase_prefix_len(&text[start..], &query) {
+                ranges.push(start..start + len);
+                start += len;
+            } else {
+                start += c.len_utf8();
+            }
+        }
+        ranges
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn full_text_search(&self, text: &str) -> bool {
-        self.title_contains(text) || self.content_contains(text) || self.tag_contains(text)
+        self.title_contains(text)
+            || self.content_contains(text)
+            || self.tag_contains(text)
+            || self.reminder_label_contains(text)
+    }
+
+    /// Case-insensitive [`Note::full_text_search`]. `lowercase_text` must already be lowercase, so callers
+    /// searching many notes only lowercase their query once.
+    #[tracing::instrument(level = "trace", skip(self))]
+    #[must_use]
+    pub fn full_text_search_lowercase(&self, lowercase_text: &str) -> bool {
+        contains_lowercase(&self.title, lowercase_text)
+            || self.content_contains_lowercase(lowercase_text)
+            || self.tags.iter().any(|tag| contains_lowercase(tag, lowercase_text))
+            || self
+                .reminders
+                .iter()
+                .filter_map(Reminder::label)
+                .any(|label| contains_lowercase(label, lowercase_text))
     }
 
     #[tracing::instrument(level = "trace")]
@@ -449,6 +742,53 @@ impl Note {
         }
     }
 
+    /// Repairs common issues with this note, returning a [`FixApplied`] for every repair that was made.
+    /// This is intended for notes coming from outside of the application (i.e. during an import), so
+    /// the `updated` timestamp is left alone but the note is marked as dirty if anything changed.
+    ///
+    /// The following repairs are made, in order:
+    /// - Leading and trailing whitespace is trimmed from the title.
+    /// - Tags are sorted and duplicates are removed.
+    /// - `created` and `updated` are swapped if `created` is the later of the two.
+    /// - Reminders with an invalid ID are removed.
+    #[tracing::instrument(level = "trace")]
+    pub fn validate_and_fix(&mut self) -> Vec<FixApplied> {
+        let mut fixes = Vec::new();
+
+        let trimmed = self.title.trim();
+        if trimmed.len() != self.title.len() {
+            self.title = trimmed.to_string();
+            fixes.push(FixApplied::TitleTrimmed);
+        }
+
+        if !self.tags.windows(2).all(|pair| pair[0] <= pair[1]) {
+            self.tags.sort_unstable();
+            fixes.push(FixApplied::TagsSorted);
+        }
+        let before = self.tags.len();
+        self.tags.dedup();
+        if before != self.tags.len() {
+            fixes.push(FixApplied::DuplicateTagsRemoved(before - self.tags.len()));
+        }
+
+        if self.created > self.updated {
+            std::mem::swap(&mut self.created, &mut self.updated);
+            fixes.push(FixApplied::TimestampsSwapped);
+        }
+
+        let before = self.reminders.len();
+        self.reminders.retain(|r| r.id().is_valid());
+        if before != self.reminders.len() {
+            fixes.push(FixApplied::InvalidRemindersRemoved(before - self.reminders.len()));
+        }
+
+        if !fixes.is_empty() {
+            self.dirty = true;
+        }
+
+        fixes
+    }
+
     #[must_use]
     pub fn ids_used(&self) -> Vec<TinyId> {
         let mut ids = if self.id.is_null() {
@@ -480,10 +820,20 @@ impl Note {
         self.pending_delete = false;
         self.title = String::new();
         self.content = String::new();
+        self.content_lowercase.take();
         self.tags = Vec::new();
         self.reminders = Vec::new();
         self.created = OffsetDateTime::UNIX_EPOCH;
         self.updated = OffsetDateTime::UNIX_EPOCH;
+        self.pinned = false;
+        self.archived = false;
+        self.attachments = Vec::new();
+    }
+
+    /// Moves this note to the given ID, keeping everything else about it as is.
+    #[tracing::instrument(level = "trace")]
+    pub(crate) fn reassign_id(&mut self, id: TinyId) {
+        self.id = id;
     }
 
     #[tracing::instrument(level = "trace")]
@@ -497,6 +847,21 @@ impl Note {
     }
 }
 
+/// A repair made by [`Note::validate_and_fix`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FixApplied {
+    /// Leading and/or trailing whitespace was removed from the title.
+    TitleTrimmed,
+    /// The tags were not in sorted order.
+    TagsSorted,
+    /// The given number of duplicate tags were removed.
+    DuplicateTagsRemoved(usize),
+    /// The `created` timestamp was later than the `updated` timestamp.
+    TimestampsSwapped,
+    /// The given number of reminders with an invalid ID were removed.
+    InvalidRemindersRemoved(usize),
+}
+
 impl std::fmt::Display for Note {
     #[tracing::instrument(level = "trace", skip(f))]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -547,6 +912,39 @@ impl HasId for Note {
     }
 }
 
+/// Whether `haystack` contains `lowercase_needle`, ignoring the case of `haystack`. Unlike lowercasing
+/// `haystack` first this never allocates, which matters when searching every note for every keystroke.
+fn contains_lowercase(haystack: &str, lowercase_needle: &str) -> bool {
+    if lowercase_needle.is_empty() {
+        return true;
+    }
+    if haystack.is_ascii() && lowercase_needle.is_ascii() {
+        return haystack
+            .as_bytes()
+            .windows(lowercase_needle.len())
+            .any(|window| window.eq_ignore_ascii_case(lowercase_needle.as_bytes()));
+    }
+    haystack
+        .char_indices()
+        .any(|(index, _)| lowercase_prefix_len(&haystack[index..], lowercase_needle).is_some())
+}
+
+/// The length in bytes of the prefix of `haystack` that matches `lowercase_needle`, if any.
+fn lowercase_prefix_len(haystack: &str, lowercase_needle: &str) -> Option<usize> {
+    let mut needle = lowercase_needle.chars().peekable();
+    for (index, c) in haystack.char_indices() {
+        for lower in c.to_lowercase() {
+            if needle.next() != Some(lower) {
+                return None;
+            }
+        }
+        if needle.peek().is_none() {
+            return Some(index + c.len_utf8());
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -640,4 +1038,393 @@ mod tests {
         assert!(!note.dirty());
         assert!(updated < *note.updated());
     }
+
+    #[test]
+    #[no_coverage]
+    fn flags() {
+        let mut note = make_one_note();
+        note.clear_flags();
+        let updated = *note.updated();
+        assert!(!note.is_pinned());
+        assert!(!note.is_archived());
+
+        note.pin();
+        assert!(note.is_pinned());
+        assert!(note.dirty());
+        assert_eq!(*note.updated(), updated, "flags should not bump the updated time");
+        note.clear_flags();
+
+        assert!(!note.flag(FlagNote::new(note.id(), NoteFlag::Pinned, true)));
+        assert!(!note.dirty());
+        assert!(!note.flag(FlagNote::new(TinyId::random(), NoteFlag::Archived, true)));
+        assert!(!note.is_archived());
+
+        note.archive();
+        assert!(note.is_archived());
+        note.unpin();
+        note.unarchive();
+        assert!(!note.is_pinned());
+        assert!(!note.is_archived());
+
+        // Notes saved before the flags existed should load with both flags unset.
+        note.pin();
+        let mut value = serde_json::to_value(&note).unwrap();
+        let object = value.as_object_mut().unwrap();
+        assert!(object.remove("pinned").is_some());
+        assert!(object.remove("archived").is_some());
+        let old: Note = serde_json::from_value(value).unwrap();
+        assert!(!old.is_pinned());
+        assert!(!old.is_archived());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn attachments() {
+        let mut note = make_one_note();
+        note.clear_flags();
+        assert!(note.attachments().is_empty());
+
+        note.add_attachment(Attachment::new("diagram", "/tmp/diagram.png", Some("image/png".to_string())));
+        note.add_attachment(Attachment::new("spec", "sha256:abc123", None));
+        assert!(note.dirty());
+        assert_eq!(note.attachments().len(), 2);
+        assert_eq!(note.attachments()[0].mime(), Some("image/png"));
+
+        note.clear_flags();
+        assert!(note.remove_attachment("missing").is_none());
+        assert!(!note.dirty());
+        let removed = note.remove_attachment("diagram").unwrap();
+        assert_eq!(removed.path_or_hash(), "/tmp/diagram.png");
+        assert!(note.dirty());
+        assert_eq!(note.attachments().len(), 1);
+
+        let bytes = rmp_serde::to_vec(&note).unwrap();
+        let loaded: Note = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(loaded.attachments(), note.attachments());
+
+        // Notes saved before attachments existed should load with none.
+        let mut value = serde_json::to_value(&note).unwrap();
+        assert!(value.as_object_mut().unwrap().remove("attachments").is_some());
+        let old: Note = serde_json::from_value(value).unwrap();
+        assert!(old.attachments().is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn make_invalid() {
+        let mut note = make_one_note();
+        note.pin();
+        note.archive();
+        note.add_attachment(Attachment::new("diagram", "/tmp/diagram.png", None));
+
+        note.make_invalid();
+        assert!(note.id().is_null());
+        assert!(note.title().is_empty());
+        assert!(note.content().is_empty());
+        assert!(note.tags().is_empty());
+        assert!(note.reminders().is_empty());
+        assert!(!note.is_pinned());
+        assert!(!note.is_archived());
+        assert!(note.attachments().is_empty());
+        assert!(!note.dirty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn validate_and_fix() {
+        let earlier = OffsetDateTime::now_utc() - time::Duration::days(1);
+        let later = OffsetDateTime::now_utc();
+        let mut invalid_reminder = Reminder::default();
+        invalid_reminder.clear();
+        let mut note = Note::existing(
+            TinyId::random(),
+            "  Title ".to_string(),
+            "content".to_string(),
+            vec!["b".to_string(), "a".to_string(), "b".to_string(), "a".to_string()],
+            vec![Reminder::default(), invalid_reminder],
+            later,
+            earlier,
+        );
+
+        let fixes = note.validate_and_fix();
+        assert_eq!(
+            fixes,
+            vec![
+                FixApplied::TitleTrimmed,
+                FixApplied::TagsSorted,
+                FixApplied::DuplicateTagsRemoved(2),
+                FixApplied::TimestampsSwapped,
+                FixApplied::InvalidRemindersRemoved(1),
+            ]
+        );
+        assert_eq!(note.title(), "Title");
+        assert_eq!(note.tags(), &["a".to_string(), "b".to_string()]);
+        assert_eq!(*note.created(), earlier);
+        assert_eq!(*note.updated(), later);
+        assert_eq!(note.reminders().len(), 1);
+        assert!(note.dirty());
+
+        note.clear_flags();
+        assert!(note.validate_and_fix().is_empty());
+        assert!(!note.dirty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn summary_line() {
+        let fits = |line: &str, width: usize| line.chars().count() <= width;
+
+        let note = Note::create(("Short", "", vec!["work"]));
+        assert_eq!(note.summary_line(40), "Short #work");
+
+        let note = Note::create(("A fairly long title for a note", "", vec!["a", "b", "c"]));
+        let line = note.summary_line(20);
+        assert!(fits(&line, 20), "'{}' is wider than 20", line);
+        assert!(line.ends_with(" #3"));
+        assert!(line.starts_with("A fairly"));
+        assert!(line.contains('…'));
+
+        let note = Note::create(("Ünïcödé títle with àccents", "", vec!["one", "two"]));
+        let line = note.summary_line(12);
+        assert!(fits(&line, 12));
+        assert!(line.ends_with("#2"));
+
+        let note = Note::create(("Title", "", vec!["a-very-long-single-tag"]));
+        let line = note.summary_line(16);
+        assert!(fits(&line, 16));
+        assert!(line.ends_with("#1"));
+
+        let note = make_one_note();
+        assert_eq!(note.summary_line(3), "ti…");
+        assert_eq!(note.summary_line(0), "");
+        assert_eq!(Note::create(("Untagged", "")).summary_line(20), "Untagged");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn extract_links() {
+        let note = Note::create((
+            "Links",
+            "See [the docs](https://docs.rs/noted) and [![badge](https://img.shields.io/b.svg)](https://ci.example.com).\n\
+             Bare: https://example.com/path?q=1, and http://old.example.com.\n\
+             [Rust](https://en.wikipedia.org/wiki/Rust_(programming_language) \"title\")\n\
+             ```\n\
+             https://inside.code.block [x](https://also.inside)\n\
+             ```\n\
+             [relative](./other.md)",
+        ));
+        assert_eq!(
+            note.extract_links(),
+            vec![
+                "https://docs.rs/noted",
+                "https://img.shields.io/b.svg",
+                "https://ci.example.com",
+                "https://example.com/path?q=1",
+                "http://old.example.com",
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+                "./other.md",
+            ]
+        );
+        assert!(Note::create(("None", "no links [here] (or here)")).extract_links().is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn headings() {
+        let note = Note::create((
+            "Headings",
+            "# Top\ntext\n## Second ##\n   ### Indented\n####### Too deep\n#hashtag\n\
+             ~~~rust\n# not a heading\n~~~\n###### Six\n#\n## C# rocks",
+        ));
+        assert_eq!(
+            note.headings(),
+            vec![
+                (1, "Top".to_string()),
+                (2, "Second".to_string()),
+                (3, "Indented".to_string()),
+                (6, "Six".to_string()),
+                (1, String::new()),
+                (2, "C# rocks".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn content_stats() {
+        let empty = Note::create(("Empty", ""));
+        assert_eq!(empty.word_count(), 0);
+        assert_eq!(empty.char_count(), 0);
+        assert_eq!(empty.reading_time_minutes(), 0);
+
+        let ascii = Note::create(("Ascii", "  The quick brown\tfox\n\njumps over  "));
+        assert_eq!(ascii.word_count(), 6);
+        assert_eq!(ascii.char_count(), 35);
+        assert_eq!(ascii.reading_time_minutes(), 1);
+
+        // CJK text has no spaces between words, and the ideographic space is Unicode whitespace.
+        let cjk = Note::create(("CJK", "你好世界\u{3000}こんにちは"));
+        assert_eq!(cjk.word_count(), 2);
+        assert_eq!(cjk.char_count(), 10);
+        assert!(cjk.content().len() > cjk.char_count());
+
+        let emoji = Note::create(("Emoji", "🦀 rust 🚀🚀"));
+        assert_eq!(emoji.word_count(), 3);
+        assert_eq!(emoji.char_count(), 9);
+
+        let long = Note::create(("Long", "word ".repeat(Note::WORDS_PER_MINUTE + 1).as_str()));
+        assert_eq!(long.word_count(), 201);
+        assert_eq!(long.reading_time_minutes(), 2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn full_text_search_reminder_labels() {
+        let mut note = make_one_note();
+        assert!(!note.full_text_search("dentist"));
+
+        note.add_reminder(Reminder::default().with_label(&"call dentist"));
+        assert!(note.reminder_label_contains("dentist"));
+        assert!(note.reminder_label_matches("call dentist"));
+        assert!(!note.reminder_label_matches("dentist"));
+        assert!(note.full_text_search("dentist"));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn wrap_content() {
+        let note = Note::create(("Title", "A short first line.\n\nThen a second paragraph that is longer.", vec![]));
+        let lines = note.wrap_content(20);
+        assert_eq!(
+            lines,
+            vec!["A short first line.", "", "Then a second", "paragraph that is", "longer."]
+        );
+        assert!(lines.iter().all(|line| line.chars().count() <= 20));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn highlight_ranges() {
+        let note = Note::create(("Title", "Find the cat. The CAT sat on the cathedral, café Café.", vec![]));
+        let ranges = note.highlight_ranges("cat");
+        assert_eq!(ranges, vec![9..12, 18..21, 33..36]);
+        for range in &ranges {
+            assert_eq!(note.content()[range.clone()].to_lowercase(), "cat");
+        }
+
+        let ranges = note.highlight_ranges("CAFÉ");
+        assert_eq!(ranges.len(), 2);
+        assert!(ranges.iter().all(|r| note.content()[r.clone()].to_lowercase() == "café"));
+
+        assert!(note.highlight_ranges("").is_empty());
+        assert!(note.highlight_ranges("dog").is_empty());
+        assert_eq!(Note::match_ranges("aaaa", "aa"), vec![0..2, 2..4]);
+        assert_eq!(Note::match_ranges("aaa", "aaaa"), vec![]);
+    }
+
+    /// The naive equivalent of [`Note::content_contains_lowercase`], lowercasing everything on every call.
+    fn naive_content_search(note: &Note, text: &str) -> bool {
+        note.content().to_lowercase().contains(&text.to_lowercase())
+    }
+
+    #[test]
+    #[no_coverage]
+    fn cached_content_search() {
+        let mut note = Note::create(("Title", "The Quick Brown FOX jumps over the lazy DOG. Ünïcödé ÄÖÜ"));
+        let queries = ["quick", "QUICK", "fox jumps", "dog.", "cat", "", "äöü", "ünïcödé", "  "];
+        for query in queries {
+            assert_eq!(
+                note.content_contains_lowercase(&query.to_lowercase()),
+                naive_content_search(&note, query),
+                "query `{}`",
+                query
+            );
+        }
+
+        // The cache is rebuilt after every kind of content change.
+        note.set_content("Something Else Entirely");
+        assert!(note.content_contains_lowercase("else"));
+        assert!(!note.content_contains_lowercase("quick"));
+        note.append_content("With MORE");
+        assert!(note.content_contains_lowercase("entirely with more"));
+        note.update_content(|content| content.replace("MORE", "Less"));
+        assert!(note.content_contains_lowercase("with less"));
+        assert!(!note.content_contains_lowercase("more"));
+        assert!(note.update((note.id(), String::from("Title"), String::from("Updated Via DTO"))));
+        assert_eq!(note.content_lowercase(), "updated via dto");
+
+        let other = Note::existing(
+            note.id(),
+            String::from("Title"),
+            String::from("From Other"),
+            Vec::new(),
+            Vec::new(),
+            *note.created(),
+            *note.updated(),
+        );
+        note.update_from(&other);
+        assert_eq!(note.content_lowercase(), "from other");
+        assert_eq!(note.clone().content_lowercase(), "from other");
+
+        assert!(note.full_text_search_lowercase("title"));
+        assert!(note.full_text_search_lowercase("other"));
+        assert!(!note.full_text_search_lowercase("missing"));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn full_text_search_ignores_case() {
+        let mut note = Note::create(("Ünïcödé TITLE", "content"));
+        note.add_tag(String::from("ÄÖÜ-Tag"));
+        note.add_reminder(Reminder::default().with_label(&"Call MÜNCHEN"));
+
+        for query in ["ünïcödé", "title", "é ti", "äöü-tag", "-tag", "münchen", "call m", ""] {
+            assert!(note.full_text_search_lowercase(query), "query {:?} should match", query);
+        }
+        for query in ["ünïcödéx", "tags", "berlin"] {
+            assert!(!note.full_text_search_lowercase(query), "query {:?} should not match", query);
+        }
+
+        assert!(contains_lowercase("Straße", "straße"));
+        assert!(!contains_lowercase("abc", "abcd"));
+        assert!(!contains_lowercase("", "a"));
+    }
+
+    #[test]
+    #[ignore]
+    #[no_coverage]
+    fn cached_content_search_bench() {
+        const QUERIES: usize = 100;
+        crate::profile_guard!("cached_content_search_bench", "types::note::Note");
+
+        let paragraph = "Lorem Ipsum Dolor Sit Amet, Consectetur Adipiscing Elit. ";
+        let note = Note::create(("Huge", paragraph.repeat(100_000).as_str()));
+        let query = "adipiscing elit. lorem";
+
+        {
+            #[cfg(feature = "flame")]
+            let _guard = ::flame::start_guard("naive_content_search");
+            for _ in 0..QUERIES {
+                assert!(naive_content_search(&note, query));
+            }
+        }
+
+        {
+            #[cfg(feature = "flame")]
+            let _guard = ::flame::start_guard("content_contains_lowercase");
+            for _ in 0..QUERIES {
+                assert!(note.content_contains_lowercase(query));
+            }
+        }
+
+        {
+            #[cfg(feature = "flame")]
+            let _guard = ::flame::start_guard("full_text_search_lowercase");
+            for _ in 0..QUERIES {
+                assert!(note.full_text_search_lowercase(query));
+            }
+        }
+
+        crate::flame_dump!(html, "note.Note.content_search");
+    }
 }
@@ -9,6 +9,7 @@ mod create {
 
     use crate::types::Reminder;
     #[derive(Debug, Default, PartialEq, PartialOrd, Clone, Hash, Serialize, Deserialize)]
+    #[serde(default)]
     pub struct CreateNote {
         pub title: Option<String>,
         pub content: Option<String>,
@@ -174,6 +175,7 @@ mod update {
 
     #[derive(Debug, PartialEq, PartialOrd, Clone, Hash, Serialize, Deserialize)]
     pub struct UpdateNote {
+        #[serde(with = "crate::util::id::as_string")]
         pub id: TinyId,
         pub title: Option<String>,
         pub content: Option<String>,
@@ -238,6 +240,11 @@ mod update {
             next
         }
 
+        #[must_use]
+        pub fn with_reminders(self, reminders: Option<Vec<Reminder>>) -> Self {
+            Self { reminders, ..self }
+        }
+
         #[must_use]
         pub fn id(&self) -> &TinyId {
             &self.id
@@ -351,6 +358,7 @@ mod delete {
 
     #[derive(Debug, PartialEq, PartialOrd, Clone, Hash, Serialize, Deserialize)]
     pub struct DeleteNote {
+        #[serde(with = "crate::util::id::as_string")]
         pub id: TinyId,
     }
 
@@ -391,8 +399,57 @@ mod delete {
     }
 }
 
+mod flag {
+    use serde::{Deserialize, Serialize};
+    use tinyid::TinyId;
+
+    /// The boolean metadata flags that can be set on a [`Note`](crate::types::Note).
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
+    pub enum NoteFlag {
+        Pinned,
+        Archived,
+    }
+
+    /// Sets a single [`NoteFlag`] without touching any of the note's content.
+    #[derive(Debug, PartialEq, PartialOrd, Clone, Hash, Serialize, Deserialize)]
+    pub struct FlagNote {
+        #[serde(with = "crate::util::id::as_string")]
+        pub id: TinyId,
+        pub flag: NoteFlag,
+        pub value: bool,
+    }
+
+    impl FlagNote {
+        #[must_use]
+        pub fn new(id: TinyId, flag: NoteFlag, value: bool) -> Self {
+            Self { id, flag, value }
+        }
+
+        #[must_use]
+        pub fn id(&self) -> &TinyId {
+            &self.id
+        }
+
+        #[must_use]
+        pub fn flag(&self) -> NoteFlag {
+            self.flag
+        }
+
+        #[must_use]
+        pub fn value(&self) -> bool {
+            self.value
+        }
+    }
+
+    impl From<(TinyId, NoteFlag, bool)> for FlagNote {
+        fn from(parts: (TinyId, NoteFlag, bool)) -> Self {
+            Self::new(parts.0, parts.1, parts.2)
+        }
+    }
+}
+
 mod dto {
-    use super::{CreateNote, DeleteNote, UpdateNote};
+    use super::{CreateNote, DeleteNote, FlagNote, UpdateNote};
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, PartialEq, PartialOrd, Clone, Hash, Serialize, Deserialize)]
@@ -400,6 +457,7 @@ mod dto {
         Create(CreateNote),
         Update(UpdateNote),
         Delete(DeleteNote),
+        Flag(FlagNote),
     }
 
     impl From<CreateNote> for NoteDto {
@@ -419,6 +477,12 @@ mod dto {
             NoteDto::Delete(note)
         }
     }
+
+    impl From<FlagNote> for NoteDto {
+        fn from(note: FlagNote) -> Self {
+            NoteDto::Flag(note)
+        }
+    }
 }
 
 mod ops {
@@ -711,4 +775,5 @@ mod ops {
 pub use create::*;
 pub use delete::*;
 pub use dto::*;
+pub use flag::*;
 pub use update::*;
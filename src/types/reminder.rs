@@ -14,18 +14,26 @@ use tinyid::TinyId;
 
 use crate::{
     types::{
-        time::{Hour, Hour12, Minute, ReminderTime, SimpleTime, TimePeriod},
-        HasId,
+        time::{Date, Hour, Hour12, Minute, ReminderTime, SimpleTime, TimePeriod, Weekday},
+        HasId, ReminderError,
     },
     util::dtf,
 };
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 struct StoredReminder {
+    #[serde(with = "crate::util::id::as_string")]
     pub id: TinyId,
     pub text: String,
     pub due: ReminderTime,
     pub has_fired: bool,
+    // Not skipped when `None`, msgpack is written positionally so skipping would shift `recurrence` into its place.
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub recurrence: Recurrence,
+    #[serde(default)]
+    pub last_fired: Option<ReminderTime>,
 }
 
 impl From<StoredReminder> for Reminder {
@@ -41,10 +49,49 @@ impl From<Reminder> for StoredReminder {
             text: reminder.text,
             due: reminder.due,
             has_fired: reminder.has_fired,
+            label: reminder.label,
+            recurrence: reminder.recurrence,
+            last_fired: reminder.last_fired,
         }
     }
 }
 
+/// How often a [`Reminder`] repeats. Every occurrence is at the same time of day as the reminder's
+/// due time, on or after its due date, see [`Reminder::next_occurrence_after`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Recurrence {
+    /// The reminder fires once, at its due time.
+    None,
+    /// The reminder fires every day.
+    Daily,
+    /// The reminder fires every week on the given day.
+    Weekly(Weekday),
+    /// The reminder fires every month on the given day of the month (1-31). Months that are too short
+    /// use their last day instead.
+    Monthly(u8),
+    /// The reminder fires every year on the month and day of its due date, February 29th falls back to
+    /// the 28th in non-leap years.
+    Yearly,
+}
+
+impl Recurrence {
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        *self == Recurrence::None
+    }
+
+    #[must_use]
+    pub fn is_recurring(&self) -> bool {
+        !self.is_none()
+    }
+}
+
+impl Default for Recurrence {
+    fn default() -> Self {
+        Recurrence::None
+    }
+}
+
 fn local_offset() -> &'static time::UtcOffset {
     static LOCAL: OnceCell<time::UtcOffset> = OnceCell::new();
     LOCAL.get_or_init(|| {
@@ -52,6 +99,19 @@ fn local_offset() -> &'static time::UtcOffset {
     })
 }
 
+/// The [`dtf::format`] description used for [`Reminder::date_display`] and [`Reminder::time_display`], which
+/// are the two halves on either side of the space.
+const DUE_DISPLAY_FORMAT: &str = "[month]-[day]-[year repr:last_two] [hour repr:12]:[minute][period]";
+
+/// Formats `due` with [`DUE_DISPLAY_FORMAT`] and splits it into its date and time displays.
+fn due_display(due: &OffsetDateTime) -> (String, String) {
+    let formatted = dtf::format(due, DUE_DISPLAY_FORMAT);
+    match formatted.split_once(' ') {
+        Some((date, time)) => (date.to_string(), time.to_string()),
+        None => (formatted, String::new()),
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(from = "StoredReminder", into = "StoredReminder")]
 pub struct Reminder {
@@ -59,6 +119,10 @@ pub struct Reminder {
     text: String,
     due: ReminderTime,
     has_fired: bool,
+    label: Option<String>,
+    recurrence: Recurrence,
+    /// When this reminder last fired, if that was recorded, see [`Reminder::current_occurrence`].
+    last_fired: Option<ReminderTime>,
     date_display: String,
     time_display: String,
 }
@@ -71,7 +135,7 @@ impl Default for Reminder {
             .checked_add(time::Duration::days(1))
             .expect("unable to add one day to now_local");
 
-        let (date, time) = dtf::short_date_and_time(&due);
+        let (date, time) = due_display(&due);
         Self {
             id: TinyId::random(),
             text: String::from("New Reminder"),
@@ -79,6 +143,9 @@ impl Default for Reminder {
             date_display: date,
             time_display: time,
             has_fired: false,
+            label: None,
+            recurrence: Recurrence::None,
+            last_fired: None,
         }
     }
 }
@@ -107,7 +174,7 @@ impl Reminder {
     pub fn new<T: ToString>(text: &T, due: OffsetDateTime) -> Self {
         let has_fired = OffsetDateTime::now_utc() > due;
 
-        let (date, time) = dtf::short_date_and_time(&due);
+        let (date, time) = due_display(&due);
         Self {
             id: TinyId::random(),
             text: text.to_string(),
@@ -115,13 +182,16 @@ impl Reminder {
             date_display: date,
             time_display: time,
             has_fired,
+            label: None,
+            recurrence: Recurrence::None,
+            last_fired: None,
         }
     }
 
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn existing(id: TinyId, text: String, due: OffsetDateTime, has_fired: bool) -> Self {
-        let (date, time) = dtf::short_date_and_time(&due);
+        let (date, time) = due_display(&due);
         Self {
             id,
             text,
@@ -129,11 +199,14 @@ impl Reminder {
             date_display: date,
             time_display: time,
             has_fired,
+            label: None,
+            recurrence: Recurrence::None,
+            last_fired: None,
         }
     }
 
     fn from_stored(stored: StoredReminder) -> Self {
-        let (date, time) = dtf::short_date_and_time(&stored.due.to_time_dt());
+        let (date, time) = due_display(&stored.due.to_time_dt());
         Self {
             id: stored.id,
             text: stored.text,
@@ -141,6 +214,9 @@ impl Reminder {
             date_display: date,
             time_display: time,
             has_fired: stored.has_fired,
+            label: stored.label,
+            recurrence: stored.recurrence,
+            last_fired: stored.last_fired,
         }
     }
 
@@ -161,6 +237,111 @@ impl Reminder {
         self.text = text.to_string();
     }
 
+    /// Builder-style setter for the optional label of this [`Reminder`].
+    #[must_use]
+    pub fn with_label<T: ToString>(mut self, label: &T) -> Self {
+        self.set_label(Some(label.to_string()));
+        self
+    }
+
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    /// Returns true if this [`Reminder`] has a label and it contains `text`.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn label_contains(&self, text: &str) -> bool {
+        self.label.as_ref().map_or(false, |label| label.contains(text))
+    }
+
+    /// Builder-style setter for the [`Recurrence`] of this [`Reminder`].
+    #[must_use]
+    pub fn with_recurrence(mut self, recurrence: Recurrence) -> Self {
+        self.set_recurrence(recurrence);
+        self
+    }
+
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn recurrence(&self) -> Recurrence {
+        self.recurrence
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub fn set_recurrence(&mut self, recurrence: Recurrence) {
+        self.recurrence = recurrence;
+    }
+
+    /// Computes the first time this [`Reminder`] fires strictly after `now`. Occurrences start at the due
+    /// date (the first date matching the [`Recurrence`] on or after it) and are always at the due time of day.
+    /// Returns `None` if the reminder does not recur and its due time is not after `now`.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn next_occurrence_after(&self, now: OffsetDateTime) -> Option<ReminderTime> {
+        use time::{util::days_in_year_month, Duration};
+
+        let now = now.to_offset(time::UtcOffset::UTC);
+        let due = self.due();
+        let time_of_day = due.time();
+        let at = |date: time::Date| date.with_time(time_of_day).assume_utc();
+        let start = due.date().max(now.date());
+
+        let next = match self.recurrence {
+            Recurrence::None => Some(due).filter(|due| *due > now),
+            Recurrence::Daily => {
+                let candidate = at(start);
+                if candidate > now {
+                    Some(candidate)
+                } else {
+                    candidate.checked_add(Duration::DAY)
+                }
+            }
+            Recurrence::Weekly(weekday) => {
+                let weekday = time::Weekday::from(weekday);
+                let ahead = (weekday.number_days_from_sunday() + 7 - start.weekday().number_days_from_sunday()) % 7;
+                let candidate = at(start).checked_add(Duration::days(ahead.into()))?;
+                if candidate > now {
+                    Some(candidate)
+                } else {
+                    candidate.checked_add(Duration::WEEK)
+                }
+            }
+            Recurrence::Monthly(day) => {
+                let (mut year, mut month) = (start.year(), start.month());
+                // The candidate in the start month may already have passed, but the next month's never has.
+                (0..2).find_map(|_| {
+                    let clamped = day.clamp(1, days_in_year_month(year, month));
+                    let candidate = time::Date::from_calendar_date(year, month, clamped).ok().map(at);
+                    if month == time::Month::December {
+                        year += 1;
+                    }
+                    month = month.next();
+                    candidate.filter(|candidate| candidate.date() >= start && *candidate > now)
+                })
+            }
+            Recurrence::Yearly => {
+                let (month, day) = (due.month(), due.day());
+                (start.year()..=start.year() + 1).find_map(|year| {
+                    let clamped = day.min(days_in_year_month(year, month));
+                    time::Date::from_calendar_date(year, month, clamped)
+                        .ok()
+                        .map(at)
+                        .filter(|candidate| candidate.date() >= start && *candidate > now)
+                })
+            }
+        };
+
+        next.map(ReminderTime::from_time_dt)
+    }
+
     #[tracing::instrument(level = "trace")]
     #[must_use]
     pub fn due(&self) -> OffsetDateTime {
@@ -259,6 +440,15 @@ impl Reminder {
         }
     }
 
+    /// Pushes the due time of this [`Reminder`] back by `by`, rolling over into the following day, month, or
+    /// year as needed, and marks it as not yet fired so that it fires again at the new time. Since due times
+    /// are only stored to the minute, any seconds in `by` are dropped.
+    #[tracing::instrument(level = "trace")]
+    pub fn snooze(&mut self, by: time::Duration) {
+        self.mutate_due(|due| *due = due.saturating_add(by));
+        self.has_fired = false;
+    }
+
     #[must_use]
     pub fn get_due_time(
         &self,
@@ -334,7 +524,40 @@ impl Reminder {
 
     #[tracing::instrument(level = "trace")]
     pub fn fire(&mut self) {
+        self.fire_at(OffsetDateTime::now_utc());
+    }
+
+    /// Marks this [`Reminder`] as fired at `at`. A recurring reminder becomes due again at its next occurrence
+    /// after `at`, see [`Reminder::current_occurrence`].
+    #[tracing::instrument(level = "trace")]
+    pub fn fire_at(&mut self, at: OffsetDateTime) {
         self.has_fired = true;
+        self.last_fired = Some(ReminderTime::from_time_dt(at));
+    }
+
+    /// The occurrence of this [`Reminder`] that is waiting to fire. That is its due time until it fires, and
+    /// afterwards the first occurrence after it last fired for a recurring reminder, or `None` for a one-off
+    /// reminder. Recurring reminders that fired without the time being recorded are treated as having last
+    /// fired at their due time.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn current_occurrence(&self) -> Option<OffsetDateTime> {
+        if !self.has_fired {
+            return Some(self.due());
+        }
+        if !self.recurrence.is_recurring() {
+            return None;
+        }
+        let last_fired = self.last_fired.map_or_else(|| self.due(), |at| at.to_time_dt());
+        self.next_occurrence_after(last_fired).map(|next| next.to_time_dt())
+    }
+
+    /// Whether the [`current occurrence`](Reminder::current_occurrence) of this [`Reminder`] is at or before
+    /// `as_of`.
+    #[tracing::instrument(level = "trace")]
+    #[must_use]
+    pub fn is_due_at(&self, as_of: OffsetDateTime) -> bool {
+        self.current_occurrence().map_or(false, |occurrence| occurrence <= as_of)
     }
 
     #[tracing::instrument(level = "trace")]
@@ -342,6 +565,9 @@ impl Reminder {
         self.due = ReminderTime::epoch();
         self.has_fired = true;
         self.text.clear();
+        self.label = None;
+        self.recurrence = Recurrence::None;
+        self.last_fired = None;
         self.id.make_null();
         self.date_display.clear();
         self.time_display.clear();
@@ -356,7 +582,7 @@ impl Reminder {
     fn update_due_display(&mut self) {
         use once_cell::sync::OnceCell;
         let local = local_offset();
-        let (date, time) = dtf::short_date_and_time(&self.due.to_time_dt().to_offset(*local));
+        let (date, time) = due_display(&self.due.to_time_dt().to_offset(*local));
         self.date_display = date;
         self.time_display = time;
     }
@@ -368,11 +594,321 @@ impl HasId for Reminder {
     }
 }
 
+/// Builds a [`Reminder`] from plain calendar and clock values, validating them instead of panicking. The
+/// date and time are interpreted in the [`offset`](ReminderBuilder::offset), which defaults to UTC.
+///
+/// ```ignore
+/// let reminder = Reminder::builder()
+///     .text("Dentist")
+///     .date(2022, 3, 14)
+///     .time(9, 30, TimePeriod::Am)
+///     .recurrence(Recurrence::Yearly)
+///     .build()?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReminderBuilder {
+    text: Option<String>,
+    date: Option<(i32, u8, u8)>,
+    time: Option<(u8, u8, TimePeriod)>,
+    offset: Option<time::UtcOffset>,
+    label: Option<String>,
+    recurrence: Recurrence,
+}
+
+impl Reminder {
+    /// Starts building a new [`Reminder`], see [`ReminderBuilder`].
+    #[must_use]
+    pub fn builder() -> ReminderBuilder {
+        ReminderBuilder::new()
+    }
+}
+
+impl ReminderBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text of the reminder, defaults to `"New Reminder"`.
+    #[must_use]
+    pub fn text<T: ToString>(mut self, text: T) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Sets the due date, `month` and `day` are 1-based.
+    #[must_use]
+    pub fn date(mut self, year: i32, month: u8, day: u8) -> Self {
+        self.date = Some((year, month, day));
+        self
+    }
+
+    /// Sets the due time on a 12-hour clock, `hour` is the clock-face value (1-12).
+    #[must_use]
+    pub fn time(mut self, hour: u8, minute: u8, period: TimePeriod) -> Self {
+        self.time = Some((hour, minute, period));
+        self
+    }
+
+    /// Sets the offset the date and time are given in.
+    #[must_use]
+    pub fn offset(mut self, offset: time::UtcOffset) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    #[must_use]
+    pub fn label<T: ToString>(mut self, label: T) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn recurrence(mut self, recurrence: Recurrence) -> Self {
+        self.recurrence = recurrence;
+        self
+    }
+
+    /// Validates the parts and builds the [`Reminder`].
+    ///
+    /// ## Errors
+    /// - [`ReminderError::Missing`] if the date or time was never set.
+    /// - [`ReminderError::InvalidDate`] if the date does not exist (e.g. February 30th or month 13).
+    /// - [`ReminderError::InvalidTime`] if the hour is not 1-12 or the minute is not 0-59.
+    /// - [`ReminderError::InvalidMonthDay`] if a [`Recurrence::Monthly`] day is not 1-31.
+    #[tracing::instrument(level = "trace")]
+    pub fn build(self) -> crate::Result<Reminder> {
+        let (year, month, day) = self.date.ok_or(ReminderError::Missing("date"))?;
+        let (hour, minute, period) = self.time.ok_or(ReminderError::Missing("time"))?;
+
+        let date = Date::try_new(year, month, day).map_err(|_| ReminderError::InvalidDate { year, month, day })?;
+        if !(1..=12).contains(&hour) || minute > 59 {
+            return Err(ReminderError::InvalidTime { hour, minute }.into());
+        }
+        if let Recurrence::Monthly(day) = self.recurrence && !(1..=31).contains(&day) {
+            return Err(ReminderError::InvalidMonthDay(day).into());
+        }
+
+        let military = hour % 12 + if period.is_pm() { 12 } else { 0 };
+        let time = SimpleTime::from_military(Hour::from_u8(military), Minute::from_u8(minute));
+        let due = time::PrimitiveDateTime::new(date.to_timelib(), time.to_time())
+            .assume_offset(self.offset.unwrap_or(time::UtcOffset::UTC));
+
+        let text = self.text.unwrap_or_else(|| String::from("New Reminder"));
+        let mut reminder = Reminder::new(&text, due).with_recurrence(self.recurrence);
+        reminder.set_label(self.label);
+        Ok(reminder)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
 
+    fn next_after(reminder: &Reminder, now: OffsetDateTime) -> Option<OffsetDateTime> {
+        reminder.next_occurrence_after(now).map(|next| next.to_time_dt())
+    }
+
+    fn recurring(due: OffsetDateTime, recurrence: Recurrence) -> Reminder {
+        Reminder::existing(TinyId::random(), String::from("Recurring"), due, false).with_recurrence(recurrence)
+    }
+
+    #[test]
+    #[no_coverage]
+    fn next_occurrence_none() {
+        use time::macros::datetime;
+
+        let reminder = recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::None);
+        assert_eq!(next_after(&reminder, datetime!(2022-01-01 00:00 UTC)), Some(datetime!(2022-01-31 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-01-31 09:30 UTC)), None);
+        assert_eq!(next_after(&reminder, datetime!(2023-01-01 00:00 UTC)), None);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn next_occurrence_daily() {
+        use time::macros::datetime;
+
+        let reminder = recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::Daily);
+        assert_eq!(next_after(&reminder, datetime!(2022-01-01 12:00 UTC)), Some(datetime!(2022-01-31 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-10 08:00 UTC)), Some(datetime!(2022-03-10 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-10 10:00 UTC)), Some(datetime!(2022-03-11 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-12-31 09:30 UTC)), Some(datetime!(2023-01-01 09:30 UTC)));
+        // The offset of `now` doesn't matter, only the instant.
+        assert_eq!(next_after(&reminder, datetime!(2022-03-10 10:00 +02:00)), Some(datetime!(2022-03-10 09:30 UTC)));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn next_occurrence_weekly() {
+        use time::macros::datetime;
+
+        // 2022-03-10 is a Thursday.
+        let reminder = recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::Weekly(Weekday::Monday));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-10 10:00 UTC)), Some(datetime!(2022-03-14 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-14 09:29 UTC)), Some(datetime!(2022-03-14 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-14 09:30 UTC)), Some(datetime!(2022-03-21 09:30 UTC)));
+
+        let reminder = recurring(datetime!(2022-03-10 09:30 UTC), Recurrence::Weekly(Weekday::Thursday));
+        assert_eq!(next_after(&reminder, datetime!(2022-01-01 00:00 UTC)), Some(datetime!(2022-03-10 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-03-10 09:30 UTC)), Some(datetime!(2022-03-17 09:30 UTC)));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn next_occurrence_monthly() {
+        use time::macros::datetime;
+
+        let reminder = recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::Monthly(31));
+        assert_eq!(next_after(&reminder, datetime!(2022-01-15 00:00 UTC)), Some(datetime!(2022-01-31 09:30 UTC)));
+        // Clamped into February, in both regular and leap years.
+        assert_eq!(next_after(&reminder, datetime!(2022-02-01 00:00 UTC)), Some(datetime!(2022-02-28 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2024-02-10 00:00 UTC)), Some(datetime!(2024-02-29 09:30 UTC)));
+        // April only has 30 days, and the 30th has already passed.
+        assert_eq!(next_after(&reminder, datetime!(2022-04-30 10:00 UTC)), Some(datetime!(2022-05-31 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-12-31 10:00 UTC)), Some(datetime!(2023-01-31 09:30 UTC)));
+
+        let reminder = recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::Monthly(5));
+        assert_eq!(next_after(&reminder, datetime!(2022-01-01 00:00 UTC)), Some(datetime!(2022-02-05 09:30 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2022-06-05 08:00 UTC)), Some(datetime!(2022-06-05 09:30 UTC)));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn next_occurrence_yearly() {
+        use time::macros::datetime;
+
+        let reminder = recurring(datetime!(2020-02-29 12:00 UTC), Recurrence::Yearly);
+        assert_eq!(next_after(&reminder, datetime!(2020-01-01 00:00 UTC)), Some(datetime!(2020-02-29 12:00 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2021-01-01 00:00 UTC)), Some(datetime!(2021-02-28 12:00 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2023-06-01 00:00 UTC)), Some(datetime!(2024-02-29 12:00 UTC)));
+        assert_eq!(next_after(&reminder, datetime!(2024-02-29 12:00 UTC)), Some(datetime!(2025-02-28 12:00 UTC)));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn recurrence_serde() {
+        use time::macros::datetime;
+
+        for recurrence in [
+            Recurrence::None,
+            Recurrence::Daily,
+            Recurrence::Weekly(Weekday::Friday),
+            Recurrence::Monthly(31),
+            Recurrence::Yearly,
+        ] {
+            let reminder = recurring(datetime!(2022-01-31 09:30 UTC), recurrence);
+            let json = serde_json::to_string(&reminder).unwrap();
+            let parsed: Reminder = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.recurrence(), recurrence);
+
+            let packed = rmp_serde::to_vec(&reminder).unwrap();
+            let parsed: Reminder = rmp_serde::from_slice(&packed).unwrap();
+            assert_eq!(parsed.recurrence(), recurrence);
+        }
+
+        // Reminders saved before recurrence existed don't recur.
+        let stored = serde_json::to_value(recurring(datetime!(2022-01-31 09:30 UTC), Recurrence::Daily)).unwrap();
+        let mut old = stored.as_object().unwrap().clone();
+        old.remove("label");
+        old.remove("recurrence");
+        let parsed: Reminder = serde_json::from_value(serde_json::Value::Object(old)).unwrap();
+        assert_eq!(parsed.recurrence(), Recurrence::None);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn snooze() {
+        use crate::types::time::Date;
+        use time::{macros::datetime, Duration};
+
+        let snoozable = |due, has_fired| {
+            Reminder::existing(TinyId::random(), String::from("Snooze"), due, has_fired)
+        };
+        let late = || snoozable(datetime!(2022-03-10 23:45 UTC), true);
+
+        let mut reminder = late();
+        reminder.snooze(Duration::minutes(30));
+        assert_eq!(reminder.due(), datetime!(2022-03-11 00:15 UTC));
+        assert!(!reminder.has_fired());
+        assert_eq!(reminder.get_due_date(), Date::from(time::macros::date!(2022-03-11)));
+
+        let mut reminder = late();
+        reminder.snooze(Duration::HOUR);
+        assert_eq!(reminder.due(), datetime!(2022-03-11 00:45 UTC));
+        let (hour, minute, period) = reminder.get_due_time();
+        assert_eq!((hour.value(), minute.value(), period), (0, 45, TimePeriod::Am));
+
+        let mut reminder = snoozable(datetime!(2022-01-31 23:45 UTC), false);
+        reminder.snooze(Duration::minutes(30));
+        assert_eq!(reminder.due(), datetime!(2022-02-01 00:15 UTC));
+
+        let mut reminder = snoozable(datetime!(2022-12-31 23:00 UTC), false);
+        reminder.snooze(Duration::days(1) + Duration::minutes(90));
+        assert_eq!(reminder.due(), datetime!(2023-01-02 00:30 UTC));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn builder() {
+        use time::macros::{datetime, offset};
+
+        let reminder = Reminder::builder()
+            .text("Dentist")
+            .date(2022, 3, 14)
+            .time(12, 5, TimePeriod::Pm)
+            .label("health")
+            .recurrence(Recurrence::Monthly(14))
+            .build()
+            .expect("Unable to build valid reminder");
+        assert_eq!(reminder.text(), "Dentist");
+        assert_eq!(reminder.label(), Some("health"));
+        assert_eq!(reminder.recurrence(), Recurrence::Monthly(14));
+        assert_eq!(reminder.due(), datetime!(2022-03-14 12:05 UTC));
+
+        let reminder = Reminder::builder()
+            .date(2024, 2, 29)
+            .time(12, 0, TimePeriod::Am)
+            .offset(offset!(+2))
+            .build()
+            .unwrap();
+        assert_eq!(reminder.text(), "New Reminder");
+        assert_eq!(reminder.due(), datetime!(2024-02-28 22:00 UTC));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn builder_rejects_invalid() {
+        let error = |builder: ReminderBuilder| match builder.build() {
+            Err(crate::Error::Reminder(error)) => error,
+            other => panic!("Expected a reminder error, got {:?}", other),
+        };
+        let valid = || Reminder::builder().date(2022, 3, 14).time(9, 30, TimePeriod::Am);
+
+        assert_eq!(
+            error(valid().date(2022, 2, 30)),
+            ReminderError::InvalidDate { year: 2022, month: 2, day: 30 }
+        );
+        assert_eq!(
+            error(valid().date(2023, 2, 29)),
+            ReminderError::InvalidDate { year: 2023, month: 2, day: 29 }
+        );
+        assert_eq!(
+            error(valid().date(2022, 13, 1)),
+            ReminderError::InvalidDate { year: 2022, month: 13, day: 1 }
+        );
+        assert_eq!(error(valid().time(0, 30, TimePeriod::Am)), ReminderError::InvalidTime { hour: 0, minute: 30 });
+        assert_eq!(error(valid().time(9, 60, TimePeriod::Pm)), ReminderError::InvalidTime { hour: 9, minute: 60 });
+        assert_eq!(error(valid().recurrence(Recurrence::Monthly(32))), ReminderError::InvalidMonthDay(32));
+        assert_eq!(error(Reminder::builder().time(9, 30, TimePeriod::Am)), ReminderError::Missing("date"));
+        assert_eq!(error(Reminder::builder().date(2022, 3, 14)), ReminderError::Missing("time"));
+        assert_eq!(
+            ReminderError::InvalidDate { year: 2022, month: 2, day: 30 }.to_string(),
+            "Invalid reminder date: 2022-02-30"
+        );
+    }
+
     #[allow(clippy::cast_lossless)]
     #[test]
     #[cfg_attr(coverage, no_coverage)]
@@ -0,0 +1,124 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use serde::{Deserialize, Serialize};
+
+/// Cleans up tags before they are stored, so that tags entered through different frontends end up
+/// consistent. Tags are always trimmed, and empty and duplicate tags are always removed (keeping the first
+/// occurrence). Lowercasing and replacing whitespace with dashes are opt-in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TagNormalizer {
+    #[serde(default)]
+    lowercase: bool,
+    #[serde(default)]
+    dash_spaces: bool,
+}
+
+/// Constructors
+impl TagNormalizer {
+    #[must_use]
+    pub fn new(lowercase: bool, dash_spaces: bool) -> Self {
+        Self {
+            lowercase,
+            dash_spaces,
+        }
+    }
+
+    #[must_use]
+    pub fn with_lowercase(self, lowercase: bool) -> Self {
+        Self { lowercase, ..self }
+    }
+
+    #[must_use]
+    pub fn with_dash_spaces(self, dash_spaces: bool) -> Self {
+        Self {
+            dash_spaces,
+            ..self
+        }
+    }
+}
+
+/// Member Functions
+impl TagNormalizer {
+    #[must_use]
+    pub fn lowercase(&self) -> bool {
+        self.lowercase
+    }
+
+    pub fn set_lowercase(&mut self, lowercase: bool) {
+        self.lowercase = lowercase;
+    }
+
+    #[must_use]
+    pub fn dash_spaces(&self) -> bool {
+        self.dash_spaces
+    }
+
+    pub fn set_dash_spaces(&mut self, dash_spaces: bool) {
+        self.dash_spaces = dash_spaces;
+    }
+
+    /// Normalizes a single tag. The result may be empty, in which case the tag should be dropped.
+    #[must_use]
+    pub fn normalize(&self, tag: &str) -> String {
+        let tag = tag.trim();
+        let tag = if self.lowercase {
+            tag.to_lowercase()
+        } else {
+            tag.to_string()
+        };
+        if self.dash_spaces {
+            tag.split_whitespace().collect::<Vec<_>>().join("-")
+        } else {
+            tag
+        }
+    }
+
+    /// Normalizes every tag in `tags`, removing any that end up empty or duplicated. The order of the
+    /// remaining tags is preserved.
+    #[must_use]
+    pub fn normalize_all(&self, tags: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = self.normalize(&tag);
+            if !tag.is_empty() && !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn normalize() {
+        let tags = || {
+            vec![" Work ", "work", "", "   ", "to do", "Work", "to  do"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            TagNormalizer::default().normalize_all(tags()),
+            vec!["Work", "work", "to do", "to  do"]
+        );
+        assert_eq!(
+            TagNormalizer::default().with_lowercase(true).normalize_all(tags()),
+            vec!["work", "to do", "to  do"]
+        );
+        assert_eq!(
+            TagNormalizer::new(true, true).normalize_all(tags()),
+            vec!["work", "to-do"]
+        );
+        assert_eq!(TagNormalizer::default().with_dash_spaces(true).normalize(" A  b\tc "), "A-b-c");
+    }
+}
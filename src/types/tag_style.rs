@@ -0,0 +1,113 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use serde::{Deserialize, Serialize};
+
+/// Display information for a tag, used by the frontends to color (and optionally
+/// decorate) tag chips so that notes can be visually grouped.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct TagStyle {
+    color: [u8; 3],
+    #[serde(default)]
+    icon: Option<String>,
+}
+
+/// Constructors
+impl TagStyle {
+    #[must_use]
+    pub fn new(color: [u8; 3], icon: Option<String>) -> Self {
+        Self { color, icon }
+    }
+
+    #[must_use]
+    pub fn from_color(color: [u8; 3]) -> Self {
+        Self::new(color, None)
+    }
+
+    /// Creates the default style for the given tag. The color is derived from a hash of
+    /// the tag text so the same tag always gets the same color, on every machine and run.
+    #[must_use]
+    pub fn default_for(tag: &str) -> Self {
+        Self::from_color(Self::default_color(tag))
+    }
+
+    /// Deterministically derives a color from the given tag text. Only the hue is taken
+    /// from the hash, saturation and value are fixed so that every color is readable.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    #[must_use]
+    pub fn default_color(tag: &str) -> [u8; 3] {
+        // FNV-1a, used instead of `DefaultHasher` as its output is stable across releases.
+        const FNV_OFFSET: u32 = 0x811c_9dc5;
+        const FNV_PRIME: u32 = 0x0100_0193;
+        const SATURATION: f32 = 0.55;
+        const VALUE: f32 = 0.85;
+
+        let hash = tag.bytes().fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+        let hue = (hash % 360) as f32;
+
+        let chroma = VALUE * SATURATION;
+        let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+        let m = VALUE - chroma;
+        let (r, g, b) = match hue as u32 {
+            0..=59 => (chroma, x, 0.0),
+            60..=119 => (x, chroma, 0.0),
+            120..=179 => (0.0, chroma, x),
+            180..=239 => (0.0, x, chroma),
+            240..=299 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        [
+            ((r + m) * 255.0).round() as u8,
+            ((g + m) * 255.0).round() as u8,
+            ((b + m) * 255.0).round() as u8,
+        ]
+    }
+}
+
+/// Member Functions
+impl TagStyle {
+    #[must_use]
+    pub fn color(&self) -> [u8; 3] {
+        self.color
+    }
+
+    pub fn set_color(&mut self, color: [u8; 3]) {
+        self.color = color;
+    }
+
+    #[must_use]
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    pub fn set_icon(&mut self, icon: Option<String>) {
+        self.icon = icon;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    #[no_coverage]
+    fn default_color_is_deterministic() {
+        for tag in ["", "work", "personal", "a much longer tag with spaces"] {
+            assert_eq!(TagStyle::default_color(tag), TagStyle::default_color(tag));
+            assert_eq!(TagStyle::default_for(tag), TagStyle::default_for(tag));
+            assert_eq!(TagStyle::default_for(tag).icon(), None);
+        }
+
+        assert_ne!(
+            TagStyle::default_color("work"),
+            TagStyle::default_color("personal")
+        );
+    }
+}
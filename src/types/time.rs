@@ -450,7 +450,7 @@ mod date {
 
             #[must_use]
             pub fn twelve_hour(self) -> Hour12 {
-                self.standard().0
+                Hour12::from_1_to_12(self.standard().0)
             }
 
             #[must_use]
@@ -458,13 +458,15 @@ mod date {
                 self.value()
             }
 
+            /// Converts this hour to a clock-face hour in the range `1..=12` and a [`TimePeriod`], so
+            /// midnight is `(12, Am)` and noon is `(12, Pm)`.
             #[must_use]
-            pub fn standard(self) -> (Hour12, TimePeriod) {
+            pub fn standard(self) -> (u8, TimePeriod) {
                 let n = self.value();
-                if n < 12 {
-                    (n.into(), TimePeriod::Am)
-                } else {
-                    ((n - 12).into(), TimePeriod::Pm)
+                let period = if n < 12 { TimePeriod::Am } else { TimePeriod::Pm };
+                match n % 12 {
+                    0 => (12, period),
+                    hour => (hour, period),
                 }
             }
 
@@ -588,32 +590,123 @@ mod date {
     )]
     pub struct Date(time::Date);
 
+    /// Errors produced by the non-panicking [`Date`] constructors.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum DateError {
+        /// The month is not in 1-12.
+        InvalidMonth(u8),
+        /// The year, month and day do not form a valid calendar date (e.g. February 30th).
+        InvalidCalendarDate { year: i32, month: u8, day: u8 },
+        /// The year, week and weekday do not form a valid ISO week date (e.g. week 54).
+        InvalidIsoWeekDate { year: i32, week: u8, weekday: Weekday },
+    }
+
+    impl std::fmt::Display for DateError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DateError::InvalidMonth(month) => write!(f, "Invalid month: {}", month),
+                DateError::InvalidCalendarDate { year, month, day } => {
+                    write!(f, "Invalid calendar date: {:04}-{:02}-{:02}", year, month, day)
+                }
+                DateError::InvalidIsoWeekDate { year, week, weekday } => {
+                    write!(f, "Invalid ISO week date: {:04}-W{:02} {}", year, week, weekday)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for DateError {}
+
     impl Date {
+        /// Creates a [`Date`] from known-good values, see [`Date::try_new`] for user input.
+        ///
+        /// ## Panics
+        /// - If the values do not form a valid date.
         #[must_use]
         pub fn new(year: i32, month: u8, day: u8) -> Self {
-            let month = Month::from_u8_unchecked(month);
-            Self(
-                time::Date::from_calendar_date(year, month.into(), day)
-                    .expect("Unable to create Date from calendar date"),
-            )
+            Self::try_new(year, month, day).expect("Unable to create Date from calendar date")
         }
 
+        /// Creates a [`Date`] from known-good values, see [`Date::try_from_calendar_date`] for user input.
+        ///
+        /// ## Panics
+        /// - If the values do not form a valid date.
         #[must_use]
         pub fn from_calendar_date(year: i32, month: impl Into<Month>, day: u8) -> Self {
-            let month = month.into();
-            Self(
-                time::Date::from_calendar_date(year, month.into(), day)
-                    .expect("Unable to create Date from calendar date"),
-            )
+            Self::try_from_calendar_date(year, month, day)
+                .expect("Unable to create Date from calendar date")
         }
 
+        /// Creates a [`Date`] from known-good values, see [`Date::try_from_iso_week_date`] for user input.
+        ///
+        /// ## Panics
+        /// - If the values do not form a valid ISO week date.
         #[must_use]
         pub fn from_iso_week_date(year: i32, week: u8, weekday: impl Into<Weekday>) -> Self {
+            Self::try_from_iso_week_date(year, week, weekday)
+                .expect("Unable to create Date from ISO week date")
+        }
+
+        /// Creates a [`Date`] from a year, 1-based month and day.
+        ///
+        /// ## Errors
+        /// - [`DateError::InvalidMonth`] if `month` is not in 1-12.
+        /// - [`DateError::InvalidCalendarDate`] if the day does not exist in that month (e.g. February 30th).
+        pub fn try_new(year: i32, month: u8, day: u8) -> Result<Self, DateError> {
+            let month = Month::try_from(month).map_err(|()| DateError::InvalidMonth(month))?;
+            Self::try_from_calendar_date(year, month, day)
+        }
+
+        /// Creates a [`Date`] from a year, [`Month`] and day.
+        ///
+        /// ## Errors
+        /// - [`DateError::InvalidCalendarDate`] if the day does not exist in that month (e.g. February 30th).
+        pub fn try_from_calendar_date(
+            year: i32,
+            month: impl Into<Month>,
+            day: u8,
+        ) -> Result<Self, DateError> {
+            let month = month.into();
+            time::Date::from_calendar_date(year, month.into(), day)
+                .map(Self)
+                .map_err(|_| DateError::InvalidCalendarDate {
+                    year,
+                    month: month.into(),
+                    day,
+                })
+        }
+
+        /// Creates a [`Date`] from an ISO year, week and [`Weekday`].
+        ///
+        /// ## Errors
+        /// - [`DateError::InvalidIsoWeekDate`] if the week does not exist in that year.
+        pub fn try_from_iso_week_date(
+            year: i32,
+            week: u8,
+            weekday: impl Into<Weekday>,
+        ) -> Result<Self, DateError> {
             let weekday = weekday.into();
-            Self(
-                time::Date::from_iso_week_date(year, week, weekday.into())
-                    .expect("Unable to create Date from ISO week date"),
-            )
+            time::Date::from_iso_week_date(year, week, weekday.into())
+                .map(Self)
+                .map_err(|_| DateError::InvalidIsoWeekDate {
+                    year,
+                    week,
+                    weekday,
+                })
+        }
+
+        /// Creates a [`Date`] from a [`chrono::NaiveDate`].
+        ///
+        /// ## Errors
+        /// - [`DateError::InvalidIsoWeekDate`] if `date` is outside of the range supported by [`time::Date`].
+        pub fn try_from_chrono(date: chrono::NaiveDate) -> Result<Self, DateError> {
+            let iso = date.iso_week();
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "the week returned by chrono is always a valid u8"
+            )]
+            let week = iso.week() as u8;
+            Self::try_from_iso_week_date(iso.year(), week, date.weekday())
         }
 
         #[must_use]
@@ -688,6 +781,65 @@ mod date {
         pub fn modify_as_timelib(&mut self, f: impl FnOnce(&mut time::Date)) {
             f(&mut self.0);
         }
+
+        /// Moves this date `n` days forward (or backward, if `n` is negative), saturating at the
+        /// minimum and maximum dates supported by [`time::Date`].
+        #[must_use]
+        pub fn add_days(self, n: i64) -> Self {
+            Self(self.0.saturating_add(Self::days_duration(n)))
+        }
+
+        /// Moves this date `n` days backward (or forward, if `n` is negative), saturating at the
+        /// minimum and maximum dates supported by [`time::Date`].
+        #[must_use]
+        pub fn sub_days(self, n: i64) -> Self {
+            Self(self.0.saturating_sub(Self::days_duration(n)))
+        }
+
+        /// `n` days as a [`time::Duration`], clamped to [`time::Duration::MIN`] or [`time::Duration::MAX`]
+        /// instead of panicking like [`time::Duration::days`] does when `n` days overflow.
+        fn days_duration(n: i64) -> time::Duration {
+            n.checked_mul(86_400).map_or(
+                if n.is_negative() { time::Duration::MIN } else { time::Duration::MAX },
+                time::Duration::seconds,
+            )
+        }
+
+        /// The number of days from this date to `other`, which is negative if `other` is earlier.
+        #[must_use]
+        pub fn days_between(self, other: Self) -> i64 {
+            (other.0 - self.0).whole_days()
+        }
+
+        /// Formats this date using a `strftime`-like `pattern`. The supported tokens are `%Y` (year),
+        /// `%m` (zero-padded month number), `%d` (zero-padded day), `%B` (month name), `%A` (weekday
+        /// name), and `%%` (a literal `%`). Any other text, including unknown tokens, is copied as-is.
+        #[must_use]
+        pub fn format(&self, pattern: &str) -> String {
+            let (year, month, day) = self.to_ymd();
+            let mut output = String::with_capacity(pattern.len() + 8);
+            let mut chars = pattern.chars();
+            while let Some(ch) = chars.next() {
+                if ch != '%' {
+                    output.push(ch);
+                    continue;
+                }
+                match chars.next() {
+                    Some('Y') => output.push_str(&format!("{:04}", year)),
+                    Some('m') => output.push_str(&format!("{:02}", u8::from(month))),
+                    Some('d') => output.push_str(&format!("{:02}", day)),
+                    Some('B') => output.push_str(&month.to_string()),
+                    Some('A') => output.push_str(&self.weekday().to_string()),
+                    Some('%') => output.push('%'),
+                    Some(other) => {
+                        output.push('%');
+                        output.push(other);
+                    }
+                    None => output.push('%'),
+                }
+            }
+            output
+        }
     }
 
     impl From<time::Date> for Date {
@@ -698,17 +850,7 @@ mod date {
 
     impl From<chrono::NaiveDate> for Date {
         fn from(date: chrono::NaiveDate) -> Self {
-            let weekday: Weekday = date.weekday().into();
-            let iso = date.iso_week();
-            let year = iso.year();
-            let week = iso.week();
-            #[allow(
-                clippy::cast_possible_truncation,
-                reason = "the week returned by chrono is always a valid u8"
-            )]
-            let time_date = time::Date::from_iso_week_date(year, week as _, weekday.into())
-                .expect("unable to build date from calendar date components");
-            Self(time_date)
+            Self::try_from_chrono(date).expect("unable to build date from calendar date components")
         }
     }
 
@@ -761,11 +903,20 @@ mod min {
             self.0.value()
         }
 
+        /// Creates a [`Minute`] from a **1-based** value, so `1` becomes minute `0` and `60`
+        /// becomes minute `59`. A value of `0` saturates to minute `0`.
         #[must_use]
         pub fn from_1_to_60(minute: u8) -> Self {
             Self::from_u8(minute.saturating_sub(1))
         }
 
+        /// The inverse of [`Minute::from_1_to_60`], returns this minute as a **1-based** value
+        /// in the range `1..=60`.
+        #[must_use]
+        pub fn to_1_to_60(&self) -> u8 {
+            self.value() + 1
+        }
+
         #[must_use]
         pub fn from_chrono<T: chrono::Timelike>(ct: &T) -> Self {
             Self(ct.minute().into())
@@ -850,19 +1001,41 @@ mod reminder_time {
         serde::Deserialize,
         serde::Serialize,
     )]
+    /// An hour on a 12-hour clock, stored **0-based** in the range `0..=11` where `0` is the
+    /// hour displayed as `12` (i.e. `0` + AM is midnight and `0` + PM is noon). Use
+    /// [`Hour12::from_1_to_12`] and [`Hour12::to_1_to_12`] when dealing with clock-face values.
     pub struct Hour12(WrappedU8<0, 11>);
 
     impl Hour12 {
+        /// Creates an [`Hour12`] from a **0-based** value, values outside of `0..=11` wrap.
         #[must_use]
         pub const fn from_u8(hour: u8) -> Self {
             Self(WrappedU8::new(hour))
         }
 
+        /// The **0-based** value of this hour, see [`Hour12::to_1_to_12`] for the clock-face value.
         #[must_use]
         pub fn value(self) -> u8 {
             self.0.value()
         }
 
+        /// Creates an [`Hour12`] from a clock-face value in the range `1..=12`, where `12` is
+        /// stored as `0`.
+        #[must_use]
+        pub const fn from_1_to_12(hour: u8) -> Self {
+            Self::from_u8(hour % 12)
+        }
+
+        /// Returns this hour as a clock-face value in the range `1..=12`, the inverse of
+        /// [`Hour12::from_1_to_12`].
+        #[must_use]
+        pub fn to_1_to_12(self) -> u8 {
+            match self.value() {
+                0 => 12,
+                n => n,
+            }
+        }
+
         #[must_use]
         pub fn from_chrono<T: chrono::Timelike>(ct: &T) -> Self {
             Self(ct.hour().into())
@@ -888,7 +1061,8 @@ mod reminder_time {
 
     impl From<Hour> for (Hour12, TimePeriod) {
         fn from(hour: Hour) -> Self {
-            hour.standard()
+            let (hour, period) = hour.standard();
+            (Hour12::from_1_to_12(hour), period)
         }
     }
 
@@ -930,9 +1104,11 @@ mod reminder_time {
 
         pub fn from_military(hour: Hour, minute: Minute) -> Self {
             let (hr, period) = hour.standard();
-            Self::new(hr, minute, period)
+            Self::new(Hour12::from_1_to_12(hr), minute, period)
         }
 
+        /// Converts this time to a 24-hour [`Hour`] and [`Minute`]. Since [`Hour12`] stores the
+        /// `12` o'clock hour as `0`, 12 AM becomes hour `0` and 12 PM becomes hour `12`.
         pub fn to_military(self) -> (Hour, Minute) {
             (
                 match self.period {
@@ -959,9 +1135,10 @@ mod reminder_time {
         }
     }
 
+    /// Displays the clock-face value, so the 0-based hour `0` is shown as `12`.
     impl std::fmt::Display for Hour12 {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "{}", self.value())
+            write!(f, "{}", self.to_1_to_12())
         }
     }
 
@@ -970,7 +1147,7 @@ mod reminder_time {
             write!(
                 f,
                 "{:02}:{:02} {}",
-                self.hour.value(),
+                self.hour.to_1_to_12(),
                 self.minute.value(),
                 self.period
             )
@@ -1103,7 +1280,7 @@ mod reminder_time {
     }
 }
 
-pub use date::{Date, Hour, TimePeriod};
+pub use date::{Date, DateError, Hour, Month, TimePeriod, Weekday};
 pub use min::Minute;
 pub use reminder_time::{Hour12, ReminderTime, SimpleTime};
 pub use timestamp::Timestamp;
@@ -1119,3 +1296,171 @@ impl std::fmt::Display for Minute {
         write!(f, "{:02}", self.value())
     }
 }
+
+impl std::fmt::Display for Month {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::fmt::Display for Weekday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Formats as an ISO 8601 `YYYY-MM-DD` date.
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format("%Y-%m-%d"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn minute_1_to_60() {
+        assert_eq!(Minute::from_1_to_60(1).value(), 0);
+        assert_eq!(Minute::from_1_to_60(60).value(), 59);
+        assert_eq!(Minute::from_1_to_60(0).value(), 0);
+        for n in 1..=60 {
+            assert_eq!(Minute::from_1_to_60(n).to_1_to_60(), n);
+        }
+        assert_eq!(Minute::from_u8(0).to_1_to_60(), 1);
+        assert_eq!(Minute::from_u8(59).to_1_to_60(), 60);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn hour12_1_to_12() {
+        assert_eq!(Hour12::from_1_to_12(12).value(), 0);
+        assert_eq!(Hour12::from_1_to_12(1).value(), 1);
+        assert_eq!(Hour12::from_1_to_12(11).value(), 11);
+        for n in 1..=12 {
+            assert_eq!(Hour12::from_1_to_12(n).to_1_to_12(), n);
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn twelve_am_pm_to_military() {
+        let midnight = SimpleTime::new(Hour12::from_1_to_12(12), Minute::from_u8(0), TimePeriod::Am);
+        let (hour, minute) = midnight.to_military();
+        assert_eq!((hour.value(), minute.value()), (0, 0));
+
+        let noon = SimpleTime::new(Hour12::from_1_to_12(12), Minute::from_u8(0), TimePeriod::Pm);
+        let (hour, minute) = noon.to_military();
+        assert_eq!((hour.value(), minute.value()), (12, 0));
+
+        let one_am = SimpleTime::new(Hour12::from_1_to_12(1), Minute::from_u8(0), TimePeriod::Am);
+        assert_eq!(one_am.to_military().0.value(), 1);
+
+        let eleven_pm = SimpleTime::new(Hour12::from_1_to_12(11), Minute::from_u8(59), TimePeriod::Pm);
+        assert_eq!(eleven_pm.to_military().0.value(), 23);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn twelve_am_pm_round_trip() {
+        for (military, minute, display) in [
+            (0, 0, "12:00 AM"),
+            (12, 0, "12:00 PM"),
+            (0, 30, "12:30 AM"),
+            (12, 30, "12:30 PM"),
+        ] {
+            let time = SimpleTime::from_military(Hour::from_u8(military), Minute::from_u8(minute));
+            assert_eq!(time.hour.to_1_to_12(), 12);
+            assert_eq!(time.to_string(), display);
+
+            let (hour, min) = time.to_military();
+            assert_eq!(hour.value(), military);
+            assert_eq!(min.value(), minute);
+        }
+
+        assert_eq!(Hour::midnight().standard(), (12, TimePeriod::Am));
+        assert_eq!(Hour::noon().standard(), (12, TimePeriod::Pm));
+        assert_eq!(Hour::from_u8(13u8).standard(), (1, TimePeriod::Pm));
+        assert_eq!(Hour::from_u8(11u8).standard(), (11, TimePeriod::Am));
+        assert_eq!(Hour::noon().twelve_hour(), Hour12::from_1_to_12(12));
+        assert_eq!(<(Hour12, TimePeriod)>::from(Hour::from_u8(23u8)), (Hour12::from_1_to_12(11), TimePeriod::Pm));
+        assert_eq!(Hour12::from_u8(0).to_string(), "12");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn date_day_arithmetic() {
+        let new_years_eve = Date::new(2021, 12, 31);
+        assert_eq!(new_years_eve.add_days(1), Date::new(2022, 1, 1));
+        assert_eq!(new_years_eve.add_days(32), Date::new(2022, 2, 1));
+        assert_eq!(Date::new(2022, 1, 1).sub_days(1), new_years_eve);
+        assert_eq!(new_years_eve.add_days(-1), Date::new(2021, 12, 30));
+        assert_eq!(new_years_eve.sub_days(-1), Date::new(2022, 1, 1));
+        assert_eq!(new_years_eve.add_days(0), new_years_eve);
+
+        // 2024 is a leap year, 2023 is not.
+        assert_eq!(Date::new(2024, 2, 28).add_days(1), Date::new(2024, 2, 29));
+        assert_eq!(Date::new(2024, 2, 29).add_days(1), Date::new(2024, 3, 1));
+        assert_eq!(Date::new(2023, 2, 28).add_days(1), Date::new(2023, 3, 1));
+        assert_eq!(Date::new(2024, 3, 1).sub_days(1), Date::new(2024, 2, 29));
+        assert_eq!(Date::new(2024, 2, 29).add_days(365), Date::new(2025, 2, 28));
+
+        assert_eq!(new_years_eve.days_between(Date::new(2022, 1, 1)), 1);
+        assert_eq!(Date::new(2022, 1, 1).days_between(new_years_eve), -1);
+        assert_eq!(Date::new(2024, 1, 1).days_between(Date::new(2025, 1, 1)), 366);
+        assert_eq!(Date::new(2023, 1, 1).days_between(Date::new(2024, 1, 1)), 365);
+        assert_eq!(new_years_eve.days_between(new_years_eve), 0);
+
+        let max = Date::from(time::Date::MAX);
+        assert_eq!(max.add_days(1), max);
+        let min = Date::from(time::Date::MIN);
+        assert_eq!(min.sub_days(1), min);
+        assert_eq!(new_years_eve.add_days(i64::MAX), max);
+        assert_eq!(new_years_eve.add_days(i64::MIN), min);
+        assert_eq!(new_years_eve.sub_days(i64::MAX), min);
+        assert_eq!(new_years_eve.sub_days(i64::MIN), max);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn date_try_constructors() {
+        assert_eq!(
+            Date::try_new(2022, 2, 30),
+            Err(DateError::InvalidCalendarDate { year: 2022, month: 2, day: 30 })
+        );
+        assert_eq!(Date::try_new(2022, 13, 1), Err(DateError::InvalidMonth(13)));
+        assert_eq!(Date::try_new(2022, 0, 1), Err(DateError::InvalidMonth(0)));
+        assert_eq!(Date::try_new(2024, 2, 29), Ok(Date::new(2024, 2, 29)));
+        assert_eq!(
+            Date::try_from_calendar_date(2023, Month::February, 29),
+            Err(DateError::InvalidCalendarDate { year: 2023, month: 2, day: 29 })
+        );
+        assert_eq!(
+            Date::try_from_iso_week_date(2022, 53, Weekday::Monday),
+            Err(DateError::InvalidIsoWeekDate { year: 2022, week: 53, weekday: Weekday::Monday })
+        );
+        assert_eq!(Date::try_from_iso_week_date(2020, 53, Weekday::Monday), Ok(Date::new(2020, 12, 28)));
+        assert_eq!(
+            Date::try_from_chrono(chrono::NaiveDate::from_ymd(2022, 3, 5)),
+            Ok(Date::new(2022, 3, 5))
+        );
+        assert!(Date::try_from_chrono(chrono::NaiveDate::from_ymd(20_000, 1, 1)).is_err());
+        assert_eq!(DateError::InvalidMonth(13).to_string(), "Invalid month: 13");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn date_display_and_format() {
+        let date = Date::new(2022, 3, 5);
+        assert_eq!(date.to_string(), "2022-03-05");
+        assert_eq!(Date::new(2021, 12, 31).to_string(), "2021-12-31");
+        assert_eq!(date.format("%B %d, %Y"), "March 05, 2022");
+        assert_eq!(date.format("%A, %m/%d"), "Saturday, 03/05");
+        assert_eq!(date.format("100%% on %Y"), "100% on 2022");
+        assert_eq!(date.format("%q %"), "%q %");
+        assert_eq!(date.format(""), "");
+    }
+}
@@ -124,6 +124,96 @@ pub fn humanize_timespan(dur: time::Duration) -> impl std::fmt::Display {
     }
 }
 
+/// Describes how long ago `past` was, relative to `now`, in lower case with the largest whole unit that fits:
+/// "just now" (under a minute), "5 minutes ago", "3 hours ago", "2 days ago" (under 30 days), "4 months ago"
+/// (months of 30 days, under 12 of them), and "1 year ago" (years of 365 days, at least one). Timestamps
+/// after `now` are "in the future".
+#[tracing::instrument]
+#[must_use]
+pub fn humanize_since(past: OffsetDateTime, now: OffsetDateTime) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    crate::flame_guard!("util", "dtf", "humanize_since");
+
+    let secs = (now - past).whole_seconds();
+    let (count, unit) = match secs {
+        _ if secs < 0 => return String::from("in the future"),
+        _ if secs < MINUTE => return String::from("just now"),
+        _ if secs < HOUR => (secs / MINUTE, "minute"),
+        _ if secs < DAY => (secs / HOUR, "hour"),
+        _ if secs < MONTH => (secs / DAY, "day"),
+        _ if secs < 12 * MONTH => (secs / MONTH, "month"),
+        _ => ((secs / YEAR).max(1), "year"),
+    };
+    format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// Parses a (small) natural-language description of a point in time, relative to `now`. Understood inputs
+/// (case-insensitive, surrounding whitespace ignored) are:
+/// - `now`, `today`, and `tomorrow`
+/// - `next <weekday>` (e.g. `next monday` or `next mon`), which is always 1 to 7 days ahead
+/// - `in <n> <unit>`, where unit is `minute(s)`/`min(s)`, `hour(s)`, `day(s)`, or `week(s)`
+/// - `YYYY-MM-DD`
+///
+/// Everything other than `in <n> minutes/hours` keeps the time of day of `now`. The result is always in the
+/// offset of `now`. Unrecognized (or out of range) input returns `None`.
+#[tracing::instrument]
+#[must_use]
+pub fn parse_relative(input: &str, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    use time::Duration;
+
+    crate::flame_guard!("util", "dtf", "parse_relative");
+
+    let input = input.trim().to_lowercase();
+    let words = input.split_whitespace().collect::<Vec<_>>();
+    match words.as_slice() {
+        ["now" | "today"] => Some(now),
+        ["tomorrow"] => now.checked_add(Duration::DAY),
+        ["next", weekday] => {
+            let weekday = parse_weekday(weekday)?;
+            let ahead = (weekday.number_days_from_sunday() + 7
+                - now.weekday().number_days_from_sunday())
+                % 7;
+            let ahead = if ahead == 0 { 7 } else { ahead };
+            now.checked_add(Duration::days(ahead.into()))
+        }
+        ["in", count, unit] => {
+            let count: i64 = count.parse::<u32>().ok()?.into();
+            let duration = match *unit {
+                "minute" | "minutes" | "min" | "mins" => Duration::minutes(count),
+                "hour" | "hours" => Duration::hours(count),
+                "day" | "days" => Duration::days(count),
+                "week" | "weeks" => Duration::weeks(count),
+                _ => return None,
+            };
+            now.checked_add(duration)
+        }
+        [date] => {
+            let format = time::macros::format_description!("[year]-[month]-[day]");
+            let date = time::Date::parse(date, format).ok()?;
+            Some(now.replace_date(date))
+        }
+        _ => None,
+    }
+}
+
+fn parse_weekday(name: &str) -> Option<time::Weekday> {
+    Some(match name {
+        "monday" | "mon" => time::Weekday::Monday,
+        "tuesday" | "tue" | "tues" => time::Weekday::Tuesday,
+        "wednesday" | "wed" => time::Weekday::Wednesday,
+        "thursday" | "thu" | "thurs" => time::Weekday::Thursday,
+        "friday" | "fri" => time::Weekday::Friday,
+        "saturday" | "sat" => time::Weekday::Saturday,
+        "sunday" | "sun" => time::Weekday::Sunday,
+        _ => return None,
+    })
+}
+
 #[tracing::instrument]
 #[must_use]
 pub fn timestamp_to_string(timestamp: &OffsetDateTime) -> String {
@@ -163,6 +253,44 @@ pub fn short_date_and_time(timestamp: &OffsetDateTime) -> (String, String) {
         .expect("no space found in short_datetime!")
 }
 
+/// The named presets accepted by [`format`], along with the format description each one stands for.
+pub const DATE_FORMAT_PRESETS: &[(&str, &str)] = &[
+    ("us-12h", "[month]-[day]-[year repr:last_two] [hour repr:12]:[minute]:[second][period]"),
+    ("us-24h", "[month]-[day]-[year repr:last_two] [hour]:[minute]:[second]"),
+    ("iso-12h", "[year]-[month]-[day] [hour repr:12]:[minute]:[second][period]"),
+    ("iso-24h", "[year]-[month]-[day] [hour]:[minute]:[second]"),
+    ("eu-24h", "[day].[month].[year] [hour]:[minute]:[second]"),
+];
+
+/// The date format used when none is configured, or when the configured one is invalid. This is the
+/// same format used by [`timestamp_to_string`].
+pub const DEFAULT_DATE_FORMAT: &str = "us-12h";
+
+/// Formats `dt` using `date_format`, which is either the name of one of the [`DATE_FORMAT_PRESETS`] or a
+/// [`time` format description](https://time-rs.github.io/book/api/format-description.html) such as
+/// `"[year]-[month]-[day] [hour]:[minute]"`. This is how front-ends should display timestamps, passing the
+/// user's configured format (i.e. `AppSettings::date_format` in the GUI).
+///
+/// Invalid format descriptions fall back to [`DEFAULT_DATE_FORMAT`] rather than failing.
+#[tracing::instrument]
+#[must_use]
+pub fn format(dt: &OffsetDateTime, date_format: &str) -> String {
+    let description = DATE_FORMAT_PRESETS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(date_format.trim()))
+        .map_or(date_format, |(_, description)| description);
+
+    time::format_description::parse(description)
+        .ok()
+        .and_then(|items| dt.format(&items).ok())
+        .unwrap_or_else(|| {
+            #[cfg(feature = "trace")] {
+                tracing::warn!(date_format, "invalid date format, using the default");
+            }
+            timestamp_to_string(dt)
+        })
+}
+
 pub enum TimeLib {
     Time,
     Chrono,
@@ -248,6 +376,31 @@ pub fn ensure_time_is_utc(dt: &mut time::OffsetDateTime) {
     }
 }
 
+/// (De)serializes an optional [`OffsetDateTime`] as an RFC 3339 string (or `null`), for output that is meant
+/// to be read by other programs. Use with `#[serde(with = "crate::util::dtf::rfc3339_option")]`.
+pub mod rfc3339_option {
+    use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serializer};
+    use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+    pub fn serialize<S: Serializer>(
+        dt: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match dt {
+            Some(dt) => serializer.serialize_some(&dt.format(&Rfc3339).map_err(S::Error::custom)?),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<OffsetDateTime>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| OffsetDateTime::parse(&s, &Rfc3339).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
 #[must_use]
 fn time_to_chrono_dt(dt: time::OffsetDateTime) -> chrono::DateTime<chrono::Utc> {
     let (year, month, day) = dt.date().to_calendar_date();
@@ -271,6 +424,85 @@ mod tests {
     use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
     use time::macros::datetime;
 
+    #[test]
+    #[no_coverage]
+    fn parse_relative_phrases() {
+        // A Thursday.
+        let now = datetime!(2022-03-10 14:30 UTC);
+        let parse = |input: &str| parse_relative(input, now);
+
+        assert_eq!(parse("now"), Some(now));
+        assert_eq!(parse("today"), Some(now));
+        assert_eq!(parse("  Today "), Some(now));
+        assert_eq!(parse("tomorrow"), Some(datetime!(2022-03-11 14:30 UTC)));
+
+        assert_eq!(parse("next monday"), Some(datetime!(2022-03-14 14:30 UTC)));
+        assert_eq!(parse("next Fri"), Some(datetime!(2022-03-11 14:30 UTC)));
+        assert_eq!(parse("next thursday"), Some(datetime!(2022-03-17 14:30 UTC)));
+        assert_eq!(parse("next wednesday"), Some(datetime!(2022-03-16 14:30 UTC)));
+
+        assert_eq!(parse("in 3 days"), Some(datetime!(2022-03-13 14:30 UTC)));
+        assert_eq!(parse("in 1 day"), Some(datetime!(2022-03-11 14:30 UTC)));
+        assert_eq!(parse("in 2 hours"), Some(datetime!(2022-03-10 16:30 UTC)));
+        assert_eq!(parse("in 45 mins"), Some(datetime!(2022-03-10 15:15 UTC)));
+        assert_eq!(parse("in 3 weeks"), Some(datetime!(2022-03-31 14:30 UTC)));
+        assert_eq!(parse("IN  12   HOURS"), Some(datetime!(2022-03-11 02:30 UTC)));
+
+        assert_eq!(parse("2022-12-25"), Some(datetime!(2022-12-25 14:30 UTC)));
+        assert_eq!(parse("2024-02-29"), Some(datetime!(2024-02-29 14:30 UTC)));
+
+        // The offset of `now` is kept.
+        let local = datetime!(2022-03-10 23:30 -05:00);
+        assert_eq!(parse_relative("in 1 hour", local), Some(datetime!(2022-03-11 00:30 -05:00)));
+        assert_eq!(parse_relative("tomorrow", local), Some(datetime!(2022-03-11 23:30 -05:00)));
+
+        for bad in [
+            "",
+            "later",
+            "next",
+            "next month",
+            "in days",
+            "in -3 days",
+            "in 3 fortnights",
+            "2023-02-29",
+            "2022/12/25",
+            "tomorrow please",
+        ] {
+            assert_eq!(parse(bad), None, "`{}` should not parse", bad);
+        }
+    }
+
+    #[test]
+    #[no_coverage]
+    fn humanize_since_buckets() {
+        use time::Duration;
+
+        let now = datetime!(2022-06-15 10:00 UTC);
+        let since = |ago: Duration| humanize_since(now - ago, now);
+
+        assert_eq!(since(Duration::ZERO), "just now");
+        assert_eq!(since(Duration::seconds(59)), "just now");
+        assert_eq!(since(Duration::MINUTE), "1 minute ago");
+        assert_eq!(since(Duration::minutes(5)), "5 minutes ago");
+        assert_eq!(since(Duration::minutes(59) + Duration::seconds(59)), "59 minutes ago");
+        assert_eq!(since(Duration::HOUR), "1 hour ago");
+        assert_eq!(since(Duration::hours(23) + Duration::minutes(59)), "23 hours ago");
+        assert_eq!(since(Duration::DAY), "1 day ago");
+        assert_eq!(since(Duration::days(3)), "3 days ago");
+        assert_eq!(since(Duration::days(29) + Duration::hours(23)), "29 days ago");
+        assert_eq!(since(Duration::days(30)), "1 month ago");
+        assert_eq!(since(Duration::days(65)), "2 months ago");
+        assert_eq!(since(Duration::days(359)), "11 months ago");
+        assert_eq!(since(Duration::days(360)), "1 year ago");
+        assert_eq!(since(Duration::days(365)), "1 year ago");
+        assert_eq!(since(Duration::days(3 * 365)), "3 years ago");
+
+        assert_eq!(since(Duration::seconds(-1)), "in the future");
+        assert_eq!(humanize_since(now, now - Duration::days(2)), "in the future");
+        // Only the instant matters, not the offset.
+        assert_eq!(humanize_since(datetime!(2022-06-15 11:55 +02:00), now), "5 minutes ago");
+    }
+
     #[test]
     #[no_coverage]
     fn seconds() {
@@ -453,6 +685,24 @@ mod tests {
         assert_eq!(output, "In 2 months");
     }
 
+    #[test]
+    #[no_coverage]
+    fn format_with_date_format() {
+        let dt = datetime!(2022-03-09 14:05:07 UTC);
+        assert_eq!(format(&dt, "us-12h"), "03-09-22 02:05:07PM");
+        assert_eq!(format(&dt, "US-24H"), "03-09-22 14:05:07");
+        assert_eq!(format(&dt, "iso-12h"), "2022-03-09 02:05:07PM");
+        assert_eq!(format(&dt, "iso-24h"), "2022-03-09 14:05:07");
+        assert_eq!(format(&dt, "eu-24h"), "09.03.2022 14:05:07");
+        assert_eq!(format(&dt, "[year]/[month]/[day] [hour]:[minute]"), "2022/03/09 14:05");
+        assert_eq!(format(&dt, DEFAULT_DATE_FORMAT), timestamp_to_string(&dt));
+
+        // Invalid format descriptions fall back to the default rather than failing.
+        assert_eq!(format(&dt, "[year"), timestamp_to_string(&dt));
+        assert_eq!(format(&dt, "[not_a_component]"), timestamp_to_string(&dt));
+        assert_eq!(format(&dt, "no components"), "no components");
+    }
+
     #[test]
     fn long_time() {
         let output = humanize_timespan_between(
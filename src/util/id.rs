@@ -0,0 +1,596 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Helpers for using [`TinyId`]s outside of the application, i.e. in URLs, filenames and serialized data.
+//!
+//! The [`Display`](std::fmt::Display) form of a [`TinyId`] is its canonical textual form. It is always
+//! [`ID_LENGTH`] characters from `A-Z`, `a-z`, `0-9`, `_`, and `-`, all of which are "unreserved" characters
+//! in URIs (RFC 3986), so it can be used as-is in routes like `/notes/:id` without percent-encoding. A
+//! separate base62 encoding would not be any shorter (62 symbols carry less information per character than
+//! the 64 used here), so none is provided. The one caveat is that ids differing only in case are distinct,
+//! so they should not be used as filenames on case-insensitive file systems.
+
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyid::TinyId;
+
+/// The number of characters in the textual form of a [`TinyId`].
+pub const ID_LENGTH: usize = 8;
+
+/// Returns `true` if `c` can appear in the textual form of a [`TinyId`].
+#[must_use]
+pub fn is_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Strictly parses the textual form of a [`TinyId`], e.g. a path segment from a URL. Unlike
+/// [`TinyId::from_str`], this rejects anything that is not exactly [`ID_LENGTH`] URL-safe characters, as
+/// well as the resulting id if it is not valid. Surrounding whitespace is not allowed.
+#[must_use]
+pub fn parse_url_segment(segment: &str) -> Option<TinyId> {
+    crate::flame_guard!("util", "id", "parse_url_segment");
+    if segment.len() != ID_LENGTH || !segment.chars().all(is_id_char) {
+        return None;
+    }
+
+    TinyId::from_str(segment).ok().filter(|id| id.is_valid())
+}
+
+/// The characters of a [`TinyId`] in ascending (ASCII) order, so that [`TimeOrdered`] ids compare the same way
+/// as the numbers they encode.
+const SORTED_ALPHABET: &[u8; 64] = b"-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+/// The number of low bits of a [`TimeOrdered`] id (its last two characters) that are random.
+const RANDOM_BITS: u32 = 12;
+
+/// The timestamp of a [`TimeOrdered`] id counts ticks of `2^TICK_SHIFT` (64) milliseconds since the Unix epoch,
+/// which fits in the remaining 36 bits until the year 2109.
+const TICK_SHIFT: u32 = 6;
+
+/// The largest number that fits in the [`ID_LENGTH`] characters of an id.
+const MAX_ENCODED: u64 = (1 << (6 * ID_LENGTH)) - 1;
+
+/// The last number encoded by [`TimeOrdered::time_ordered`], so ids created in the same tick still increase.
+static LAST_TIME_ORDERED: AtomicU64 = AtomicU64::new(0);
+
+/// Extends [`TinyId`] with ids that sort in the order they were created.
+pub trait TimeOrdered {
+    /// Creates an id whose first six characters encode the current time (in 64 millisecond ticks) and whose
+    /// last two are random, so that ids created later compare (and sort, as text) after earlier ones. Ids
+    /// created in the same tick by this process are still unique and increasing, the later one is made one
+    /// greater than the last id if its random part would not sort after it.
+    fn time_ordered() -> Self;
+}
+
+impl TimeOrdered for TinyId {
+    fn time_ordered() -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
+        let random = fastrand::u64(..1 << RANDOM_BITS);
+
+        let mut value = 0;
+        // The closure always returns `Some`, so this never fails.
+        let _ = LAST_TIME_ORDERED.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            value = next_time_ordered(millis, random, last);
+            Some(value)
+        });
+        encode_sorted(value)
+    }
+}
+
+/// The number encoded by the next [`TimeOrdered`] id, created at `millis` since the Unix epoch with the given
+/// `random` low bits, after `last` was encoded.
+fn next_time_ordered(millis: u64, random: u64, last: u64) -> u64 {
+    let ticks = (millis >> TICK_SHIFT).min(MAX_ENCODED >> RANDOM_BITS);
+    let value = (ticks << RANDOM_BITS) | (random & ((1 << RANDOM_BITS) - 1));
+    if value > last {
+        value
+    } else {
+        last.saturating_add(1).min(MAX_ENCODED)
+    }
+}
+
+/// Encodes the low `6 * ID_LENGTH` bits of `value` as an id, most significant first, using the
+/// [`SORTED_ALPHABET`].
+#[allow(clippy::cast_possible_truncation)]
+fn encode_sorted(value: u64) -> TinyId {
+    let text = (0..ID_LENGTH)
+        .rev()
+        .map(|i| SORTED_ALPHABET[((value >> (6 * i)) & 0x3F) as usize] as char)
+        .collect::<String>();
+    TinyId::from_str(&text).expect("Encoded ids only contain id characters")
+}
+
+/// Converts `value` into a valid [`TinyId`] by mapping each of its big-endian bytes to an id character (`byte % 64`).
+///
+/// Use this instead of `TinyId::from(u64)` and `TinyId::from_u64_unchecked`, which copy the raw bytes of the
+/// number into the id and so produce invalid ids for almost every input. Distinct numbers can produce the same
+/// id, and `TinyId::to_u64` does not give back `value`.
+#[must_use]
+pub fn id_from_u64(value: u64) -> TinyId {
+    let text = value
+        .to_be_bytes()
+        .iter()
+        .map(|&byte| SORTED_ALPHABET[usize::from(byte % 64)] as char)
+        .collect::<String>();
+    TinyId::from_str(&text).expect("Mapped ids only contain id characters")
+}
+
+/// The number of characters in a [`LongId`].
+pub const LONG_ID_LENGTH: usize = 16;
+
+/// An id like [`TinyId`] that is [`LONG_ID_LENGTH`] characters long instead of [`ID_LENGTH`], for when the chance
+/// of two random [`TinyId`]s colliding is too high. It uses the same characters, so everything said about the
+/// textual form of a [`TinyId`] applies to it too, and it is stored as that textual form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongId {
+    data: [u8; LONG_ID_LENGTH],
+}
+
+impl LongId {
+    /// Creates a random id.
+    #[must_use]
+    pub fn random() -> Self {
+        let mut data = [0; LONG_ID_LENGTH];
+        for byte in &mut data {
+            *byte = SORTED_ALPHABET[usize::from(fastrand::u8(..) % 64)];
+        }
+        Self { data }
+    }
+
+    /// The null id, which is never valid.
+    #[must_use]
+    pub const fn null() -> Self {
+        Self {
+            data: [0; LONG_ID_LENGTH],
+        }
+    }
+
+    /// Creates an id from the characters in `bytes`.
+    ///
+    /// ## Errors
+    /// - If any byte is not an id character (see [`is_id_char`]).
+    pub fn from_bytes(bytes: [u8; LONG_ID_LENGTH]) -> Result<Self, LongIdError> {
+        match bytes.iter().find(|&&byte| !is_id_char(byte as char)) {
+            Some(&byte) => Err(LongIdError::InvalidCharacter(byte as char)),
+            None => Ok(Self { data: bytes }),
+        }
+    }
+
+    /// The characters of this id.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; LONG_ID_LENGTH] {
+        &self.data
+    }
+
+    #[must_use]
+    pub fn is_null(&self) -> bool {
+        self.data == [0; LONG_ID_LENGTH]
+    }
+
+    /// Returns `true` if this id is not null and only contains id characters.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        !self.is_null() && self.data.iter().all(|&byte| is_id_char(byte as char))
+    }
+}
+
+impl Default for LongId {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+impl std::fmt::Display for LongId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.data.iter().try_for_each(|&byte| write!(f, "{}", byte as char))
+    }
+}
+
+impl FromStr for LongId {
+    type Err = LongIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(c) = s.chars().find(|&c| !is_id_char(c)) {
+            return Err(LongIdError::InvalidCharacter(c));
+        }
+        let bytes = <[u8; LONG_ID_LENGTH]>::try_from(s.as_bytes()).map_err(|_| LongIdError::InvalidLength(s.len()))?;
+        Self::from_bytes(bytes)
+    }
+}
+
+impl serde::Serialize for LongId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LongId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The reasons the textual form of a [`LongId`] can be rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LongIdError {
+    /// The text is not [`LONG_ID_LENGTH`] characters long.
+    InvalidLength(usize),
+    /// The text contains a character that is not an id character.
+    InvalidCharacter(char),
+}
+
+impl std::fmt::Display for LongIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(f, "Ids must be {} characters long, not {}", LONG_ID_LENGTH, len),
+            Self::InvalidCharacter(c) => write!(f, "'{}' can not appear in an id", c),
+        }
+    }
+}
+
+impl std::error::Error for LongIdError {}
+
+/// Serde support for storing a [`TinyId`] in its textual form, used as `#[serde(with = "crate::util::id::as_string")]`.
+///
+/// The serde support in `tinyid` writes the struct holding the id's bytes, which is verbose in every format
+/// (`{"data":[97,98,...]}` in JSON, a nested array of numbers in msgpack). Ids are written as their
+/// [`Display`](std::fmt::Display) form instead and read back with the validating [`TinyId::from_str`]. The old
+/// byte form is still accepted when reading, so existing databases keep loading.
+pub mod as_string {
+    use std::{fmt, str::FromStr};
+
+    use serde::{
+        de::{self, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serializer,
+    };
+    use tinyid::TinyId;
+
+    use super::ID_LENGTH;
+
+    /// Serializes `id` as its textual form.
+    ///
+    /// ## Errors
+    /// - Forwards any error from `serializer`.
+    pub fn serialize<S: Serializer>(id: &TinyId, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(id)
+    }
+
+    /// Deserializes an id from its textual form, or from the byte form written by `tinyid`.
+    ///
+    /// ## Errors
+    /// - If the data is neither form, or the text is not a valid id.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TinyId, D::Error> {
+        deserializer.deserialize_any(IdVisitor)
+    }
+
+    fn from_text<E: de::Error>(text: &str) -> Result<TinyId, E> {
+        match TinyId::from_str(text) {
+            Ok(id) => Ok(id),
+            // Null ids (e.g. of cleared reminders) are written like any other, so they must be read back too.
+            Err(_) if text == TinyId::null().to_string() => Ok(TinyId::null()),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Str(text), &IdVisitor)),
+        }
+    }
+
+    /// Reads the bytes of an id as written by `tinyid`, by handing them to its own deserializer.
+    fn from_bytes<E: de::Error>(bytes: Vec<u8>) -> Result<TinyId, E> {
+        if bytes.len() != ID_LENGTH {
+            return Err(E::invalid_length(bytes.len(), &IdVisitor));
+        }
+        TinyId::deserialize(IntoDeserializer::<'_, E>::into_deserializer(vec![bytes]))
+    }
+
+    struct IdVisitor;
+
+    impl<'de> Visitor<'de> for IdVisitor {
+        type Value = TinyId;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "a {} character id, or the {} bytes of one", ID_LENGTH, ID_LENGTH)
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<TinyId, E> {
+            from_text(text)
+        }
+
+        fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<TinyId, E> {
+            from_bytes(bytes.to_vec())
+        }
+
+        /// The bytes themselves, or in positional formats like msgpack the struct holding them.
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TinyId, A::Error> {
+            match seq.next_element::<BytePart>()? {
+                Some(BytePart::Bytes(bytes)) => {
+                    if seq.next_element::<IgnoredAny>()?.is_some() {
+                        return Err(de::Error::invalid_length(2, &self));
+                    }
+                    from_bytes(bytes)
+                }
+                Some(BytePart::Byte(first)) => {
+                    let mut bytes = vec![first];
+                    while let Some(byte) = seq.next_element::<u8>()? {
+                        bytes.push(byte);
+                    }
+                    from_bytes(bytes)
+                }
+                None => Err(de::Error::invalid_length(0, &self)),
+            }
+        }
+
+        /// The struct holding the bytes, in self-describing formats like JSON.
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<TinyId, A::Error> {
+            let mut bytes = None;
+            while let Some(key) = map.next_key::<String>()? {
+                if key == "data" {
+                    bytes = Some(map.next_value::<Vec<u8>>()?);
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+            from_bytes(bytes.ok_or_else(|| de::Error::missing_field("data"))?)
+        }
+    }
+
+    /// The first element of a sequence read by [`IdVisitor::visit_seq`]: either the first byte of the id, or all
+    /// of its bytes.
+    enum BytePart {
+        Byte(u8),
+        Bytes(Vec<u8>),
+    }
+
+    impl<'de> Deserialize<'de> for BytePart {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct BytePartVisitor;
+
+            impl<'de> Visitor<'de> for BytePartVisitor {
+                type Value = BytePart;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    formatter.write_str("a byte or a sequence of bytes")
+                }
+
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<BytePart, E> {
+                    u8::try_from(value)
+                        .map(BytePart::Byte)
+                        .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+                }
+
+                fn visit_i64<E: de::Error>(self, value: i64) -> Result<BytePart, E> {
+                    u8::try_from(value)
+                        .map(BytePart::Byte)
+                        .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+                }
+
+                fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<BytePart, E> {
+                    Ok(BytePart::Bytes(bytes.to_vec()))
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BytePart, A::Error> {
+                    let mut bytes = Vec::with_capacity(ID_LENGTH);
+                    while let Some(byte) = seq.next_element::<u8>()? {
+                        bytes.push(byte);
+                    }
+                    Ok(BytePart::Bytes(bytes))
+                }
+            }
+
+            deserializer.deserialize_any(BytePartVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn url_segments() {
+        for _ in 0..1_000 {
+            let id = TinyId::random();
+            let text = id.to_string();
+            assert_eq!(text.len(), ID_LENGTH);
+            assert!(text.chars().all(is_id_char), "`{}` is not url safe", text);
+            assert_eq!(parse_url_segment(&text), Some(id));
+        }
+
+        for malformed in [
+            "",
+            "short",
+            "waytoolong",
+            " abcdefg",
+            "abcdefg ",
+            "abc/defg",
+            "abc?defg",
+            "abc%20de",
+            "abc.defg",
+            "abcdéfg",
+        ] {
+            assert_eq!(parse_url_segment(malformed), None, "`{}` should be rejected", malformed);
+        }
+        assert_eq!(parse_url_segment(&TinyId::null().to_string()), None);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn time_ordered_ids() {
+        assert!(SORTED_ALPHABET.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(SORTED_ALPHABET.iter().all(|&c| is_id_char(c as char)));
+
+        // Simulated time steps, some shorter than a tick.
+        let mut millis = 1_654_041_600_000;
+        let mut last = 0;
+        let mut previous = String::new();
+        for step in 0..2000 {
+            millis += fastrand::u64(..200);
+            last = next_time_ordered(millis, fastrand::u64(..), last);
+            let id = encode_sorted(last);
+            let text = id.to_string();
+            assert_eq!(text.len(), ID_LENGTH);
+            assert!(id.is_valid(), "{}", text);
+            assert!(text > previous, "step {}: {} should sort after {}", step, text, previous);
+            previous = text;
+        }
+
+        // A later tick always sorts after an earlier one, whatever the random bits.
+        let early = encode_sorted(next_time_ordered(millis, (1 << RANDOM_BITS) - 1, 0)).to_string();
+        let late = encode_sorted(next_time_ordered(millis + 64, 0, 0)).to_string();
+        assert!(late > early);
+
+        let first = TinyId::time_ordered();
+        let second = TinyId::time_ordered();
+        assert!(first.is_valid() && second.is_valid());
+        assert!(second.to_string() > first.to_string());
+        assert_eq!(parse_url_segment(&first.to_string()), Some(first));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn time_ordered_ids_in_the_same_millisecond() {
+        let millis = 1_654_041_600_123;
+        let mut last = 0;
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..10_000 {
+            last = next_time_ordered(millis, fastrand::u64(..), last);
+            assert!(seen.insert(encode_sorted(last).to_string()));
+        }
+
+        let ids = (0..10_000).map(|_| TinyId::time_ordered().to_string()).collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn ids_from_u64_are_valid() {
+        for _ in 0..10_000 {
+            let value = fastrand::u64(..);
+            let id = id_from_u64(value);
+            assert!(id.is_valid(), "{} -> {}", value, id);
+            assert_eq!(id, id_from_u64(value));
+        }
+
+        for value in [1, 63, 64, u64::MAX] {
+            assert!(id_from_u64(value).is_valid(), "{}", value);
+        }
+        assert_eq!(id_from_u64(0).to_string(), "--------");
+        assert_eq!(id_from_u64(u64::MAX).to_string(), "zzzzzzzz");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn long_ids() {
+        for _ in 0..10_000 {
+            let id = LongId::random();
+            assert!(id.is_valid(), "{}", id);
+            let text = id.to_string();
+            assert_eq!(text.len(), LONG_ID_LENGTH);
+            assert_eq!(text.parse::<LongId>(), Ok(id));
+            assert_eq!(LongId::from_bytes(*id.as_bytes()), Ok(id));
+        }
+
+        assert!(LongId::null().is_null());
+        assert!(!LongId::null().is_valid());
+        assert_eq!(LongId::default(), LongId::null());
+        assert_eq!("short".parse::<LongId>(), Err(LongIdError::InvalidLength(5)));
+        assert_eq!("abcdefgh/jklmnop".parse::<LongId>(), Err(LongIdError::InvalidCharacter('/')));
+        assert_eq!("abcdefghéjklmno".parse::<LongId>(), Err(LongIdError::InvalidCharacter('é')));
+        assert_eq!(LongId::from_bytes([b' '; LONG_ID_LENGTH]), Err(LongIdError::InvalidCharacter(' ')));
+
+        let id = LongId::random();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"{}\"", id));
+        assert_eq!(serde_json::from_str::<LongId>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<LongId>("\"short\"").is_err());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn long_ids_do_not_collide() {
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..200_000 {
+            assert!(seen.insert(LongId::random()));
+        }
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Stored {
+        #[serde(with = "as_string")]
+        id: TinyId,
+    }
+
+    /// How ids were stored before [`as_string`], using the serde support in `tinyid`.
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct OldStored {
+        id: TinyId,
+    }
+
+    #[test]
+    #[no_coverage]
+    fn serde_as_string() {
+        let id = TinyId::random();
+        let stored = Stored { id };
+
+        let json = serde_json::to_string(&stored).unwrap();
+        assert_eq!(json, format!("{{\"id\":\"{}\"}}", id));
+        assert_eq!(serde_json::from_str::<Stored>(&json).unwrap(), stored);
+
+        let msgpack = rmp_serde::to_vec(&stored).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Stored>(&msgpack).unwrap(), stored);
+        assert!(msgpack.len() < rmp_serde::to_vec(&OldStored { id }).unwrap().len());
+
+        let null = Stored { id: TinyId::null() };
+        let json = serde_json::to_string(&null).unwrap();
+        assert_eq!(serde_json::from_str::<Stored>(&json).unwrap(), null);
+
+        assert!(serde_json::from_str::<Stored>(r#"{"id":"short"}"#).is_err());
+        assert!(serde_json::from_str::<Stored>(r#"{"id":"abc/defg"}"#).is_err());
+        assert!(serde_json::from_str::<Stored>(r#"{"id":12}"#).is_err());
+
+        // Notes and their reminders store their ids as text.
+        let note = crate::types::Note::create((
+            "Title",
+            "Content",
+            vec![],
+            vec![crate::types::Reminder::new(&"Soon", time::OffsetDateTime::now_utc())],
+        ));
+        let value = serde_json::to_value(&note).unwrap();
+        assert_eq!(value["id"], note.id().to_string());
+        assert_eq!(value["reminders"][0]["id"], note.reminders()[0].id().to_string());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn serde_reads_old_byte_form() {
+        for _ in 0..100 {
+            let id = TinyId::random();
+            let expected = Stored { id };
+
+            let old_json = serde_json::to_string(&OldStored { id }).unwrap();
+            assert_eq!(serde_json::from_str::<Stored>(&old_json).unwrap(), expected, "{}", old_json);
+
+            let old_msgpack = rmp_serde::to_vec(&OldStored { id }).unwrap();
+            assert_eq!(rmp_serde::from_slice::<Stored>(&old_msgpack).unwrap(), expected);
+            let old_named = rmp_serde::to_vec_named(&OldStored { id }).unwrap();
+            assert_eq!(rmp_serde::from_slice::<Stored>(&old_named).unwrap(), expected);
+
+            // A bare array of the id's bytes.
+            let bytes = serde_json::to_string(&id.to_string().into_bytes()).unwrap();
+            let json = format!("{{\"id\":{}}}", bytes);
+            assert_eq!(serde_json::from_str::<Stored>(&json).unwrap(), expected, "{}", json);
+        }
+
+        assert!(serde_json::from_str::<Stored>(r#"{"id":[1,2,3]}"#).is_err());
+        assert!(serde_json::from_str::<Stored>(r#"{"id":{"other":[1,2,3,4,5,6,7,8]}}"#).is_err());
+    }
+}
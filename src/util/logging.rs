@@ -0,0 +1,92 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Runtime logging setup for the front-ends.
+//!
+//! This is independent of the `trace` feature, which only controls whether the hot-path
+//! records inside the library are compiled in at all. Front-ends call [`init_logging`] once
+//! at startup to decide, at runtime, how much is logged and where it goes.
+
+use std::{
+    fs::OpenOptions,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use tracing::{Level, Subscriber};
+
+/// Where log output is written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LogTarget {
+    /// Write to standard error.
+    Stderr,
+    /// Append to the file at the given path, creating it if it does not exist.
+    File(PathBuf),
+}
+
+impl Default for LogTarget {
+    fn default() -> Self {
+        Self::Stderr
+    }
+}
+
+/// Installs a global `tracing` subscriber that records everything at or above `level` to `target`.
+/// This should only be called once, at startup.
+///
+/// ## Errors
+/// - [`Error::Io`](crate::Error::Io) if `target` is a file that cannot be opened.
+/// - [`Error::Unknown`](crate::Error::Unknown) if a global subscriber has already been set.
+pub fn init_logging(level: Level, target: LogTarget) -> crate::Result {
+    let subscriber = create_subscriber(level, target)?;
+    tracing::subscriber::set_global_default(subscriber).map_err(|err| crate::Error::unknown(err.to_string()))
+}
+
+/// Creates the subscriber used by [`init_logging`] without installing it.
+fn create_subscriber(level: Level, target: LogTarget) -> crate::Result<Box<dyn Subscriber + Send + Sync>> {
+    let builder = tracing_subscriber::fmt().with_max_level(level).with_target(true);
+    Ok(match target {
+        LogTarget::Stderr => Box::new(builder.with_writer(std::io::stderr).finish()),
+        LogTarget::File(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            Box::new(
+                builder
+                    .with_ansi(false)
+                    .with_writer(Mutex::new(file))
+                    .finish(),
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+
+    #[test]
+    #[no_coverage]
+    fn logs_to_file() {
+        let tempfile = std::env::temp_dir().join(format!(
+            "logging-tests-logs_to_file-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        assert!(!tempfile.exists(), "tempfile should not already exist!");
+
+        let subscriber = create_subscriber(Level::INFO, LogTarget::File(tempfile.clone()))
+            .expect("Unable to create subscriber");
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(answer = 42, "captured event");
+            tracing::debug!("filtered event");
+        });
+
+        let contents = std::fs::read_to_string(&tempfile).expect("Unable to read log file");
+        assert!(contents.contains("captured event"));
+        assert!(contents.contains("answer=42"));
+        assert!(!contents.contains("filtered event"));
+
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+    }
+}
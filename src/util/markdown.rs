@@ -0,0 +1,309 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Conversion between [`Note`]s and markdown documents with front-matter, as used by
+//! [`Database::export_markdown`](crate::db::Database::export_markdown) and
+//! [`Database::import_markdown`](crate::db::Database::import_markdown).
+
+use std::str::FromStr;
+
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tinyid::TinyId;
+
+use crate::types::Note;
+
+/// How the metadata of a [`Note`] (id, title, tags, and timestamps) is written at the top of
+/// an exported markdown document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrontMatter {
+    /// A `---` delimited YAML block, as expected by Obsidian and Jekyll.
+    Yaml,
+    /// A `+++` delimited TOML block, as expected by Hugo and Zola.
+    Toml,
+    /// No front-matter, the title is written as a `# ` heading and all other metadata is dropped.
+    None,
+}
+
+impl FrontMatter {
+    /// The line that opens and closes the front-matter block, if there is one.
+    #[must_use]
+    pub fn delimiter(self) -> Option<&'static str> {
+        match self {
+            FrontMatter::Yaml => Some("---"),
+            FrontMatter::Toml => Some("+++"),
+            FrontMatter::None => None,
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            FrontMatter::Toml => " = ",
+            _ => ": ",
+        }
+    }
+}
+
+impl Default for FrontMatter {
+    fn default() -> Self {
+        FrontMatter::Yaml
+    }
+}
+
+/// Renders the given [`Note`] as a markdown document using the given [`FrontMatter`]. Strings and
+/// lists are written in their JSON form, which is valid in both YAML and TOML.
+///
+/// ## Errors
+/// - If one of the note's timestamps cannot be formatted.
+pub fn to_markdown(note: &Note, front_matter: FrontMatter) -> crate::Result<String> {
+    crate::flame_guard!("util", "markdown", "to_markdown");
+    let Some(delimiter) = front_matter.delimiter() else {
+        return Ok(format!("# {}\n\n{}", note.title(), note.content()));
+    };
+
+    let sep = front_matter.separator();
+    let quote = |value: &str| serde_json::to_string(value).expect("strings always serialize");
+    let mut output = String::new();
+    output.push_str(delimiter);
+    output.push('\n');
+    for (key, value) in [
+        ("id", quote(&note.id().to_string())),
+        ("title", quote(note.title())),
+        ("tags", serde_json::to_string(note.tags())?),
+        ("created", note.created().format(&Rfc3339).map_err(time::Error::from)?),
+        ("updated", note.updated().format(&Rfc3339).map_err(time::Error::from)?),
+    ] {
+        output.push_str(key);
+        output.push_str(sep);
+        output.push_str(&value);
+        output.push('\n');
+    }
+    output.push_str(delimiter);
+    output.push_str("\n\n");
+    output.push_str(note.content());
+
+    Ok(output)
+}
+
+/// Parses a markdown document written by [`to_markdown`] (in any [`FrontMatter`] format) back into a [`Note`].
+/// Missing metadata is filled in: a random id, the first `# ` heading (or nothing) as the title, no tags,
+/// and the current time for both timestamps. Unknown front-matter keys are ignored.
+///
+/// ## Errors
+/// - If the front-matter is not closed.
+/// - If the id, tags, or a timestamp are present but cannot be parsed.
+pub fn from_markdown(text: &str) -> crate::Result<Note> {
+    crate::flame_guard!("util", "markdown", "from_markdown");
+    let front_matter = [FrontMatter::Yaml, FrontMatter::Toml]
+        .into_iter()
+        .find(|fm| text.starts_with(&format!("{}\n", fm.delimiter().unwrap_or_default())))
+        .unwrap_or(FrontMatter::None);
+
+    let now = OffsetDateTime::now_utc();
+    let (mut id, mut title, mut tags, mut created, mut updated) = (None, None, Vec::new(), now, now);
+    let body = match front_matter.delimiter() {
+        Some(delimiter) => {
+            let rest = &text[delimiter.len() + 1..];
+            let closing = format!("\n{}\n", delimiter);
+            let (block, body) = if let Some(block) = rest.strip_prefix(&closing[1..]) {
+                ("", block)
+            } else {
+                rest.split_once(&closing)
+                    .ok_or_else(|| crate::Error::serde("markdown front-matter is not closed"))?
+            };
+
+            for line in block.lines() {
+                let Some((key, value)) = line.split_once(front_matter.separator().trim()) else {
+                    continue;
+                };
+                let value = value.trim();
+                let unquoted = || -> crate::Result<String> {
+                    if value.starts_with('"') {
+                        Ok(serde_json::from_str(value)?)
+                    } else {
+                        Ok(value.to_string())
+                    }
+                };
+                match key.trim() {
+                    "id" => id = Some(TinyId::from_str(&unquoted()?)?),
+                    "title" => title = Some(unquoted()?),
+                    "tags" => tags = serde_json::from_str(value)?,
+                    "created" => created = OffsetDateTime::parse(&unquoted()?, &Rfc3339).map_err(time::Error::from)?,
+                    "updated" => updated = OffsetDateTime::parse(&unquoted()?, &Rfc3339).map_err(time::Error::from)?,
+                    _ => {}
+                }
+            }
+
+            body.strip_prefix('\n').unwrap_or(body)
+        }
+        None => text,
+    };
+
+    let content = match title {
+        Some(_) => body.to_string(),
+        None => match body.strip_prefix("# ") {
+            Some(heading) => {
+                let (heading, rest) = heading.split_once('\n').unwrap_or((heading, ""));
+                title = Some(heading.trim().to_string());
+                rest.strip_prefix('\n').unwrap_or(rest).to_string()
+            }
+            None => body.to_string(),
+        },
+    };
+
+    Ok(Note::existing(
+        id.unwrap_or_else(TinyId::random),
+        title.unwrap_or_default(),
+        content,
+        tags,
+        Vec::new(),
+        created,
+        updated,
+    ))
+}
+
+/// Iterates over the lines of `text` that are not part of a fenced (`` ``` `` or `~~~`) code block.
+/// The fence lines themselves are skipped, and an unclosed fence runs to the end of `text`.
+pub(crate) fn prose_lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut fence: Option<(char, usize)> = None;
+    text.lines().filter(move |line| {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let run = marker.map_or(0, |c| trimmed.chars().take_while(|ch| *ch == c).count());
+        let is_fence = line.len() - trimmed.len() <= 3 && run >= 3;
+        match (fence, marker) {
+            (None, Some(c)) if is_fence => {
+                fence = Some((c, run));
+                false
+            }
+            (Some((open, len)), Some(c)) if is_fence && c == open && run >= len => {
+                fence = None;
+                false
+            }
+            (Some(_), _) => false,
+            (None, _) => true,
+        }
+    })
+}
+
+/// Finds the end of a link target starting at the beginning of `text`, stopping at whitespace, at any of
+/// `stops`, or at a `)` that does not close a `(` within the target.
+fn link_target_len(text: &str, stops: &[char]) -> usize {
+    let mut depth = 0_usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return i,
+            ')' => depth -= 1,
+            c if c.is_whitespace() || stops.contains(&c) => return i,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Extracts the targets of all inline `[text](url)` links and all bare `http://` or `https://` URLs from `text`,
+/// in the order they appear. Fenced code blocks are ignored.
+pub(crate) fn links(text: &str) -> Vec<String> {
+    const SCHEMES: [&str; 2] = ["http://", "https://"];
+    let mut links = Vec::new();
+
+    for line in prose_lines(text) {
+        let mut i = 0;
+        while i < line.len() {
+            let rest = &line[i..];
+            if rest.starts_with("](") && line[..i].contains('[') {
+                let target = &rest[2..];
+                let len = link_target_len(target, &[]);
+                if len > 0 {
+                    links.push(target[..len].trim_start_matches('<').trim_end_matches('>').to_string());
+                }
+                i += 2 + len;
+            } else if SCHEMES.iter().any(|scheme| rest.starts_with(scheme)) {
+                let len = link_target_len(rest, &['<', '>', '"', '\'', '`', ']']);
+                let url = rest[..len].trim_end_matches(|c: char| ".,;:!?".contains(c));
+                links.push(url.to_string());
+                i += len;
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+
+    links
+}
+
+/// Extracts all ATX (`#` prefixed) headings from `text` as `(level, text)`, in the order they appear. Any closing
+/// sequence of `#`s is removed from the text. Fenced code blocks are ignored.
+pub(crate) fn headings(text: &str) -> Vec<(u8, String)> {
+    prose_lines(text)
+        .filter_map(|line| {
+            let trimmed = line.trim_start();
+            if line.len() - trimmed.len() > 3 {
+                return None;
+            }
+            let level = trimmed.chars().take_while(|c| *c == '#').count();
+            let rest = &trimmed[level..];
+            if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+                return None;
+            }
+
+            let rest = rest.trim();
+            let without_closing = rest.trim_end_matches('#');
+            let text = if without_closing.is_empty() || without_closing.ends_with(char::is_whitespace) {
+                without_closing.trim_end()
+            } else {
+                rest
+            };
+            u8::try_from(level).ok().map(|level| (level, text.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+
+    #[test]
+    #[no_coverage]
+    fn round_trip() {
+        let note = Note::create((
+            "A \"quoted\" title: with colons",
+            "Some content.\n\n## With a heading\n\n---\n\nAnd a rule.",
+            vec!["tag one", "tag,two"],
+        ));
+
+        for front_matter in [FrontMatter::Yaml, FrontMatter::Toml] {
+            let markdown = to_markdown(&note, front_matter).unwrap();
+            assert!(markdown.starts_with(front_matter.delimiter().unwrap()));
+            let parsed = from_markdown(&markdown).unwrap();
+            assert_eq!(parsed.id(), note.id());
+            assert_eq!(parsed.title(), note.title());
+            assert_eq!(parsed.content(), note.content());
+            assert_eq!(parsed.tags(), note.tags());
+            assert_eq!(parsed.created(), note.created());
+            assert_eq!(parsed.updated(), note.updated());
+        }
+
+        let markdown = to_markdown(&note, FrontMatter::None).unwrap();
+        assert!(markdown.starts_with("# A \"quoted\""));
+        let parsed = from_markdown(&markdown).unwrap();
+        assert_ne!(parsed.id(), note.id());
+        assert_eq!(parsed.title(), note.title());
+        assert_eq!(parsed.content(), note.content());
+        assert!(parsed.tags().is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn malformed() {
+        assert!(from_markdown("---\nid: \"AAAAAAAA\"\n\nno closing delimiter").is_err());
+        assert!(from_markdown("+++\ntags = not a list\n+++\n\nbody").is_err());
+        let plain = from_markdown("just some text").unwrap();
+        assert_eq!(plain.title(), "");
+        assert_eq!(plain.content(), "just some text");
+    }
+}
@@ -8,9 +8,13 @@
 ///
 /// It stands for "Date Time Formatting" you perv, get your mind out of the gutter.
 pub mod dtf;
+pub mod id;
+pub mod logging;
+pub mod markdown;
 pub mod persist;
 pub mod profiling;
 pub mod scoped;
+pub mod text;
 pub mod validation;
 pub mod variadic;
 pub mod wrapping;
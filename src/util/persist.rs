@@ -281,6 +281,49 @@ impl Persistence {
         }
     }
 
+    /// Serializes the given data and saves it to the file at `path` atomically: the data is first written
+    /// (and synced) to a sibling temp file (`<path>.tmp`), which is then renamed over `path`. Since a rename
+    /// on the same filesystem is atomic, the original file is either fully replaced or left intact, even if
+    /// the process is killed mid-write. On failure the temp file is removed.
+    ///
+    /// ## Errors
+    /// - `Error::Io` - If any i/o errors occur
+    /// - `Error::Json` or `Error::SerDe` - If the (de)serialization process fails
+    /// - `Error::NotImplemented` - If the requested method is not (yet) implemented
+    #[tracing::instrument(skip(data), fields(path = path.as_ref().display().to_string().as_str()))]
+    pub fn save_to_file_atomic<T>(data: &T, path: impl AsRef<Path>, method: Method) -> crate::Result
+    where
+        T: serde::Serialize,
+    {
+        use std::io::Write;
+        // crate::profile_guard!("save_to_file_atomic", "util::Persistence");
+
+        let path = path.as_ref();
+        let temp = Self::temp_path_for(path);
+        let result = Self::save_to_bytes(data, method).and_then(|bytes| {
+            let mut file = std::fs::File::create(&temp)?;
+            file.write_all(&bytes)?;
+            file.sync_all()?;
+            std::fs::rename(&temp, path)?;
+            Ok(())
+        });
+
+        if result.is_err() && temp.is_file() {
+            let _ = std::fs::remove_file(&temp);
+        }
+
+        result
+    }
+
+    /// The sibling temp file used by [`Persistence::save_to_file_atomic`] for the given path.
+    #[must_use]
+    pub fn temp_path_for(path: impl AsRef<Path>) -> std::path::PathBuf {
+        let path = path.as_ref();
+        let mut name = path.file_name().map(std::ffi::OsStr::to_os_string).unwrap_or_default();
+        name.push(".tmp");
+        path.with_file_name(name)
+    }
+
     /// Saves the given data to a file **only if it does not already exist**. Otherwise it
     /// functions identically to [`Persistence::save_to_file`].
     ///
@@ -319,6 +362,50 @@ impl Persistence {
         Self::save_to_file(data, path, Self::DEFAULT_METHOD)
     }
 
+    /// Attempts to determine which [`Method`] was used to serialize the given bytes. Methods
+    /// are checked from most to least restrictive, so `Json` is tried before `MsgPack`. Returns
+    /// `None` if no working method is able to parse the data.
+    #[tracing::instrument(skip(bytes))]
+    #[must_use]
+    pub fn detect_method(bytes: &[u8]) -> Option<Method> {
+        // crate::profile_guard!("detect_method", "util::Persistence");
+        if bytes.is_empty() {
+            return None;
+        }
+
+        if serde_json::from_slice::<serde::de::IgnoredAny>(bytes).is_ok() {
+            return Some(Method::Json);
+        }
+
+        if rmp_serde::from_slice::<serde::de::IgnoredAny>(bytes).is_ok() {
+            return Some(Method::MsgPack);
+        }
+
+        None
+    }
+
+    /// Reads the file at the given path and attempts to determine which [`Method`] was used
+    /// to serialize it. See [`Persistence::detect_method`].
+    ///
+    /// ## Errors
+    /// - `Error::Io` - If any i/o errors occur
+    /// - `Error::Database` - If the file does not exist
+    #[tracing::instrument(fields(path = path.as_ref().display().to_string().as_str()))]
+    pub fn detect_method_for_file(path: impl AsRef<Path>) -> crate::Result<Option<Method>> {
+        // crate::profile_guard!("detect_method_for_file", "util::Persistence");
+        let path = path.as_ref();
+
+        if !path.exists() {
+            return crate::Error::Database(crate::DatabaseError::DataFileNotFound(
+                path.to_path_buf(),
+            ))
+            .into();
+        }
+
+        let bytes = std::fs::read(path)?;
+        Ok(Self::detect_method(&bytes))
+    }
+
     /// TODO: Checkout [this serde docs page](https://serde.rs/transcode.html) to simplify this.
     ///
     /// Converts a file from one serialization format to another. Unfortunately there is
@@ -569,6 +656,37 @@ mod tests {
         assert_eq!(cereal, data);
     }
 
+    #[test]
+    #[no_coverage]
+    fn detect_method() {
+        let data = TestStruct {
+            length: 10,
+            flag: true,
+            decimal: 1.0,
+            number: -1,
+            text: "hello".to_string(),
+        };
+
+        for method in Method::working_methods() {
+            let bytes = Persistence::save_to_bytes(&data, method).unwrap();
+            assert_eq!(Persistence::detect_method(&bytes), Some(method));
+        }
+
+        assert_eq!(Persistence::detect_method(&[]), None);
+
+        let tempfile = std::env::temp_dir().join(format!(
+            "persist-tests-detect_method-{:010}.tmp",
+            fastrand::u32(..)
+        ));
+        assert!(Persistence::detect_method_for_file(&tempfile).is_err());
+        Persistence::save_to_file(&data, &tempfile, Method::Json).unwrap();
+        assert_eq!(
+            Persistence::detect_method_for_file(&tempfile).unwrap(),
+            Some(Method::Json)
+        );
+        std::fs::remove_file(tempfile).expect("Unable to delete tempfile");
+    }
+
     #[test]
     #[no_coverage]
     fn method() {
@@ -585,6 +703,7 @@ mod tests {
         save_and_load_file,
         convert_bytes,
         convert_file,
-        save_and_load_file_default
+        save_and_load_file_default,
+        detect_method
     );
 }
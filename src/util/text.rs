@@ -0,0 +1,151 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Helpers for rendering text at a fixed width, i.e. in the TUI or in plain text exports.
+
+use std::borrow::Cow;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Hard-wraps `text` so that no line is wider than `width` columns, as measured by [`unicode_width`].
+///
+/// Lines are broken at whitespace, and runs of whitespace between words are collapsed to a single space.
+/// Existing line breaks are kept, so blank lines (i.e. between paragraphs) stay blank. A word wider than
+/// `width` is broken between characters, never inside one, and a single character wider than `width`
+/// gets a line to itself. A `width` of zero disables wrapping and returns the lines of `text` as-is.
+#[must_use]
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    crate::flame_guard!("util", "text", "wrap");
+    if width == 0 {
+        return text.lines().map(ToString::to_string).collect();
+    }
+
+    let mut lines = Vec::new();
+    for source in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in source.split_whitespace() {
+            let word_width = word.width();
+            if line_width > 0 && line_width + 1 + word_width <= width {
+                line.push(' ');
+                line.push_str(word);
+                line_width += 1 + word_width;
+                continue;
+            }
+
+            if line_width > 0 {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            if word_width <= width {
+                line.push_str(word);
+                line_width = word_width;
+                continue;
+            }
+
+            // The word does not fit on a line of its own, so it is split between characters.
+            for c in word.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if line_width > 0 && line_width + char_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += char_width;
+            }
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Shortens `text` to at most `width` columns, as measured by [`unicode_width`], ending it with an ellipsis
+/// if anything had to be cut. Text that already fits is returned as-is.
+#[must_use]
+pub fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+    crate::flame_guard!("util", "text", "truncate");
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+
+    // One column is kept free for the ellipsis.
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if truncated_width + char_width >= width {
+            break;
+        }
+        truncated.push(c);
+        truncated_width += char_width;
+    }
+    if width > 0 {
+        truncated.push('…');
+    }
+    Cow::Owned(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const PARAGRAPH: &str = "The quick brown fox jumps over the lazy dog, then takes a well deserved nap under the old oak tree.";
+
+    #[test]
+    #[no_coverage]
+    fn wrap_paragraph() {
+        let lines = wrap(PARAGRAPH, 20);
+        assert_eq!(
+            lines,
+            vec![
+                "The quick brown fox",
+                "jumps over the lazy",
+                "dog, then takes a",
+                "well deserved nap",
+                "under the old oak",
+                "tree.",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.width() <= 20));
+        assert_eq!(lines.join(" "), PARAGRAPH, "no words should be split or lost");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn wrap_keeps_characters_whole() {
+        // Each of these is two columns wide, so only two fit in a line of five.
+        let lines = wrap("日本語のテキスト", 5);
+        assert_eq!(lines, vec!["日本", "語の", "テキ", "スト"]);
+        assert!(lines.iter().all(|line| line.width() <= 5));
+        assert_eq!(lines.concat(), "日本語のテキスト");
+
+        assert_eq!(wrap("abcdefgh ij", 3), vec!["abc", "def", "gh", "ij"]);
+        assert_eq!(wrap("日本", 1), vec!["日", "本"], "wide characters get a line of their own");
+        assert_eq!(wrap("naïve café", 5), vec!["naïve", "café"]);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn wrap_keeps_line_breaks() {
+        assert_eq!(wrap("one two\n\nthree   four", 7), vec!["one two", "", "three", "four"]);
+        assert_eq!(wrap("one two\nthree", 0), vec!["one two", "three"]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    #[no_coverage]
+    fn truncate_with_ellipsis() {
+        assert!(matches!(truncate("fits", 4), Cow::Borrowed("fits")));
+        assert_eq!(truncate("too long", 4), "too…");
+        assert_eq!(truncate("日本語", 4), "日…");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate("abc", 1), "…");
+        assert_eq!(truncate("abc", 0), "");
+        assert!(truncate("The quick brown fox", 10).width() <= 10);
+    }
+}
@@ -6,6 +6,10 @@
 
 use crate::util::variadic::ZeroOrMore;
 
+mod note;
+
+pub use note::{NoteValidationError, NoteValidator, MAX_TITLE_LENGTH};
+
 pub trait Validator {
     type Input;
     type Err: std::error::Error;
@@ -17,9 +21,47 @@ pub trait Validator {
     fn validate(&self, input: &Self::Input) -> Result<(), Self::Err>;
 }
 
-type ValidatorFunc<T, E> = Box<dyn Fn(&T) -> Result<(), E>>;
+/// A single requirement checked by a [`GenericValidator`].
+pub type ValidatorFunc<T, E> = Box<dyn Fn(&T) -> Result<(), E>>;
+
+/// The errors produced by the built-in [`GenericValidator`] combinators. Validators with their own error
+/// type can use the combinators by implementing `From<RuleError>` for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleError {
+    /// The input is empty or only whitespace.
+    Empty,
+    /// The input has `len` characters, fewer than the required `min`.
+    TooShort { len: usize, min: usize },
+    /// The input has `len` characters, more than the allowed `max`.
+    TooLong { len: usize, max: usize },
+    /// The input did not satisfy a [`GenericValidator::matches`] predicate.
+    NoMatch,
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "value is empty"),
+            Self::TooShort { len, min } => {
+                write!(f, "value is too short ({} characters, minimum is {})", len, min)
+            }
+            Self::TooLong { len, max } => {
+                write!(f, "value is too long ({} characters, maximum is {})", len, max)
+            }
+            Self::NoMatch => write!(f, "value does not match the required pattern"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
 
-struct GenericValidator<TInput, TErr>
+/// A [`Validator`] built from a list of requirements, which are checked in the order they were added.
+///
+/// ```ignore
+/// let title = GenericValidator::<String, RuleError>::new().non_empty().max_len(120);
+/// assert_eq!(title.validate(&String::new()), Err(RuleError::Empty));
+/// ```
+pub struct GenericValidator<TInput, TErr>
 where
     TErr: std::error::Error,
 {
@@ -27,6 +69,7 @@ where
 }
 
 impl<T, E: std::error::Error> GenericValidator<T, E> {
+    #[must_use]
     pub fn new() -> Self {
         Self { reqs: Vec::new() }
     }
@@ -37,14 +80,17 @@ impl<T, E: std::error::Error> GenericValidator<T, E> {
         }
     }
 
+    #[must_use]
     pub fn len(&self) -> usize {
         self.reqs.len()
     }
 
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.reqs.is_empty()
     }
 
+    #[must_use]
     pub fn has_reqs(&self) -> bool {
         !self.is_empty()
     }
@@ -53,21 +99,167 @@ impl<T, E: std::error::Error> GenericValidator<T, E> {
         self.reqs.push(req);
     }
 
+    /// Builder-style version of [`GenericValidator::add_req`].
+    #[must_use]
+    pub fn with_req(mut self, req: ValidatorFunc<T, E>) -> Self {
+        self.add_req(req);
+        self
+    }
+
     pub fn clear_reqs(&mut self) {
         self.reqs.clear();
     }
 
+    /// Checks every requirement in order, stopping at the first one that fails.
+    ///
+    /// ## Errors
+    /// - The error of the first failing requirement.
     pub fn validate(&self, input: &T) -> Result<(), E> {
         for req in &self.reqs {
             req(input)?;
         }
         Ok(())
     }
+
+    /// Checks every requirement, even after one fails, which is useful for reporting every problem with a
+    /// form at once. Use [`GenericValidator::validate`] to stop at the first failure instead.
+    ///
+    /// ## Errors
+    /// - The errors of every failing requirement, in the order the requirements were added.
+    pub fn validate_all(&self, input: &T) -> Result<(), Vec<E>> {
+        let errors = self
+            .reqs
+            .iter()
+            .filter_map(|req| req(input).err())
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Combinators
+impl<T, E: std::error::Error + From<RuleError>> GenericValidator<T, E> {
+    /// Requires `pred` to return `true`, failing with [`RuleError::NoMatch`] otherwise.
+    #[must_use]
+    pub fn matches(self, pred: impl Fn(&T) -> bool + 'static) -> Self {
+        self.with_req(box move |input: &T| {
+            if pred(input) {
+                Ok(())
+            } else {
+                Err(RuleError::NoMatch.into())
+            }
+        })
+    }
+}
+
+/// Text Combinators, lengths are counted in characters.
+impl<T: AsRef<str>, E: std::error::Error + From<RuleError>> GenericValidator<T, E> {
+    /// Requires the input to contain something other than whitespace, failing with [`RuleError::Empty`].
+    #[must_use]
+    pub fn non_empty(self) -> Self {
+        self.with_req(box |input: &T| {
+            if input.as_ref().trim().is_empty() {
+                Err(RuleError::Empty.into())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Requires the input to have at least `min` characters, failing with [`RuleError::TooShort`].
+    #[must_use]
+    pub fn min_len(self, min: usize) -> Self {
+        self.with_req(box move |input: &T| {
+            let len = input.as_ref().chars().count();
+            if len < min {
+                Err(RuleError::TooShort { len, min }.into())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Requires the input to have at most `max` characters, failing with [`RuleError::TooLong`].
+    #[must_use]
+    pub fn max_len(self, max: usize) -> Self {
+        self.with_req(box move |input: &T| {
+            let len = input.as_ref().chars().count();
+            if len > max {
+                Err(RuleError::TooLong { len, max }.into())
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+impl<T, E: std::error::Error> Default for GenericValidator<T, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E: std::error::Error> Validator for GenericValidator<T, E> {
+    type Input = T;
+    type Err = E;
+
+    fn validate(&self, input: &Self::Input) -> Result<(), Self::Err> {
+        GenericValidator::validate(self, input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn title_chain() {
+        fn check<V: Validator<Input = String, Err = RuleError>>(validator: &V, title: &str) -> Result<(), RuleError> {
+            validator.validate(&title.to_string())
+        }
+
+        let title = GenericValidator::<String, RuleError>::new().non_empty().max_len(120);
+        assert_eq!(title.len(), 2);
+        assert_eq!(check(&title, "A fine title"), Ok(()));
+        assert_eq!(check(&title, ""), Err(RuleError::Empty));
+        assert_eq!(check(&title, "   "), Err(RuleError::Empty));
+        assert_eq!(check(&title, &"x".repeat(121)), Err(RuleError::TooLong { len: 121, max: 120 }));
+        assert_eq!(check(&title, &"é".repeat(120)), Ok(()));
+
+        // The first failing rule wins, even when later rules also fail.
+        let strict = GenericValidator::<String, RuleError>::new()
+            .min_len(3)
+            .matches(|s: &String| s.starts_with('#'))
+            .non_empty();
+        assert_eq!(check(&strict, ""), Err(RuleError::TooShort { len: 0, min: 3 }));
+        assert_eq!(check(&strict, "title"), Err(RuleError::NoMatch));
+        assert_eq!(check(&strict, "#tag"), Ok(()));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn validate_all_collects_every_error() {
+        let validator = GenericValidator::<String, RuleError>::new()
+            .max_len(4)
+            .non_empty()
+            .matches(|s: &String| s.chars().all(char::is_alphanumeric));
+
+        assert_eq!(
+            validator.validate_all(&"far too long!".to_string()),
+            Err(vec![RuleError::TooLong { len: 13, max: 4 }, RuleError::NoMatch])
+        );
+        assert_eq!(
+            validator.validate(&"far too long!".to_string()),
+            Err(RuleError::TooLong { len: 13, max: 4 })
+        );
+        assert_eq!(validator.validate_all(&"abc".to_string()), Ok(()));
+        assert_eq!(GenericValidator::<String, RuleError>::new().validate_all(&String::new()), Ok(()));
+    }
 
     #[test]
     #[ignore]
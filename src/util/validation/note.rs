@@ -0,0 +1,238 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::collections::HashSet;
+
+use crate::types::{CreateNote, NoteDto, UpdateNote};
+
+use super::Validator;
+
+/// The maximum number of characters allowed in a note title by the default [`NoteValidator`].
+pub const MAX_TITLE_LENGTH: usize = 120;
+
+/// The first invariant broken by a note, as reported by [`NoteValidator`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NoteValidationError {
+    /// The title is missing, empty, or only whitespace.
+    EmptyTitle,
+    /// The title has `len` characters, more than the allowed `max`.
+    TitleTooLong { len: usize, max: usize },
+    /// A tag is empty.
+    BlankTag,
+    /// The given tag appears more than once.
+    DuplicateTag(String),
+    /// The given tag contains whitespace.
+    TagContainsWhitespace(String),
+}
+
+impl std::fmt::Display for NoteValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyTitle => write!(f, "note title is empty"),
+            Self::TitleTooLong { len, max } => {
+                write!(f, "note title is too long ({} characters, maximum is {})", len, max)
+            }
+            Self::BlankTag => write!(f, "a tag is empty"),
+            Self::DuplicateTag(tag) => write!(f, "tag '{}' appears more than once", tag),
+            Self::TagContainsWhitespace(tag) => write!(f, "tag '{}' contains whitespace", tag),
+        }
+    }
+}
+
+impl std::error::Error for NoteValidationError {}
+
+/// Checks the invariants of a [`CreateNote`] or [`UpdateNote`] before it is applied: the title must not be
+/// empty and must be at most [`NoteValidator::max_title_len`] characters, and tags must be unique, non-empty,
+/// and must not contain whitespace. Fields an [`UpdateNote`] does not change are not checked.
+///
+/// As a [`Validator`] the input is a [`NoteDto`], delete and flag dtos are always valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoteValidator {
+    max_title_len: usize,
+}
+
+impl Default for NoteValidator {
+    fn default() -> Self {
+        Self::new(MAX_TITLE_LENGTH)
+    }
+}
+
+impl NoteValidator {
+    #[must_use]
+    pub fn new(max_title_len: usize) -> Self {
+        Self { max_title_len }
+    }
+
+    #[must_use]
+    pub fn max_title_len(&self) -> usize {
+        self.max_title_len
+    }
+
+    pub fn set_max_title_len(&mut self, max_title_len: usize) {
+        self.max_title_len = max_title_len;
+    }
+
+    /// Validates a [`CreateNote`], which must have a title.
+    ///
+    /// ## Errors
+    /// - The first [`NoteValidationError`] found, checking the title before the tags.
+    pub fn validate_create(&self, dto: &CreateNote) -> Result<(), NoteValidationError> {
+        self.check_title(dto.title().unwrap_or_default())?;
+        Self::check_tags(dto.tags())
+    }
+
+    /// Validates the fields an [`UpdateNote`] changes.
+    ///
+    /// ## Errors
+    /// - The first [`NoteValidationError`] found, checking the title before the tags.
+    pub fn validate_update(&self, dto: &UpdateNote) -> Result<(), NoteValidationError> {
+        if let Some(title) = dto.title() {
+            self.check_title(title)?;
+        }
+        dto.tags().map_or(Ok(()), Self::check_tags)
+    }
+
+    fn check_title(&self, title: &str) -> Result<(), NoteValidationError> {
+        if title.trim().is_empty() {
+            return Err(NoteValidationError::EmptyTitle);
+        }
+        let len = title.chars().count();
+        if len > self.max_title_len {
+            return Err(NoteValidationError::TitleTooLong {
+                len,
+                max: self.max_title_len,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_tags(tags: &[String]) -> Result<(), NoteValidationError> {
+        let mut seen = HashSet::with_capacity(tags.len());
+        for tag in tags {
+            if tag.is_empty() {
+                return Err(NoteValidationError::BlankTag);
+            }
+            if tag.chars().any(char::is_whitespace) {
+                return Err(NoteValidationError::TagContainsWhitespace(tag.clone()));
+            }
+            if !seen.insert(tag.as_str()) {
+                return Err(NoteValidationError::DuplicateTag(tag.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Validator for NoteValidator {
+    type Input = NoteDto;
+    type Err = NoteValidationError;
+
+    fn validate(&self, input: &Self::Input) -> Result<(), Self::Err> {
+        match input {
+            NoteDto::Create(dto) => self.validate_create(dto),
+            NoteDto::Update(dto) => self.validate_update(dto),
+            NoteDto::Delete(_) | NoteDto::Flag(_) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tinyid::TinyId;
+
+    fn create(title: &str, tags: Vec<&str>) -> CreateNote {
+        (title, "content", tags).into()
+    }
+
+    #[test]
+    #[no_coverage]
+    fn valid_notes() {
+        let validator = NoteValidator::default();
+        assert_eq!(validator.validate_create(&create("Title", vec!["a", "b"])), Ok(()));
+        assert_eq!(validator.validate(&NoteDto::Create(create("Title", vec![]))), Ok(()));
+        assert_eq!(validator.validate_update(&UpdateNote::empty(TinyId::random())), Ok(()));
+    }
+
+    #[test]
+    #[no_coverage]
+    fn empty_title() {
+        let validator = NoteValidator::default();
+        assert_eq!(validator.validate_create(&create("", vec![])), Err(NoteValidationError::EmptyTitle));
+        assert_eq!(validator.validate_create(&create(" \t", vec![])), Err(NoteValidationError::EmptyTitle));
+        assert_eq!(
+            validator.validate_create(&CreateNote::empty()),
+            Err(NoteValidationError::EmptyTitle)
+        );
+        let update = UpdateNote::empty(TinyId::random()).with_title(Some(String::new()));
+        assert_eq!(
+            validator.validate(&NoteDto::Update(update)),
+            Err(NoteValidationError::EmptyTitle)
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn title_too_long() {
+        let validator = NoteValidator::new(5);
+        assert_eq!(validator.validate_create(&create("short", vec![])), Ok(()));
+        assert_eq!(
+            validator.validate_create(&create("longer", vec![])),
+            Err(NoteValidationError::TitleTooLong { len: 6, max: 5 })
+        );
+        assert_eq!(
+            NoteValidator::default().validate_create(&create(&"x".repeat(MAX_TITLE_LENGTH + 1), vec![])),
+            Err(NoteValidationError::TitleTooLong {
+                len: MAX_TITLE_LENGTH + 1,
+                max: MAX_TITLE_LENGTH
+            })
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn duplicate_tags() {
+        let validator = NoteValidator::default();
+        assert_eq!(
+            validator.validate_create(&create("Title", vec!["a", "b", "a"])),
+            Err(NoteValidationError::DuplicateTag(String::from("a")))
+        );
+        let update = UpdateNote::empty(TinyId::random())
+            .with_tags(Some(vec![String::from("x"), String::from("x")]));
+        assert_eq!(
+            validator.validate_update(&update),
+            Err(NoteValidationError::DuplicateTag(String::from("x")))
+        );
+    }
+
+    #[test]
+    #[no_coverage]
+    fn blank_tags() {
+        let validator = NoteValidator::default();
+        assert_eq!(
+            validator.validate_create(&create("Title", vec!["a", ""])),
+            Err(NoteValidationError::BlankTag)
+        );
+        let update = UpdateNote::empty(TinyId::random()).with_tags(Some(vec![String::new()]));
+        assert_eq!(validator.validate(&NoteDto::Update(update)), Err(NoteValidationError::BlankTag));
+        assert_eq!(NoteValidationError::BlankTag.to_string(), "a tag is empty");
+    }
+
+    #[test]
+    #[no_coverage]
+    fn tag_whitespace() {
+        let validator = NoteValidator::default();
+        assert_eq!(
+            validator.validate_create(&create("Title", vec!["to do"])),
+            Err(NoteValidationError::TagContainsWhitespace(String::from("to do")))
+        );
+        assert_eq!(
+            validator.validate_create(&create("Title", vec!["ok", "tab\there"])),
+            Err(NoteValidationError::TagContainsWhitespace(String::from("tab\there")))
+        );
+    }
+}
@@ -4,6 +4,257 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+/// Defines a signed wrapping number `$name` over `$base`, which serializes as the plain inner `$base` (named
+/// by `$serde`, since serde wants a string literal). `$range_size` is the expression for `RANGE_SIZE`, which
+/// is the only thing that differs between the base types. The paths are relative to the module the type is
+/// defined in, i.e. a direct child of this one.
+macro_rules! wrapped_signed {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident($base:ident, $serde:tt);
+        RANGE_SIZE = $range_size:expr;
+    ) => {
+        $(#[$meta])*
+        #[derive(
+            Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+        )]
+        #[serde(from = $serde, into = $serde)]
+        pub struct $name<const MIN: $base = { $base::MIN }, const MAX: $base = { $base::MAX }>($base);
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> $name<MINIMUM, MAXIMUM> {
+            pub const MAX: $base = MAXIMUM;
+            pub const MIN: $base = MINIMUM;
+            pub const RANGE_SIZE: usize = $range_size;
+
+            #[must_use]
+            pub const fn new(value: $base) -> Self {
+                Self::from_wide(value as i128)
+            }
+
+            #[must_use]
+            pub const fn create<const N: isize>() -> Self {
+                Self::from_any_signed(N)
+            }
+
+            #[must_use]
+            pub const fn from_any_signed(value: impl Into<isize>) -> Self {
+                let value = value.into();
+                Self::from_wide(value as i128)
+            }
+
+            #[must_use]
+            pub const fn value(self) -> $base {
+                self.0
+            }
+
+            /// Adds `rhs`, stopping at [`Self::MAX`] (or [`Self::MIN`] for a negative `rhs`) instead of wrapping
+            /// around like `+` does.
+            #[must_use]
+            pub const fn saturating_add(self, rhs: $base) -> Self {
+                Self::clamp_wide(self.0 as i128 + rhs as i128)
+            }
+
+            /// Subtracts `rhs`, stopping at [`Self::MIN`] (or [`Self::MAX`] for a negative `rhs`) instead of
+            /// wrapping around like `-` does.
+            #[must_use]
+            pub const fn saturating_sub(self, rhs: $base) -> Self {
+                Self::clamp_wide(self.0 as i128 - rhs as i128)
+            }
+
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+            )]
+            const fn clamp_wide(value: i128) -> Self {
+                if value < MINIMUM as i128 {
+                    Self(MINIMUM)
+                } else if value > MAXIMUM as i128 {
+                    Self(MAXIMUM)
+                } else {
+                    Self(value as $base)
+                }
+            }
+
+            /// Wraps `value` into the range, doing the math in `i128` so that the full range of the base type
+            /// (and the result of any operation between two values of it) can never overflow.
+            #[allow(
+                clippy::cast_possible_truncation,
+                reason = "wrap_wide returns a value within MINIMUM..=MAXIMUM"
+            )]
+            const fn from_wide(value: i128) -> Self {
+                Self(super::wrap_wide(value, MINIMUM as i128, MAXIMUM as i128) as $base)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> super::super::WrappedNumber for $name<MINIMUM, MAXIMUM> {
+            type BaseType = $base;
+
+            const RANGE_SIZE: usize = $name::<MINIMUM, MAXIMUM>::RANGE_SIZE;
+
+            const MIN: Self::BaseType = $name::<MINIMUM, MAXIMUM>::MIN;
+
+            const MAX: Self::BaseType = $name::<MINIMUM, MAXIMUM>::MAX;
+
+            fn value(self) -> Self::BaseType {
+                $name::value(self)
+            }
+
+            fn create(n: impl Into<Self::BaseType>) -> Self {
+                $name::<MINIMUM, MAXIMUM>::new(n.into())
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Add<Self> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self::from_wide(self.value() as i128 + rhs.value() as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Add<$base> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn add(self, rhs: $base) -> Self::Output {
+                self + Self::new(rhs)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Sub<Self> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self::from_wide(self.value() as i128 - rhs.value() as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Sub<$base> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn sub(self, rhs: $base) -> Self::Output {
+                self - Self::new(rhs)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Mul<Self> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::from_wide(self.value() as i128 * rhs.value() as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Mul<$base> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn mul(self, rhs: $base) -> Self::Output {
+                self * Self::new(rhs)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Div<Self> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                Self::from_wide(self.value() as i128 / rhs.value() as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Div<$base> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn div(self, rhs: $base) -> Self::Output {
+                self / Self::new(rhs)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Rem<Self> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self::Output {
+                Self::from_wide(self.value() as i128 % rhs.value() as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Rem<$base> for $name<MINIMUM, MAXIMUM> {
+            type Output = Self;
+
+            fn rem(self, rhs: $base) -> Self::Output {
+                self % Self::new(rhs)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::Deref for $name<MINIMUM, MAXIMUM> {
+            type Target = $base;
+
+            #[must_use]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> std::ops::DerefMut for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base>
+            super::super::StandardOps<$name<MINIMUM, MAXIMUM>, $name<MINIMUM, MAXIMUM>>
+            for $name<MINIMUM, MAXIMUM>
+        {
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> super::super::StandardOps<$name<MINIMUM, MAXIMUM>, $base>
+            for $name<MINIMUM, MAXIMUM>
+        {
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<i8> for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn from(n: i8) -> Self {
+                Self::from_wide(n as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<i16> for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn from(n: i16) -> Self {
+                Self::from_wide(n as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<i32> for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn from(n: i32) -> Self {
+                Self::from_wide(n as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<i64> for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn from(n: i64) -> Self {
+                Self::from_wide(n as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<isize> for $name<MINIMUM, MAXIMUM> {
+            #[must_use]
+            fn from(n: isize) -> Self {
+                Self::from_wide(n as i128)
+            }
+        }
+
+        impl<const MINIMUM: $base, const MAXIMUM: $base> From<$name<MINIMUM, MAXIMUM>> for $base {
+            #[must_use]
+            fn from(n: $name<MINIMUM, MAXIMUM>) -> Self {
+                n.value()
+            }
+        }
+    };
+}
+
 mod n16;
 mod n32;
 mod n64;
@@ -14,24 +265,30 @@ fn abs_diff(a: isize, b: isize) -> usize {
     a.abs_diff(b)
 }
 
-const fn wrap_within(mut value: i64, mut min: i64, mut max: i64) -> i64 {
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "wrap_wide returns a value within min..=max"
+)]
+const fn wrap_within(value: i64, mut min: i64, mut max: i64) -> i64 {
     debug_assert!(min <= max);
     if max < min {
         (min, max) = (max, min);
     }
 
-    let size = max.abs_diff(min) + 1;
-    loop {
-        if value >= min && value <= max {
-            break value;
-        }
+    wrap_wide(value as i128, min as i128, max as i128) as i64
+}
 
-        if value < min {
-            value = value.wrapping_add_unsigned(size);
-        } else {
-            value = value.wrapping_sub_unsigned(size);
-        }
-    }
+/// Wraps `value` into `min..=max` in a single step. Every signed base type (and the result of any
+/// arithmetic between two values of one) fits in an `i128`, so this can neither overflow nor loop.
+const fn wrap_wide(value: i128, min: i128, max: i128) -> i128 {
+    debug_assert!(min <= max);
+    let size = max - min + 1;
+    min + (value - min).rem_euclid(size)
 }
 
+pub use n16::WrappedI16;
+pub use n32::WrappedI32;
+#[cfg(target_pointer_width = "64")]
+pub use n64::WrappedI64;
 pub use n8::WrappedI8;
+pub use nsize::WrappedISize;
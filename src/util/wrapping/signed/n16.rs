@@ -1 +1,61 @@
+// Copyright (c) 2022 Tony Barbitta
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+wrapped_signed! {
+    /// Serializes as the plain inner `i16`. Deserializing goes through `From<i16>`, so an
+    /// out of range stored value is wrapped into the range rather than accepted verbatim.
+    pub struct WrappedI16(i16, "i16");
+    RANGE_SIZE = Self::MIN.abs_diff(Self::MAX) as usize + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn wraps_negatives() {
+        type Tester = WrappedI16<-10, 10>;
+        assert_eq!(Tester::RANGE_SIZE, 21);
+        assert_eq!(Tester::new(-10).value(), -10);
+        assert_eq!(Tester::new(10).value(), 10);
+        assert_eq!(Tester::new(-11).value(), 10);
+        assert_eq!(Tester::new(11).value(), -10);
+        assert_eq!(Tester::new(-25).value(), -4);
+        assert_eq!(Tester::new(-42).value(), 0);
+        assert_eq!(Tester::from_any_signed(-100isize).value(), 5);
+        assert_eq!(Tester::from(1_000_000_000_007i64).value(), 8);
+
+        let tester = Tester::new(-8);
+        assert_eq!((tester - 5).value(), 8);
+        assert_eq!((tester + 20).value(), -9);
+        assert_eq!((tester * 3).value(), -3);
+        assert_eq!((tester - Tester::new(10)).value(), 3);
+        assert_eq!((tester / 2).value(), -4);
+        assert_eq!((tester % 3).value(), -2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn full_range() {
+        type Tester = WrappedI16;
+        assert_eq!(Tester::new(i16::MIN).value(), i16::MIN);
+        assert_eq!((Tester::new(i16::MAX) + 1).value(), i16::MIN);
+        assert_eq!((Tester::new(i16::MIN) - 1).value(), i16::MAX);
+    }
+    #[test]
+    #[no_coverage]
+    fn saturating() {
+        type Tester = WrappedI16<-10, 10>;
+        assert_eq!(Tester::new(8).saturating_add(5).value(), 10);
+        assert_eq!(Tester::new(8).saturating_add(-30).value(), -10);
+        assert_eq!(Tester::new(-8).saturating_sub(5).value(), -10);
+        assert_eq!(Tester::new(-8).saturating_sub(-5).value(), -3);
+        assert_eq!((Tester::new(-8) - 5).value(), 8);
+        assert_eq!(WrappedI16::<{ i16::MIN }, { i16::MAX }>::new(i16::MAX).saturating_add(1).value(), i16::MAX);
+    }
+}
@@ -3,3 +3,61 @@
 // This Source Code Form is subject to the terms of the Mozilla Public
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+wrapped_signed! {
+    /// Serializes as the plain inner `i64`. Deserializing goes through `From<i64>`, so an
+    /// out of range stored value is wrapped into the range rather than accepted verbatim.
+    pub struct WrappedI64(i64, "i64");
+    RANGE_SIZE = {
+        if cfg!(target_pointer_width = "64") {
+            #[allow(clippy::cast_possible_truncation, reason = "cfg ensures this is safe")]
+            {
+                Self::MIN.abs_diff(Self::MAX).saturating_add(1) as usize
+            }
+        } else {
+            match Self::MIN.abs_diff(Self::MAX).saturating_add(1).try_into() {
+                Ok(value) => value,
+                // TODO: Should this panic instead of potentially not working correctly?
+                Err(_) => usize::MAX,
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    #[no_coverage]
+    fn wraps_negatives() {
+        type Tester = WrappedI64<-10, 10>;
+        assert_eq!(Tester::RANGE_SIZE, 21);
+        assert_eq!(Tester::new(-10).value(), -10);
+        assert_eq!(Tester::new(10).value(), 10);
+        assert_eq!(Tester::new(-11).value(), 10);
+        assert_eq!(Tester::new(11).value(), -10);
+        assert_eq!(Tester::new(-25).value(), -4);
+        assert_eq!(Tester::new(-42).value(), 0);
+        assert_eq!(Tester::from_any_signed(-100isize).value(), 5);
+        assert_eq!(Tester::from(1_000_000_000_007i64).value(), 8);
+
+        let tester = Tester::new(-8);
+        assert_eq!((tester - 5).value(), 8);
+        assert_eq!((tester + 20).value(), -9);
+        assert_eq!((tester * 3).value(), -3);
+        assert_eq!((tester - Tester::new(10)).value(), 3);
+        assert_eq!((tester / 2).value(), -4);
+        assert_eq!((tester % 3).value(), -2);
+    }
+
+    #[test]
+    #[no_coverage]
+    fn full_range() {
+        type Tester = WrappedI64;
+        assert_eq!(Tester::new(i64::MIN).value(), i64::MIN);
+        assert_eq!((Tester::new(i64::MAX) + 1).value(), i64::MIN);
+        assert_eq!((Tester::new(i64::MIN) - 1).value(), i64::MAX);
+    }
+}
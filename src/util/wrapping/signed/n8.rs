@@ -6,7 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Serializes as the plain inner `i8`. Deserializing goes through `From<i8>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(from = "i8", into = "i8")]
 pub struct WrappedI8<const MIN: i8 = { i8::MIN }, const MAX: i8 = { i8::MAX }>(i8);
 
 impl<const MINIMUM: i8, const MAXIMUM: i8> WrappedI8<MINIMUM, MAXIMUM> {
@@ -60,6 +65,34 @@ impl<const MINIMUM: i8, const MAXIMUM: i8> WrappedI8<MINIMUM, MAXIMUM> {
     pub const fn value(self) -> i8 {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] (or [`Self::MIN`] for a negative `rhs`) instead of wrapping
+    /// around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: i8) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] (or [`Self::MAX`] for a negative `rhs`) instead of
+    /// wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: i8) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as i8)
+        }
+    }
 }
 
 impl<const MINIMUM: i8, const MAXIMUM: i8> WrappedNumber for WrappedI8<MINIMUM, MAXIMUM> {
@@ -188,6 +221,20 @@ impl<const MINIMUM: i8, const MAXIMUM: i8> StandardOps<WrappedI8<MINIMUM, MAXIMU
 {
 }
 
+impl<const MINIMUM: i8, const MAXIMUM: i8> From<i8> for WrappedI8<MINIMUM, MAXIMUM> {
+    #[must_use]
+    fn from(n: i8) -> Self {
+        Self::new(n)
+    }
+}
+
+impl<const MINIMUM: i8, const MAXIMUM: i8> From<WrappedI8<MINIMUM, MAXIMUM>> for i8 {
+    #[must_use]
+    fn from(n: WrappedI8<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
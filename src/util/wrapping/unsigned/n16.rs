@@ -6,7 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Serializes as the plain inner `u16`. Deserializing goes through `From<u16>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(from = "u16", into = "u16")]
 pub struct WrappedU16<const MIN: u16 = { u16::MIN }, const MAX: u16 = { u16::MAX }>(u16);
 
 impl<const MINIMUM: u16, const MAXIMUM: u16> WrappedU16<MINIMUM, MAXIMUM> {
@@ -80,6 +85,33 @@ impl<const MINIMUM: u16, const MAXIMUM: u16> WrappedU16<MINIMUM, MAXIMUM> {
     pub const fn value(self) -> u16 {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] instead of wrapping around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: u16) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] instead of wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: u16) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as u16)
+        }
+    }
 }
 
 impl<const MINIMUM: u16, const MAXIMUM: u16> WrappedNumber for WrappedU16<MINIMUM, MAXIMUM> {
@@ -257,3 +289,10 @@ impl<const MINIMUM: u16, const MAXIMUM: u16> From<usize> for WrappedU16<MINIMUM,
         WrappedU16::new(wrapped)
     }
 }
+
+impl<const MINIMUM: u16, const MAXIMUM: u16> From<WrappedU16<MINIMUM, MAXIMUM>> for u16 {
+    #[must_use]
+    fn from(n: WrappedU16<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}
@@ -6,7 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Serializes as the plain inner `u32`. Deserializing goes through `From<u32>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(from = "u32", into = "u32")]
 pub struct WrappedU32<const MIN: u32 = { u32::MIN }, const MAX: u32 = { u32::MAX }>(u32);
 
 impl<const MINIMUM: u32, const MAXIMUM: u32> WrappedU32<MINIMUM, MAXIMUM> {
@@ -80,6 +85,33 @@ impl<const MINIMUM: u32, const MAXIMUM: u32> WrappedU32<MINIMUM, MAXIMUM> {
     pub const fn value(self) -> u32 {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] instead of wrapping around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: u32) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] instead of wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: u32) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as u32)
+        }
+    }
 }
 
 impl<const MINIMUM: u32, const MAXIMUM: u32> WrappedNumber for WrappedU32<MINIMUM, MAXIMUM> {
@@ -252,3 +284,10 @@ impl<const MINIMUM: u32, const MAXIMUM: u32> From<usize> for WrappedU32<MINIMUM,
         WrappedU32::new(wrapped)
     }
 }
+
+impl<const MINIMUM: u32, const MAXIMUM: u32> From<WrappedU32<MINIMUM, MAXIMUM>> for u32 {
+    #[must_use]
+    fn from(n: WrappedU32<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}
@@ -6,7 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Serializes as the plain inner `u64`. Deserializing goes through `From<u64>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(from = "u64", into = "u64")]
 pub struct WrappedU64<const MIN: u64 = { u64::MIN }, const MAX: u64 = { u64::MAX }>(u64);
 
 impl<const MINIMUM: u64, const MAXIMUM: u64> WrappedU64<MINIMUM, MAXIMUM> {
@@ -93,6 +98,33 @@ impl<const MINIMUM: u64, const MAXIMUM: u64> WrappedU64<MINIMUM, MAXIMUM> {
     pub const fn value(self) -> u64 {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] instead of wrapping around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: u64) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] instead of wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: u64) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as u64)
+        }
+    }
 }
 
 impl<const MINIMUM: u64, const MAXIMUM: u64> WrappedNumber for WrappedU64<MINIMUM, MAXIMUM> {
@@ -267,3 +299,10 @@ impl<const MINIMUM: u64, const MAXIMUM: u64> TryFrom<usize> for WrappedU64<MINIM
         }
     }
 }
+
+impl<const MINIMUM: u64, const MAXIMUM: u64> From<WrappedU64<MINIMUM, MAXIMUM>> for u64 {
+    #[must_use]
+    fn from(n: WrappedU64<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}
@@ -6,9 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
+/// Serializes as the plain inner `u8`. Deserializing goes through `From<u8>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
 #[derive(
     Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
 )]
+#[serde(from = "u8", into = "u8")]
 pub struct WrappedU8<const MIN: u8 = { u8::MIN }, const MAX: u8 = { u8::MAX }>(u8);
 
 impl<const MINIMUM: u8, const MAXIMUM: u8> WrappedU8<MINIMUM, MAXIMUM> {
@@ -82,6 +85,33 @@ impl<const MINIMUM: u8, const MAXIMUM: u8> WrappedU8<MINIMUM, MAXIMUM> {
     pub const fn value(self) -> u8 {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] instead of wrapping around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: u8) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] instead of wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: u8) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as u8)
+        }
+    }
 }
 
 impl<const MINIMUM: u8, const MAXIMUM: u8> WrappedNumber for WrappedU8<MINIMUM, MAXIMUM> {
@@ -261,6 +291,13 @@ impl<const MINIMUM: u8, const MAXIMUM: u8> From<usize> for WrappedU8<MINIMUM, MA
     }
 }
 
+impl<const MINIMUM: u8, const MAXIMUM: u8> From<WrappedU8<MINIMUM, MAXIMUM>> for u8 {
+    #[must_use]
+    fn from(n: WrappedU8<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,4 +383,27 @@ mod tests {
         let result = tester + Tester::new(2);
         assert_eq!(result.value(), 5);
     }
+    #[test]
+    #[no_coverage]
+    fn serde_rewraps() {
+        type Hour = WrappedU8<0, 23>;
+        let hour: Hour = serde_json::from_str("25").expect("Unable to deserialize");
+        assert_eq!(hour.value(), 1);
+        assert_eq!(serde_json::to_string(&Hour::new(5)).unwrap(), "5");
+
+        let bytes = rmp_serde::to_vec(&Hour::new(17)).unwrap();
+        assert_eq!(rmp_serde::from_slice::<Hour>(&bytes).unwrap(), Hour::new(17));
+        assert_eq!(rmp_serde::from_slice::<Hour>(&rmp_serde::to_vec(&47u8).unwrap()).unwrap().value(), 23);
+    }
+    #[test]
+    #[no_coverage]
+    fn saturating() {
+        type Hour = WrappedU8<0, 23>;
+        assert_eq!(Hour::new(23).saturating_add(5).value(), 23);
+        assert_eq!((Hour::new(23) + 5).value(), 4);
+        assert_eq!(Hour::new(20).saturating_add(2).value(), 22);
+        assert_eq!(Hour::new(2).saturating_sub(5).value(), 0);
+        assert_eq!((Hour::new(2) - 5).value(), 21);
+        assert_eq!(WrappedU8::<0, 255>::new(250).saturating_add(255).value(), 255);
+    }
 }
@@ -6,7 +6,12 @@
 
 use super::super::{StandardOps, WrappedNumber};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Serializes as the plain inner `usize`. Deserializing goes through `From<usize>`, so an
+/// out of range stored value is wrapped into the range rather than accepted verbatim.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(from = "usize", into = "usize")]
 pub struct WrappedUSize<const MIN: usize = { usize::MIN }, const MAX: usize = { usize::MAX }>(
     usize,
 );
@@ -82,6 +87,33 @@ impl<const MINIMUM: usize, const MAXIMUM: usize> WrappedUSize<MINIMUM, MAXIMUM>
     pub const fn value(self) -> usize {
         self.0
     }
+
+    /// Adds `rhs`, stopping at [`Self::MAX`] instead of wrapping around like `+` does.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: usize) -> Self {
+        Self::clamp_wide(self.0 as i128 + rhs as i128)
+    }
+
+    /// Subtracts `rhs`, stopping at [`Self::MIN`] instead of wrapping around like `-` does.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: usize) -> Self {
+        Self::clamp_wide(self.0 as i128 - rhs as i128)
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "the value is checked to be within MINIMUM..=MAXIMUM"
+    )]
+    const fn clamp_wide(value: i128) -> Self {
+        if value < MINIMUM as i128 {
+            Self(MINIMUM)
+        } else if value > MAXIMUM as i128 {
+            Self(MAXIMUM)
+        } else {
+            Self(value as usize)
+        }
+    }
 }
 
 impl<const MINIMUM: usize, const MAXIMUM: usize> WrappedNumber for WrappedUSize<MINIMUM, MAXIMUM> {
@@ -289,3 +321,10 @@ impl<const MINIMUM: usize, const MAXIMUM: usize> From<usize> for WrappedUSize<MI
         Self::new(n)
     }
 }
+
+impl<const MINIMUM: usize, const MAXIMUM: usize> From<WrappedUSize<MINIMUM, MAXIMUM>> for usize {
+    #[must_use]
+    fn from(n: WrappedUSize<MINIMUM, MAXIMUM>) -> Self {
+        n.value()
+    }
+}